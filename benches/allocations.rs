@@ -0,0 +1,24 @@
+//! Count the allocations made to build syntax trees, as criterion only
+//! measures time.
+//!
+//! Run with `cargo bench --bench allocations`.
+
+use std::fs;
+
+#[path = "../tests/test_utils/allocations.rs"]
+mod allocations;
+
+fn main() {
+    let readme = fs::read_to_string("readme.md").unwrap();
+    let one_mb = readme.repeat(1024 * 1024 / readme.len() + 1);
+    let options = markdown::ParseOptions::gfm();
+
+    for (name, doc) in [("readme", &readme), ("1 mb", &one_mb)] {
+        let (_, owned) = allocations::allocations(|| markdown::to_mdast(doc, &options).unwrap());
+        let (_, borrowed) =
+            allocations::allocations(|| markdown::to_mdast_ref(doc, &options).unwrap());
+
+        println!("mdast/to_mdast/{}: {} allocations", name, owned);
+        println!("mdast/to_mdast_ref/{}: {} allocations", name, borrowed);
+    }
+}
@@ -9,16 +9,73 @@ fn readme(c: &mut Criterion) {
     });
 }
 
-// fn one_and_a_half_mb(c: &mut Criterion) {
-//     let doc = fs::read_to_string("../a-dump-of-markdown/markdown.md").unwrap();
-//     let mut group = c.benchmark_group("giant");
-//     group.sample_size(10);
-//     group.bench_with_input(BenchmarkId::new("giant", "1.5 mb"), &doc, |b, s| {
-//         b.iter(|| markdown::to_html(s));
-//     });
-//     group.finish();
-// }
-// , one_and_a_half_mb
-
-criterion_group!(benches, readme);
+fn snippets(c: &mut Criterion) {
+    let docs = (0..10_000)
+        .map(|index| format!("Some *emphasis*, `code`, and a [link][d{}].", index % 1000))
+        .collect::<Vec<_>>();
+    let options = || {
+        let mut options = markdown::Options::default();
+        for index in 0..1000 {
+            options
+                .parse
+                .definitions
+                .insert(format!("D{}", index), (format!("#{}", index), None));
+        }
+        options
+    };
+    let mut group = c.benchmark_group("snippets");
+    group.sample_size(10);
+
+    group.bench_with_input(
+        BenchmarkId::new("to_html_with_options", "10k"),
+        &docs,
+        |b, docs| {
+            let options = options();
+            b.iter(|| {
+                for doc in docs {
+                    markdown::to_html_with_options(doc, &options).unwrap();
+                }
+            });
+        },
+    );
+
+    group.bench_with_input(BenchmarkId::new("parser", "10k"), &docs, |b, docs| {
+        let parser = markdown::Parser::new(options());
+        b.iter(|| {
+            for doc in docs {
+                parser.to_html(doc).unwrap();
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn one_mb(c: &mut Criterion) {
+    let readme = fs::read_to_string("readme.md").unwrap();
+    let doc = readme.repeat(1024 * 1024 / readme.len() + 1);
+    let mut group = c.benchmark_group("large");
+    group.sample_size(10);
+    group.bench_with_input(BenchmarkId::new("large", "1 mb"), &doc, |b, s| {
+        b.iter(|| markdown::to_html(s));
+    });
+    group.finish();
+}
+
+fn mdast(c: &mut Criterion) {
+    let readme = fs::read_to_string("readme.md").unwrap();
+    let doc = readme.repeat(1024 * 1024 / readme.len() + 1);
+    let options = markdown::ParseOptions::gfm();
+    let mut group = c.benchmark_group("mdast");
+    group.sample_size(10);
+    group.bench_with_input(BenchmarkId::new("to_mdast", "1 mb"), &doc, |b, s| {
+        b.iter(|| markdown::to_mdast(s, &options).unwrap());
+    });
+    group.bench_with_input(BenchmarkId::new("to_mdast_ref", "1 mb"), &doc, |b, s| {
+        b.iter(|| markdown::to_mdast_ref(s, &options).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, readme, snippets, one_mb, mdast);
 criterion_main!(benches);
@@ -0,0 +1,32 @@
+// Call `markdown-rs` from Node.js.
+//
+// Build the bindings first (from the root of the repo):
+//
+// ```sh
+// cargo rustc --release --lib --features wasm --target wasm32-unknown-unknown --crate-type cdylib
+// wasm-bindgen --target nodejs --out-dir examples/wasm/pkg target/wasm32-unknown-unknown/release/markdown.wasm
+// node examples/wasm/index.mjs
+// ```
+import {createRequire} from 'node:module'
+
+const require = createRequire(import.meta.url)
+const {toHtml, toHtmlWithOptions, toMdastJson} = require('./pkg/markdown.js')
+
+// Safely turn (untrusted?) markdown into HTML.
+console.log(toHtml('## Hello, *world*!'))
+
+// Turn trusted markdown into HTML.
+// Options are JSON, with camel-cased field names.
+console.log(
+  toHtmlWithOptions(
+    '<div style="color: tomato">\n\n# Hello, tomato!\n\n</div>',
+    JSON.stringify({compile: {allowDangerousHtml: true}})
+  )
+)
+
+// Turn markdown into a syntax tree.
+console.log(
+  JSON.parse(
+    toMdastJson('* [x] a ~~b~~', JSON.stringify({constructs: {gfmTaskListItem: true, gfmStrikethrough: true}}))
+  )
+)
@@ -0,0 +1,134 @@
+//! Split markdown into top-level blocks.
+
+use crate::event::{Event, Kind, Name};
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+/// Kind of a top-level block.
+///
+/// Named after the [mdast][crate::mdast] node the block turns into.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockKind {
+    /// Block quote (`> a`).
+    Blockquote,
+    /// Code, fenced (` ```a `) or indented.
+    Code,
+    /// Definition (`[a]: b`).
+    Definition,
+    /// GFM: footnote definition (`[^a]: b`).
+    FootnoteDefinition,
+    /// Heading, ATX (`# a`) or setext (`a` and `===` on the next line).
+    Heading,
+    /// HTML (`<div>`).
+    Html,
+    /// List, ordered (`1. a`) or unordered (`* a`).
+    List,
+    /// Math (flow) (`$$`).
+    Math,
+    /// MDX: expression (`{a}`).
+    MdxFlowExpression,
+    /// MDX: JSX tag (`<a>`).
+    ///
+    /// Each tag is a block: content between an opening and a closing tag on
+    /// other lines is in other blocks.
+    MdxJsxFlowElement,
+    /// MDX: ESM (`import a from 'b'`).
+    MdxjsEsm,
+    /// Paragraph.
+    Paragraph,
+    /// GFM: table.
+    Table,
+    /// Thematic break (`***`).
+    ThematicBreak,
+    /// Frontmatter: TOML (`+++`).
+    Toml,
+    /// Frontmatter: YAML (`---`).
+    Yaml,
+}
+
+/// Get the byte ranges and kinds of the top-level blocks in `events`.
+///
+/// Blank lines, and line endings and whitespace at the end of containers,
+/// are not in a block.
+pub fn ranges(events: &[Event], bytes: &[u8]) -> Vec<(Range<usize>, BlockKind)> {
+    let mut result = vec![];
+    let mut index = 0;
+    let mut depth = 0;
+    let mut start = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            if depth == 0 {
+                start = index;
+            }
+
+            depth += 1;
+        } else {
+            depth -= 1;
+
+            if depth == 0 {
+                if let Some(kind) = kind(&event.name, bytes[events[start].point.index]) {
+                    result.push((events[start].point.index..end(events, index), kind));
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+/// Get the kind of a top-level block, from its name and first byte.
+fn kind(name: &Name, head: u8) -> Option<BlockKind> {
+    match name {
+        Name::BlockQuote => Some(BlockKind::Blockquote),
+        Name::CodeFenced | Name::CodeIndented => Some(BlockKind::Code),
+        Name::Definition => Some(BlockKind::Definition),
+        Name::Frontmatter => Some(if head == b'+' {
+            BlockKind::Toml
+        } else {
+            BlockKind::Yaml
+        }),
+        Name::GfmFootnoteDefinition => Some(BlockKind::FootnoteDefinition),
+        Name::GfmTable => Some(BlockKind::Table),
+        Name::HeadingAtx | Name::HeadingSetext => Some(BlockKind::Heading),
+        Name::HtmlFlow => Some(BlockKind::Html),
+        Name::ListOrdered | Name::ListUnordered => Some(BlockKind::List),
+        Name::MathFlow => Some(BlockKind::Math),
+        Name::MdxEsm => Some(BlockKind::MdxjsEsm),
+        Name::MdxFlowExpression => Some(BlockKind::MdxFlowExpression),
+        Name::MdxJsxFlowTag => Some(BlockKind::MdxJsxFlowElement),
+        Name::Paragraph => Some(BlockKind::Paragraph),
+        Name::ThematicBreak => Some(BlockKind::ThematicBreak),
+        _ => None,
+    }
+}
+
+/// Get where the block exited at `exit` ends, without the blank lines, line
+/// endings, and whitespace that containers (block quotes, lists, and GFM
+/// footnote definitions) end in.
+fn end(events: &[Event], exit: usize) -> usize {
+    let mut index = exit;
+
+    while index > 0
+        && (matches!(
+            events[index].name,
+            Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+        ) || (events[index].kind == Kind::Exit
+            && matches!(
+                events[index].name,
+                Name::BlockQuote
+                    | Name::GfmFootnoteDefinition
+                    | Name::ListItem
+                    | Name::ListOrdered
+                    | Name::ListUnordered
+            )))
+    {
+        index -= 1;
+    }
+
+    events[index].point.index
+}
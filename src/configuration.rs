@@ -1,8 +1,9 @@
 use crate::util::{
     line_ending::LineEnding,
     mdx::{EsmParse as MdxEsmParse, ExpressionParse as MdxExpressionParse},
+    slug::Slugify,
 };
-use alloc::{boxed::Box, fmt, string::String};
+use alloc::{boxed::Box, collections::BTreeMap, fmt, string::String, vec::Vec};
 
 /// Control which constructs are enabled.
 ///
@@ -21,6 +22,12 @@ use alloc::{boxed::Box, fmt, string::String};
 /// // To turn on all of GFM, use the `gfm` method:
 /// let gfm = Constructs::gfm();
 ///
+/// // To start from nothing, use the `none` method:
+/// let emphasis_only = Constructs {
+///   attention: true,
+///   ..Constructs::none()
+/// };
+///
 /// // Or, mix and match:
 /// let custom = Constructs {
 ///   math_flow: true,
@@ -31,6 +38,11 @@ use alloc::{boxed::Box, fmt, string::String};
 /// ```
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct Constructs {
     /// Attention.
     ///
@@ -379,6 +391,90 @@ impl Default for Constructs {
 }
 
 impl Constructs {
+    /// Nothing.
+    ///
+    /// This turns off every construct, so that all markdown is text (in
+    /// paragraphs).
+    /// Use it to turn on only the constructs you need.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Only support emphasis and strong:
+    /// let options = Options {
+    ///     parse: ParseOptions {
+    ///         constructs: Constructs {
+    ///             attention: true,
+    ///             ..Constructs::none()
+    ///         },
+    ///         ..ParseOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("# *a* [b](c)", &options)?,
+    ///     "<p># <em>a</em> [b](c)</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn none() -> Self {
+        Self {
+            attention: false,
+            autolink: false,
+            block_quote: false,
+            character_escape: false,
+            character_reference: false,
+            code_indented: false,
+            code_fenced: false,
+            code_text: false,
+            definition: false,
+            frontmatter: false,
+            gfm_autolink_literal: false,
+            gfm_label_start_footnote: false,
+            gfm_footnote_definition: false,
+            gfm_strikethrough: false,
+            gfm_table: false,
+            gfm_task_list_item: false,
+            hard_break_escape: false,
+            hard_break_trailing: false,
+            heading_atx: false,
+            heading_setext: false,
+            html_flow: false,
+            html_text: false,
+            label_start_image: false,
+            label_start_link: false,
+            label_end: false,
+            list_item: false,
+            math_flow: false,
+            math_text: false,
+            mdx_esm: false,
+            mdx_expression_flow: false,
+            mdx_expression_text: false,
+            mdx_jsx_flow: false,
+            mdx_jsx_text: false,
+            thematic_break: false,
+        }
+    }
+
+    /// Everything but MDX.
+    ///
+    /// This turns on `CommonMark`, GFM, math, and frontmatter.
+    /// MDX is not turned on, as it conflicts with some `CommonMark`
+    /// constructs (see [`Constructs::mdx()`][]).
+    pub fn all() -> Self {
+        Self {
+            frontmatter: true,
+            math_flow: true,
+            math_text: true,
+            ..Self::gfm()
+        }
+    }
+
     /// GFM.
     ///
     /// GFM stands for **GitHub flavored markdown**.
@@ -434,6 +530,99 @@ impl Constructs {
     }
 }
 
+/// Quote to use around attribute values in HTML.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::Quote;
+/// # fn main() {
+///
+/// // Use single quotes:
+/// let single = Quote::Single;
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum Quote {
+    /// Double quote (`"`).
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <a href="b">c</a>
+    /// ```
+    #[default]
+    Double,
+    /// Single quote (`'`).
+    ///
+    /// ## Example
+    ///
+    /// ```html
+    /// <a href='b'>c</a>
+    /// ```
+    Single,
+}
+
+impl Quote {
+    /// Turn the quote into a [str].
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Quote::Double => "\"",
+            Quote::Single => "'",
+        }
+    }
+}
+
+/// Unit that columns and offsets in positions count in.
+///
+/// Markdown is parsed as bytes, so by default positions count UTF-8 bytes.
+/// Some tools expect something else: the language server protocol and
+/// JavaScript count UTF-16 code units, and some editors count characters.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::PositionEncoding;
+/// # fn main() {
+///
+/// // Count UTF-16 code units, as in the language server protocol:
+/// let utf16 = PositionEncoding::Utf16;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum PositionEncoding {
+    /// UTF-8 bytes.
+    ///
+    /// ## Example
+    ///
+    /// `é` (`U+00E9`) is 2 long, `😀` (`U+1F600`) is 4 long.
+    #[default]
+    Utf8,
+    /// UTF-16 code units.
+    ///
+    /// ## Example
+    ///
+    /// `é` (`U+00E9`) is 1 long, `😀` (`U+1F600`) is 2 long.
+    Utf16,
+    /// Unicode code points (characters).
+    ///
+    /// ## Example
+    ///
+    /// `é` (`U+00E9`) is 1 long, `😀` (`U+1F600`) is 1 long.
+    CodePoints,
+}
+
 /// Configuration that describes how to compile to HTML.
 ///
 /// You likely either want to turn on the dangerous options
@@ -466,12 +655,21 @@ impl Constructs {
 /// # }
 /// ```
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
 pub struct CompileOptions {
     /// Whether to allow (dangerous) HTML.
     ///
     /// The default is `false`, which still parses the HTML according to
     /// `CommonMark` but shows the HTML as text instead of as elements.
+    /// That means every `<` in text is escaped as `&lt;`, whether it starts
+    /// HTML (`a <b> c`) or not (`a < b`).
+    /// Autolinks (`<https://example.com>`) are not HTML and still become
+    /// links; turn off the `autolink` construct to show them as text too.
     ///
     /// Pass `true` for trusted content to get actual HTML elements.
     ///
@@ -553,6 +751,138 @@ pub struct CompileOptions {
     /// ```
     pub allow_dangerous_protocol: bool,
 
+    /// Elements to wrap code (fenced and indented) in.
+    ///
+    /// The default is `None`, which wraps code in `<pre><code>`.
+    /// Pass a list of elements, from outer to inner, to use something else,
+    /// such as `<code>` or `<div class="code">` for a syntax highlighter.
+    /// Each element is a tag name, optionally followed by attributes
+    /// (`div class="code"`).
+    /// An empty list is the same as `None`.
+    ///
+    /// Elements with invalid tag names, and attributes with invalid names,
+    /// are ignored, and attribute values are encoded.
+    ///
+    /// Source position attributes are added to the outer element, and the
+    /// `language-*` class (and `code_indented_class`) to the inner element,
+    /// after its own classes.
+    /// Math (flow) is not affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` wraps code in `<pre><code>` by default:
+    /// assert_eq!(
+    ///     to_html("```js\na\n```"),
+    ///     "<pre><code class=\"language-js\">a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_fence_wrapper` to use other elements:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "```js\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_fence_wrapper: Some(vec!["div class=\"code\"".into(), "code".into()]),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<div class=\"code\"><code class=\"language-js\">a\n</code></div>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_fence_wrapper: Option<Vec<String>>,
+
+    /// Class to add to code (indented).
+    ///
+    /// The default is `None`, which compiles code (indented) like fenced code
+    /// without info string: `<pre><code>`.
+    /// Pass a string to add it as a class, to tell them apart in the HTML
+    /// (for example, to style them differently, or to find them and switch
+    /// them to fenced code).
+    ///
+    /// This does not affect code (fenced), which still gets a
+    /// `language-*` class when it has an info string.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add a class to indented code by default:
+    /// assert_eq!(
+    ///     to_html("    a"),
+    ///     "<pre><code>a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_indented_class` to add one:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "    a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_indented_class: Some("code-indented".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code class=\"code-indented\">a\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_indented_class: Option<String>,
+
+    /// Whether to collapse whitespace in text to a single space.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to turn each run of spaces, tabs, and line endings in text
+    /// into one space, and to turn the line endings in paragraphs and
+    /// headings (setext) into spaces.
+    /// The line ending after a hard break (`<br />`) becomes a space too.
+    ///
+    /// Code (text), code (flow), math, and HTML are not touched, and neither
+    /// are destinations and titles.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps whitespace by default:
+    /// assert_eq!(
+    ///     to_html("a  b\nc `d  e`"),
+    ///     "<p>a  b\nc <code>d  e</code></p>"
+    /// );
+    ///
+    /// // Pass `collapse_whitespace` to collapse it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a  b\nc `d  e`",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               collapse_whitespace: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a b c <code>d  e</code></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub collapse_whitespace: bool,
+
     // To do: `doc_markdown` is broken.
     #[allow(clippy::doc_markdown)]
     /// Default line ending to use when compiling to HTML, for line endings not
@@ -599,6 +929,43 @@ pub struct CompileOptions {
     /// ```
     pub default_line_ending: LineEnding,
 
+    /// Whether to encode non-ASCII characters as numeric character references.
+    ///
+    /// The default is `false`, which passes non-ASCII characters through
+    /// as-is.
+    /// Pass `true` to turn every character above U+007F in the output into a
+    /// hexadecimal character reference, such as `&#xE9;` for `é`, which is
+    /// useful when the result is consumed by tools that only handle ASCII.
+    /// This applies to everything, including code and raw HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` passes non-ASCII through by default:
+    /// assert_eq!(to_html("café 🦀"), "<p>café 🦀</p>");
+    ///
+    /// // Pass `encode_non_ascii: true` to encode it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "café 🦀",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///                 encode_non_ascii: true,
+    ///                 ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>caf&#xE9; &#x1F980;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub encode_non_ascii: bool,
+
     /// Textual label to use for the footnotes section.
     ///
     /// The default value is `"Footnotes"`.
@@ -634,7 +1001,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               gfm_footnote_label: Some("Notes de bas de page".into()),
     ///               ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
@@ -679,7 +1047,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               gfm_footnote_label_tag_name: Some("h1".into()),
     ///               ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
@@ -727,7 +1096,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
     ///               ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
@@ -770,7 +1140,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               gfm_footnote_back_label: Some("Arrière".into()),
     ///               ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
@@ -826,7 +1197,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               gfm_footnote_clobber_prefix: Some("".into()),
     ///               ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
@@ -858,7 +1230,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///                 gfm_task_list_item_checkable: true,
     ///                 ..CompileOptions::gfm()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>"
@@ -893,7 +1266,8 @@ pub struct CompileOptions {
     ///             compile: CompileOptions {
     ///               allow_dangerous_html: true,
     ///               ..CompileOptions::default()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "<iframe>"
@@ -909,7 +1283,8 @@ pub struct CompileOptions {
     ///               allow_dangerous_html: true,
     ///               gfm_tagfilter: true,
     ///               ..CompileOptions::default()
-    ///             }
+    ///             },
+    ///             ..Default::default()
     ///         }
     ///     )?,
     ///     "&lt;iframe>"
@@ -923,352 +1298,1655 @@ pub struct CompileOptions {
     /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
     /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
     pub gfm_tagfilter: bool,
-}
-
-impl CompileOptions {
-    /// GFM.
-    ///
-    /// GFM stands for **GitHub flavored markdown**.
-    /// On the compilation side, GFM turns on the GFM tag filter.
-    /// The tagfilter is useless, but it’s included here for consistency, and
-    /// this method exists for parity to parse options.
-    ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>.
-    pub fn gfm() -> Self {
-        Self {
-            gfm_tagfilter: true,
-            ..Self::default()
-        }
-    }
-}
 
-/// Configuration that describes how to parse from markdown.
-///
-/// You can use this:
-///
-/// *   To control what markdown constructs are turned on and off
-/// *   To control some of those constructs
-/// *   To add support for certain programming languages when parsing MDX
-///
-/// In most cases, you will want to use the default trait or `gfm` method.
-///
-/// ## Examples
-///
-/// ```
-/// use markdown::ParseOptions;
-/// # fn main() {
-///
-/// // Use the default trait to parse markdown according to `CommonMark`:
-/// let commonmark = ParseOptions::default();
-///
-/// // Use the `gfm` method to parse markdown according to GFM:
-/// let gfm = ParseOptions::gfm();
-/// # }
-/// ```
-#[allow(clippy::struct_excessive_bools)]
-pub struct ParseOptions {
-    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
-    /// Which constructs to enable and disable.
-    ///
-    /// The default is to follow `CommonMark`.
+    /// Whether to add `id`s to headings.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to add an `id` attribute to `<h1>` through `<h6>`, made
+    /// from the text of the heading like GitHub does it: lowercased, with
+    /// spaces turned into dashes, and most punctuation removed.
+    /// When several headings result in the same `id`, the later ones get a
+    /// suffix (`-1`, `-2`, and so on).
+    /// Headings without text get no `id`.
+    ///
+    /// To make `id`s in a different way, pass
+    /// [`slugify`][Options::slugify] too.
+    /// In [`mdast_to_html`][crate::mdast_to_html], an `id` in the
+    /// [`data`][crate::mdast::Heading::data] of a heading is used instead of
+    /// its text (it is still made unique).
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` follows CommonMark by default:
+    /// // `markdown-rs` does not add `id`s to headings by default:
     /// assert_eq!(
-    ///     to_html("    indented code?"),
-    ///     "<pre><code>indented code?\n</code></pre>"
+    ///     to_html("# Hello, world!"),
+    ///     "<h1>Hello, world!</h1>"
     /// );
     ///
-    /// // Pass `constructs` to choose what to enable and disable:
+    /// // Pass `heading_ids: true` to add them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "    indented code?",
+    ///         "# Hello, world!\n\n## Hello, world!",
     ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 code_indented: false,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               ..ParseOptions::default()
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>indented code?</p>"
+    ///     "<h1 id=\"hello-world\">Hello, world!</h1>\n<h2 id=\"hello-world-1\">Hello, world!</h2>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub constructs: Constructs,
+    ///
+    /// ## References
+    ///
+    /// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+    pub heading_ids: bool,
 
-    /// Whether to support GFM strikethrough with a single tilde
+    /// Number to add to the rank of headings.
     ///
-    /// This option does nothing if `gfm_strikethrough` is not turned on in
-    /// `constructs`.
-    /// This option does not affect strikethrough with double tildes.
+    /// The default is `0`, which turns `# a` into `<h1>` and `## b` into
+    /// `<h2>`.
+    /// Pass a number to shift headings, such as `2` when embedding a
+    /// document in a page that already has an `<h1>` and `<h2>`, so that
+    /// `# a` turns into `<h3>`.
+    /// Negative numbers shift headings up.
+    /// Ranks stay between `1` and `6`, so with `2`, `#####` and `######` both
+    /// turn into `<h6>`.
     ///
-    /// The default is `true`, which follows how markdown on `github.com`
-    /// works, as strikethrough with single tildes is supported.
-    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
-    /// strikethrough with single tildes.
+    /// Only HTML is affected: the [`depth`][crate::mdast::Heading::depth] of
+    /// headings in syntax trees is not.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single tildes by default:
+    /// // `markdown-rs` turns `#` into `<h1>` by default:
+    /// assert_eq!(
+    ///     to_html("# a\n\n##### b"),
+    ///     "<h1>a</h1>\n<h5>b</h5>"
+    /// );
+    ///
+    /// // Pass `heading_offset: 2` to shift headings down:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "# a\n\n##### b",
     ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               ..ParseOptions::default()
+    ///             compile: CompileOptions {
+    ///               heading_offset: 2,
+    ///               ..CompileOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><del>a</del></p>"
+    ///     "<h3>a</h3>\n<h6>b</h6>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub heading_offset: i8,
+
+    /// Whether to obfuscate email autolinks.
+    ///
+    /// The default is `false`, which compiles email addresses in autolinks
+    /// (`<user@example.com>`, and with GFM, `user@example.com` and
+    /// `mailto:user@example.com`) as they are.
+    /// Pass `true` to encode every character of both the address and the
+    /// `href` as a character reference.
+    /// Browsers show the same thing, but naïve email harvesters don’t
+    /// find them.
+    ///
+    /// Links to email addresses in other links (such as
+    /// `[a](mailto:user@example.com)`) are not affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` compiles email autolinks as they are by default:
+    /// assert_eq!(
+    ///     to_html("<a@b.c>"),
+    ///     "<p><a href=\"mailto:a@b.c\">a@b.c</a></p>"
     /// );
     ///
-    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// // Pass `obfuscate_email_autolinks: true` to obfuscate them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         "<a@b.c>",
     ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               gfm_strikethrough_single_tilde: false,
-    ///               ..ParseOptions::default()
+    ///             compile: CompileOptions {
+    ///               obfuscate_email_autolinks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x61;&#64;&#x62;&#46;&#x63;\">&#97;&#x40;&#98;&#x2e;&#99;</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub obfuscate_email_autolinks: bool,
+
+    /// Whether to keep a tab in code whose indent ends inside it.
+    ///
+    /// The default is `false`, which turns the rest of such a tab into
+    /// spaces, as required by `CommonMark`: the indent of code (and of
+    /// containers such as block quotes and list items) is counted in columns,
+    /// so it can end halfway through a tab, and only the columns after it are
+    /// content.
+    /// Pass `true` to keep the tab instead.
+    ///
+    /// Tabs elsewhere in code (such as after other characters, or after a
+    /// tab that is part of the indent) are always kept.
+    /// This applies to the code (and math) in [`to_html()`][crate::to_html],
+    /// but not to the `value` of code in mdast, which is made when parsing.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` turns the rest of tabs in indents into spaces by default:
+    /// assert_eq!(
+    ///     to_html(">\t\ta\tb"),
+    ///     "<blockquote>\n<pre><code>  a\tb\n</code></pre>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `preserve_tabs_in_code: true` to keep them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         ">\t\ta\tb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///                 preserve_tabs_in_code: true,
+    ///                 ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<blockquote>\n<pre><code>\ta\tb\n</code></pre>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub preserve_tabs_in_code: bool,
+
+    /// Quote to use around attribute values.
+    ///
+    /// The default is [`Quote::Double`][], which uses `"` and encodes `"` in
+    /// values as `&quot;`.
+    /// Pass [`Quote::Single`][] to use `'` instead, which also encodes `'` in
+    /// values as `&#39;`.
+    ///
+    /// This applies to all attributes generated by `markdown-rs` (such as
+    /// `href`, `src`, `alt`, `title`, `class`, and `id`), but not to HTML
+    /// written in markdown, nor to `gfm_footnote_label_attributes`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options, Quote};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses double quotes by default:
+    /// assert_eq!(
+    ///     to_html("[a](b 'c')"),
+    ///     "<p><a href=\"b\" title=\"c\">a</a></p>"
+    /// );
+    ///
+    /// // Pass `quote: Quote::Single` to use single quotes:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](b \"c'd\")",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               quote: Quote::Single,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href='b' title='c&#39;d'>a</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub quote: Quote,
+
+    /// Whether to number ordered lists from 1.
+    ///
+    /// The default is `false`, which keeps the number of the first item: as
+    /// required by `CommonMark`, a list that starts at another number gets a
+    /// `start` attribute, such as `<ol start="5">`.
+    /// Pass `true` to drop that attribute, so that lists always start at 1.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps the start of lists by default:
+    /// assert_eq!(
+    ///     to_html("5. a\n6. b"),
+    ///     "<ol start=\"5\">\n<li>a</li>\n<li>b</li>\n</ol>"
+    /// );
+    ///
+    /// // Pass `renumber_ordered_lists: true` to start at 1:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "5. a\n6. b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///                 renumber_ordered_lists: true,
+    ///                 ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<ol>\n<li>a</li>\n<li>b</li>\n</ol>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub renumber_ordered_lists: bool,
+
+    /// Whether to skip nodes that cannot be turned into HTML, when compiling
+    /// a syntax tree with [`mdast_to_html()`][crate::mdast_to_html].
+    ///
+    /// The default is `false`, which errors on MDX nodes (ESM, expressions,
+    /// and JSX).
+    /// Pass `true` to drop them instead, as is done when compiling MDX
+    /// directly: ESM and expressions are removed, JSX elements are replaced by
+    /// their children.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast_to_html, to_mdast, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a <b>c</b> {d}", &ParseOptions::mdx())?;
+    ///
+    /// // `markdown-rs` errors on MDX nodes by default:
+    /// assert!(mdast_to_html(&tree, &Options::default()).is_err());
+    ///
+    /// // Pass `skip_unknown_nodes: true` to drop them:
+    /// assert_eq!(
+    ///     mdast_to_html(
+    ///         &tree,
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               skip_unknown_nodes: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a c </p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub skip_unknown_nodes: bool,
+
+    /// Whether to add the lines of block constructs in the input to their
+    /// elements in the output.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to add `data-line` (the line where a construct starts) and
+    /// `data-end-line` (the line where it ends) attributes, which can be used
+    /// to map HTML back to markdown, such as to sync the scroll position of an
+    /// editor and its preview.
+    ///
+    /// Only the elements of block constructs get these attributes: `<p>`,
+    /// `<h1>` through `<h6>`, `<blockquote>`, `<pre>`, `<ol>`, `<ul>`,
+    /// `<li>`, `<hr />`, and `<table>`.
+    /// When compiling a syntax tree with
+    /// [`mdast_to_html()`][crate::mdast_to_html], nodes without positions do
+    /// not get them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add lines by default:
+    /// assert_eq!(
+    ///     to_html("# a\n\nb\nc"),
+    ///     "<h1>a</h1>\n<p>b\nc</p>"
+    /// );
+    ///
+    /// // Pass `source_position: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# a\n\nb\nc",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               source_position: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 data-line=\"1\" data-end-line=\"1\">a</h1>\n<p data-line=\"3\" data-end-line=\"4\">b\nc</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub source_position: bool,
+
+    /// Whether to drop HTML comments from the output.
+    ///
+    /// The default is `false`, which compiles comments like other HTML:
+    /// passed through with `allow_dangerous_html`, encoded otherwise.
+    /// Pass `true` to remove them, which is useful to keep notes to yourself
+    /// (such as `<!-- TODO -->`) out of published pages.
+    ///
+    /// This applies to HTML (flow) and HTML (text) that starts with a
+    /// comment (`<!--`).
+    /// For HTML (flow), that is the whole block, including anything after the
+    /// comment on its last line.
+    /// Other HTML is not affected.
+    /// The syntax tree still contains these comments as `Html` nodes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // With `allow_dangerous_html`, `markdown-rs` passes comments through:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<!-- TODO -->\n\na <!-- b --> c",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<!-- TODO -->\n<p>a <!-- b --> c</p>"
+    /// );
+    ///
+    /// // Pass `strip_comments: true` to remove them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<!-- TODO -->\n\na <!-- b --> c",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               strip_comments: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a  c</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub strip_comments: bool,
+
+    /// HTML to use for thematic breaks.
+    ///
+    /// The default is `None`, which compiles thematic breaks to `<hr />`.
+    /// Pass a string to use it instead, such as to add a class or to use a
+    /// custom snippet.
+    ///
+    /// > 👉 **Note**: the string is emitted as-is: it is not encoded or
+    /// > checked, so you are responsible for it being well-formed (and safe)
+    /// > HTML.
+    ///
+    /// Lines from [`source_position`][CompileOptions::source_position] are
+    /// not added to this HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` compiles thematic breaks to `<hr />` by default:
+    /// assert_eq!(
+    ///     to_html("a\n\n***"),
+    ///     "<p>a</p>\n<hr />"
+    /// );
+    ///
+    /// // Pass `thematic_break_html` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\n\n***",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               thematic_break_html: Some("<hr class=\"divider\">".into()),
+    ///               ..CompileOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>~a~</p>"
+    ///     "<p>a</p>\n<hr class=\"divider\">"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub thematic_break_html: Option<String>,
+
+    /// Whether to end the output with a line ending.
+    ///
+    /// The default is `None`, which ends the output with a line ending if the
+    /// document ends with one (or with blank lines), like `CommonMark` does.
+    /// Pass `Some(true)` to always end it with one, or `Some(false)` to never
+    /// do that, so that the output does not depend on how the document
+    /// ends, which is useful when comparing output.
+    /// The line ending is the one used in the document, or
+    /// [`default_line_ending`][CompileOptions::default_line_ending].
+    /// Nothing is added to empty output.
+    ///
+    /// Blank lines at the start or end of the document never result in
+    /// anything else in the output.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` ends the output like the document by default:
+    /// assert_eq!(to_html("a"), "<p>a</p>");
+    /// assert_eq!(to_html("\n\na\n\n"), "<p>a</p>\n");
+    ///
+    /// // Pass `trailing_newline: Some(false)` to never end with a line ending:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "\n\na\n\n",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               trailing_newline: Some(false),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a</p>"
+    /// );
+    ///
+    /// // Or `trailing_newline: Some(true)` to always end with one:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               trailing_newline: Some(true),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a</p>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub trailing_newline: Option<bool>,
+
+    /// Whether to wrap paragraphs that are not in containers in `<p>`.
+    ///
+    /// The default is `true`.
+    /// Pass `false` to leave out `<p>` and `</p>` around paragraphs at the
+    /// top level of the document, which is useful when compiling a fragment
+    /// that will be placed in some other element (such as a table cell or a
+    /// tooltip).
+    /// Other constructs, such as lists, still work, and paragraphs in block
+    /// quotes, list items, and GFM footnote definitions are still wrapped
+    /// when they would otherwise be.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` wraps paragraphs by default:
+    /// assert_eq!(
+    ///     to_html("*a*\n\n> b"),
+    ///     "<p><em>a</em></p>\n<blockquote>\n<p>b</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `wrap_paragraphs: false` to not wrap those at the top level:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "*a*\n\n> b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               wrap_paragraphs: false,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<em>a</em>\n<blockquote>\n<p>b</p>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub wrap_paragraphs: bool,
+}
+
+impl Default for CompileOptions {
+    /// Safe `CommonMark` defaults.
+    fn default() -> Self {
+        Self {
+            allow_dangerous_html: false,
+            allow_dangerous_protocol: false,
+            code_fence_wrapper: None,
+            code_indented_class: None,
+            collapse_whitespace: false,
+            default_line_ending: LineEnding::default(),
+            encode_non_ascii: false,
+            gfm_footnote_label: None,
+            gfm_footnote_label_tag_name: None,
+            gfm_footnote_label_attributes: None,
+            gfm_footnote_back_label: None,
+            gfm_footnote_clobber_prefix: None,
+            gfm_task_list_item_checkable: false,
+            gfm_tagfilter: false,
+            heading_ids: false,
+            heading_offset: 0,
+            obfuscate_email_autolinks: false,
+            preserve_tabs_in_code: false,
+            quote: Quote::default(),
+            renumber_ordered_lists: false,
+            skip_unknown_nodes: false,
+            source_position: false,
+            strip_comments: false,
+            thematic_break_html: None,
+            trailing_newline: None,
+            wrap_paragraphs: true,
+        }
+    }
+}
+
+impl CompileOptions {
+    /// GFM.
+    ///
+    /// GFM stands for **GitHub flavored markdown**.
+    /// On the compilation side, GFM turns on the GFM tag filter.
+    /// The tagfilter is useless, but it’s included here for consistency, and
+    /// this method exists for parity to parse options.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>.
+    pub fn gfm() -> Self {
+        Self {
+            gfm_tagfilter: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration that describes how to parse from markdown.
+///
+/// You can use this:
+///
+/// *   To control what markdown constructs are turned on and off
+/// *   To control some of those constructs
+/// *   To add support for certain programming languages when parsing MDX
+///
+/// In most cases, you will want to use the default trait or `gfm` method.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ParseOptions;
+/// # fn main() {
+///
+/// // Use the default trait to parse markdown according to `CommonMark`:
+/// let commonmark = ParseOptions::default();
+///
+/// // Use the `gfm` method to parse markdown according to GFM:
+/// let gfm = ParseOptions::gfm();
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct ParseOptions {
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+    /// Which constructs to enable and disable.
+    ///
+    /// The default is to follow `CommonMark`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("    indented code?"),
+    ///     "<pre><code>indented code?\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `constructs` to choose what to enable and disable:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "    indented code?",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 code_indented: false,
+    ///                 ..Constructs::default()
+    ///               },
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>indented code?</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub constructs: Constructs,
+
+    /// Definitions to use in addition to those in the document.
+    ///
+    /// The default is to have no extra definitions: references only match
+    /// definitions in the document.
+    /// Pass a map of labels to destinations (URLs) and optional titles to
+    /// define those labels for every document parsed with these options, which
+    /// is useful to share a glossary between fragments rendered on their own.
+    ///
+    /// Labels are normalized like labels in markdown, so `Alpha  Bravo`
+    /// matches `[alpha bravo]`.
+    /// Definitions in the document come first, so they take precedence.
+    ///
+    /// Syntax trees contain no `Definition` nodes for these definitions:
+    /// [`mdast_to_html()`][crate::mdast_to_html] uses them from the options
+    /// instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` only uses definitions in the document by default:
+    /// assert_eq!(to_html("[CommonMark]"), "<p>[CommonMark]</p>");
+    ///
+    /// // Pass `definitions` to add more:
+    /// let mut definitions = std::collections::BTreeMap::new();
+    /// definitions.insert(
+    ///     "commonmark".into(),
+    ///     ("https://commonmark.org".into(), Some("CommonMark".into())),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[CommonMark]",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 definitions,
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://commonmark.org\" title=\"CommonMark\">CommonMark</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub definitions: BTreeMap<String, (String, Option<String>)>,
+
+    /// Whether to support GFM strikethrough with a single tilde
+    ///
+    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// `constructs`.
+    /// This option does not affect strikethrough with double tildes.
+    ///
+    /// The default is `true`, which follows how markdown on `github.com`
+    /// works, as strikethrough with single tildes is supported.
+    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
+    /// strikethrough with single tildes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single tildes by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><del>a</del></p>"
+    /// );
+    ///
+    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_strikethrough_single_tilde: false,
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>~a~</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_strikethrough_single_tilde: bool,
+
+    /// Whether to support math (text) with a single dollar
+    ///
+    /// This option does nothing if `math_text` is not turned on in
+    /// `constructs`.
+    /// This option does not affect math (text) with two or more dollars.
+    ///
+    /// The default is `true`, which is more close to how code (text) and
+    /// Pandoc work, as it allows math with a single dollar to form.
+    /// However, single dollars can interfere with “normal” dollars in text.
+    /// Pass `false`, to only allow math (text) to form when two or more
+    /// dollars are used.
+    /// If you pass `false`, you can still use two or more dollars for text
+    /// math.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single dollars by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               math_text_single_dollar: false,
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>$a$</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_text_single_dollar: bool,
+
+    /// Whether to add positional info to nodes in the syntax tree.
+    ///
+    /// This option only affects [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// The default is `true`, which adds a [`Position`][crate::unist::Position]
+    /// to every node.
+    /// Pass `false` to leave every `position` as `None`, which uses less
+    /// memory and is faster, when you do not need to know where nodes come
+    /// from.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` adds positions by default:
+    /// assert!(to_mdast("a", &ParseOptions::default())?.position().is_some());
+    ///
+    /// // Pass `mdast_positions: false` to turn that off:
+    /// assert_eq!(
+    ///     to_mdast(
+    ///         "a",
+    ///         &ParseOptions {
+    ///             mdast_positions: false,
+    ///             ..ParseOptions::default()
+    ///         }
+    ///     )?
+    ///     .position(),
+    ///     None
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub mdast_positions: bool,
+
+    /// Whether to add positional info of markers to some nodes in the syntax
+    /// tree.
+    ///
+    /// This option only affects [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// The default is `false`.
+    /// Pass `true` to fill the `markers` field of headings (the sequences of
+    /// number signs, or of the underline), inline code (the sequences of
+    /// grave accents), and links (the brackets and parens, or the angle
+    /// brackets of autolinks), such as for decorations in editors.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add marker positions by default:
+    /// let tree = to_mdast("## a ##", &ParseOptions::default())?;
+    /// if let Node::Heading(heading) = &tree.children().unwrap()[0] {
+    ///     assert!(heading.markers.is_empty());
+    /// }
+    ///
+    /// // Pass `mdast_marker_positions: true` to add them:
+    /// let tree = to_mdast(
+    ///     "## a ##",
+    ///     &ParseOptions {
+    ///         mdast_marker_positions: true,
+    ///         ..ParseOptions::default()
+    ///     },
+    /// )?;
+    /// if let Node::Heading(heading) = &tree.children().unwrap()[0] {
+    ///     assert_eq!(
+    ///         format!("{:?}", heading.markers),
+    ///         "[1:1-1:3 (0-2), 1:6-1:8 (5-7)]"
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub mdast_marker_positions: bool,
+
+    /// Unit that columns and offsets count in, in positions in the syntax
+    /// tree.
+    ///
+    /// This option only affects [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// The default is [`PositionEncoding::Utf8`][], which counts bytes, so
+    /// that an offset can be used to slice the input.
+    /// Pass [`PositionEncoding::Utf16`][] to count like the language server
+    /// protocol and JavaScript do, or [`PositionEncoding::CodePoints`][] to
+    /// count characters.
+    /// With those, a tab counts as one unit in columns, like in the language
+    /// server protocol, instead of up to the next tab stop.
+    /// Lines are not affected.
+    /// The `stops` of MDX expressions and ESM are not affected either: they
+    /// are byte indices into the value of those nodes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, ParseOptions, PositionEncoding};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` counts UTF-8 bytes by default:
+    /// assert_eq!(
+    ///     format!("{:?}", to_mdast("😀", &ParseOptions::default())?.position()),
+    ///     "Some(1:1-1:5 (0-4))"
+    /// );
+    ///
+    /// // Pass `position_encoding: PositionEncoding::Utf16` to count UTF-16 code units:
+    /// assert_eq!(
+    ///     format!(
+    ///         "{:?}",
+    ///         to_mdast(
+    ///             "😀",
+    ///             &ParseOptions {
+    ///                 position_encoding: PositionEncoding::Utf16,
+    ///                 ..ParseOptions::default()
+    ///             }
+    ///         )?
+    ///         .position()
+    ///     ),
+    ///     "Some(1:1-1:3 (0-2))"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub position_encoding: PositionEncoding,
+
+    /// Whether to report definitions that are not used because an earlier
+    /// definition has the same identifier.
+    ///
+    /// This option only affects
+    /// [`to_mdast_with_messages()`][crate::to_mdast_with_messages()].
+    ///
+    /// The default is `false`.
+    /// Pass `true` to get a message, with the position of the duplicate, for
+    /// each such definition, which is useful for linting.
+    /// Like in `CommonMark`, the first definition is still the one that is
+    /// used: this does not change the syntax tree or HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast_with_messages, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not report duplicate definitions by default:
+    /// let (_, messages) = to_mdast_with_messages("[a]: b\n[A]: c", &ParseOptions::default())?;
+    /// assert!(messages.is_empty());
+    ///
+    /// // Pass `report_duplicate_definitions: true` to report them:
+    /// let (_, messages) = to_mdast_with_messages(
+    ///     "[a]: b\n[A]: c",
+    ///     &ParseOptions {
+    ///         report_duplicate_definitions: true,
+    ///         ..ParseOptions::default()
+    ///     }
+    /// )?;
+    /// assert_eq!(
+    ///     messages[0].to_string(),
+    ///     "2:1-2:7: Unexpected duplicate definition `A`, expected unique identifiers (the first definition, at 1:1, is used) (markdown-rs:duplicate-definition)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub report_duplicate_definitions: bool,
+
+    /// Function to parse expressions with.
+    ///
+    /// This function can be used to add support for arbitrary programming
+    /// languages within expressions.
+    ///
+    /// It only makes sense to pass this when compiling to a syntax tree
+    /// with [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// For an example that adds support for JavaScript with SWC, see
+    /// `tests/test_utils/mod.rs`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub mdx_expression_parse: Option<Box<MdxExpressionParse>>,
+
+    /// Function to parse ESM with.
+    ///
+    /// This function can be used to add support for arbitrary programming
+    /// languages within ESM blocks, however, the keywords (`export`,
+    /// `import`) are currently hardcoded JavaScript-specific.
+    ///
+    /// > 👉 **Note**: please raise an issue if you’re interested in working on
+    /// > MDX that is aware of, say, Rust, or other programming languages.
+    ///
+    /// It only makes sense to pass this when compiling to a syntax tree
+    /// with [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// For an example that adds support for JavaScript with SWC, see
+    /// `tests/test_utils/mod.rs`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub mdx_esm_parse: Option<Box<MdxEsmParse>>,
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("constructs", &self.constructs)
+            .field("definitions", &self.definitions)
+            .field(
+                "gfm_strikethrough_single_tilde",
+                &self.gfm_strikethrough_single_tilde,
+            )
+            .field("math_text_single_dollar", &self.math_text_single_dollar)
+            .field("mdast_positions", &self.mdast_positions)
+            .field("mdast_marker_positions", &self.mdast_marker_positions)
+            .field("position_encoding", &self.position_encoding)
+            .field(
+                "report_duplicate_definitions",
+                &self.report_duplicate_definitions,
+            )
+            .field(
+                "mdx_expression_parse",
+                &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "mdx_esm_parse",
+                &self.mdx_esm_parse.as_ref().map(|_d| "[Function]"),
+            )
+            .finish()
+    }
+}
+
+impl Default for ParseOptions {
+    /// `CommonMark` defaults.
+    fn default() -> Self {
+        Self {
+            constructs: Constructs::default(),
+            definitions: BTreeMap::new(),
+            gfm_strikethrough_single_tilde: true,
+            math_text_single_dollar: true,
+            mdast_positions: true,
+            mdast_marker_positions: false,
+            position_encoding: PositionEncoding::default(),
+            report_duplicate_definitions: false,
+            mdx_expression_parse: None,
+            mdx_esm_parse: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// GFM.
+    ///
+    /// GFM stands for GitHub flavored markdown.
+    /// GFM extends `CommonMark` and adds support for autolink literals,
+    /// footnotes, strikethrough, tables, and tasklists.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>
+    pub fn gfm() -> Self {
+        Self {
+            constructs: Constructs::gfm(),
+            ..Self::default()
+        }
+    }
+
+    /// MDX.
+    ///
+    /// This turns on `CommonMark`, turns off some conflicting constructs
+    /// (autolinks, code (indented), and HTML), and turns on MDX (ESM,
+    /// expressions, and JSX).
+    ///
+    /// For more information, see the MDX website:
+    /// <https://mdxjs.com>.
+    ///
+    /// > 👉 **Note**: to support ESM, you *must* pass
+    /// > [`mdx_esm_parse`][MdxEsmParse] in [`ParseOptions`][] too.
+    /// > Otherwise, ESM is treated as normal markdown.
+    /// >
+    /// > You *can* pass
+    /// > [`mdx_expression_parse`][MdxExpressionParse]
+    /// > to parse expressions according to a certain grammar (typically, a
+    /// > programming language).
+    /// > Otherwise, expressions are parsed with a basic algorithm that only
+    /// > cares about braces.
+    pub fn mdx() -> Self {
+        Self {
+            constructs: Constructs::mdx(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration that describes how to parse from markdown and compile to
+/// HTML.
+///
+/// In most cases, you will want to use the default trait or `gfm` method.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::Options;
+/// # fn main() {
+///
+/// // Use the default trait to compile markdown to HTML according to `CommonMark`:
+/// let commonmark = Options::default();
+///
+/// // Use the `gfm` method to compile markdown to HTML according to GFM:
+/// let gfm = Options::gfm();
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct Options {
+    /// Configuration that describes how to parse from markdown.
+    pub parse: ParseOptions,
+    /// Configuration that describes how to compile to HTML.
+    pub compile: CompileOptions,
+    /// Function to turn the text of a heading into a slug, used for `id`s.
+    ///
+    /// The default is `None`, which makes slugs like GitHub does.
+    /// Pass a function to change that, such as to transliterate non-ASCII
+    /// characters or to drop certain words.
+    /// The results are made unique afterwards: when a slug was already used,
+    /// a suffix (`-1`, `-2`, and so on) is added.
+    /// An empty slug results in no `id`.
+    ///
+    /// This option does nothing if
+    /// [`heading_ids`][CompileOptions::heading_ids] is not turned on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps non-ASCII characters in slugs by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# Über\n\n# Uber",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 id=\"über\">Über</h1>\n<h1 id=\"uber\">Uber</h1>"
+    /// );
+    ///
+    /// // Pass `slugify` to make slugs differently:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# Über\n\n# Uber",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             slugify: Some(Box::new(|value| {
+    ///                 value.to_lowercase().replace('ü', "u")
+    ///             })),
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 id=\"uber\">Über</h1>\n<h1 id=\"uber-1\">Uber</h1>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub slugify: Option<Box<Slugify>>,
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+}
+
+impl fmt::Debug for Options {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Options")
+            .field("parse", &self.parse)
+            .field("compile", &self.compile)
+            .field("slugify", &self.slugify.as_ref().map(|_d| "[Function]"))
+            .finish()
+    }
+}
+
+impl Options {
+    /// GFM.
+    ///
+    /// GFM stands for GitHub flavored markdown.
+    /// GFM extends `CommonMark` and adds support for autolink literals,
+    /// footnotes, strikethrough, tables, and tasklists.
+    /// On the compilation side, GFM turns on the GFM tag filter.
+    /// The tagfilter is useless, but it’s included here for consistency.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>
+    pub fn gfm() -> Self {
+        Self {
+            parse: ParseOptions::gfm(),
+            compile: CompileOptions::gfm(),
+            slugify: None,
+        }
+    }
+}
+
+/// Configuration that describes how to serialize a syntax tree to markdown.
+///
+/// The defaults match the markdown style of Prettier.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::SerializeOptions;
+/// # fn main() {
+///
+/// // Use the default trait to get Prettier’s style:
+/// let prettier = SerializeOptions::default();
+///
+/// // Or, change some things:
+/// let custom = SerializeOptions {
+///   bullet: '*',
+///   emphasis: '*',
+///   ..SerializeOptions::default()
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct SerializeOptions {
+    /// Marker to use for unordered list items.
+    ///
+    /// The default is `'-'`.
+    /// Can also be `'*'` or `'+'`.
+    ///
+    /// The other marker ([`bullet_other`][SerializeOptions::bullet_other])
+    /// is used where this one cannot be: for a list directly after another
+    /// list, as they would otherwise join, and for lists that would turn into
+    /// a thematic break.
+    ///
+    /// Only used for lists whose first item has no `marker` (such as lists
+    /// made programmatically), as lists from markdown keep their marker where
+    /// they can.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::build::{list, list_item, paragraph, root, text};
+    /// use markdown::{mdast_to_markdown_with_options, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = root(vec![list(
+    ///     false,
+    ///     vec![
+    ///         list_item(vec![paragraph(vec![text("a")])]),
+    ///         list_item(vec![paragraph(vec![text("b")])]),
+    ///     ],
+    /// )]);
+    ///
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             bullet: '+',
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "+ a\n+ b\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub bullet: char,
+
+    /// Marker to use for unordered list items where
+    /// [`bullet`][SerializeOptions::bullet] cannot be used.
+    ///
+    /// The default is `None`, which uses `'*'` if `bullet` is `'-'`, and
+    /// `'-'` otherwise.
+    /// Can also be `'*'`, `'+'`, or `'-'`, but must be different from
+    /// `bullet`.
+    ///
+    /// It is used for a list directly after another list, as they would
+    /// otherwise join, for lists whose items start with a thematic break made
+    /// with the same marker (`- ---`), and for a list whose first item is
+    /// empty, when it is directly in the first items of two lists that use
+    /// the same marker (`- - -`), as those would be thematic breaks.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::build::{list, list_item, root};
+    /// use markdown::{mdast_to_markdown, mdast_to_markdown_with_options, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = root(vec![list(
+    ///     false,
+    ///     vec![list_item(vec![list(
+    ///         false,
+    ///         vec![list_item(vec![list(false, vec![list_item(vec![])])])],
+    ///     )])],
+    /// )]);
+    ///
+    /// // `markdown-rs` uses `*` as the other bullet by default:
+    /// assert_eq!(mdast_to_markdown(&tree)?, "- - *\n");
+    ///
+    /// // Pass `bullet_other: Some('+')` to use `+`:
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             bullet_other: Some('+'),
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "- - +\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub bullet_other: Option<char>,
+
+    /// Whether to add a closing sequence to ATX headings.
+    ///
+    /// The default is `false`, which serializes headings such as `## a`.
+    /// Pass `true` to add as many `#`s as there are at the start to the end
+    /// as well, such as `## a ##`.
+    /// The parser drops closing sequences, so the tree does not know whether
+    /// the source had them.
+    ///
+    /// Setext headings are not affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast_to_markdown, mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("## a ##", &ParseOptions::default())?;
+    ///
+    /// // `markdown-rs` does not add closing sequences by default:
+    /// assert_eq!(mdast_to_markdown(&tree)?, "## a\n");
+    ///
+    /// // Pass `close_atx: true` to add them:
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             close_atx: true,
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "## a ##\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub close_atx: bool,
+
+    /// Marker to use for emphasis.
+    ///
+    /// The default is `'_'`.
+    /// Can also be `'*'`.
+    ///
+    /// Only used for emphasis without a `marker` (such as emphasis made
+    /// programmatically), as emphasis from markdown keeps its marker.
+    /// Underscores do not work inside words, so asterisks are used there.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::build::{emphasis, paragraph, root, text};
+    /// use markdown::{mdast_to_markdown_with_options, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = root(vec![paragraph(vec![
+    ///     emphasis(vec![text("a")]),
+    ///     text(" b"),
+    ///     emphasis(vec![text("c")]),
+    ///     text("d"),
+    /// ])]);
+    ///
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             emphasis: '*',
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "*a* b*c*d\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub emphasis: char,
+
+    /// Marker to use for fenced code.
+    ///
+    /// The default is `` '`' ``.
+    /// Can also be `'~'`.
+    ///
+    /// Tildes are used when the info string contains a grave accent, as that
+    /// cannot occur after grave accents.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("```js\na\n```", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             fence: '~',
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "~~~js\na\n~~~\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fence: char,
+
+    /// Minimum number of markers in a fence of fenced code.
+    ///
+    /// The default is `3`, which is also the minimum.
+    /// Fences are longer when the code contains a sequence of that many
+    /// markers.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("```\na\n```", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             fence_length: 5,
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "`````\na\n`````\n"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_strikethrough_single_tilde: bool,
+    pub fence_length: usize,
 
-    /// Whether to support math (text) with a single dollar
+    /// Whether to increment the number of ordered list items.
     ///
-    /// This option does nothing if `math_text` is not turned on in
-    /// `constructs`.
-    /// This option does not affect math (text) with two or more dollars.
+    /// The default is `true`, which numbers items `1.`, `2.`, `3.`.
+    /// Pass `false` to use the number of the first item (typically `1.`) for
+    /// all items.
     ///
-    /// The default is `true`, which is more close to how code (text) and
-    /// Pandoc work, as it allows math with a single dollar to form.
-    /// However, single dollars can interfere with “normal” dollars in text.
-    /// Pass `false`, to only allow math (text) to form when two or more
-    /// dollars are used.
-    /// If you pass `false`, you can still use two or more dollars for text
-    /// math.
+    /// Only used for items without a `number` (such as items made
+    /// programmatically), as items from markdown keep their number.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::mdast::build::{list, list_item, paragraph, root, text};
+    /// use markdown::{mdast_to_markdown_with_options, SerializeOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single dollars by default:
+    /// let tree = root(vec![list(
+    ///     true,
+    ///     vec![
+    ///         list_item(vec![paragraph(vec![text("a")])]),
+    ///         list_item(vec![paragraph(vec![text("b")])]),
+    ///     ],
+    /// )]);
+    ///
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "$a$",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               ..ParseOptions::default()
-    ///             },
-    ///             ..Options::default()
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             increment_list_marker: false,
+    ///             ..SerializeOptions::default()
     ///         }
     ///     )?,
-    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    ///     "1. a\n1. b\n"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub increment_list_marker: bool,
+
+    /// Width to wrap paragraphs at.
+    ///
+    /// The default is `None`, which keeps line endings as they are in the
+    /// tree and does not add new ones.
+    /// Pass a number of characters to turn spaces in the text of paragraphs
+    /// into line endings where lines would otherwise be longer.
+    /// Lines can still be longer when they contain a long word or other
+    /// content.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("alpha bravo charlie delta", &ParseOptions::default())?;
     ///
-    /// // Pass `math_text_single_dollar: false` to turn that off:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "$a$",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               math_text_single_dollar: false,
-    ///               ..ParseOptions::default()
-    ///             },
-    ///             ..Options::default()
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             line_width: Some(12),
+    ///             ..SerializeOptions::default()
     ///         }
     ///     )?,
-    ///     "<p>$a$</p>"
+    ///     "alpha bravo\ncharlie\ndelta\n"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub math_text_single_dollar: bool,
+    pub line_width: Option<usize>,
 
-    /// Function to parse expressions with.
-    ///
-    /// This function can be used to add support for arbitrary programming
-    /// languages within expressions.
-    ///
-    /// It only makes sense to pass this when compiling to a syntax tree
-    /// with [`to_mdast()`][crate::to_mdast()].
+    /// Thematic break to use.
     ///
-    /// For an example that adds support for JavaScript with SWC, see
-    /// `tests/test_utils/mod.rs`.
-    pub mdx_expression_parse: Option<Box<MdxExpressionParse>>,
-
-    /// Function to parse ESM with.
+    /// The default is `"---"`.
+    /// Can be three or more of the same marker (`*`, `-`, or `_`), optionally
+    /// with spaces or tabs between them, such as `"* * *"`.
     ///
-    /// This function can be used to add support for arbitrary programming
-    /// languages within ESM blocks, however, the keywords (`export`,
-    /// `import`) are currently hardcoded JavaScript-specific.
+    /// ## Examples
     ///
-    /// > 👉 **Note**: please raise an issue if you’re interested in working on
-    /// > MDX that is aware of, say, Rust, or other programming languages.
+    /// ```
+    /// use markdown::{mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// It only makes sense to pass this when compiling to a syntax tree
-    /// with [`to_mdast()`][crate::to_mdast()].
+    /// let tree = to_mdast("***", &ParseOptions::default())?;
     ///
-    /// For an example that adds support for JavaScript with SWC, see
-    /// `tests/test_utils/mod.rs`.
-    pub mdx_esm_parse: Option<Box<MdxEsmParse>>,
-    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
-}
-
-impl fmt::Debug for ParseOptions {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ParseOptions")
-            .field("constructs", &self.constructs)
-            .field(
-                "gfm_strikethrough_single_tilde",
-                &self.gfm_strikethrough_single_tilde,
-            )
-            .field("math_text_single_dollar", &self.math_text_single_dollar)
-            .field(
-                "mdx_expression_parse",
-                &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
-            )
-            .field(
-                "mdx_esm_parse",
-                &self.mdx_esm_parse.as_ref().map(|_d| "[Function]"),
-            )
-            .finish()
-    }
-}
-
-impl Default for ParseOptions {
-    /// `CommonMark` defaults.
-    fn default() -> Self {
-        Self {
-            constructs: Constructs::default(),
-            gfm_strikethrough_single_tilde: true,
-            math_text_single_dollar: true,
-            mdx_expression_parse: None,
-            mdx_esm_parse: None,
-        }
-    }
-}
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             rule: "_ _ _".into(),
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "_ _ _\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub rule: String,
 
-impl ParseOptions {
-    /// GFM.
+    /// Marker to use for strong.
     ///
-    /// GFM stands for GitHub flavored markdown.
-    /// GFM extends `CommonMark` and adds support for autolink literals,
-    /// footnotes, strikethrough, tables, and tasklists.
+    /// The default is `'*'`.
+    /// Can also be `'_'`.
     ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>
-    pub fn gfm() -> Self {
-        Self {
-            constructs: Constructs::gfm(),
-            ..Self::default()
-        }
-    }
-
-    /// MDX.
+    /// Only used for strong without a `marker` (such as strong made
+    /// programmatically), as strong from markdown keeps its marker.
+    /// Underscores do not work inside words, so asterisks are used there.
     ///
-    /// This turns on `CommonMark`, turns off some conflicting constructs
-    /// (autolinks, code (indented), and HTML), and turns on MDX (ESM,
-    /// expressions, and JSX).
+    /// ## Examples
     ///
-    /// For more information, see the MDX website:
-    /// <https://mdxjs.com>.
+    /// ```
+    /// use markdown::mdast::build::{strong, paragraph, root, text};
+    /// use markdown::{mdast_to_markdown_with_options, SerializeOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// > 👉 **Note**: to support ESM, you *must* pass
-    /// > [`mdx_esm_parse`][MdxEsmParse] in [`ParseOptions`][] too.
-    /// > Otherwise, ESM is treated as normal markdown.
-    /// >
-    /// > You *can* pass
-    /// > [`mdx_expression_parse`][MdxExpressionParse]
-    /// > to parse expressions according to a certain grammar (typically, a
-    /// > programming language).
-    /// > Otherwise, expressions are parsed with a basic algorithm that only
-    /// > cares about braces.
-    pub fn mdx() -> Self {
-        Self {
-            constructs: Constructs::mdx(),
-            ..Self::default()
-        }
-    }
-}
-
-/// Configuration that describes how to parse from markdown and compile to
-/// HTML.
-///
-/// In most cases, you will want to use the default trait or `gfm` method.
-///
-/// ## Examples
-///
-/// ```
-/// use markdown::Options;
-/// # fn main() {
-///
-/// // Use the default trait to compile markdown to HTML according to `CommonMark`:
-/// let commonmark = Options::default();
-///
-/// // Use the `gfm` method to compile markdown to HTML according to GFM:
-/// let gfm = Options::gfm();
-/// # }
-/// ```
-#[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Default)]
-pub struct Options {
-    /// Configuration that describes how to parse from markdown.
-    pub parse: ParseOptions,
-    /// Configuration that describes how to compile to HTML.
-    pub compile: CompileOptions,
+    /// let tree = root(vec![paragraph(vec![
+    ///     strong(vec![text("a")]),
+    ///     text(" b"),
+    ///     strong(vec![text("c")]),
+    ///     text("d"),
+    /// ])]);
+    ///
+    /// assert_eq!(
+    ///     mdast_to_markdown_with_options(
+    ///         &tree,
+    ///         &SerializeOptions {
+    ///             strong: '_',
+    ///             ..SerializeOptions::default()
+    ///         }
+    ///     )?,
+    ///     "__a__ b**c**d\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub strong: char,
 }
 
-impl Options {
-    /// GFM.
-    ///
-    /// GFM stands for GitHub flavored markdown.
-    /// GFM extends `CommonMark` and adds support for autolink literals,
-    /// footnotes, strikethrough, tables, and tasklists.
-    /// On the compilation side, GFM turns on the GFM tag filter.
-    /// The tagfilter is useless, but it’s included here for consistency.
-    ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>
-    pub fn gfm() -> Self {
+impl Default for SerializeOptions {
+    /// Prettier’s markdown style.
+    fn default() -> Self {
         Self {
-            parse: ParseOptions::gfm(),
-            compile: CompileOptions::gfm(),
+            bullet: '-',
+            bullet_other: None,
+            close_atx: false,
+            emphasis: '_',
+            fence: '`',
+            fence_length: 3,
+            increment_list_marker: true,
+            line_width: None,
+            rule: "---".into(),
+            strong: '*',
         }
     }
 }
@@ -1282,6 +2960,8 @@ mod tests {
     #[test]
     fn test_constructs() {
         Constructs::default();
+        Constructs::none();
+        Constructs::all();
         Constructs::gfm();
         Constructs::mdx();
 
@@ -1320,6 +3000,63 @@ mod tests {
         );
         assert!(constructs.mdx_jsx_flow, "should support `mdx` shortcut (3)");
         assert!(!constructs.frontmatter, "should support `mdx` shortcut (4)");
+
+        let constructs = Constructs::none();
+        assert!(!constructs.attention, "should support `none` shortcut (1)");
+        assert!(
+            !constructs.gfm_autolink_literal,
+            "should support `none` shortcut (2)"
+        );
+        assert!(
+            !constructs.mdx_jsx_flow,
+            "should support `none` shortcut (3)"
+        );
+        assert!(
+            !constructs.frontmatter,
+            "should support `none` shortcut (4)"
+        );
+        assert_eq!(
+            Constructs {
+                attention: true,
+                ..Constructs::none()
+            },
+            Constructs {
+                autolink: false,
+                block_quote: false,
+                character_escape: false,
+                character_reference: false,
+                code_indented: false,
+                code_fenced: false,
+                code_text: false,
+                definition: false,
+                hard_break_escape: false,
+                hard_break_trailing: false,
+                heading_atx: false,
+                heading_setext: false,
+                html_flow: false,
+                html_text: false,
+                label_start_image: false,
+                label_start_link: false,
+                label_end: false,
+                list_item: false,
+                thematic_break: false,
+                ..Constructs::default()
+            },
+            "should support `none` shortcut (5)"
+        );
+
+        let constructs = Constructs::all();
+        assert!(constructs.attention, "should support `all` shortcut (1)");
+        assert!(
+            constructs.gfm_autolink_literal,
+            "should support `all` shortcut (2)"
+        );
+        assert!(
+            !constructs.mdx_jsx_flow,
+            "should support `all` shortcut (3)"
+        );
+        assert!(constructs.frontmatter, "should support `all` shortcut (4)");
+        assert!(constructs.math_text, "should support `all` shortcut (5)");
     }
 
     #[test]
@@ -1372,7 +3109,7 @@ mod tests {
 
         assert_eq!(
             format!("{:?}", ParseOptions::default()),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: None, mdx_esm_parse: None }",
+            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, definitions: {}, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdast_positions: true, mdast_marker_positions: false, position_encoding: Utf8, report_duplicate_definitions: false, mdx_expression_parse: None, mdx_esm_parse: None }",
             "should support `Debug` trait"
         );
         assert_eq!(
@@ -1385,7 +3122,7 @@ mod tests {
                 })),
                 ..Default::default()
             }),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
+            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, definitions: {}, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdast_positions: true, mdast_marker_positions: false, position_encoding: Utf8, report_duplicate_definitions: false, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
             "should support `Debug` trait on mdx functions"
         );
     }
@@ -1416,6 +3153,60 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_options_serde() {
+        let options: Options = serde_json::from_str(
+            r#"{
+                "parse": {"constructs": {"gfmTable": true}, "mathTextSingleDollar": false},
+                "compile": {"allowDangerousHtml": true, "defaultLineEnding": "\r\n"}
+            }"#,
+        )
+        .unwrap();
+        assert!(
+            options.parse.constructs.gfm_table,
+            "should support deserializing constructs"
+        );
+        assert!(
+            options.parse.constructs.attention,
+            "should default missing constructs"
+        );
+        assert!(
+            !options.parse.math_text_single_dollar,
+            "should support deserializing parse options"
+        );
+        assert!(
+            options.parse.gfm_strikethrough_single_tilde,
+            "should default missing parse options"
+        );
+        assert!(
+            options.compile.allow_dangerous_html,
+            "should support deserializing compile options"
+        );
+        assert_eq!(
+            options.compile.default_line_ending,
+            LineEnding::CarriageReturnLineFeed,
+            "should support deserializing line endings"
+        );
+
+        let options: Options = serde_json::from_str("{}").unwrap();
+        assert_eq!(
+            options.parse.constructs,
+            Constructs::default(),
+            "should default everything"
+        );
+    }
+
+    #[test]
+    fn test_serialize_options() {
+        let options = SerializeOptions::default();
+        assert_eq!(options.bullet, '-', "should default to Prettier (1)");
+        assert_eq!(options.emphasis, '_', "should default to Prettier (2)");
+        assert_eq!(options.strong, '*', "should default to Prettier (3)");
+        assert_eq!(options.rule, "---", "should default to Prettier (4)");
+        assert_eq!(options.line_width, None, "should default to Prettier (5)");
+    }
+
     #[test]
     fn test_options() {
         Options::default();
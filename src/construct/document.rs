@@ -481,6 +481,13 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
 
     // Flush if needed.
     if *phase != Phase::After {
+        // When a new container closes the flow, the flow does not continue on
+        // this line, so it must not take the line ending before it (such as
+        // in unclosed fenced code).
+        if *phase == Phase::Prefix {
+            child.lazy = true;
+        }
+
         let state = tokenizer
             .tokenize_state
             .document_child_state
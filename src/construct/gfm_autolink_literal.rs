@@ -612,7 +612,8 @@ pub fn trail_char_ref_inside(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
-/// Resolve: postprocess text to find email autolink literals.
+/// Resolve: postprocess text to find email autolink literals, and turn
+/// protocol and www autolink literals in links into data.
 pub fn resolve(tokenizer: &mut Tokenizer) {
     tokenizer.map.consume(&mut tokenizer.events);
 
@@ -626,6 +627,18 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
             if event.name == Name::Link {
                 links += 1;
             }
+            // Links cannot contain links: when a URL is (part of) the text
+            // of a link, it is not an autolink literal but data.
+            else if links > 0
+                && matches!(
+                    event.name,
+                    Name::GfmAutolinkLiteralProtocol | Name::GfmAutolinkLiteralWww
+                )
+            {
+                tokenizer.events[index].name = Name::Data;
+                tokenizer.events[index + 1].name = Name::Data;
+                index += 1;
+            }
         } else {
             if event.name == Name::Data && links == 0 {
                 let slice = Slice::from_position(
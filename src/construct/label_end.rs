@@ -277,7 +277,7 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         id = new_id;
     }
 
-    let defined = tokenizer.parse_state.definitions.contains(&id);
+    let defined = tokenizer.parse_state.is_defined(&id);
 
     match tokenizer.current {
         // Resource (`[asd](fgh)`)?
@@ -592,9 +592,8 @@ pub fn reference_full_after(tokenizer: &mut Tokenizer) -> State {
 
     if tokenizer
         .parse_state
-        .definitions
         // We don’t care about virtual spaces, so `as_str` is fine.
-        .contains(&normalize_identifier(
+        .is_defined(&normalize_identifier(
             Slice::from_position(
                 tokenizer.parse_state.bytes,
                 &Position::from_exit_event(
@@ -762,6 +761,9 @@ fn inject_labels(tokenizer: &mut Tokenizer, labels: &[Label]) {
                     link: None,
                 }],
             );
+            // Before the label text exit, as it is at the same place if the
+            // caret is the only thing in the label.
+            tokenizer.map.add(label.start.1 + 1, 0, caret);
             tokenizer.map.add(
                 label.end.0,
                 0,
@@ -774,10 +776,6 @@ fn inject_labels(tokenizer: &mut Tokenizer, labels: &[Label]) {
             );
         }
 
-        if !caret.is_empty() {
-            tokenizer.map.add(label.start.1 + 1, 0, caret);
-        }
-
         // Insert a label exit.
         tokenizer.map.add(
             label.end.0 + 4,
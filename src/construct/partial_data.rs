@@ -62,8 +62,9 @@ pub fn at_break(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn inside(tokenizer: &mut Tokenizer) -> State {
     if let Some(byte) = tokenizer.current {
-        if byte != b'\n' && !tokenizer.tokenize_state.markers.contains(&byte) {
-            tokenizer.consume();
+        let markers = tokenizer.tokenize_state.markers;
+        if byte != b'\n' && !markers.contains(&byte) {
+            tokenizer.consume_run(markers);
             return State::Next(StateName::DataInside);
         }
     }
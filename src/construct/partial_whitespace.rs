@@ -60,7 +60,7 @@
 use crate::event::{Event, Kind, Name};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::HARD_BREAK_PREFIX_SIZE_MIN,
+    constant::{HARD_BREAK_PREFIX_SIZE_MIN, TAB_SIZE},
     slice::{Position, Slice},
 };
 use alloc::vec;
@@ -184,9 +184,18 @@ fn trim_data(
         if index > 0 || slice.before > 0 {
             let enter_point = tokenizer.events[exit_index - 1].point.clone();
             let mut exit_point = enter_point.clone();
-            exit_point.index += index;
-            exit_point.column += index;
-            exit_point.vs = 0;
+
+            // Move past the rest of a tab that the data starts in.
+            if exit_point.vs > 0 {
+                exit_point.index += 1;
+                exit_point.column += TAB_SIZE - (exit_point.column - 1) % TAB_SIZE;
+                exit_point.vs = 0;
+            }
+
+            if index > 0 {
+                exit_point =
+                    exit_point.shift_to(tokenizer.parse_state.bytes, exit_point.index + index);
+            }
 
             tokenizer.map.add(
                 exit_index - 1,
@@ -232,7 +232,7 @@ pub fn data(tokenizer: &mut Tokenizer) -> State {
         tokenizer.exit(tokenizer.tokenize_state.token_3.clone());
         State::Retry(StateName::RawTextBetween)
     } else {
-        tokenizer.consume();
+        tokenizer.consume_run(&[tokenizer.tokenize_state.marker]);
         State::Next(StateName::RawTextData)
     }
 }
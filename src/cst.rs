@@ -0,0 +1,109 @@
+//! Concrete syntax tree.
+//!
+//! This is the tree made by [`to_cst()`][crate::to_cst].
+//! Where [mdast][crate::mdast] is abstract, this tree has a node for each
+//! span of the [events][crate::event], including whitespace
+//! (`SpaceOrTab`, `LineEnding`), markers (such as `BlockQuoteMarker`),
+//! fences, and the backslashes of escapes.
+//! Nothing is lost: each byte of the markdown is in exactly one leaf (a node
+//! without children), and the leaves, in order, make up the whole markdown.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::{event::Name, to_cst, ParseOptions};
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! let value = "> a";
+//! let nodes = to_cst(value, &ParseOptions::default())?;
+//!
+//! assert_eq!(nodes[0].name, Name::BlockQuote);
+//! assert_eq!(nodes[0].children[0].name, Name::BlockQuotePrefix);
+//! assert_eq!(nodes[0].children[0].source(value), "> ");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::event::{Event, Kind, Name, Point};
+use crate::util::slice::{Position, Slice};
+use alloc::{vec, vec::Vec};
+
+/// Span of markdown.
+#[derive(Clone, Debug)]
+pub struct Node {
+    /// Name of the span, such as `Paragraph` or `LineEnding`.
+    pub name: Name,
+    /// Where the span starts.
+    pub start: Point,
+    /// Where the span ends.
+    pub end: Point,
+    /// Spans in this span.
+    ///
+    /// Empty for leaves.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Get the markdown of the node from `value`, the markdown the tree was
+    /// made from.
+    ///
+    /// A tab that is split between nodes (such as in `>\ta`, where part of
+    /// it belongs to the block quote prefix and the rest to the paragraph) is
+    /// in the source of the first node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_cst, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let value = "# *a*";
+    /// let nodes = to_cst(value, &ParseOptions::default())?;
+    ///
+    /// assert_eq!(nodes[0].source(value), "# *a*");
+    /// assert_eq!(nodes[0].children[2].source(value), "*a*");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn source<'a>(&self, value: &'a str) -> &'a str {
+        Slice::from_position(
+            value.as_bytes(),
+            &Position {
+                start: &self.start,
+                end: &self.end,
+            },
+        )
+        .as_str()
+    }
+}
+
+/// Turn events into nodes.
+pub(crate) fn from_events(events: Vec<Event>) -> Vec<Node> {
+    let mut result = vec![];
+    // Nodes that are entered but not yet exited.
+    let mut stack: Vec<Node> = vec![];
+
+    for event in events {
+        if event.kind == Kind::Enter {
+            stack.push(Node {
+                name: event.name,
+                start: event.point.clone(),
+                end: event.point,
+                children: vec![],
+            });
+        } else {
+            let mut node = stack.pop().expect("expected open node");
+            node.end = event.point;
+
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(node);
+            } else {
+                result.push(node);
+            }
+        }
+    }
+
+    debug_assert!(stack.is_empty(), "expected no open nodes");
+    result
+}
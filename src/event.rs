@@ -1,7 +1,41 @@
 //! Semantic labels of things happening.
+//!
+//! Events are what the tokenizer makes of markdown, before they are turned
+//! into HTML or a syntax tree.
+//! Get them with [`to_events()`][crate::to_events].
+//! They are useful for things that need every bit of syntax, such as
+//! syntax highlighters and linters.
+//!
+//! Each span of markdown is an [`Event`][] of kind [`Kind::Enter`][] where
+//! it starts and one of kind [`Kind::Exit`][] where it ends, with the same
+//! [`Name`][].
+//! Events are guaranteed to be:
+//!
+//! *   paired: each enter is followed by its exit, and spans nest like a
+//!     tree, so the exit of a span comes before the exit of the span it is in
+//! *   ordered: points never go back, by `index` and `vs`, and by `line`
+//!     and `column`
+//! *   void where [`VOID_EVENTS`][] says so: there are no spans in those
+//! *   complete: the markdown of void spans, in order, is the whole markdown
+//! *   resolved: names used while parsing only (such as
+//!     [`AttentionSequence`][Name::AttentionSequence]) are not left
+//!
+//! Points use byte offsets into the markdown, and their columns count bytes
+//! and expand tabs, see [`Point`][].
+//! Use [`source()`][] to get the markdown of a span.
+//!
+//! > 👉 **Note**: names, and which spans are in which, follow
+//! > [`micromark`][micromark] and change when constructs change, so they are
+//! > less stable than the rest of the API.
+//!
+//! [micromark]: https://github.com/micromark/micromark
+
+// Names link to the constructs that make them, which are not public.
+#![allow(rustdoc::private_intra_doc_links)]
 
 use crate::unist;
 use crate::util::constant::TAB_SIZE;
+use crate::util::slice::{Position as SlicePosition, Slice};
 
 /// Semantic label of a span.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -3491,7 +3525,7 @@ pub struct Link {
 pub struct Point {
     /// 1-indexed line number.
     pub line: usize,
-    /// 1-indexed column number.
+    /// 1-indexed column number, counting bytes.
     ///
     /// This is increased up to a tab stop for tabs.
     /// Some editors count tabs as 1 character, so this position is not the
@@ -3499,14 +3533,20 @@ pub struct Point {
     pub column: usize,
     /// 0-indexed position in the document.
     ///
-    /// Also an `index` into `bytes`.
+    /// Also an `index` into `bytes`: the byte offset into the markdown.
     pub index: usize,
     /// Virtual step on the same `index`.
+    ///
+    /// When a tab at `index` is split (such as when part of it is the indent
+    /// of a list item and the rest is in the content), this is the number of
+    /// its columns that are before this point.
+    /// `column` already includes it.
     pub vs: usize,
 }
 
 impl Point {
-    /// Create a unist point.
+    /// Create a unist point, with `line`, `column`, and `index` as
+    /// `offset`.
     pub fn to_unist(&self) -> unist::Point {
         unist::Point {
             line: self.line,
@@ -3517,6 +3557,7 @@ impl Point {
 
     /// Create a new point, that is shifted from the close earlier current
     /// point, to `index`.
+    #[must_use]
     pub fn shift_to(&self, bytes: &[u8], index: usize) -> Point {
         let mut next = self.clone();
         debug_assert!(index > next.index, "expected to shift forward");
@@ -3566,3 +3607,73 @@ pub struct Event {
     /// Link to another event.
     pub link: Option<Link>,
 }
+
+/// Get the source of a span: the part of `value` between the point of the
+/// event at `index` in `events` and that of its matching enter or exit.
+///
+/// `value` must be the markdown that `events` were made from.
+/// A tab that is split between spans (see [`Point::vs`][]) is in the first
+/// of them.
+///
+/// ## Panics
+///
+/// Panics if `index` is not in `events`, or if the events are not paired.
+/// Events from [`to_events()`][crate::to_events] are always paired.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::{source, Kind, Name}, to_events, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "a *b*\r\nc";
+/// let events = to_events(value, &ParseOptions::default())?;
+/// let emphasis = events
+///     .iter()
+///     .position(|event| event.kind == Kind::Exit && event.name == Name::Emphasis)
+///     .unwrap();
+///
+/// assert_eq!(source(value, &events, emphasis), "*b*");
+/// assert_eq!(source(value, &events, 0), value);
+/// # Ok(())
+/// # }
+/// ```
+pub fn source<'a>(value: &'a str, events: &[Event], index: usize) -> &'a str {
+    let mut other = index;
+    let mut depth = 0;
+
+    // Walk to the matching event, stepping over nested spans.
+    loop {
+        let event = &events[other];
+        if event.kind == events[index].kind {
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
+
+        if depth == 0 {
+            break;
+        }
+
+        if events[index].kind == Kind::Enter {
+            other += 1;
+        } else {
+            other -= 1;
+        }
+    }
+
+    let (start, end) = if events[index].kind == Kind::Enter {
+        (index, other)
+    } else {
+        (other, index)
+    };
+
+    Slice::from_position(
+        value.as_bytes(),
+        &SlicePosition {
+            start: &events[start].point,
+            end: &events[end].point,
+        },
+    )
+    .as_str()
+}
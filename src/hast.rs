@@ -0,0 +1,174 @@
+//! HTML syntax tree: [hast][].
+//!
+//! This is the tree made by [`to_hast()`][crate::to_hast] and
+//! [`mdast_to_hast()`][crate::mdast_to_hast], which can be turned into a
+//! string with [`hast_to_html()`][crate::hast_to_html].
+//!
+//! [hast]: https://github.com/syntax-tree/hast
+
+use crate::unist::Position;
+use alloc::{fmt, string::String, vec::Vec};
+
+/// Nodes.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "type")
+)]
+pub enum Node {
+    /// Root.
+    Root(Root),
+    /// Element.
+    Element(Element),
+    /// Text.
+    Text(Text),
+    /// Raw HTML.
+    Raw(Raw),
+}
+
+impl fmt::Debug for Node {
+    // Debug the wrapped struct.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Root(x) => x.fmt(f),
+            Node::Element(x) => x.fmt(f),
+            Node::Text(x) => x.fmt(f),
+            Node::Raw(x) => x.fmt(f),
+        }
+    }
+}
+
+impl Node {
+    /// Get the children of a parent node.
+    ///
+    /// Returns `None` for text and raw HTML.
+    #[must_use]
+    pub fn children(&self) -> Option<&Vec<Node>> {
+        match self {
+            Node::Root(x) => Some(&x.children),
+            Node::Element(x) => Some(&x.children),
+            Node::Text(_) | Node::Raw(_) => None,
+        }
+    }
+
+    /// Get the children of a parent node, mutably.
+    ///
+    /// Returns `None` for text and raw HTML.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match self {
+            Node::Root(x) => Some(&mut x.children),
+            Node::Element(x) => Some(&mut x.children),
+            Node::Text(_) | Node::Raw(_) => None,
+        }
+    }
+
+    /// Get the positional info of a node.
+    #[must_use]
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            Node::Root(x) => x.position.as_ref(),
+            Node::Element(x) => x.position.as_ref(),
+            Node::Text(x) => x.position.as_ref(),
+            Node::Raw(x) => x.position.as_ref(),
+        }
+    }
+}
+
+/// Document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "root")
+)]
+pub struct Root {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Element.
+///
+/// ```html
+/// <a href="b">c</a>
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "element", rename_all = "camelCase")
+)]
+pub struct Element {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    ///
+    /// Elements made for a markdown node have the position of that node.
+    pub position: Option<Position>,
+    // Element.
+    /// Name of the element (such as `a`).
+    pub tag_name: String,
+    /// Attributes, as name/value pairs, in order.
+    ///
+    /// Values are not encoded: the serializer does that.
+    /// Boolean attributes (such as `disabled`) have an empty value.
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Element {
+    /// Get the value of an attribute.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Text.
+///
+/// ```html
+/// a &amp; b
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "text")
+)]
+pub struct Text {
+    // Text.
+    /// Content model, not encoded: the serializer does that.
+    pub value: String,
+    /// Positional info.
+    ///
+    /// Line endings and other text generated between elements have no
+    /// position.
+    pub position: Option<Position>,
+}
+
+/// Raw HTML, which is serialized as is.
+///
+/// This is HTML written in markdown, with `allow_dangerous_html` on.
+///
+/// ```html
+/// <b>
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "raw")
+)]
+pub struct Raw {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+}
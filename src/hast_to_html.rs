@@ -0,0 +1,58 @@
+//! Turn an HTML syntax tree into a string of HTML.
+
+use crate::hast::Node;
+use crate::to_html::attribute;
+use crate::util::encode::{encode, encode_non_ascii};
+use crate::CompileOptions;
+use alloc::string::String;
+
+/// Elements that cannot have children, and are written as `<x />`.
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Turn an HTML syntax tree into a string of HTML.
+pub fn serialize(tree: &Node, options: &CompileOptions) -> String {
+    let mut result = String::new();
+    one(tree, options, &mut result);
+
+    if options.encode_non_ascii {
+        result = encode_non_ascii(&result);
+    }
+
+    result
+}
+
+/// Serialize a node.
+fn one(node: &Node, options: &CompileOptions, result: &mut String) {
+    match node {
+        Node::Root(root) => {
+            for child in &root.children {
+                one(child, options, result);
+            }
+        }
+        Node::Element(element) => {
+            let tag_name = encode(&element.tag_name, true);
+            result.push('<');
+            result.push_str(&tag_name);
+            for (name, value) in &element.attributes {
+                result.push_str(&attribute(&options.quote, name, &encode(value, true)));
+            }
+
+            if VOID_ELEMENTS.contains(&element.tag_name.as_str()) {
+                result.push_str(" />");
+            } else {
+                result.push('>');
+                for child in &element.children {
+                    one(child, options, result);
+                }
+                result.push_str("</");
+                result.push_str(&tag_name);
+                result.push('>');
+            }
+        }
+        Node::Text(text) => result.push_str(&encode(&text.value, true)),
+        Node::Raw(raw) => result.push_str(&raw.value),
+    }
+}
@@ -1,7 +1,9 @@
 //! Public API of `markdown-rs`.
 //!
 //! This module exposes primarily [`to_html()`][].
-//! It also exposes [`to_html_with_options()`][] and [`to_mdast()`][].
+//! It also exposes [`to_html_with_options()`][], [`to_mdast()`][],
+//! [`to_text()`][], [`mdast_to_html()`][], [`mdast_to_markdown()`][], and
+//! [`mdast_to_markdown_with_options()`][].
 //!
 //! *   [`to_html()`][]
 //!     — safe way to transform (untrusted?) markdown into HTML
@@ -9,8 +11,55 @@
 //!     — like `to_html` but lets you configure how markdown is turned into
 //!     HTML, such as allowing dangerous HTML or turning on/off different
 //!     constructs (GFM, MDX, and the like)
+//! *   [`to_html_range()`][]
+//!     — like `to_html_with_options` but only for the blocks on some lines,
+//!     such as those visible in a preview
+//! *   [`to_html_and_source_map()`][]
+//!     — like `to_html_with_options` but also maps the blocks in the HTML to
+//!     where they are in the markdown, such as to sync scrolling in an
+//!     editor and its preview
+//! *   `to_html_from_reader()`
+//!     — like `to_html_with_options` but reads markdown from a file or other
+//!     reader (requires the `std` feature)
+//! *   `to_mdast_from_reader()`
+//!     — like `to_mdast` but reads markdown from a file or other reader
+//!     (requires the `std` feature)
 //! *   [`to_mdast()`][]
 //!     — turn markdown into a syntax tree
+//! *   [`to_mdast_ref()`][]
+//!     — like `to_mdast` but the tree borrows values from the markdown
+//! *   [`to_text()`][]
+//!     — turn markdown into plain text
+//! *   [`to_hast()`][]
+//!     — turn markdown into an HTML syntax tree, to change before
+//!     serializing it with [`hast_to_html()`][]
+//! *   [`mdast_to_html()`][]
+//!     — turn a syntax tree into HTML
+//! *   [`mdast_to_html_with_renderer()`][]
+//!     — like `mdast_to_html` but lets you change how nodes are turned into
+//!     HTML
+//! *   [`mdast_to_hast()`][]
+//!     — turn a syntax tree into an HTML syntax tree
+//! *   [`mdast_to_markdown()`][]
+//!     — turn a syntax tree back into markdown
+//! *   [`mdast_to_markdown_with_options()`][]
+//!     — like `mdast_to_markdown` but lets you configure the style of the
+//!     markdown, such as which markers to use
+//! *   [`to_events()`][]
+//!     — turn markdown into the events of the tokenizer, such as for syntax
+//!     highlighting
+//! *   [`events_to_mdast()`][]
+//!     — turn events into a syntax tree, such as to get both from one parse
+//! *   [`block_ranges()`][]
+//!     — split markdown into top-level blocks, such as to only render
+//!     blocks that changed
+//! *   [`to_cst()`][]
+//!     — turn markdown into a lossless syntax tree of those events, such as
+//!     for formatters
+//! *   [`mdast_to_events()`][]
+//!     — turn a syntax tree into the events of the tokenizer
+//! *   [`Parser`][]
+//!     — configure once, then turn many documents into HTML or syntax trees
 //!
 //! ## Features
 //!
@@ -21,6 +70,16 @@
 //! *   **`log`**
 //!     — enable logging (includes `dep:log`);
 //!     you can show logs with `RUST_LOG=debug`
+//! *   **`std`**
+//!     — enable things that need the standard library, such as reading
+//!     markdown from a [`Read`][std::io::Read]er
+//! *   **`swar`**
+//!     — scan plain text a 64-bit word (8 bytes) at a time instead of a byte
+//!     at a time, which is faster on documents with long runs of text
+//!     (SWAR: SIMD within a register; no SIMD instructions are used)
+//! *   **`wasm`**
+//!     — expose functions to JavaScript with `wasm-bindgen`, see [`wasm`][]
+//!     (includes `serde`, `dep:serde_json`, and `dep:wasm-bindgen`)
 
 #![no_std]
 #![deny(clippy::pedantic)]
@@ -34,21 +93,34 @@
 )]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+mod block;
 mod configuration;
 mod construct;
-mod event;
+mod hast_to_html;
+mod mdast_to_hast;
+mod mdast_to_html;
 mod parser;
 mod resolve;
 mod state;
 mod subtokenize;
 mod to_html;
+mod to_markdown;
 mod to_mdast;
+mod to_text;
 mod tokenizer;
 mod util;
 
+pub mod cst;
+pub mod event;
+pub mod hast;
 pub mod mdast; // To do: externalize?
+pub mod mdast_ref;
 pub mod message; // To do: externalize.
 pub mod unist; // To do: externalize.
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[doc(hidden)]
 pub use util::identifier::{id_cont, id_start};
@@ -59,16 +131,32 @@ pub use util::sanitize_uri::sanitize;
 #[doc(hidden)]
 pub use util::location::Location;
 
+pub use util::line_index::LineIndex;
+
+pub use util::normalize_identifier::normalize_identifier;
+
 pub use util::line_ending::LineEnding;
 
+pub use util::slug::{Slugger, Slugify};
+
 pub use util::mdx::{
     EsmParse as MdxEsmParse, ExpressionKind as MdxExpressionKind,
     ExpressionParse as MdxExpressionParse, Signal as MdxSignal,
 };
 
-pub use configuration::{CompileOptions, Constructs, Options, ParseOptions};
+pub use configuration::{
+    CompileOptions, Constructs, Options, ParseOptions, PositionEncoding, Quote, SerializeOptions,
+};
+
+pub use mdast_to_html::{RenderContext, Renderer};
+
+pub use to_html::Mapping;
 
-use alloc::string::String;
+pub use block::BlockKind;
+
+pub use parser::Parser;
+
+use alloc::{string::String, vec::Vec};
 
 /// Turn markdown into HTML.
 ///
@@ -127,10 +215,196 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
     Ok(to_html::compile(
         &events,
         parse_state.bytes,
+        &to_html::option_definitions(&options.parse.definitions),
+        &options.compile,
+        options.slugify.as_deref(),
+    ))
+}
+
+/// Turn part of markdown into HTML, with configuration.
+///
+/// Only the top-level blocks (such as paragraphs, lists, and block quotes)
+/// that overlap `lines` (1-indexed, end exclusive) are compiled, which is
+/// useful to render what is visible in a preview.
+/// Blocks that straddle the start or end of `lines` are included whole.
+/// The whole document is parsed and compiled, so the HTML of blocks is the
+/// same as with [`to_html_with_options()`][]: references and footnote calls
+/// are resolved with definitions from anywhere in the document, and
+/// headings and footnote calls are counted from the start of the document.
+///
+/// The footnote section is only added when a footnote definition that is
+/// called is on the lines.
+/// It is then included whole, with all footnotes in the document.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_html_with_options()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_range, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "# a\n\n[b]\n\nc\nd\n\n[b]: e";
+///
+/// assert_eq!(
+///     to_html_range(value, &Options::default(), 3..6)?,
+///     "<p><a href=\"e\">b</a></p>\n<p>c\nd</p>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_range(
+    value: &str,
+    options: &Options,
+    lines: core::ops::Range<usize>,
+) -> Result<String, message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse)?;
+    Ok(to_html::compile_range(
+        &events,
+        parse_state.bytes,
+        &to_html::option_definitions(&options.parse.definitions),
+        &options.compile,
+        options.slugify.as_deref(),
+        &lines,
+    ))
+}
+
+/// Turn markdown into HTML, with configuration, and a source map.
+///
+/// The HTML is the same as with [`to_html_with_options()`][].
+/// The source map has a [`Mapping`][] for each block, in the order in which
+/// the blocks start in the HTML.
+/// The blocks are those that get attributes with
+/// [`source_position`][CompileOptions::source_position] (paragraphs,
+/// headings, thematic breaks, block quotes, lists, list items, code, and
+/// tables), and GFM footnote definitions, whose HTML is the item in the
+/// footnote section.
+/// The footnote section itself is generated, so its mapping has no name and
+/// an empty input range at the end of the markdown.
+///
+/// To get line numbers in the HTML instead, use `source_position`.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_html_with_options()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::Name, to_html_and_source_map, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "# a\n\n> b";
+/// let (html, mappings) = to_html_and_source_map(value, &Options::default())?;
+///
+/// assert_eq!(html, "<h1>a</h1>\n<blockquote>\n<p>b</p>\n</blockquote>");
+/// assert_eq!(mappings.len(), 3);
+/// assert_eq!(mappings[1].name, Some(Name::BlockQuote));
+/// assert_eq!(&value[mappings[1].input.clone()], "> b");
+/// assert_eq!(&html[mappings[2].output.clone()], "<p>b</p>");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_and_source_map(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<Mapping>), message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse)?;
+    Ok(to_html::compile_with_source_map(
+        &events,
+        parse_state.bytes,
+        &to_html::option_definitions(&options.parse.definitions),
         &options.compile,
+        options.slugify.as_deref(),
     ))
 }
 
+/// Turn markdown from a reader into HTML, with configuration.
+///
+/// The whole input is buffered: all of `reader` is read into a string, which
+/// is then compiled like [`to_html_with_options()`][].
+/// Nothing can be compiled before the end of the input, as, for example,
+/// references can come before the definitions they use.
+/// So the markdown, its events, and the HTML all have to fit in memory.
+///
+/// Requires the `std` feature.
+///
+/// ## Errors
+///
+/// Errors with [`ReadError::Io`][message::ReadError::Io] if `reader` cannot
+/// be read or does not contain UTF-8, and with
+/// [`ReadError::Message`][message::ReadError::Message] in the same cases as
+/// [`to_html_with_options()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_from_reader, Options};
+/// # fn main() -> Result<(), markdown::message::ReadError> {
+///
+/// let reader = "# Hello, world!".as_bytes();
+///
+/// assert_eq!(
+///     to_html_from_reader(reader, &Options::default())?,
+///     "<h1>Hello, world!</h1>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn to_html_from_reader<R: std::io::Read>(
+    reader: R,
+    options: &Options,
+) -> Result<String, message::ReadError> {
+    Ok(to_html_with_options(&read(reader)?, options)?)
+}
+
+/// Turn markdown from a reader into a syntax tree.
+///
+/// The whole input is buffered: all of `reader` is read into a string, which
+/// is then parsed like [`to_mdast()`][].
+/// So the markdown, its events, and the tree all have to fit in memory.
+///
+/// Requires the `std` feature.
+///
+/// ## Errors
+///
+/// Errors with [`ReadError::Io`][message::ReadError::Io] if `reader` cannot
+/// be read or does not contain UTF-8, and with
+/// [`ReadError::Message`][message::ReadError::Message] in the same cases as
+/// [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast_from_reader, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::ReadError> {
+///
+/// let reader = "# Hello, world!".as_bytes();
+/// let tree = to_mdast_from_reader(reader, &ParseOptions::default())?;
+///
+/// assert!(matches!(tree.children().unwrap()[0], Node::Heading(_)));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn to_mdast_from_reader<R: std::io::Read>(
+    reader: R,
+    options: &ParseOptions,
+) -> Result<mdast::Node, message::ReadError> {
+    Ok(to_mdast(&read(reader)?, options)?)
+}
+
+/// Read all of `reader` into a string.
+#[cfg(feature = "std")]
+fn read<R: std::io::Read>(mut reader: R) -> Result<String, std::io::Error> {
+    let mut value = String::new();
+    reader.read_to_string(&mut value)?;
+    Ok(value)
+}
+
 /// Turn markdown into a syntax tree.
 ///
 /// ## Errors
@@ -150,12 +424,696 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
 /// let tree = to_mdast("# Hey, *you*!", &ParseOptions::default())?;
 ///
 /// println!("{:?}", tree);
-/// // => Root { children: [Heading { children: [Text { value: "Hey, ", position: Some(1:3-1:8 (2-7)) }, Emphasis { children: [Text { value: "you", position: Some(1:9-1:12 (8-11)) }], position: Some(1:8-1:13 (7-12)) }, Text { value: "!", position: Some(1:13-1:14 (12-13)) }], position: Some(1:1-1:14 (0-13)), depth: 1 }], position: Some(1:1-1:14 (0-13)) }
+/// // => Root { children: [Heading { children: [Text { value: "Hey, ", position: Some(1:3-1:8 (2-7)), data: None }, Emphasis { children: [Text { value: "you", position: Some(1:9-1:12 (8-11)), data: None }], position: Some(1:8-1:13 (7-12)), data: None, marker: Some('*') }, Text { value: "!", position: Some(1:13-1:14 (12-13)), data: None }], position: Some(1:1-1:14 (0-13)), data: None, depth: 1, setext: false, markers: [] }], position: Some(1:1-1:14 (0-13)), data: None }
 /// # Ok(())
 /// # }
 /// ```
 pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, message::Message> {
-    let (events, parse_state) = parser::parse(value, options)?;
-    let node = to_mdast::compile(&events, parse_state.bytes)?;
+    let (events, _) = parser::parse(value, options)?;
+    let node = to_mdast::compile(&events, value, options)?;
+    Ok(node)
+}
+
+/// Turn markdown into a syntax tree that borrows from `value`.
+///
+/// The tree is the same as with [`to_mdast()`][], but values that are
+/// written verbatim in `value` (such as text without character escapes and
+/// character references, code, and URLs) are not copied.
+/// This allocates less for big documents.
+/// See [`mdast_ref`][] for more info.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast_ref::Node, to_mdast_ref, ParseOptions};
+/// use std::borrow::Cow;
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "```rs\nfn a() {}\n```";
+/// let tree = to_mdast_ref(value, &ParseOptions::default())?;
+///
+/// if let Node::Code(code) = &tree.children().unwrap()[0] {
+///     assert!(matches!(code.value, Cow::Borrowed("fn a() {}")));
+///     assert!(matches!(code.lang, Some(Cow::Borrowed("rs"))));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_ref<'a>(
+    value: &'a str,
+    options: &ParseOptions,
+) -> Result<mdast_ref::Node<'a>, message::Message> {
+    let (events, _) = parser::parse(value, options)?;
+    let node = to_mdast::compile_ref(&events, value, options)?;
     Ok(node)
 }
+
+/// Turn markdown into a syntax tree and HTML, parsing it once.
+///
+/// The tree is the same as with [`to_mdast()`][] (with `options.parse`), and
+/// the HTML is the same as with [`to_html_with_options()`][].
+/// Use this when both are needed (such as HTML for a page and the tree for
+/// its table of contents or frontmatter), as markdown is parsed only once.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_html_with_options()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::Node, to_mdast_and_html, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (tree, html) = to_mdast_and_html("# Hey, *you*!", &Options::default())?;
+///
+/// assert!(matches!(tree.children().unwrap()[0], Node::Heading(_)));
+/// assert_eq!(html, "<h1>Hey, <em>you</em>!</h1>");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_and_html(
+    value: &str,
+    options: &Options,
+) -> Result<(mdast::Node, String), message::Message> {
+    let (events, parse_state) = parser::parse(value, &options.parse)?;
+    let node = to_mdast::compile(&events, value, &options.parse)?;
+    let html = to_html::compile(
+        &events,
+        parse_state.bytes,
+        &to_html::option_definitions(&options.parse.definitions),
+        &options.compile,
+        options.slugify.as_deref(),
+    );
+    Ok((node, html))
+}
+
+/// Turn markdown into a syntax tree, and get messages about it.
+///
+/// Like [`to_mdast()`][], but also returns a list of messages about things
+/// that are not errors, but that you might want to know about, such as for
+/// linting.
+/// Which messages are made is configured with options, such as
+/// [`report_duplicate_definitions`][ParseOptions::report_duplicate_definitions].
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_mdast_with_messages, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (tree, messages) = to_mdast_with_messages(
+///     "[a]: b\n[a]: c",
+///     &ParseOptions {
+///         report_duplicate_definitions: true,
+///         ..ParseOptions::default()
+///     },
+/// )?;
+///
+/// assert_eq!(tree.children().unwrap().len(), 2);
+/// assert_eq!(messages[0].rule_id.as_str(), "duplicate-definition");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_mdast_with_messages(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<(mdast::Node, Vec<message::Message>), message::Message> {
+    let tree = to_mdast(value, options)?;
+    let mut messages = Vec::new();
+
+    if options.report_duplicate_definitions {
+        messages.append(&mut to_mdast::duplicate_definitions(&tree));
+    }
+
+    Ok((tree, messages))
+}
+
+/// Turn markdown into plain text.
+///
+/// The result is the text of the document, without markup: headings and
+/// paragraphs as their text, links and images as their label, code as
+/// written, and list items prefixed with `- ` (or their number).
+/// Blocks are separated by a blank line, except in tight lists, where they
+/// are on their own line.
+/// HTML, definitions, footnotes, frontmatter, and MDX expressions, ESM, and
+/// JSX tags are dropped.
+///
+/// ## Errors
+///
+/// `to_text()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_text, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let text = to_text("# Hey, *you*!\n\n* [a](b)\n* c", &ParseOptions::default())?;
+///
+/// assert_eq!(text, "Hey, you!\n\n- a\n- c");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_text(value: &str, options: &ParseOptions) -> Result<String, message::Message> {
+    let (events, parse_state) = parser::parse(value, options)?;
+    Ok(to_text::compile(&events, parse_state.bytes))
+}
+
+/// Turn a syntax tree into HTML.
+///
+/// The result is the same HTML as [`to_html_with_options()`][] gives for the
+/// markdown the tree was made from.
+/// References are resolved with the `Definition` nodes in the tree, footnote
+/// calls with the `FootnoteDefinition` nodes.
+/// References and footnote calls that cannot be resolved are turned into the
+/// text they were written as.
+/// Of `options.parse`, only `definitions` is used, after those in the tree.
+/// To compile nodes differently, use
+/// [`mdast_to_html_with_renderer()`][].
+///
+/// Some things are not in the tree, so HTML can differ from
+/// [`to_html_with_options()`][] slightly: line endings are those found in
+/// the tree (or `default_line_ending`), `code_indented_class` is not used,
+/// and email autolinks are not obfuscated.
+///
+/// ## Errors
+///
+/// `mdast_to_html()` errors on MDX nodes (ESM, expressions, and JSX), unless
+/// `skip_unknown_nodes` is on.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast_to_html, to_mdast, Options, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# Hey, *you*!\n\n[a]\n\n[a]: b", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     mdast_to_html(&tree, &Options::default())?,
+///     "<h1>Hey, <em>you</em>!</h1>\n<p><a href=\"b\">a</a></p>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_html(tree: &mdast::Node, options: &Options) -> Result<String, message::Message> {
+    mdast_to_html::compile(tree, options, None)
+}
+
+/// Turn a syntax tree into HTML, with a custom renderer.
+///
+/// The HTML is the same as with [`mdast_to_html()`][], except for the nodes
+/// that `renderer` compiles differently.
+/// See [`Renderer`][] for more info.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`mdast_to_html()`][], and when a method of
+/// `renderer` errors.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::Heading, mdast_to_html_with_renderer, message, to_mdast, Options, ParseOptions,
+///     RenderContext, Renderer,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// struct Flat;
+///
+/// impl Renderer for Flat {
+///     fn heading(&self, _node: &Heading, context: &mut RenderContext) -> Result<(), message::Message> {
+///         context.line_ending_if_needed();
+///         context.push("<p>");
+///         context.render_children()?;
+///         context.push("</p>");
+///         Ok(())
+///     }
+/// }
+///
+/// let tree = to_mdast("# Hey, *you*!", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     mdast_to_html_with_renderer(&tree, &Options::default(), &Flat)?,
+///     "<p>Hey, <em>you</em>!</p>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_html_with_renderer(
+    tree: &mdast::Node,
+    options: &Options,
+    renderer: &dyn Renderer,
+) -> Result<String, message::Message> {
+    mdast_to_html::compile(tree, options, Some(renderer))
+}
+
+/// Turn markdown into an HTML syntax tree.
+///
+/// The tree is equivalent to the HTML of [`mdast_to_html()`][]: serializing
+/// it with [`hast_to_html()`][] gives the same string, including the ids,
+/// classes, and other attributes generated by options.
+/// Change the tree in between to post-process the HTML without parsing it
+/// again.
+///
+/// Elements and text made for markdown nodes have the positions of those
+/// nodes.
+/// Line endings between elements, and elements generated for the footnote
+/// section, have no position.
+///
+/// HTML in markdown is text, or raw HTML with `allow_dangerous_html`, so it
+/// is not parsed into elements.
+/// Attributes in `gfm_footnote_label_attributes` and `code_fence_wrapper`
+/// are parsed, so they are serialized like other attributes (with `quote`,
+/// and `x=""` for `x`).
+///
+/// ## Errors
+///
+/// `to_hast()` errors like [`to_mdast()`][], and on MDX nodes (ESM,
+/// expressions, and JSX), unless `skip_unknown_nodes` is on.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{hast, hast_to_html, to_hast, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let options = Options::default();
+/// let mut tree = to_hast("# Hey, *you*!", &options)?;
+///
+/// if let Some(hast::Node::Element(heading)) = tree.children_mut().unwrap().first_mut() {
+///     assert_eq!(heading.tag_name, "h1");
+///     heading.attributes.push(("class".into(), "title".into()));
+/// }
+///
+/// assert_eq!(
+///     hast_to_html(&tree, &options.compile),
+///     "<h1 class=\"title\">Hey, <em>you</em>!</h1>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_hast(value: &str, options: &Options) -> Result<hast::Node, message::Message> {
+    mdast_to_hast(&to_mdast(value, &options.parse)?, options)
+}
+
+/// Turn a syntax tree into an HTML syntax tree.
+///
+/// See [`to_hast()`][] for how the result relates to
+/// [`mdast_to_html()`][].
+///
+/// ## Errors
+///
+/// `mdast_to_hast()` errors on MDX nodes (ESM, expressions, and JSX), unless
+/// `skip_unknown_nodes` is on.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{hast, mdast_to_hast, to_mdast, Options, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("[a](b)", &ParseOptions::default())?;
+/// let hast = mdast_to_hast(&tree, &Options::default())?;
+///
+/// let paragraph = &hast.children().unwrap()[0];
+/// let link = &paragraph.children().unwrap()[0];
+///
+/// match link {
+///     hast::Node::Element(link) => {
+///         assert_eq!(link.tag_name, "a");
+///         assert_eq!(link.attribute("href"), Some("b"));
+///     }
+///     _ => panic!("expected an element, not {:?}", link),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_hast(
+    tree: &mdast::Node,
+    options: &Options,
+) -> Result<hast::Node, message::Message> {
+    mdast_to_hast::compile(tree, options)
+}
+
+/// Turn an HTML syntax tree into HTML.
+///
+/// Text and attribute values are encoded, raw HTML is kept as is.
+/// Of the options, `quote` and `encode_non_ascii` are used.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     hast::{Element, Node, Root, Text},
+///     hast_to_html, CompileOptions,
+/// };
+/// # fn main() {
+///
+/// let tree = Node::Root(Root {
+///     children: vec![Node::Element(Element {
+///         children: vec![Node::Text(Text {
+///             value: "a & b".into(),
+///             position: None,
+///         })],
+///         position: None,
+///         tag_name: "p".into(),
+///         attributes: vec![("title".into(), "\"c\"".into())],
+///     })],
+///     position: None,
+/// });
+///
+/// assert_eq!(
+///     hast_to_html(&tree, &CompileOptions::default()),
+///     "<p title=\"&quot;c&quot;\">a &amp; b</p>"
+/// );
+/// # }
+/// ```
+pub fn hast_to_html(tree: &hast::Node, options: &CompileOptions) -> String {
+    hast_to_html::serialize(tree, options)
+}
+
+/// Turn a syntax tree into markdown.
+///
+/// This is the inverse of [`to_mdast()`][]: parsing the result again gives
+/// an equivalent tree.
+/// Text is escaped where needed (such as a literal `*` as `\\*`), code is
+/// fenced with a fence longer than any sequence in it, and headings are
+/// written as ATX headings (`# a`), unless they contain line endings.
+/// The style of the source is kept where it is in the tree (such as the
+/// markers of emphasis and list items), otherwise the markdown style of
+/// Prettier is used.
+///
+/// Escaping accounts for `CommonMark` and GFM syntax: text serialized with
+/// other extensions (such as math or MDX) turned on might be seen as syntax.
+///
+/// ## Errors
+///
+/// `mdast_to_markdown()` errors if nodes are used where they cannot occur,
+/// such as a `ListItem` outside of a `List`, a `TableRow` outside of a
+/// `Table`, or a `Paragraph` in phrasing content.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast_to_markdown, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# Hey, *you*!\n\n* a", &ParseOptions::default())?;
+///
+/// assert_eq!(mdast_to_markdown(&tree)?, "# Hey, *you*!\n\n* a\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_markdown(tree: &mdast::Node) -> Result<String, message::Message> {
+    to_markdown::serialize(tree, &SerializeOptions::default())
+}
+
+/// Turn markdown into events.
+///
+/// Events are the flat output of the tokenizer, before it is turned into
+/// HTML or a syntax tree, with the name of each span and where it starts and
+/// ends.
+/// See [`event`][] for what events look like and which guarantees they give.
+/// Points in events point into `value`.
+///
+/// Events are only returned once all of `value` is parsed, as whether
+/// earlier markdown is some syntax can depend on later markdown:
+///
+/// *   references (such as `[a]`) are links only if there is a definition,
+///     which can be at the end of the document
+/// *   GFM footnote calls (such as `[^a]`) likewise depend on footnote
+///     definitions
+/// *   lists are spread (loose) depending on blank lines between their later
+///     items
+/// *   emphasis, strong, links, and GFM tables, strikethrough, and autolink
+///     literals are only known when the text or lines after their start are
+///     seen
+///
+/// So events cannot be streamed while parsing, and memory use grows with
+/// the size of `value`.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::{Kind, Name}, to_events, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "*a*";
+/// let events = to_events(value, &ParseOptions::default())?;
+/// let names = events
+///     .iter()
+///     .filter(|event| event.kind == Kind::Enter)
+///     .map(|event| event.name.clone())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     names,
+///     vec![
+///         Name::Paragraph,
+///         Name::Emphasis,
+///         Name::EmphasisSequence,
+///         Name::EmphasisText,
+///         Name::Data,
+///         Name::EmphasisSequence
+///     ]
+/// );
+/// assert_eq!(&value[events[1].point.index..events[10].point.index], "*a*");
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_events(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<Vec<event::Event>, message::Message> {
+    let (events, _) = parser::parse(value, options)?;
+    Ok(events)
+}
+
+/// Turn events into a syntax tree.
+///
+/// `events` must be those of `value` from [`to_events()`][] with the same
+/// `options`: the tree is then the same as with [`to_mdast()`][].
+/// Use this to parse once when both events (such as for a custom compiler)
+/// and a tree are needed.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][], when there are MDX JSX tags
+/// that do not match.
+///
+/// ## Panics
+///
+/// Panics if `events` are not those of `value`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{events_to_mdast, mdast::Node, to_events, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "# Hey, *you*!";
+/// let options = ParseOptions::default();
+/// let events = to_events(value, &options)?;
+/// let tree = events_to_mdast(&events, value, &options)?;
+///
+/// assert!(matches!(tree.children().unwrap()[0], Node::Heading(_)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn events_to_mdast(
+    events: &[event::Event],
+    value: &str,
+    options: &ParseOptions,
+) -> Result<mdast::Node, message::Message> {
+    to_mdast::compile(events, value, options)
+}
+
+/// Get the byte ranges and kinds of the top-level blocks in markdown.
+///
+/// This does not build a syntax tree or HTML, so it is cheaper than
+/// [`to_mdast()`][] when only the blocks are needed, such as to cache the
+/// rendering of each block and only render blocks whose markdown changed.
+///
+/// Blank lines between blocks are in neither block, and neither are the
+/// line endings and whitespace at the end of containers (block quotes,
+/// lists, and GFM footnote definitions).
+/// Blank lines in blocks, such as between list items or in fenced code, are
+/// in the block.
+/// Setext headings include their text, which would be a paragraph without
+/// the underline.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{block_ranges, BlockKind, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "# a\n\nb\nc\n---\n\n* d\n\n* e\n\n";
+///
+/// assert_eq!(
+///     block_ranges(value, &ParseOptions::default())?,
+///     vec![
+///         (0..3, BlockKind::Heading),
+///         (5..12, BlockKind::Heading),
+///         (14..22, BlockKind::List),
+///     ]
+/// );
+/// assert_eq!(&value[5..12], "b\nc\n---");
+/// assert_eq!(&value[14..22], "* d\n\n* e");
+/// # Ok(())
+/// # }
+/// ```
+pub fn block_ranges(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<Vec<(core::ops::Range<usize>, BlockKind)>, message::Message> {
+    let (events, parse_state) = parser::parse(value, options)?;
+    Ok(block::ranges(&events, parse_state.bytes))
+}
+
+/// Turn markdown into a concrete syntax tree.
+///
+/// The tree is made from the same events as [`to_events()`][], with a
+/// node for each span.
+/// Unlike the syntax tree of [`to_mdast()`][], it is lossless: the leaves,
+/// in order, make up all of `value`.
+/// See [`cst`][] for more info.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{cst::Node, event::Name, to_cst, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// /// Get the source of the leaves in `nodes`.
+/// fn leaves<'a>(nodes: &[Node], value: &'a str, result: &mut Vec<&'a str>) {
+///     for node in nodes {
+///         if node.children.is_empty() {
+///             result.push(node.source(value));
+///         } else {
+///             leaves(&node.children, value, result);
+///         }
+///     }
+/// }
+///
+/// let value = "- *a*\\\n  b";
+/// let nodes = to_cst(value, &ParseOptions::default())?;
+/// let mut result = vec![];
+/// leaves(&nodes, value, &mut result);
+///
+/// assert_eq!(nodes[0].name, Name::ListUnordered);
+/// assert_eq!(result, vec!["-", " ", "*", "a", "*", "\\", "\n", "  ", "b"]);
+/// assert_eq!(result.concat(), value);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_cst(value: &str, options: &ParseOptions) -> Result<Vec<cst::Node>, message::Message> {
+    let (events, _) = parser::parse(value, options)?;
+    Ok(cst::from_events(events))
+}
+
+/// Turn a syntax tree into events.
+///
+/// The events are those the tokenizer makes for the markdown the tree is
+/// serialized as (with [`mdast_to_markdown()`][]), so compilers working on
+/// events can also handle trees that were made or changed programmatically.
+/// Events point into that markdown, which is returned too.
+/// Text that would otherwise be seen as syntax is escaped, so a `*` in a
+/// `Text` results in a `CharacterEscape` event.
+///
+/// Turn on the constructs used in the tree in `options` (such as GFM for
+/// `Delete` and `Table`, or MDX for JSX), otherwise their markdown is not
+/// seen as syntax.
+///
+/// ## Errors
+///
+/// Errors in the same cases as [`mdast_to_markdown()`][] and
+/// [`to_mdast()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event::{Kind, Name}, mdast::build, mdast_to_events, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = build::root(vec![build::paragraph(vec![build::text("a*b")])]);
+/// let (value, events) = mdast_to_events(&tree, &ParseOptions::default())?;
+///
+/// assert_eq!(value, "a\\*b\n");
+/// assert!(events
+///     .iter()
+///     .any(|event| event.kind == Kind::Enter && event.name == Name::CharacterEscape));
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_events(
+    tree: &mdast::Node,
+    options: &ParseOptions,
+) -> Result<(String, Vec<event::Event>), message::Message> {
+    let value = mdast_to_markdown(tree)?;
+    let (events, _) = parser::parse(&value, options)?;
+    Ok((value, events))
+}
+
+/// Turn a syntax tree into markdown, with configuration.
+///
+/// ## Errors
+///
+/// `mdast_to_markdown_with_options()` errors if nodes are used where they
+/// cannot occur (see [`mdast_to_markdown()`][]), or if options have values
+/// that cannot be used (such as `'x'` as `bullet`).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast_to_markdown_with_options, to_mdast, ParseOptions, SerializeOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# Hey, *you*!\n\n* a", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     mdast_to_markdown_with_options(
+///         &tree,
+///         &SerializeOptions {
+///             bullet: '*',
+///             ..SerializeOptions::default()
+///         }
+///     )?,
+///     "# Hey, *you*!\n\n* a\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn mdast_to_markdown_with_options(
+    tree: &mdast::Node,
+    options: &SerializeOptions,
+) -> Result<String, message::Message> {
+    to_markdown::serialize(tree, options)
+}
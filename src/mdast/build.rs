@@ -0,0 +1,553 @@
+//! Build mdast nodes.
+//!
+//! Each function creates a [`Node`][] without positional info, filling in
+//! the fields that are not passed with the values markdown would have.
+//! When you need something else (such as a title on a link), construct the
+//! node yourself.
+//!
+//! The same functions are also available as associated functions on
+//! [`Node`][], such as [`Node::root()`][], which take a title for links and
+//! images.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::mdast::build::{emphasis, heading, paragraph, root, text};
+//! use markdown::mdast_to_markdown;
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! let tree = root(vec![
+//!     heading(2, vec![text("Hey")]),
+//!     paragraph(vec![text("Hi, "), emphasis(vec![text("you")]), text("!")]),
+//! ]);
+//!
+//! assert_eq!(mdast_to_markdown(&tree)?, "## Hey\n\nHi, _you_!\n");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::mdast::{
+    AlignKind, BlockQuote, Break, BreakKind, Code, Definition, Delete, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, Html, Image, ImageReference, InlineCode,
+    InlineMath, Link, LinkReference, List, ListItem, Math, Node, Paragraph, ReferenceKind, Root,
+    Strong, Table, TableCell, TableRow, Text, ThematicBreak, Toml, Yaml,
+};
+use crate::util::normalize_identifier::normalize_identifier;
+use alloc::vec::Vec;
+
+/// Build a [`Root`][].
+#[must_use]
+pub fn root(children: Vec<Node>) -> Node {
+    Node::Root(Root {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`Paragraph`][].
+#[must_use]
+pub fn paragraph(children: Vec<Node>) -> Node {
+    Node::Paragraph(Paragraph {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build an ATX [`Heading`][] of rank `depth` (`1` to `6`).
+#[must_use]
+pub fn heading(depth: u8, children: Vec<Node>) -> Node {
+    Node::Heading(Heading {
+        children,
+        position: None,
+        depth,
+        setext: false,
+        data: None,
+        markers: Vec::new(),
+    })
+}
+
+/// Build a [`ThematicBreak`][].
+#[must_use]
+pub fn thematic_break() -> Node {
+    Node::ThematicBreak(ThematicBreak {
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`BlockQuote`][].
+#[must_use]
+pub fn block_quote(children: Vec<Node>) -> Node {
+    Node::BlockQuote(BlockQuote {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a tight [`List`][].
+///
+/// Ordered lists start at `1`.
+#[must_use]
+pub fn list(ordered: bool, children: Vec<Node>) -> Node {
+    Node::List(List {
+        children,
+        position: None,
+        ordered,
+        start: if ordered { Some(1) } else { None },
+        spread: false,
+        data: None,
+    })
+}
+
+/// Build a tight [`ListItem`][], without a checkbox.
+#[must_use]
+pub fn list_item(children: Vec<Node>) -> Node {
+    Node::ListItem(ListItem {
+        children,
+        position: None,
+        spread: false,
+        checked: None,
+        marker: None,
+        number: None,
+        indent: None,
+        data: None,
+    })
+}
+
+/// Build an [`Html`][] node.
+#[must_use]
+pub fn html(value: &str) -> Node {
+    Node::Html(Html {
+        value: value.into(),
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`Code`][] block, with an optional language.
+#[must_use]
+pub fn code(value: &str, lang: Option<&str>) -> Node {
+    Node::Code(Code {
+        value: value.into(),
+        position: None,
+        lang: lang.map(Into::into),
+        meta: None,
+        data: None,
+    })
+}
+
+/// Build a [`Math`][] block.
+#[must_use]
+pub fn math(value: &str) -> Node {
+    Node::Math(Math {
+        value: value.into(),
+        position: None,
+        meta: None,
+        data: None,
+    })
+}
+
+/// Build a [`Definition`][] of `label` to `url`.
+#[must_use]
+pub fn definition(label: &str, url: &str) -> Node {
+    Node::Definition(Definition {
+        position: None,
+        url: url.into(),
+        title: None,
+        identifier: normalize_identifier(label),
+        label: Some(label.into()),
+        data: None,
+    })
+}
+
+/// Build a [`Text`][] node.
+#[must_use]
+pub fn text(value: &str) -> Node {
+    Node::Text(Text {
+        value: value.into(),
+        position: None,
+        data: None,
+    })
+}
+
+/// Build an [`Emphasis`][] node.
+#[must_use]
+pub fn emphasis(children: Vec<Node>) -> Node {
+    Node::Emphasis(Emphasis {
+        children,
+        position: None,
+        marker: None,
+        data: None,
+    })
+}
+
+/// Build a [`Strong`][] node.
+#[must_use]
+pub fn strong(children: Vec<Node>) -> Node {
+    Node::Strong(Strong {
+        children,
+        position: None,
+        marker: None,
+        data: None,
+    })
+}
+
+/// Build a [`Delete`][] node.
+#[must_use]
+pub fn delete(children: Vec<Node>) -> Node {
+    Node::Delete(Delete {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build an [`InlineCode`][] node.
+#[must_use]
+pub fn inline_code(value: &str) -> Node {
+    Node::InlineCode(InlineCode {
+        value: value.into(),
+        position: None,
+        sequence_size: None,
+        padding: false,
+        data: None,
+        markers: Vec::new(),
+    })
+}
+
+/// Build an [`InlineMath`][] node.
+#[must_use]
+pub fn inline_math(value: &str) -> Node {
+    Node::InlineMath(InlineMath {
+        value: value.into(),
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`Break`][], made with a backslash.
+#[must_use]
+pub fn break_node() -> Node {
+    Node::Break(Break {
+        position: None,
+        kind: BreakKind::Escape,
+        data: None,
+    })
+}
+
+/// Build a [`Link`][] to `url`.
+#[must_use]
+pub fn link(url: &str, children: Vec<Node>) -> Node {
+    Node::Link(Link {
+        children,
+        position: None,
+        url: url.into(),
+        title: None,
+        data: None,
+        markers: Vec::new(),
+    })
+}
+
+/// Build an [`Image`][] of `url`.
+#[must_use]
+pub fn image(url: &str, alt: &str) -> Node {
+    Node::Image(Image {
+        position: None,
+        alt: alt.into(),
+        url: url.into(),
+        title: None,
+        data: None,
+    })
+}
+
+/// Build a full [`LinkReference`][] to `label`.
+#[must_use]
+pub fn link_reference(label: &str, children: Vec<Node>) -> Node {
+    Node::LinkReference(LinkReference {
+        children,
+        position: None,
+        reference_kind: ReferenceKind::Full,
+        identifier: normalize_identifier(label),
+        label: Some(label.into()),
+        data: None,
+    })
+}
+
+/// Build a full [`ImageReference`][] to `label`.
+#[must_use]
+pub fn image_reference(label: &str, alt: &str) -> Node {
+    Node::ImageReference(ImageReference {
+        position: None,
+        alt: alt.into(),
+        reference_kind: ReferenceKind::Full,
+        identifier: normalize_identifier(label),
+        label: Some(label.into()),
+        data: None,
+    })
+}
+
+/// Build a [`FootnoteDefinition`][] of `label`.
+#[must_use]
+pub fn footnote_definition(label: &str, children: Vec<Node>) -> Node {
+    Node::FootnoteDefinition(FootnoteDefinition {
+        children,
+        position: None,
+        identifier: normalize_identifier(label),
+        label: Some(label.into()),
+        data: None,
+    })
+}
+
+/// Build a [`FootnoteReference`][] to `label`.
+#[must_use]
+pub fn footnote_reference(label: &str) -> Node {
+    Node::FootnoteReference(FootnoteReference {
+        position: None,
+        identifier: normalize_identifier(label),
+        label: Some(label.into()),
+        data: None,
+    })
+}
+
+/// Build a [`Table`][], with one [`AlignKind`][] per column.
+#[must_use]
+pub fn table(align: Vec<AlignKind>, children: Vec<Node>) -> Node {
+    Node::Table(Table {
+        children,
+        position: None,
+        align,
+        data: None,
+    })
+}
+
+/// Build a [`TableRow`][].
+#[must_use]
+pub fn table_row(children: Vec<Node>) -> Node {
+    Node::TableRow(TableRow {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`TableCell`][].
+#[must_use]
+pub fn table_cell(children: Vec<Node>) -> Node {
+    Node::TableCell(TableCell {
+        children,
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`Yaml`][] frontmatter node.
+#[must_use]
+pub fn yaml(value: &str) -> Node {
+    Node::Yaml(Yaml {
+        value: value.into(),
+        position: None,
+        data: None,
+    })
+}
+
+/// Build a [`Toml`][] frontmatter node.
+#[must_use]
+pub fn toml(value: &str) -> Node {
+    Node::Toml(Toml {
+        value: value.into(),
+        position: None,
+        data: None,
+    })
+}
+
+impl Node {
+    /// Build a [`Root`][], like [`root()`][root()].
+    #[must_use]
+    pub fn root(children: Vec<Node>) -> Node {
+        root(children)
+    }
+
+    /// Build a [`Paragraph`][], like [`paragraph()`][paragraph()].
+    #[must_use]
+    pub fn paragraph(children: Vec<Node>) -> Node {
+        paragraph(children)
+    }
+
+    /// Build an ATX [`Heading`][], like [`heading()`][heading()].
+    #[must_use]
+    pub fn heading(depth: u8, children: Vec<Node>) -> Node {
+        heading(depth, children)
+    }
+
+    /// Build a [`ThematicBreak`][], like [`thematic_break()`][thematic_break()].
+    #[must_use]
+    pub fn thematic_break() -> Node {
+        thematic_break()
+    }
+
+    /// Build a [`BlockQuote`][], like [`block_quote()`][block_quote()].
+    #[must_use]
+    pub fn block_quote(children: Vec<Node>) -> Node {
+        block_quote(children)
+    }
+
+    /// Build a tight [`List`][], like [`list()`][list()].
+    #[must_use]
+    pub fn list(ordered: bool, children: Vec<Node>) -> Node {
+        list(ordered, children)
+    }
+
+    /// Build a tight [`ListItem`][], like [`list_item()`][list_item()].
+    #[must_use]
+    pub fn list_item(children: Vec<Node>) -> Node {
+        list_item(children)
+    }
+
+    /// Build an [`Html`][], like [`html()`][html()].
+    #[must_use]
+    pub fn html(value: &str) -> Node {
+        html(value)
+    }
+
+    /// Build a [`Code`][], like [`code()`][code()].
+    #[must_use]
+    pub fn code(value: &str, lang: Option<&str>) -> Node {
+        code(value, lang)
+    }
+
+    /// Build a [`Math`][], like [`math()`][math()].
+    #[must_use]
+    pub fn math(value: &str) -> Node {
+        math(value)
+    }
+
+    /// Build a [`Definition`][], like [`definition()`][definition()].
+    #[must_use]
+    pub fn definition(label: &str, url: &str) -> Node {
+        definition(label, url)
+    }
+
+    /// Build a [`Text`][], like [`text()`][text()].
+    #[must_use]
+    pub fn text(value: &str) -> Node {
+        text(value)
+    }
+
+    /// Build an [`Emphasis`][], like [`emphasis()`][emphasis()].
+    #[must_use]
+    pub fn emphasis(children: Vec<Node>) -> Node {
+        emphasis(children)
+    }
+
+    /// Build a [`Strong`][], like [`strong()`][strong()].
+    #[must_use]
+    pub fn strong(children: Vec<Node>) -> Node {
+        strong(children)
+    }
+
+    /// Build a [`Delete`][], like [`delete()`][delete()].
+    #[must_use]
+    pub fn delete(children: Vec<Node>) -> Node {
+        delete(children)
+    }
+
+    /// Build an [`InlineCode`][], like [`inline_code()`][inline_code()].
+    #[must_use]
+    pub fn inline_code(value: &str) -> Node {
+        inline_code(value)
+    }
+
+    /// Build an [`InlineMath`][], like [`inline_math()`][inline_math()].
+    #[must_use]
+    pub fn inline_math(value: &str) -> Node {
+        inline_math(value)
+    }
+
+    /// Build a [`Break`][], like [`break_node()`][break_node()].
+    #[must_use]
+    pub fn break_node() -> Node {
+        break_node()
+    }
+
+    /// Build a [`Link`][] to `url`, with an optional title.
+    #[must_use]
+    pub fn link(url: &str, title: Option<&str>, children: Vec<Node>) -> Node {
+        Node::Link(Link {
+            children,
+            position: None,
+            url: url.into(),
+            title: title.map(Into::into),
+            data: None,
+            markers: Vec::new(),
+        })
+    }
+
+    /// Build an [`Image`][] of `url`, with an optional title.
+    #[must_use]
+    pub fn image(url: &str, title: Option<&str>, alt: &str) -> Node {
+        Node::Image(Image {
+            position: None,
+            alt: alt.into(),
+            url: url.into(),
+            title: title.map(Into::into),
+            data: None,
+        })
+    }
+
+    /// Build a full [`LinkReference`][], like [`link_reference()`][link_reference()].
+    #[must_use]
+    pub fn link_reference(label: &str, children: Vec<Node>) -> Node {
+        link_reference(label, children)
+    }
+
+    /// Build a full [`ImageReference`][], like [`image_reference()`][image_reference()].
+    #[must_use]
+    pub fn image_reference(label: &str, alt: &str) -> Node {
+        image_reference(label, alt)
+    }
+
+    /// Build a [`FootnoteDefinition`][], like [`footnote_definition()`][footnote_definition()].
+    #[must_use]
+    pub fn footnote_definition(label: &str, children: Vec<Node>) -> Node {
+        footnote_definition(label, children)
+    }
+
+    /// Build a [`FootnoteReference`][], like [`footnote_reference()`][footnote_reference()].
+    #[must_use]
+    pub fn footnote_reference(label: &str) -> Node {
+        footnote_reference(label)
+    }
+
+    /// Build a [`Table`][], like [`table()`][table()].
+    #[must_use]
+    pub fn table(align: Vec<AlignKind>, children: Vec<Node>) -> Node {
+        table(align, children)
+    }
+
+    /// Build a [`TableRow`][], like [`table_row()`][table_row()].
+    #[must_use]
+    pub fn table_row(children: Vec<Node>) -> Node {
+        table_row(children)
+    }
+
+    /// Build a [`TableCell`][], like [`table_cell()`][table_cell()].
+    #[must_use]
+    pub fn table_cell(children: Vec<Node>) -> Node {
+        table_cell(children)
+    }
+
+    /// Build a [`Yaml`][], like [`yaml()`][yaml()].
+    #[must_use]
+    pub fn yaml(value: &str) -> Node {
+        yaml(value)
+    }
+
+    /// Build a [`Toml`][], like [`toml()`][toml()].
+    #[must_use]
+    pub fn toml(value: &str) -> Node {
+        toml(value)
+    }
+}
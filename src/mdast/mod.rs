@@ -0,0 +1,4715 @@
+//! markdown syntax tree: [mdast][].
+//!
+//! [mdast]: https://github.com/syntax-tree/mdast
+
+pub mod build;
+pub mod stats;
+pub mod toc;
+
+use crate::unist::Position;
+use crate::util::{
+    normalize_identifier::normalize_identifier,
+    slug::{self, Slugger, Slugify},
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    fmt, format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::slice;
+
+/// MDX: relative byte index into a string, to an absolute byte index into the
+/// whole document.
+pub type Stop = (usize, usize);
+
+/// Extra info on a node, by key.
+///
+/// The parser does not add data, and compilers ignore it, unless noted
+/// otherwise.
+/// Plugins and utilities can use it to store things they compute, such as a
+/// slug for a heading.
+pub type Data = BTreeMap<String, String>;
+
+/// Explicitness of a reference.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum ReferenceKind {
+    /// The reference is implicit, its identifier inferred from its content.
+    Shortcut,
+    /// The reference is explicit, its identifier inferred from its content.
+    Collapsed,
+    /// The reference is explicit, its identifier explicitly set.
+    Full,
+}
+
+/// GFM: alignment of phrasing content.
+///
+/// Used to align the contents of table cells within a table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum AlignKind {
+    /// Left alignment.
+    ///
+    /// See the `left` value of the `text-align` CSS property.
+    ///
+    /// ```markdown
+    ///   | | aaa |
+    /// > | | :-- |
+    ///       ^^^
+    /// ```
+    Left,
+    /// Right alignment.
+    ///
+    /// See the `right` value of the `text-align` CSS property.
+    ///
+    /// ```markdown
+    ///   | | aaa |
+    /// > | | --: |
+    ///       ^^^
+    /// ```
+    Right,
+    /// Center alignment.
+    ///
+    /// See the `center` value of the `text-align` CSS property.
+    ///
+    /// ```markdown
+    ///   | | aaa |
+    /// > | | :-: |
+    ///       ^^^
+    /// ```
+    Center,
+    /// No alignment.
+    ///
+    /// Phrasing content is aligned as defined by the host environment.
+    ///
+    /// ```markdown
+    ///   | | aaa |
+    /// > | | --- |
+    ///       ^^^
+    /// ```
+    None,
+}
+
+/// Style of a hard break.
+///
+/// Used when serializing to markdown, not when compiling to HTML.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum BreakKind {
+    /// Hard break made with a backslash.
+    ///
+    /// ```markdown
+    /// > | a\
+    ///      ^
+    ///   | b
+    /// ```
+    #[default]
+    Escape,
+    /// Hard break made with two or more trailing spaces.
+    ///
+    /// ```markdown
+    /// > | a␠␠
+    ///      ^^
+    ///   | b
+    /// ```
+    Trailing,
+}
+
+/// Nodes.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "type")
+)]
+pub enum Node {
+    // Document:
+    /// Root.
+    Root(Root),
+
+    // Container:
+    /// Block quote.
+    BlockQuote(BlockQuote),
+    /// Footnote definition.
+    FootnoteDefinition(FootnoteDefinition),
+    /// MDX: JSX element (container).
+    MdxJsxFlowElement(MdxJsxFlowElement),
+    /// List.
+    List(List),
+
+    // Frontmatter:
+    /// MDX.js ESM.
+    MdxjsEsm(MdxjsEsm),
+    /// Toml.
+    Toml(Toml),
+    /// Yaml.
+    Yaml(Yaml),
+
+    // Phrasing:
+    /// Break.
+    Break(Break),
+    /// Code (phrasing).
+    InlineCode(InlineCode),
+    /// Math (phrasing).
+    InlineMath(InlineMath),
+    /// Delete.
+    Delete(Delete),
+    /// Emphasis.
+    Emphasis(Emphasis),
+    // MDX: expression (text).
+    MdxTextExpression(MdxTextExpression),
+    /// Footnote reference.
+    FootnoteReference(FootnoteReference),
+    /// Html (phrasing).
+    Html(Html),
+    /// Image.
+    Image(Image),
+    /// Image reference.
+    ImageReference(ImageReference),
+    // MDX: JSX element (text).
+    MdxJsxTextElement(MdxJsxTextElement),
+    /// Link.
+    Link(Link),
+    /// Link reference.
+    LinkReference(LinkReference),
+    /// Strong
+    Strong(Strong),
+    /// Text.
+    Text(Text),
+
+    // Flow:
+    /// Code (flow).
+    Code(Code),
+    /// Math (flow).
+    Math(Math),
+    // MDX: expression (flow).
+    MdxFlowExpression(MdxFlowExpression),
+    /// Heading.
+    Heading(Heading),
+    /// Html (flow).
+    // Html(Html),
+    /// Table.
+    Table(Table),
+    /// Thematic break.
+    ThematicBreak(ThematicBreak),
+
+    // Table content.
+    /// Table row.
+    TableRow(TableRow),
+
+    // Row content.
+    /// Table cell.
+    TableCell(TableCell),
+
+    // List content.
+    /// List item.
+    ListItem(ListItem),
+
+    // Content.
+    /// Definition.
+    Definition(Definition),
+    /// Paragraph.
+    Paragraph(Paragraph),
+}
+
+/// Kinds of nodes, without their fields.
+///
+/// Mirrors the variants of [`Node`][], which can be matched with
+/// [`Node::kind()`][] and queried with [`Node::find_all()`][] and
+/// [`Node::find_first()`][].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NodeKind {
+    // Document:
+    /// Root.
+    Root,
+
+    // Container:
+    /// Block quote.
+    BlockQuote,
+    /// Footnote definition.
+    FootnoteDefinition,
+    /// MDX: JSX element (container).
+    MdxJsxFlowElement,
+    /// List.
+    List,
+
+    // Frontmatter:
+    /// MDX.js ESM.
+    MdxjsEsm,
+    /// Toml.
+    Toml,
+    /// Yaml.
+    Yaml,
+
+    // Phrasing:
+    /// Break.
+    Break,
+    /// Code (phrasing).
+    InlineCode,
+    /// Math (phrasing).
+    InlineMath,
+    /// Delete.
+    Delete,
+    /// Emphasis.
+    Emphasis,
+    // MDX: expression (text).
+    MdxTextExpression,
+    /// Footnote reference.
+    FootnoteReference,
+    /// Html (phrasing).
+    Html,
+    /// Image.
+    Image,
+    /// Image reference.
+    ImageReference,
+    // MDX: JSX element (text).
+    MdxJsxTextElement,
+    /// Link.
+    Link,
+    /// Link reference.
+    LinkReference,
+    /// Strong
+    Strong,
+    /// Text.
+    Text,
+
+    // Flow:
+    /// Code (flow).
+    Code,
+    /// Math (flow).
+    Math,
+    // MDX: expression (flow).
+    MdxFlowExpression,
+    /// Heading.
+    Heading,
+    /// Html (flow).
+    // Html,
+    /// Table.
+    Table,
+    /// Thematic break.
+    ThematicBreak,
+
+    // Table content.
+    /// Table row.
+    TableRow,
+
+    // Row content.
+    /// Table cell.
+    TableCell,
+
+    // List content.
+    /// List item.
+    ListItem,
+
+    // Content.
+    /// Definition.
+    Definition,
+    /// Paragraph.
+    Paragraph,
+}
+
+impl fmt::Debug for Node {
+    // Debug the wrapped struct.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Root(x) => x.fmt(f),
+            Node::BlockQuote(x) => x.fmt(f),
+            Node::FootnoteDefinition(x) => x.fmt(f),
+            Node::MdxJsxFlowElement(x) => x.fmt(f),
+            Node::List(x) => x.fmt(f),
+            Node::MdxjsEsm(x) => x.fmt(f),
+            Node::Toml(x) => x.fmt(f),
+            Node::Yaml(x) => x.fmt(f),
+            Node::Break(x) => x.fmt(f),
+            Node::InlineCode(x) => x.fmt(f),
+            Node::InlineMath(x) => x.fmt(f),
+            Node::Delete(x) => x.fmt(f),
+            Node::Emphasis(x) => x.fmt(f),
+            Node::MdxTextExpression(x) => x.fmt(f),
+            Node::FootnoteReference(x) => x.fmt(f),
+            Node::Html(x) => x.fmt(f),
+            Node::Image(x) => x.fmt(f),
+            Node::ImageReference(x) => x.fmt(f),
+            Node::MdxJsxTextElement(x) => x.fmt(f),
+            Node::Link(x) => x.fmt(f),
+            Node::LinkReference(x) => x.fmt(f),
+            Node::Strong(x) => x.fmt(f),
+            Node::Text(x) => x.fmt(f),
+            Node::Code(x) => x.fmt(f),
+            Node::Math(x) => x.fmt(f),
+            Node::MdxFlowExpression(x) => x.fmt(f),
+            Node::Heading(x) => x.fmt(f),
+            Node::Table(x) => x.fmt(f),
+            Node::ThematicBreak(x) => x.fmt(f),
+            Node::TableRow(x) => x.fmt(f),
+            Node::TableCell(x) => x.fmt(f),
+            Node::ListItem(x) => x.fmt(f),
+            Node::Definition(x) => x.fmt(f),
+            Node::Paragraph(x) => x.fmt(f),
+        }
+    }
+}
+
+fn children_to_string(children: &[Node]) -> String {
+    children.iter().map(ToString::to_string).collect()
+}
+
+/// Get the text of blocks, with `separator` between those that have text.
+fn join_text(children: &[Node], separator: &str) -> String {
+    children
+        .iter()
+        .map(Node::to_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+// To do: clippy may be right but that’s a breaking change.
+#[allow(clippy::to_string_trait_impl)]
+impl ToString for Node {
+    fn to_string(&self) -> String {
+        match self {
+            // Parents.
+            Node::Root(x) => children_to_string(&x.children),
+            Node::BlockQuote(x) => children_to_string(&x.children),
+            Node::FootnoteDefinition(x) => children_to_string(&x.children),
+            Node::MdxJsxFlowElement(x) => children_to_string(&x.children),
+            Node::List(x) => children_to_string(&x.children),
+            Node::Delete(x) => children_to_string(&x.children),
+            Node::Emphasis(x) => children_to_string(&x.children),
+            Node::MdxJsxTextElement(x) => children_to_string(&x.children),
+            Node::Link(x) => children_to_string(&x.children),
+            Node::LinkReference(x) => children_to_string(&x.children),
+            Node::Strong(x) => children_to_string(&x.children),
+            Node::Heading(x) => children_to_string(&x.children),
+            Node::Table(x) => children_to_string(&x.children),
+            Node::TableRow(x) => children_to_string(&x.children),
+            Node::TableCell(x) => children_to_string(&x.children),
+            Node::ListItem(x) => children_to_string(&x.children),
+            Node::Paragraph(x) => children_to_string(&x.children),
+
+            // Literals.
+            Node::MdxjsEsm(x) => x.value.clone(),
+            Node::Toml(x) => x.value.clone(),
+            Node::Yaml(x) => x.value.clone(),
+            Node::InlineCode(x) => x.value.clone(),
+            Node::InlineMath(x) => x.value.clone(),
+            Node::MdxTextExpression(x) => x.value.clone(),
+            Node::Html(x) => x.value.clone(),
+            Node::Text(x) => x.value.clone(),
+            Node::Code(x) => x.value.clone(),
+            Node::Math(x) => x.value.clone(),
+            Node::MdxFlowExpression(x) => x.value.clone(),
+
+            // Voids.
+            Node::Break(_)
+            | Node::FootnoteReference(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => String::new(),
+        }
+    }
+}
+
+impl Node {
+    /// Get the children of a parent node.
+    ///
+    /// Returns `None` for literals and other nodes that cannot have children.
+    #[must_use]
+    pub fn children(&self) -> Option<&Vec<Node>> {
+        match self {
+            // Parent.
+            Node::Root(x) => Some(&x.children),
+            Node::Paragraph(x) => Some(&x.children),
+            Node::Heading(x) => Some(&x.children),
+            Node::BlockQuote(x) => Some(&x.children),
+            Node::List(x) => Some(&x.children),
+            Node::ListItem(x) => Some(&x.children),
+            Node::Emphasis(x) => Some(&x.children),
+            Node::Strong(x) => Some(&x.children),
+            Node::Link(x) => Some(&x.children),
+            Node::LinkReference(x) => Some(&x.children),
+            Node::FootnoteDefinition(x) => Some(&x.children),
+            Node::Table(x) => Some(&x.children),
+            Node::TableRow(x) => Some(&x.children),
+            Node::TableCell(x) => Some(&x.children),
+            Node::Delete(x) => Some(&x.children),
+            Node::MdxJsxFlowElement(x) => Some(&x.children),
+            Node::MdxJsxTextElement(x) => Some(&x.children),
+            // Non-parent.
+            Node::MdxjsEsm(_)
+            | Node::Toml(_)
+            | Node::Yaml(_)
+            | Node::Break(_)
+            | Node::InlineCode(_)
+            | Node::InlineMath(_)
+            | Node::MdxTextExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::Html(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::Text(_)
+            | Node::Code(_)
+            | Node::Math(_)
+            | Node::MdxFlowExpression(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => None,
+        }
+    }
+
+    /// Get the children of a parent node, mutably.
+    ///
+    /// Returns `None` for literals and other nodes that cannot have children.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match self {
+            // Parent.
+            Node::Root(x) => Some(&mut x.children),
+            Node::Paragraph(x) => Some(&mut x.children),
+            Node::Heading(x) => Some(&mut x.children),
+            Node::BlockQuote(x) => Some(&mut x.children),
+            Node::List(x) => Some(&mut x.children),
+            Node::ListItem(x) => Some(&mut x.children),
+            Node::Emphasis(x) => Some(&mut x.children),
+            Node::Strong(x) => Some(&mut x.children),
+            Node::Link(x) => Some(&mut x.children),
+            Node::LinkReference(x) => Some(&mut x.children),
+            Node::FootnoteDefinition(x) => Some(&mut x.children),
+            Node::Table(x) => Some(&mut x.children),
+            Node::TableRow(x) => Some(&mut x.children),
+            Node::TableCell(x) => Some(&mut x.children),
+            Node::Delete(x) => Some(&mut x.children),
+            Node::MdxJsxFlowElement(x) => Some(&mut x.children),
+            Node::MdxJsxTextElement(x) => Some(&mut x.children),
+            // Non-parent.
+            Node::MdxjsEsm(_)
+            | Node::Toml(_)
+            | Node::Yaml(_)
+            | Node::Break(_)
+            | Node::InlineCode(_)
+            | Node::InlineMath(_)
+            | Node::MdxTextExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::Html(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::Text(_)
+            | Node::Code(_)
+            | Node::Math(_)
+            | Node::MdxFlowExpression(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => None,
+        }
+    }
+
+    /// Drop the node and its descendants without recursion.
+    ///
+    /// Dropping a node normally is recursive, which overflows the stack for
+    /// very deep trees, such as tens of thousands of nested block quotes.
+    /// Use this to drop trees made from untrusted markdown.
+    pub fn drop_iterative(mut self) {
+        let mut stack = vec![];
+
+        if let Some(children) = self.children_mut() {
+            stack.append(children);
+        }
+
+        while let Some(mut node) = stack.pop() {
+            if let Some(children) = node.children_mut() {
+                stack.append(children);
+            }
+        }
+    }
+
+    /// Get the position of a node, if it has one.
+    #[must_use]
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            Node::Root(x) => x.position.as_ref(),
+            Node::BlockQuote(x) => x.position.as_ref(),
+            Node::FootnoteDefinition(x) => x.position.as_ref(),
+            Node::MdxJsxFlowElement(x) => x.position.as_ref(),
+            Node::List(x) => x.position.as_ref(),
+            Node::MdxjsEsm(x) => x.position.as_ref(),
+            Node::Toml(x) => x.position.as_ref(),
+            Node::Yaml(x) => x.position.as_ref(),
+            Node::Break(x) => x.position.as_ref(),
+            Node::InlineCode(x) => x.position.as_ref(),
+            Node::InlineMath(x) => x.position.as_ref(),
+            Node::Delete(x) => x.position.as_ref(),
+            Node::Emphasis(x) => x.position.as_ref(),
+            Node::MdxTextExpression(x) => x.position.as_ref(),
+            Node::FootnoteReference(x) => x.position.as_ref(),
+            Node::Html(x) => x.position.as_ref(),
+            Node::Image(x) => x.position.as_ref(),
+            Node::ImageReference(x) => x.position.as_ref(),
+            Node::MdxJsxTextElement(x) => x.position.as_ref(),
+            Node::Link(x) => x.position.as_ref(),
+            Node::LinkReference(x) => x.position.as_ref(),
+            Node::Strong(x) => x.position.as_ref(),
+            Node::Text(x) => x.position.as_ref(),
+            Node::Code(x) => x.position.as_ref(),
+            Node::Math(x) => x.position.as_ref(),
+            Node::MdxFlowExpression(x) => x.position.as_ref(),
+            Node::Heading(x) => x.position.as_ref(),
+            Node::Table(x) => x.position.as_ref(),
+            Node::ThematicBreak(x) => x.position.as_ref(),
+            Node::TableRow(x) => x.position.as_ref(),
+            Node::TableCell(x) => x.position.as_ref(),
+            Node::ListItem(x) => x.position.as_ref(),
+            Node::Definition(x) => x.position.as_ref(),
+            Node::Paragraph(x) => x.position.as_ref(),
+        }
+    }
+
+    /// Get the position of a node, mutably, if it has one.
+    pub fn position_mut(&mut self) -> Option<&mut Position> {
+        match self {
+            Node::Root(x) => x.position.as_mut(),
+            Node::BlockQuote(x) => x.position.as_mut(),
+            Node::FootnoteDefinition(x) => x.position.as_mut(),
+            Node::MdxJsxFlowElement(x) => x.position.as_mut(),
+            Node::List(x) => x.position.as_mut(),
+            Node::MdxjsEsm(x) => x.position.as_mut(),
+            Node::Toml(x) => x.position.as_mut(),
+            Node::Yaml(x) => x.position.as_mut(),
+            Node::Break(x) => x.position.as_mut(),
+            Node::InlineCode(x) => x.position.as_mut(),
+            Node::InlineMath(x) => x.position.as_mut(),
+            Node::Delete(x) => x.position.as_mut(),
+            Node::Emphasis(x) => x.position.as_mut(),
+            Node::MdxTextExpression(x) => x.position.as_mut(),
+            Node::FootnoteReference(x) => x.position.as_mut(),
+            Node::Html(x) => x.position.as_mut(),
+            Node::Image(x) => x.position.as_mut(),
+            Node::ImageReference(x) => x.position.as_mut(),
+            Node::MdxJsxTextElement(x) => x.position.as_mut(),
+            Node::Link(x) => x.position.as_mut(),
+            Node::LinkReference(x) => x.position.as_mut(),
+            Node::Strong(x) => x.position.as_mut(),
+            Node::Text(x) => x.position.as_mut(),
+            Node::Code(x) => x.position.as_mut(),
+            Node::Math(x) => x.position.as_mut(),
+            Node::MdxFlowExpression(x) => x.position.as_mut(),
+            Node::Heading(x) => x.position.as_mut(),
+            Node::Table(x) => x.position.as_mut(),
+            Node::ThematicBreak(x) => x.position.as_mut(),
+            Node::TableRow(x) => x.position.as_mut(),
+            Node::TableCell(x) => x.position.as_mut(),
+            Node::ListItem(x) => x.position.as_mut(),
+            Node::Definition(x) => x.position.as_mut(),
+            Node::Paragraph(x) => x.position.as_mut(),
+        }
+    }
+
+    /// Set (or clear) the position of a node.
+    pub fn position_set(&mut self, position: Option<Position>) {
+        match self {
+            Node::Root(x) => x.position = position,
+            Node::BlockQuote(x) => x.position = position,
+            Node::FootnoteDefinition(x) => x.position = position,
+            Node::MdxJsxFlowElement(x) => x.position = position,
+            Node::List(x) => x.position = position,
+            Node::MdxjsEsm(x) => x.position = position,
+            Node::Toml(x) => x.position = position,
+            Node::Yaml(x) => x.position = position,
+            Node::Break(x) => x.position = position,
+            Node::InlineCode(x) => x.position = position,
+            Node::InlineMath(x) => x.position = position,
+            Node::Delete(x) => x.position = position,
+            Node::Emphasis(x) => x.position = position,
+            Node::MdxTextExpression(x) => x.position = position,
+            Node::FootnoteReference(x) => x.position = position,
+            Node::Html(x) => x.position = position,
+            Node::Image(x) => x.position = position,
+            Node::ImageReference(x) => x.position = position,
+            Node::MdxJsxTextElement(x) => x.position = position,
+            Node::Link(x) => x.position = position,
+            Node::LinkReference(x) => x.position = position,
+            Node::Strong(x) => x.position = position,
+            Node::Text(x) => x.position = position,
+            Node::Code(x) => x.position = position,
+            Node::Math(x) => x.position = position,
+            Node::MdxFlowExpression(x) => x.position = position,
+            Node::Heading(x) => x.position = position,
+            Node::Table(x) => x.position = position,
+            Node::ThematicBreak(x) => x.position = position,
+            Node::TableRow(x) => x.position = position,
+            Node::TableCell(x) => x.position = position,
+            Node::ListItem(x) => x.position = position,
+            Node::Definition(x) => x.position = position,
+            Node::Paragraph(x) => x.position = position,
+        }
+    }
+
+    /// Iterate over this node and its descendants.
+    ///
+    /// Nodes are yielded in the same order as with [`visit()`][]: pre-order,
+    /// depth-first.
+    /// The iterator is lazy and uses no recursion, so deeply nested trees do
+    /// not overflow the stack.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# a\n\nb\n\n## c", &ParseOptions::default())?;
+    /// let headings = tree
+    ///     .iter()
+    ///     .filter(|node| matches!(node, Node::Heading(_)))
+    ///     .map(ToString::to_string)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(headings, vec!["a", "c"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.iter_depth())
+    }
+
+    /// Iterate over this node and its descendants, with their depth.
+    ///
+    /// Like [`Node::iter()`][], but yields the depth of each node along with
+    /// it: `0` for this node, `1` for its children, and so on.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("> a", &ParseOptions::default())?;
+    /// let depths = tree
+    ///     .iter_depth()
+    ///     .map(|(depth, _)| depth)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(depths, vec![0, 1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn iter_depth(&self) -> IterDepth<'_> {
+        IterDepth {
+            root: Some(self),
+            stack: vec![],
+        }
+    }
+
+    /// Get the human-visible text of this node and its descendants.
+    ///
+    /// Unlike [`ToString`][], which concatenates the values of all literals,
+    /// this only includes what a reader sees: text, code, and math, plus the
+    /// alt text of images, and a line ending for each hard break.
+    /// Blocks are separated by a blank line (or a line ending, in tight
+    /// lists), table rows by a line ending, and cells by a tab.
+    /// HTML, definitions, footnote references, frontmatter, and MDX
+    /// expressions and ESM are ignored.
+    /// This is useful for generating slugs, search indexes, or descriptions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# *a* `b` <i>c</i> ![d](e)", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(tree.to_text(), "a b c d");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            // Parents of blocks: blank lines between them.
+            Node::Root(Root { children, .. })
+            | Node::BlockQuote(BlockQuote { children, .. })
+            | Node::FootnoteDefinition(FootnoteDefinition { children, .. })
+            | Node::MdxJsxFlowElement(MdxJsxFlowElement { children, .. }) => {
+                join_text(children, "\n\n")
+            }
+            // Tight lists and list items: line endings between blocks.
+            Node::List(List {
+                children, spread, ..
+            })
+            | Node::ListItem(ListItem {
+                children, spread, ..
+            }) => join_text(children, if *spread { "\n\n" } else { "\n" }),
+            Node::Table(Table { children, .. }) => join_text(children, "\n"),
+            Node::TableRow(TableRow { children, .. }) => children
+                .iter()
+                .map(Node::to_text)
+                .collect::<Vec<_>>()
+                .join("\t"),
+
+            // Parents of phrasing content.
+            Node::Delete(_)
+            | Node::Emphasis(_)
+            | Node::MdxJsxTextElement(_)
+            | Node::Link(_)
+            | Node::LinkReference(_)
+            | Node::Strong(_)
+            | Node::Heading(_)
+            | Node::TableCell(_)
+            | Node::Paragraph(_) => self
+                .children()
+                .map(|children| children.iter().map(Node::to_text).collect())
+                .unwrap_or_default(),
+
+            // Visible literals.
+            Node::InlineCode(InlineCode { value, .. })
+            | Node::InlineMath(InlineMath { value, .. })
+            | Node::Text(Text { value, .. })
+            | Node::Code(Code { value, .. })
+            | Node::Math(Math { value, .. })
+            | Node::Image(Image { alt: value, .. })
+            | Node::ImageReference(ImageReference { alt: value, .. }) => value.clone(),
+            Node::Break(_) => "\n".into(),
+
+            // Invisible.
+            Node::MdxjsEsm(_)
+            | Node::Toml(_)
+            | Node::Yaml(_)
+            | Node::MdxTextExpression(_)
+            | Node::Html(_)
+            | Node::MdxFlowExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => String::new(),
+        }
+    }
+
+    /// Get the source of this node, as written in `value`.
+    ///
+    /// `value` must be the document that this node was parsed from, with
+    /// positions that count bytes (the default
+    /// [`position_encoding`][crate::ParseOptions::position_encoding]).
+    /// Returns `None` if the node has no position, or if its position is not
+    /// in `value`.
+    ///
+    /// Fields of nodes are decoded: for example, the `url` of a link has
+    /// character escapes and references resolved.
+    /// Use this to get what was actually written instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let value = "[a](b\\)c \"d&amp;e\")";
+    /// let tree = to_mdast(value, &ParseOptions::default())?;
+    /// let link = &tree.children().unwrap()[0].children().unwrap()[0];
+    ///
+    /// if let Node::Link(node) = link {
+    ///     assert_eq!(node.url, "b)c");
+    ///     assert_eq!(node.title, Some("d&e".into()));
+    /// }
+    ///
+    /// assert_eq!(link.source(value), Some("[a](b\\)c \"d&amp;e\")"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn source<'a>(&self, value: &'a str) -> Option<&'a str> {
+        let position = self.position()?;
+        value.get(position.start.offset..position.end.offset)
+    }
+
+    /// Find the nodes at a byte offset into the document.
+    ///
+    /// Returns the nodes whose position contains `offset` (where the start
+    /// is included and the end is not), from this node down to the innermost
+    /// node, or `None` if no node contains it.
+    /// An offset in the markers of a construct (such as the `**` of strong)
+    /// ends at that construct, and one in blank lines between blocks only
+    /// finds the root.
+    /// Nodes without a position are skipped, but their children are still
+    /// checked.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a **b**", &ParseOptions::default())?;
+    /// let names = |offset| {
+    ///     tree.node_at_offset(offset).map(|nodes| {
+    ///         nodes
+    ///             .iter()
+    ///             .map(|node| match node {
+    ///                 Node::Root(_) => "root",
+    ///                 Node::Paragraph(_) => "paragraph",
+    ///                 Node::Strong(_) => "strong",
+    ///                 _ => "text",
+    ///             })
+    ///             .collect::<Vec<_>>()
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(names(4), Some(vec!["root", "paragraph", "strong", "text"]));
+    /// assert_eq!(names(2), Some(vec!["root", "paragraph", "strong"]));
+    /// assert_eq!(names(7), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn node_at_offset(&self, offset: usize) -> Option<Vec<&Node>> {
+        let mut nodes = vec![];
+        node_at_offset_impl(self, offset, &mut nodes);
+
+        if nodes.is_empty() {
+            None
+        } else {
+            Some(nodes)
+        }
+    }
+
+    /// Check whether this node is equal to `other`, ignoring positions.
+    ///
+    /// Nodes are compared like with `==`, including their descendants and
+    /// fields such as `value`, `url`, `title`, and `identifier`, but the
+    /// `position` (and `markers`) of every node is treated as equal.
+    /// This is useful to compare a parsed tree with one built by hand.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("***", &ParseOptions::default())?;
+    /// let expected = Node::root(vec![Node::thematic_break()]);
+    ///
+    /// assert_ne!(tree, expected);
+    /// assert!(tree.eq_ignoring_positions(&expected));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn eq_ignoring_positions(&self, other: &Node) -> bool {
+        let mut left = self.clone();
+        let mut right = other.clone();
+        let strip = |node: &mut Node, _: &[usize]| {
+            node.position_set(None);
+            match node {
+                Node::Heading(node) => node.markers.clear(),
+                Node::InlineCode(node) => node.markers.clear(),
+                Node::Link(node) => node.markers.clear(),
+                _ => {}
+            }
+            VisitAction::Continue
+        };
+        visit_mut(&mut left, strip);
+        visit_mut(&mut right, strip);
+        left == right
+    }
+
+    /// Merge adjacent [`Text`][] nodes, in this node and its descendants.
+    ///
+    /// The parser does not produce adjacent texts (such as around character
+    /// escapes and references), but trees that were changed or built by hand
+    /// can have them.
+    /// Texts are merged into the first one, and only when they are siblings:
+    /// merging does not cross other nodes.
+    /// The position of a merged text spans from the start of the first text
+    /// to the end of the last one, or is `None` when one of them has no
+    /// position.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::Node;
+    ///
+    /// let mut tree = Node::paragraph(vec![
+    ///     Node::text("a"),
+    ///     Node::text("*"),
+    ///     Node::text("b"),
+    ///     Node::emphasis(vec![Node::text("c")]),
+    ///     Node::text("d"),
+    /// ]);
+    ///
+    /// tree.normalize();
+    ///
+    /// assert_eq!(
+    ///     tree,
+    ///     Node::paragraph(vec![
+    ///         Node::text("a*b"),
+    ///         Node::emphasis(vec![Node::text("c")]),
+    ///         Node::text("d"),
+    ///     ])
+    /// );
+    /// ```
+    pub fn normalize(&mut self) {
+        if let Some(children) = self.children_mut() {
+            let mut result: Vec<Node> = Vec::with_capacity(children.len());
+
+            for mut child in children.drain(..) {
+                if let (Some(Node::Text(previous)), Node::Text(text)) = (result.last_mut(), &child)
+                {
+                    previous.value.push_str(&text.value);
+                    previous.position = match (previous.position.take(), &text.position) {
+                        (Some(start), Some(end)) => Some(Position {
+                            start: start.start,
+                            end: end.end.clone(),
+                        }),
+                        _ => None,
+                    };
+                } else {
+                    child.normalize();
+                    result.push(child);
+                }
+            }
+
+            *children = result;
+        }
+    }
+
+    /// Get the kind of this node.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::{Node, NodeKind};
+    ///
+    /// assert_eq!(Node::text("a").kind(), NodeKind::Text);
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> NodeKind {
+        match self {
+            Node::Root(_) => NodeKind::Root,
+            Node::BlockQuote(_) => NodeKind::BlockQuote,
+            Node::FootnoteDefinition(_) => NodeKind::FootnoteDefinition,
+            Node::MdxJsxFlowElement(_) => NodeKind::MdxJsxFlowElement,
+            Node::List(_) => NodeKind::List,
+            Node::MdxjsEsm(_) => NodeKind::MdxjsEsm,
+            Node::Toml(_) => NodeKind::Toml,
+            Node::Yaml(_) => NodeKind::Yaml,
+            Node::Break(_) => NodeKind::Break,
+            Node::InlineCode(_) => NodeKind::InlineCode,
+            Node::InlineMath(_) => NodeKind::InlineMath,
+            Node::Delete(_) => NodeKind::Delete,
+            Node::Emphasis(_) => NodeKind::Emphasis,
+            Node::MdxTextExpression(_) => NodeKind::MdxTextExpression,
+            Node::FootnoteReference(_) => NodeKind::FootnoteReference,
+            Node::Html(_) => NodeKind::Html,
+            Node::Image(_) => NodeKind::Image,
+            Node::ImageReference(_) => NodeKind::ImageReference,
+            Node::MdxJsxTextElement(_) => NodeKind::MdxJsxTextElement,
+            Node::Link(_) => NodeKind::Link,
+            Node::LinkReference(_) => NodeKind::LinkReference,
+            Node::Strong(_) => NodeKind::Strong,
+            Node::Text(_) => NodeKind::Text,
+            Node::Code(_) => NodeKind::Code,
+            Node::Math(_) => NodeKind::Math,
+            Node::MdxFlowExpression(_) => NodeKind::MdxFlowExpression,
+            Node::Heading(_) => NodeKind::Heading,
+            Node::Table(_) => NodeKind::Table,
+            Node::ThematicBreak(_) => NodeKind::ThematicBreak,
+            Node::TableRow(_) => NodeKind::TableRow,
+            Node::TableCell(_) => NodeKind::TableCell,
+            Node::ListItem(_) => NodeKind::ListItem,
+            Node::Definition(_) => NodeKind::Definition,
+            Node::Paragraph(_) => NodeKind::Paragraph,
+        }
+    }
+
+    /// Find all nodes of a kind, in this node and its descendants.
+    ///
+    /// Nodes are returned in the order of [`Node::iter()`][]: pre-order,
+    /// depth-first.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodeKind, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# a\n\nb\n\n> ## c", &ParseOptions::default())?;
+    /// let headings = tree
+    ///     .find_all(NodeKind::Heading)
+    ///     .into_iter()
+    ///     .map(ToString::to_string)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(headings, vec!["a", "c"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn find_all(&self, kind: NodeKind) -> Vec<&Node> {
+        self.find_all_by(|node| node.kind() == kind)
+    }
+
+    /// Find the first node of a kind, in this node and its descendants.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::NodeKind, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("a [b](c) [d](e)", &ParseOptions::default())?;
+    ///
+    /// assert_eq!(
+    ///     tree.find_first(NodeKind::Link).map(ToString::to_string),
+    ///     Some("b".into())
+    /// );
+    /// assert_eq!(tree.find_first(NodeKind::Image), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn find_first(&self, kind: NodeKind) -> Option<&Node> {
+        self.find_first_by(|node| node.kind() == kind)
+    }
+
+    /// Find all nodes that match `predicate`, in this node and its
+    /// descendants.
+    ///
+    /// Like [`Node::find_all()`][], but with a function instead of a kind.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("# a\n\n## b\n\n# c", &ParseOptions::default())?;
+    /// let found = tree.find_all_by(|node| matches!(node, Node::Heading(heading) if heading.depth == 1));
+    ///
+    /// assert_eq!(found.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_all_by<F>(&self, mut predicate: F) -> Vec<&Node>
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        self.iter().filter(|node| predicate(node)).collect()
+    }
+
+    /// Find the first node that matches `predicate`, in this node and its
+    /// descendants.
+    ///
+    /// Like [`Node::find_first()`][], but with a function instead of a kind.
+    /// Stops at the first match.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{mdast::Node, to_mdast, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast("```js\na\n```\n\n```rs\nb\n```", &ParseOptions::default())?;
+    /// let found = tree.find_first_by(|node| {
+    ///     matches!(node, Node::Code(code) if code.lang.as_deref() == Some("rs"))
+    /// });
+    ///
+    /// assert_eq!(found.map(ToString::to_string), Some("b".into()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_first_by<F>(&self, mut predicate: F) -> Option<&Node>
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        self.iter().find(|node| predicate(node))
+    }
+
+    /// Get the name of the node, such as `Paragraph`.
+    pub(crate) fn name(&self) -> String {
+        let value = format!("{:?}", self);
+        value
+            .split([' ', '('])
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// Find the nodes at a byte offset in a node and its descendants.
+///
+/// Returns whether a node was found.
+fn node_at_offset_impl<'a>(node: &'a Node, offset: usize, nodes: &mut Vec<&'a Node>) -> bool {
+    if let Some(position) = node.position() {
+        if offset < position.start.offset || offset >= position.end.offset {
+            return false;
+        }
+
+        nodes.push(node);
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            if node_at_offset_impl(child, offset, nodes) {
+                return true;
+            }
+        }
+    }
+
+    node.position().is_some()
+}
+
+/// Iterator over a node and its descendants, created by [`Node::iter()`][].
+#[derive(Clone, Debug)]
+pub struct Iter<'a>(IterDepth<'a>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, node)| node)
+    }
+}
+
+impl<'a> IntoIterator for &'a Node {
+    type Item = &'a Node;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over a node and its descendants, with their depth, created by
+/// [`Node::iter_depth()`][].
+#[derive(Clone, Debug)]
+pub struct IterDepth<'a> {
+    /// Node to start with, if not yet yielded.
+    root: Option<&'a Node>,
+    /// Remaining children of each ancestor of the next node.
+    stack: Vec<slice::Iter<'a, Node>>,
+}
+
+impl<'a> Iterator for IterDepth<'a> {
+    type Item = (usize, &'a Node);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = if let Some(node) = self.root.take() {
+            (0, node)
+        } else {
+            loop {
+                let siblings = self.stack.last_mut()?;
+
+                if let Some(node) = siblings.next() {
+                    break (self.stack.len(), node);
+                }
+
+                self.stack.pop();
+            }
+        };
+
+        if let Some(children) = node.children() {
+            self.stack.push(children.iter());
+        }
+
+        Some((depth, node))
+    }
+}
+
+/// Get the definitions in `tree`, by identifier.
+///
+/// Identifiers are normalized like the parser does when matching references
+/// to definitions (whitespace is collapsed and case is folded), so the
+/// `identifier` of a [`LinkReference`][] or [`ImageReference`][] from the
+/// parser can be used to find its definition.
+/// When several definitions have the same identifier, the first one is used,
+/// like in `CommonMark`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::{definitions, Node}, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("[Alpha]\n\n[ALPHA]: a\n[alpha]: b", &ParseOptions::default())?;
+/// let definitions = definitions(&tree);
+///
+/// if let Node::LinkReference(reference) = &tree.children().unwrap()[0].children().unwrap()[0] {
+///     assert_eq!(definitions[&reference.identifier].url, "a");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn definitions(tree: &Node) -> BTreeMap<String, &Definition> {
+    let mut result = BTreeMap::new();
+
+    for node in tree {
+        if let Node::Definition(definition) = node {
+            result
+                .entry(normalize_identifier(&definition.identifier))
+                .or_insert(definition);
+        }
+    }
+
+    result
+}
+
+/// Get the GFM footnote definitions in `tree`, by identifier.
+///
+/// Like [`definitions()`][], but for [`FootnoteDefinition`][]s, which can be
+/// found with the `identifier` of a [`FootnoteReference`][] from the parser.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::footnote_definitions, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("[^a]\n\n[^A]: b", &ParseOptions::gfm())?;
+///
+/// assert_eq!(footnote_definitions(&tree)["a"].label, Some("A".into()));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn footnote_definitions(tree: &Node) -> BTreeMap<String, &FootnoteDefinition> {
+    let mut result = BTreeMap::new();
+
+    for node in tree {
+        if let Node::FootnoteDefinition(definition) = node {
+            result
+                .entry(normalize_identifier(&definition.identifier))
+                .or_insert(definition);
+        }
+    }
+
+    result
+}
+
+/// What to do after visiting a node with [`visit_mut()`][].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VisitAction {
+    /// Visit the children of the node, then its next sibling.
+    Continue,
+    /// Do not visit the children of the node, continue with its next sibling.
+    Skip,
+    /// Stop visiting.
+    Stop,
+}
+
+/// Visit every node in `tree`.
+///
+/// Nodes are visited in pre-order, depth-first: a node is visited before its
+/// children, and children are visited in order, so nodes are visited in the
+/// order they occur in the document.
+/// `visitor` is called with each node and its ancestors, from the root to the
+/// parent of the node (so the list is empty for `tree` itself).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::{visit, Node}, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# *a*\n\nb", &ParseOptions::default())?;
+/// let mut texts = vec![];
+///
+/// visit(&tree, |node, ancestors| {
+///     if let Node::Text(text) = node {
+///         texts.push((text.value.clone(), ancestors.len()));
+///     }
+/// });
+///
+/// assert_eq!(texts, vec![("a".into(), 3), ("b".into(), 2)]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn visit<'a, F>(tree: &'a Node, mut visitor: F)
+where
+    F: FnMut(&'a Node, &[&'a Node]),
+{
+    visit_impl(tree, &mut vec![], &mut visitor);
+}
+
+/// Visit a node and its descendants.
+fn visit_impl<'a, F>(node: &'a Node, ancestors: &mut Vec<&'a Node>, visitor: &mut F)
+where
+    F: FnMut(&'a Node, &[&'a Node]),
+{
+    visitor(node, ancestors);
+
+    if let Some(children) = node.children() {
+        ancestors.push(node);
+        for child in children {
+            visit_impl(child, ancestors, visitor);
+        }
+        ancestors.pop();
+    }
+}
+
+/// Visit every node in `tree`, mutably.
+///
+/// Nodes are visited in the same order as with [`visit()`][]: pre-order,
+/// depth-first.
+/// `visitor` is called with each node and its path: the indices of the
+/// children to take, from `tree` down, to get to the node (so the path is
+/// empty for `tree` itself).
+/// What it returns decides what to visit next.
+///
+/// The children of a node are visited after `visitor` returns for that node,
+/// so when `visitor` changes the children of a node, the new children are
+/// visited.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::{visit_mut, Node, Text, VisitAction},
+///     mdast_to_markdown, to_mdast, ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("*a* `b` c", &ParseOptions::default())?;
+///
+/// // Turn emphasis into plain text.
+/// visit_mut(&mut tree, |node, _| {
+///     if let Node::Emphasis(_) = node {
+///         *node = Node::Text(Text {
+///             value: node.to_string(),
+///             position: node.position().cloned(),
+///             data: None,
+///         });
+///     }
+///     VisitAction::Continue
+/// });
+///
+/// assert_eq!(mdast_to_markdown(&tree)?, "a `b` c\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn visit_mut<F>(tree: &mut Node, mut visitor: F)
+where
+    F: FnMut(&mut Node, &[usize]) -> VisitAction,
+{
+    visit_mut_impl(tree, &mut vec![], &mut visitor);
+}
+
+/// Visit a node and its descendants, mutably.
+///
+/// Returns whether to stop.
+fn visit_mut_impl<F>(node: &mut Node, path: &mut Vec<usize>, visitor: &mut F) -> bool
+where
+    F: FnMut(&mut Node, &[usize]) -> VisitAction,
+{
+    match visitor(node, path) {
+        VisitAction::Continue => {}
+        VisitAction::Skip => return false,
+        VisitAction::Stop => return true,
+    }
+
+    if let Some(children) = node.children_mut() {
+        let mut index = 0;
+
+        while index < children.len() {
+            path.push(index);
+            let stop = visit_mut_impl(&mut children[index], path, visitor);
+            path.pop();
+
+            if stop {
+                return true;
+            }
+
+            index += 1;
+        }
+    }
+
+    false
+}
+
+/// Shift the depth of every heading in `tree` by `delta`.
+///
+/// Depths are clamped to `1` through `6`, so headings that would go past
+/// them end up at the same depth as their neighbors.
+/// Use [`normalize_headings()`][] to make the smallest depth `1` instead.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::relevel_headings, mdast_to_markdown, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// // Embed a document under an `h1`:
+/// let mut tree = to_mdast("# a\n\n## b\n\n###### c", &ParseOptions::default())?;
+/// relevel_headings(&mut tree, 1);
+///
+/// assert_eq!(mdast_to_markdown(&tree)?, "## a\n\n### b\n\n###### c\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn relevel_headings(tree: &mut Node, delta: i8) {
+    shift_headings(tree, i16::from(delta));
+}
+
+/// Shift the depth of every heading in `tree` so that the smallest depth is
+/// `1`.
+///
+/// The differences between depths are kept, including skipped levels.
+/// Does nothing if there are no headings.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::normalize_headings, mdast_to_markdown, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("### a\n\n##### b\n\n### c", &ParseOptions::default())?;
+/// normalize_headings(&mut tree);
+///
+/// assert_eq!(mdast_to_markdown(&tree)?, "# a\n\n### b\n\n# c\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn normalize_headings(tree: &mut Node) {
+    let min = tree
+        .iter()
+        .filter_map(|node| match node {
+            Node::Heading(heading) => Some(heading.depth),
+            _ => None,
+        })
+        .min();
+
+    if let Some(min) = min {
+        shift_headings(tree, 1 - i16::from(min));
+    }
+}
+
+/// Shift the depth of every heading in `tree` by `delta`, clamped.
+fn shift_headings(tree: &mut Node, delta: i16) {
+    visit_mut(tree, |node, _| {
+        if let Node::Heading(heading) = node {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let depth = (i16::from(heading.depth) + delta).clamp(1, 6) as u8;
+            heading.depth = depth;
+        }
+        VisitAction::Continue
+    });
+}
+
+/// Add a unique slug to every heading in `tree`, as `id` in its `data`.
+///
+/// Slugs are made like the [`heading_ids`][crate::CompileOptions::heading_ids]
+/// option of [`mdast_to_html()`][crate::mdast_to_html] makes them: from the
+/// `id` in the `data` of a heading if there is one, and otherwise from its
+/// text, made unique with `slugger`.
+/// Headings without a slug (such as headings without text) are left as-is.
+/// Pass the same `slugger` for several trees to make slugs unique across
+/// them, or a new one for each tree.
+///
+/// Returns the text of the headings, by slug, which can be used to check
+/// fragments (such as `#alpha` in `[a](#alpha)`).
+///
+/// To make slugs in a different way, use [`slug_headings_with_slugify()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::{slug_headings, Node}, to_mdast, ParseOptions, Slugger};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut slugger = Slugger::default();
+/// let mut tree = to_mdast("# Alpha\n\n## *Alpha*", &ParseOptions::default())?;
+/// let slugs = slug_headings(&mut tree, &mut slugger);
+///
+/// assert_eq!(slugs["alpha-1"], "Alpha");
+///
+/// if let Node::Heading(heading) = &tree.children().unwrap()[0] {
+///     assert_eq!(heading.data.as_ref().unwrap()["id"], "alpha");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn slug_headings(tree: &mut Node, slugger: &mut Slugger) -> BTreeMap<String, String> {
+    slug_headings_impl(tree, slugger, None)
+}
+
+/// Add a unique slug to every heading in `tree`, as `id` in its `data`, with
+/// a custom function to make slugs.
+///
+/// Like [`slug_headings()`][], but `slugify` is used to make slugs, like the
+/// [`slugify`][crate::Options::slugify] option.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::slug_headings_with_slugify, to_mdast, ParseOptions, Slugger};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("# a", &ParseOptions::default())?;
+/// let slugs = slug_headings_with_slugify(&mut tree, &mut Slugger::default(), &|value| {
+///     format!("x-{}", value)
+/// });
+///
+/// assert_eq!(slugs["x-a"], "a");
+/// # Ok(())
+/// # }
+/// ```
+pub fn slug_headings_with_slugify(
+    tree: &mut Node,
+    slugger: &mut Slugger,
+    slugify: &Slugify,
+) -> BTreeMap<String, String> {
+    slug_headings_impl(tree, slugger, Some(slugify))
+}
+
+/// Add slugs to headings.
+fn slug_headings_impl(
+    tree: &mut Node,
+    slugger: &mut Slugger,
+    slugify: Option<&Slugify>,
+) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+
+    visit_mut(tree, |node, _| {
+        if let Node::Heading(heading) = node {
+            if let Some(value) = slug::heading(heading, slugify, slugger) {
+                heading
+                    .data
+                    .get_or_insert_with(Box::default)
+                    .insert("id".into(), value.clone());
+                result.insert(value, heading.children.iter().map(Node::to_text).collect());
+            }
+
+            // Headings cannot contain headings.
+            return VisitAction::Skip;
+        }
+        VisitAction::Continue
+    });
+
+    result
+}
+
+/// Kind of node a URL is used for, passed to [`rewrite_urls()`][].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UrlKind {
+    /// Link (`[a](b)`, `<https://c>`, `[d][]`).
+    Link,
+    /// Image (`![a](b)`, `![c][]`).
+    Image,
+}
+
+/// Info on a URL, passed to [`rewrite_urls()`][].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UrlContext<'a> {
+    /// Whether the URL is used for a link or an image.
+    ///
+    /// The URL of a [`Definition`][] is used for an image if all references
+    /// to it are image references, and for a link otherwise (including when
+    /// there are no references to it).
+    pub kind: UrlKind,
+    /// Whether the URL is in a [`Definition`][], and so used through
+    /// references (`[a][b]`, `![c][d]`).
+    pub reference: bool,
+    /// Whether the URL is in a link that shows it as its text, such as an
+    /// autolink (`<https://a>`) or a GFM autolink literal (`www.b.c`).
+    ///
+    /// When the URL of such a link is replaced, its text is replaced too.
+    pub autolink: bool,
+    /// Title of the node (`"d"` in `[a](b "d")`), if any.
+    pub title: Option<&'a str>,
+}
+
+/// Rewrite the URLs of links, images, and definitions in `tree`.
+///
+/// `rewrite` is called with info on each URL and the URL itself, in the
+/// order they occur in the document.
+/// When it returns `Some`, the URL is replaced with the returned value.
+/// References have no URL of their own: the URL of their [`Definition`][] is
+/// rewritten instead.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::{rewrite_urls, UrlKind},
+///     mdast_to_markdown, to_mdast, ParseOptions,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast(
+///     "[a](/docs/a) ![b](/docs/b.png) [c]\n\n[c]: /docs/c",
+///     &ParseOptions::default(),
+/// )?;
+///
+/// // Move the docs, except for images.
+/// rewrite_urls(&mut tree, |context, url| {
+///     if context.kind == UrlKind::Link {
+///         url.strip_prefix("/docs/").map(|rest| format!("/guide/{}", rest))
+///     } else {
+///         None
+///     }
+/// });
+///
+/// assert_eq!(
+///     mdast_to_markdown(&tree)?,
+///     "[a](/guide/a) ![b](/docs/b.png) [c]\n\n[c]: /guide/c\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn rewrite_urls<F>(tree: &mut Node, mut rewrite: F)
+where
+    F: FnMut(UrlContext, &str) -> Option<String>,
+{
+    // Whether each identifier is used by link references (first) and image
+    // references (second).
+    let mut references: BTreeMap<String, (bool, bool)> = BTreeMap::new();
+
+    for node in &*tree {
+        match node {
+            Node::LinkReference(reference) => {
+                references
+                    .entry(normalize_identifier(&reference.identifier))
+                    .or_default()
+                    .0 = true;
+            }
+            Node::ImageReference(reference) => {
+                references
+                    .entry(normalize_identifier(&reference.identifier))
+                    .or_default()
+                    .1 = true;
+            }
+            _ => {}
+        }
+    }
+
+    visit_mut(tree, |node, _| {
+        match node {
+            Node::Link(link) => {
+                let prefix = autolink_prefix(link);
+                let context = UrlContext {
+                    kind: UrlKind::Link,
+                    reference: false,
+                    autolink: prefix.is_some(),
+                    title: link.title.as_deref(),
+                };
+
+                if let Some(url) = rewrite(context, &link.url) {
+                    if let (Some(prefix), Some(Node::Text(text))) =
+                        (prefix, link.children.first_mut())
+                    {
+                        text.value = String::from(url.strip_prefix(prefix).unwrap_or(&url));
+                    }
+
+                    link.url = url;
+                }
+            }
+            Node::Image(image) => {
+                let context = UrlContext {
+                    kind: UrlKind::Image,
+                    reference: false,
+                    autolink: false,
+                    title: image.title.as_deref(),
+                };
+
+                if let Some(url) = rewrite(context, &image.url) {
+                    image.url = url;
+                }
+            }
+            Node::Definition(definition) => {
+                let image = references
+                    .get(&normalize_identifier(&definition.identifier))
+                    .map_or(false, |(link, image)| *image && !link);
+                let context = UrlContext {
+                    kind: if image { UrlKind::Image } else { UrlKind::Link },
+                    reference: true,
+                    autolink: false,
+                    title: definition.title.as_deref(),
+                };
+
+                if let Some(url) = rewrite(context, &definition.url) {
+                    definition.url = url;
+                }
+            }
+            _ => {}
+        }
+        VisitAction::Continue
+    });
+}
+
+/// Turn references in `tree` into links and images.
+///
+/// Each [`LinkReference`][] and [`ImageReference`][] whose identifier
+/// matches a [`Definition`][] in `tree` (as with [`definitions()`][]) is
+/// replaced with a [`Link`][] or [`Image`][] with the URL and title of that
+/// definition, so the tree can be used without looking up definitions.
+///
+/// Pass `true` as `remove_definitions` to also remove all definitions, which
+/// are no longer used afterwards.
+/// References that do not match a definition (which the parser does not
+/// make, but which can be made programmatically) are kept, or, when
+/// `unresolved_to_text` is `true`, turned into text, like
+/// [`mdast_to_html()`][crate::mdast_to_html] does (such as `[a][b]`).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{mdast::resolve_references, mdast_to_markdown, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut tree = to_mdast("[a] ![b][A]\n\n[a]: c \"d\"", &ParseOptions::default())?;
+/// resolve_references(&mut tree, true, false);
+///
+/// assert_eq!(mdast_to_markdown(&tree)?, "[a](c \"d\") ![b](c \"d\")\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn resolve_references(tree: &mut Node, remove_definitions: bool, unresolved_to_text: bool) {
+    let definitions = definitions(tree)
+        .into_iter()
+        .map(|(identifier, definition)| {
+            (
+                identifier,
+                (definition.url.clone(), definition.title.clone()),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    if let Some(children) = tree.children_mut() {
+        resolve_references_impl(
+            children,
+            &definitions,
+            remove_definitions,
+            unresolved_to_text,
+        );
+    }
+}
+
+/// Turn references in `children` (and their descendants) into links and
+/// images.
+fn resolve_references_impl(
+    children: &mut Vec<Node>,
+    definitions: &BTreeMap<String, (String, Option<String>)>,
+    remove_definitions: bool,
+    unresolved_to_text: bool,
+) {
+    let mut result = Vec::with_capacity(children.len());
+
+    for child in children.drain(..) {
+        let mut child = match child {
+            Node::Definition(_) if remove_definitions => continue,
+            Node::LinkReference(reference) => {
+                let id = normalize_identifier(&reference.identifier);
+
+                if let Some((url, title)) = definitions.get(&id) {
+                    Node::Link(Link {
+                        children: reference.children,
+                        position: reference.position,
+                        data: reference.data,
+                        url: url.clone(),
+                        title: title.clone(),
+                        markers: vec![],
+                    })
+                } else if unresolved_to_text {
+                    let mut inner = reference.children;
+                    resolve_references_impl(
+                        &mut inner,
+                        definitions,
+                        remove_definitions,
+                        unresolved_to_text,
+                    );
+                    result.push(Node::Text(Text {
+                        value: "[".into(),
+                        position: None,
+                        data: None,
+                    }));
+                    result.append(&mut inner);
+                    result.push(Node::Text(Text {
+                        value: format!(
+                            "]{}",
+                            reference_suffix(
+                                reference.reference_kind,
+                                &reference.identifier,
+                                reference.label.as_deref()
+                            )
+                        ),
+                        position: None,
+                        data: None,
+                    }));
+                    continue;
+                } else {
+                    Node::LinkReference(reference)
+                }
+            }
+            Node::ImageReference(reference) => {
+                let id = normalize_identifier(&reference.identifier);
+
+                if let Some((url, title)) = definitions.get(&id) {
+                    Node::Image(Image {
+                        position: reference.position,
+                        data: reference.data,
+                        alt: reference.alt,
+                        url: url.clone(),
+                        title: title.clone(),
+                    })
+                } else if unresolved_to_text {
+                    Node::Text(Text {
+                        value: format!(
+                            "![{}]{}",
+                            reference.alt,
+                            reference_suffix(
+                                reference.reference_kind,
+                                &reference.identifier,
+                                reference.label.as_deref()
+                            )
+                        ),
+                        position: reference.position,
+                        data: reference.data,
+                    })
+                } else {
+                    Node::ImageReference(reference)
+                }
+            }
+            child => child,
+        };
+
+        if let Some(children) = child.children_mut() {
+            resolve_references_impl(
+                children,
+                definitions,
+                remove_definitions,
+                unresolved_to_text,
+            );
+        }
+
+        result.push(child);
+    }
+
+    *children = result;
+}
+
+/// Get the end of an unresolved reference as text (`[b]` in `[a][b]`, `[]`
+/// in `[a][]`, or nothing in `[a]`).
+fn reference_suffix(kind: ReferenceKind, identifier: &str, label: Option<&str>) -> String {
+    match kind {
+        ReferenceKind::Full => format!("[{}]", label.unwrap_or(identifier)),
+        ReferenceKind::Collapsed => "[]".into(),
+        ReferenceKind::Shortcut => String::new(),
+    }
+}
+
+/// Get the part of the URL of `node` before its text, if the link shows its
+/// URL as its text (such as `mailto:` for the email autolink `<a@b.c>`).
+fn autolink_prefix(node: &Link) -> Option<&'static str> {
+    if node.title.is_some() {
+        return None;
+    }
+
+    if let [Node::Text(text)] = node.children.as_slice() {
+        ["", "mailto:", "http://"]
+            .iter()
+            .find(|prefix| {
+                !text.value.is_empty() && node.url.strip_prefix(**prefix) == Some(&text.value)
+            })
+            .copied()
+    } else {
+        None
+    }
+}
+
+/// MDX: attribute content.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxExpressionAttribute")
+)]
+pub enum AttributeContent {
+    /// JSX expression.
+    ///
+    /// ```markdown
+    /// > | <a {...b} />
+    ///        ^^^^^^
+    /// ```
+    Expression { value: String, stops: Vec<Stop> },
+    /// JSX property.
+    ///
+    /// ```markdown
+    /// > | <a b />
+    ///        ^
+    /// ```
+    Property(MdxJsxAttribute),
+}
+//
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxAttributeValueExpression")
+)]
+pub struct AttributeValueExpression {
+    pub value: String,
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: attribute value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "type")
+)]
+pub enum AttributeValue {
+    /// Expression value.
+    ///
+    /// ```markdown
+    /// > | <a b={c} />
+    ///          ^^^
+    /// ```
+    Expression(AttributeValueExpression),
+    /// Static value.
+    ///
+    /// ```markdown
+    /// > | <a b="c" />
+    ///          ^^^
+    /// ```
+    #[cfg_attr(feature = "serde", serde(rename = "literal"))]
+    Literal(String),
+}
+
+/// Document.
+///
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "root")
+)]
+pub struct Root {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Paragraph.
+///
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "paragraph")
+)]
+pub struct Paragraph {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Heading.
+///
+/// ```markdown
+/// > | # a
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "heading")
+)]
+pub struct Heading {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Rank (between `1` and `6`, both including).
+    pub depth: u8,
+    /// Whether the heading is underlined (setext, `a\n=`) instead of
+    /// starting with number signs (atx, `# a`).
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub setext: bool,
+    /// Positional info of the markers: the opening and closing (if any)
+    /// sequences of number signs (atx), or the sequence of the underline
+    /// (setext).
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub markers: Vec<Position>,
+}
+
+/// Thematic break.
+///
+/// ```markdown
+/// > | ***
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "thematicBreak")
+)]
+pub struct ThematicBreak {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Block quote.
+///
+/// ```markdown
+/// > | > a
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "blockquote")
+)]
+pub struct BlockQuote {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// List.
+///
+/// ```markdown
+/// > | * a
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "list")
+)]
+pub struct List {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Ordered (`true`) or unordered (`false`).
+    pub ordered: bool,
+    /// Starting number of the list.
+    /// `None` when unordered.
+    pub start: Option<u32>,
+    /// One or more of its children are separated with a blank line from its
+    /// siblings (when `true`), or not (when `false`).
+    ///
+    /// A list is loose in `CommonMark` (its paragraphs are wrapped in `<p>`)
+    /// when it is spread or when one of its items is spread.
+    pub spread: bool,
+}
+
+/// List item.
+///
+/// ```markdown
+/// > | * a
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "listItem")
+)]
+pub struct ListItem {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// The item contains two or more children separated by a blank line
+    /// (when `true`), or not (when `false`).
+    pub spread: bool,
+    /// GFM: whether the item is done (when `true`), not done (when `false`),
+    /// or indeterminate or not applicable (`None`).
+    pub checked: Option<bool>,
+    /// Marker used in the source (`*`, `+`, or `-` in unordered lists, `.` or
+    /// `)` in ordered lists), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+    /// Number of the item in the source (such as `3` for `3. a`), if known
+    /// and ordered.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub number: Option<u32>,
+    /// Size of the prefix of the item in the source, from the start of the
+    /// marker to where content starts (such as `4` for `-   a`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub indent: Option<usize>,
+}
+
+/// Html (flow or phrasing).
+///
+/// ```markdown
+/// > | <a>
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "html")
+)]
+pub struct Html {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Code (flow).
+///
+/// ```markdown
+/// > | ~~~
+///     ^^^
+/// > | a
+///     ^
+/// > | ~~~
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "code")
+)]
+pub struct Code {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// The language of computer code being marked up.
+    pub lang: Option<String>,
+    /// Custom info relating to the node.
+    pub meta: Option<String>,
+}
+
+/// Math (flow).
+///
+/// ```markdown
+/// > | $$
+///     ^^
+/// > | a
+///     ^
+/// > | $$
+///     ^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "math")
+)]
+pub struct Math {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Custom info relating to the node.
+    pub meta: Option<String>,
+}
+
+/// Definition.
+///
+/// ```markdown
+/// > | [a]: b
+///     ^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "definition")
+)]
+pub struct Definition {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with [`normalize_identifier`][], which the
+    /// parser does.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// Text.
+///
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "text")
+)]
+pub struct Text {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Emphasis.
+///
+/// ```markdown
+/// > | *a*
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "emphasis")
+)]
+pub struct Emphasis {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Marker used in the source (`*` or `_`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+}
+
+/// Strong.
+///
+/// ```markdown
+/// > | **a**
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "strong")
+)]
+pub struct Strong {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Marker used in the source (`*` or `_`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+}
+
+/// Code (phrasing).
+///
+/// ```markdown
+/// > | `a`
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "inlineCode")
+)]
+pub struct InlineCode {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Number of grave accents in the sequences used in the source, if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    #[cfg_attr(feature = "serde", serde(rename = "sequenceSize"))]
+    pub sequence_size: Option<usize>,
+    /// Whether the source had a space (or line ending) between the value and
+    /// both sequences.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub padding: bool,
+    /// Positional info of the markers: the opening and closing sequences of
+    /// grave accents.
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub markers: Vec<Position>,
+}
+
+/// Math (phrasing).
+///
+/// ```markdown
+/// > | $a$
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "inlineMath")
+)]
+pub struct InlineMath {
+    // Text.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Break.
+///
+/// ```markdown
+/// > | a\
+///      ^
+///   | b
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "break")
+)]
+pub struct Break {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Style of the break.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: BreakKind,
+}
+
+/// Link.
+///
+/// ```markdown
+/// > | [a](b)
+///     ^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "link")
+)]
+pub struct Link {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>, // Extra.
+    /// Positional info of the markers: the opening and closing brackets
+    /// and parens of a resource (`[a](b)`), or the angle brackets of an
+    /// autolink (`<c>`).
+    /// GFM autolink literals (`www.d.e`) have no markers.
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub markers: Vec<Position>,
+}
+
+/// Image.
+///
+/// ```markdown
+/// > | ![a](b)
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "image")
+)]
+pub struct Image {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    pub alt: String,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: String,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<String>,
+}
+
+/// Link reference.
+///
+/// ```markdown
+/// > | [a]
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "linkReference")
+)]
+pub struct LinkReference {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Reference.
+    /// Explicitness of a reference.
+    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with [`normalize_identifier`][], which the
+    /// parser does.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// Image reference.
+///
+/// ```markdown
+/// > | ![a]
+///     ^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "imageReference")
+)]
+pub struct ImageReference {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    pub alt: String,
+    // Reference.
+    /// Explicitness of a reference.
+    #[cfg_attr(feature = "serde", serde(rename = "referenceType"))]
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with [`normalize_identifier`][], which the
+    /// parser does.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: footnote definition.
+///
+/// ```markdown
+/// > | [^a]: b
+///     ^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "footnoteDefinition")
+)]
+pub struct FootnoteDefinition {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with [`normalize_identifier`][], which the
+    /// parser does.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: footnote reference.
+///
+/// ```markdown
+/// > | [^a]
+///     ^^^^
+/// ```
+///
+/// Like GitHub, the parser only makes footnote references when there is a
+/// matching footnote definition in the document: otherwise, `[^a]` is text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "footnoteReference")
+)]
+pub struct FootnoteReference {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with [`normalize_identifier`][], which the
+    /// parser does.
+    pub identifier: String,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<String>,
+}
+
+/// GFM: table.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// > | | - |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "table")
+)]
+pub struct Table {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Represents how cells in columns are aligned.
+    pub align: Vec<AlignKind>,
+}
+
+/// GFM: table row.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "tableRow")
+)]
+pub struct TableRow {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// GFM: table cell.
+///
+/// ```markdown
+/// > | | a |
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "tableCell")
+)]
+pub struct TableCell {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// GFM: delete.
+///
+/// ```markdown
+/// > | ~~a~~
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "delete")
+)]
+pub struct Delete {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Frontmatter: yaml.
+///
+/// ```markdown
+/// > | ---
+///     ^^^
+/// > | a: b
+///     ^^^^
+/// > | ---
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "yaml")
+)]
+pub struct Yaml {
+    // Void.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// Frontmatter: toml.
+///
+/// ```markdown
+/// > | +++
+///     ^^^
+/// > | a: b
+///     ^^^^
+/// > | +++
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "toml")
+)]
+pub struct Toml {
+    // Void.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+}
+
+/// MDX: ESM.
+///
+/// ```markdown
+/// > | import a from 'b'
+///     ^^^^^^^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxjsEsm")
+)]
+pub struct MdxjsEsm {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (flow).
+///
+/// ```markdown
+/// > | {a}
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxFlowExpression")
+)]
+pub struct MdxFlowExpression {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (text).
+///
+/// ```markdown
+/// > | a {b}
+///       ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxTextExpression")
+)]
+pub struct MdxTextExpression {
+    // Literal.
+    /// Content model.
+    pub value: String,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: JSX element (container).
+///
+/// ```markdown
+/// > | <a />
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxFlowElement")
+)]
+pub struct MdxJsxFlowElement {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<String>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
+
+/// MDX: JSX element (text).
+///
+/// ```markdown
+/// > | <a />.
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxTextElement")
+)]
+pub struct MdxJsxTextElement {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub data: Option<Box<Data>>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<String>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
+
+/// MDX: JSX attribute.
+///
+/// ```markdown
+/// > | <a b />
+///        ^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "mdxJsxAttribute")
+)]
+pub struct MdxJsxAttribute {
+    // Void.
+    /// Positional info.
+    // pub position: Option<Position>,
+    /// Key.
+    pub name: String,
+    /// Value.
+    pub value: Option<AttributeValue>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unist::Position;
+    use alloc::{format, string::ToString, vec};
+
+    // Literals.
+
+    #[test]
+    fn text() {
+        let mut node = Node::Text(Text {
+            value: "a".into(),
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Text { value: \"a\", position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Text { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_code() {
+        let mut node = Node::InlineCode(InlineCode {
+            value: "a".into(),
+            position: None,
+            sequence_size: None,
+            padding: false,
+            data: None,
+            markers: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineCode { value: \"a\", position: None, data: None, sequence_size: None, padding: false, markers: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineCode { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, sequence_size: None, padding: false, markers: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn code() {
+        let mut node = Node::Code(Code {
+            value: "a".into(),
+            position: None,
+            lang: None,
+            meta: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Code { value: \"a\", position: None, data: None, lang: None, meta: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Code { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, lang: None, meta: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn inline_math() {
+        let mut node = Node::InlineMath(InlineMath {
+            value: "a".into(),
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineMath { value: \"a\", position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "InlineMath { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn math() {
+        let mut node = Node::Math(Math {
+            value: "a".into(),
+            position: None,
+            meta: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Math { value: \"a\", position: None, data: None, meta: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Math { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, meta: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn html() {
+        let mut node = Node::Html(Html {
+            value: "a".into(),
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Html { value: \"a\", position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Html { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn mdx_text_expression() {
+        let mut node = Node::MdxTextExpression(MdxTextExpression {
+            value: "a".into(),
+            stops: vec![],
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxTextExpression { value: \"a\", position: None, data: None, stops: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxTextExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, stops: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn mdx_flow_expression() {
+        let mut node = Node::MdxFlowExpression(MdxFlowExpression {
+            value: "a".into(),
+            stops: vec![],
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxFlowExpression { value: \"a\", position: None, data: None, stops: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxFlowExpression { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, stops: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn mdxjs_esm() {
+        let mut node = Node::MdxjsEsm(MdxjsEsm {
+            value: "a".into(),
+            stops: vec![],
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxjsEsm { value: \"a\", position: None, data: None, stops: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxjsEsm { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None, stops: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn toml() {
+        let mut node = Node::Toml(Toml {
+            value: "a".into(),
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Toml { value: \"a\", position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Toml { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn yaml() {
+        let mut node = Node::Yaml(Yaml {
+            value: "a".into(),
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Yaml { value: \"a\", position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "a", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Yaml { value: \"a\", position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    // Voids.
+
+    #[test]
+    fn break_node() {
+        let mut node = Node::Break(Break {
+            position: None,
+            kind: BreakKind::Escape,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Break { position: None, data: None, kind: Escape }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Break { position: Some(1:1-1:2 (0-1)), data: None, kind: Escape }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn thematic_break() {
+        let mut node = Node::ThematicBreak(ThematicBreak {
+            position: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ThematicBreak { position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ThematicBreak { position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn footnote_reference() {
+        let mut node = Node::FootnoteReference(FootnoteReference {
+            position: None,
+            identifier: "a".into(),
+            label: Some("b".into()),
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteReference { position: None, data: None, identifier: \"a\", label: Some(\"b\") }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteReference { position: Some(1:1-1:2 (0-1)), data: None, identifier: \"a\", label: Some(\"b\") }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn image_reference() {
+        let mut node = Node::ImageReference(ImageReference {
+            position: None,
+            alt: "a".into(),
+            identifier: "b".into(),
+            label: Some("c".into()),
+            reference_kind: ReferenceKind::Full,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ImageReference { position: None, data: None, alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ImageReference { position: Some(1:1-1:2 (0-1)), data: None, alt: \"a\", reference_kind: Full, identifier: \"b\", label: Some(\"c\") }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn image() {
+        let mut node = Node::Image(Image {
+            position: None,
+            alt: "a".into(),
+            url: "b".into(),
+            title: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Image { position: None, data: None, alt: \"a\", url: \"b\", title: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Image { position: Some(1:1-1:2 (0-1)), data: None, alt: \"a\", url: \"b\", title: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn definition() {
+        let mut node = Node::Definition(Definition {
+            position: None,
+            identifier: "a".into(),
+            label: None,
+            url: "b".into(),
+            title: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Definition { position: None, data: None, url: \"b\", title: None, identifier: \"a\", label: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(node.children_mut(), None, "should support `children_mut`");
+        assert_eq!(node.children(), None, "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Definition { position: Some(1:1-1:2 (0-1)), data: None, url: \"b\", title: None, identifier: \"a\", label: None }",
+            "should support `position_set`"
+        );
+    }
+
+    // Parents.
+
+    #[test]
+    fn root() {
+        let mut node = Node::Root(Root {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Root { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Root { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn block_quote() {
+        let mut node = Node::BlockQuote(BlockQuote {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "BlockQuote { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "BlockQuote { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn delete() {
+        let mut node = Node::Delete(Delete {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Delete { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Delete { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn emphasis() {
+        let mut node = Node::Emphasis(Emphasis {
+            position: None,
+            children: vec![],
+            marker: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Emphasis { children: [], position: None, data: None, marker: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Emphasis { children: [], position: Some(1:1-1:2 (0-1)), data: None, marker: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn strong() {
+        let mut node = Node::Strong(Strong {
+            position: None,
+            children: vec![],
+            marker: None,
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Strong { children: [], position: None, data: None, marker: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Strong { children: [], position: Some(1:1-1:2 (0-1)), data: None, marker: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn paragraph() {
+        let mut node = Node::Paragraph(Paragraph {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Paragraph { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Paragraph { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn table_row() {
+        let mut node = Node::TableRow(TableRow {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "TableRow { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "TableRow { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn table_cell() {
+        let mut node = Node::TableCell(TableCell {
+            position: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "TableCell { children: [], position: None, data: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "TableCell { children: [], position: Some(1:1-1:2 (0-1)), data: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn heading() {
+        let mut node = Node::Heading(Heading {
+            position: None,
+            depth: 1,
+            setext: false,
+            children: vec![],
+            data: None,
+            markers: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Heading { children: [], position: None, data: None, depth: 1, setext: false, markers: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Heading { children: [], position: Some(1:1-1:2 (0-1)), data: None, depth: 1, setext: false, markers: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn table() {
+        let mut node = Node::Table(Table {
+            position: None,
+            align: vec![],
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Table { children: [], position: None, data: None, align: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Table { children: [], position: Some(1:1-1:2 (0-1)), data: None, align: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn list_item() {
+        let mut node = Node::ListItem(ListItem {
+            position: None,
+            spread: false,
+            checked: None,
+            marker: None,
+            number: None,
+            indent: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ListItem { children: [], position: None, data: None, spread: false, checked: None, marker: None, number: None, indent: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ListItem { children: [], position: Some(1:1-1:2 (0-1)), data: None, spread: false, checked: None, marker: None, number: None, indent: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn list() {
+        let mut node = Node::List(List {
+            position: None,
+            spread: false,
+            ordered: false,
+            start: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "List { children: [], position: None, data: None, ordered: false, start: None, spread: false }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "List { children: [], position: Some(1:1-1:2 (0-1)), data: None, ordered: false, start: None, spread: false }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn link_reference() {
+        let mut node = Node::LinkReference(LinkReference {
+            position: None,
+            identifier: "a".into(),
+            label: None,
+            reference_kind: ReferenceKind::Full,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "LinkReference { children: [], position: None, data: None, reference_kind: Full, identifier: \"a\", label: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "LinkReference { children: [], position: Some(1:1-1:2 (0-1)), data: None, reference_kind: Full, identifier: \"a\", label: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn link() {
+        let mut node = Node::Link(Link {
+            position: None,
+            url: "a".into(),
+            title: None,
+            children: vec![],
+            data: None,
+            markers: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Link { children: [], position: None, data: None, url: \"a\", title: None, markers: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Link { children: [], position: Some(1:1-1:2 (0-1)), data: None, url: \"a\", title: None, markers: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn footnote_definition() {
+        let mut node = Node::FootnoteDefinition(FootnoteDefinition {
+            position: None,
+            identifier: "a".into(),
+            label: None,
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteDefinition { children: [], position: None, data: None, identifier: \"a\", label: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "FootnoteDefinition { children: [], position: Some(1:1-1:2 (0-1)), data: None, identifier: \"a\", label: None }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn mdx_jsx_flow_element() {
+        let mut node = Node::MdxJsxFlowElement(MdxJsxFlowElement {
+            position: None,
+            name: None,
+            attributes: vec![],
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxJsxFlowElement { children: [], position: None, data: None, name: None, attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxJsxFlowElement { children: [], position: Some(1:1-1:2 (0-1)), data: None, name: None, attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn mdx_jsx_text_element() {
+        let mut node = Node::MdxJsxTextElement(MdxJsxTextElement {
+            position: None,
+            name: None,
+            attributes: vec![],
+            children: vec![],
+            data: None,
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxJsxTextElement { children: [], position: None, data: None, name: None, attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "MdxJsxTextElement { children: [], position: Some(1:1-1:2 (0-1)), data: None, name: None, attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn accessors_mut() {
+        let mut node = Node::Paragraph(Paragraph {
+            children: vec![],
+            position: Some(Position::new(1, 1, 0, 1, 2, 1)),
+            data: None,
+        });
+
+        node.children_mut().unwrap().push(Node::Text(Text {
+            value: "a".into(),
+            position: None,
+            data: None,
+        }));
+        node.position_mut().unwrap().end.column = 3;
+
+        assert_eq!(
+            node.children(),
+            Some(&vec![Node::Text(Text {
+                value: "a".into(),
+                position: None,
+                data: None,
+            })]),
+            "should support changing children with `children_mut`"
+        );
+        assert_eq!(
+            node.position(),
+            Some(&Position::new(1, 1, 0, 1, 3, 1)),
+            "should support changing the position with `position_mut`"
+        );
+        node.position_set(None);
+        assert_eq!(
+            node.position(),
+            None,
+            "should support clearing the position with `position_set`"
+        );
+    }
+
+    // Visitors.
+
+    /// Create a paragraph, for the visitors.
+    fn visit_paragraph(children: Vec<Node>) -> Node {
+        Node::Paragraph(Paragraph {
+            children,
+            position: None,
+            data: None,
+        })
+    }
+
+    /// Create text, for the visitors.
+    fn visit_text(value: &str) -> Node {
+        Node::Text(Text {
+            value: value.into(),
+            position: None,
+            data: None,
+        })
+    }
+
+    #[test]
+    fn visit_order() {
+        let tree = Node::Root(Root {
+            children: vec![
+                visit_paragraph(vec![
+                    visit_text("a"),
+                    Node::Emphasis(Emphasis {
+                        children: vec![visit_text("b")],
+                        position: None,
+                        marker: None,
+                        data: None,
+                    }),
+                ]),
+                visit_paragraph(vec![visit_text("c")]),
+            ],
+            position: None,
+            data: None,
+        });
+        let mut seen = vec![];
+
+        visit(&tree, |node, ancestors| {
+            let names = ancestors.iter().map(|node| node.name()).collect::<Vec<_>>();
+            seen.push(format!(
+                "{}:{}:{}",
+                node.name(),
+                node.to_string(),
+                names.join(",")
+            ));
+        });
+
+        assert_eq!(
+            seen,
+            vec![
+                "Root:abc:",
+                "Paragraph:ab:Root",
+                "Text:a:Root,Paragraph",
+                "Emphasis:b:Root,Paragraph",
+                "Text:b:Root,Paragraph,Emphasis",
+                "Paragraph:c:Root",
+                "Text:c:Root,Paragraph",
+            ],
+            "should visit in pre-order, with ancestors"
+        );
+    }
+
+    #[test]
+    fn iter_order() {
+        let tree = visit_paragraph(vec![
+            visit_paragraph(vec![visit_text("a"), visit_text("b")]),
+            visit_text("c"),
+        ]);
+        let mut values = vec![];
+
+        for node in &tree {
+            values.push(node.to_string());
+        }
+
+        assert_eq!(
+            values,
+            vec!["abc", "ab", "a", "b", "c"],
+            "should iterate in pre-order"
+        );
+
+        assert_eq!(
+            tree.iter_depth()
+                .map(|(depth, node)| (depth, node.to_string()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "abc".into()),
+                (1, "ab".into()),
+                (2, "a".into()),
+                (2, "b".into()),
+                (1, "c".into())
+            ],
+            "should iterate with depth"
+        );
+
+        assert_eq!(
+            visit_text("a").iter().count(),
+            1,
+            "should iterate over a literal"
+        );
+    }
+
+    #[test]
+    fn iter_deep() {
+        let mut tree = visit_text("a");
+
+        for _ in 0..10_000 {
+            tree = Node::BlockQuote(BlockQuote {
+                children: vec![tree],
+                position: None,
+                data: None,
+            });
+        }
+
+        assert_eq!(
+            tree.iter_depth()
+                .last()
+                .map(|(depth, node)| (depth, node.to_string())),
+            Some((10_000, "a".into())),
+            "should iterate over deep trees"
+        );
+
+        // Dropping deep trees recurses, so take them apart first.
+        let mut stack = vec![tree];
+        while let Some(mut node) = stack.pop() {
+            if let Some(children) = node.children_mut() {
+                stack.append(children);
+            }
+        }
+    }
+
+    #[test]
+    fn find() {
+        let tree = Node::root(vec![
+            Node::heading(1, vec![Node::text("a")]),
+            Node::paragraph(vec![
+                Node::link("b", None, vec![Node::text("c")]),
+                Node::emphasis(vec![Node::link("d", None, vec![])]),
+            ]),
+            Node::block_quote(vec![Node::heading(2, vec![Node::text("e")])]),
+        ]);
+
+        assert_eq!(tree.kind(), NodeKind::Root, "should get the kind");
+
+        assert_eq!(
+            tree.find_all(NodeKind::Heading)
+                .iter()
+                .map(|node| node.to_string())
+                .collect::<Vec<_>>(),
+            vec!["a", "e"],
+            "should find all nodes of a kind, in pre-order"
+        );
+
+        assert_eq!(
+            tree.find_all(NodeKind::Text).len(),
+            3,
+            "should find all nodes of a kind, in descendants"
+        );
+
+        assert_eq!(
+            tree.find_all(NodeKind::Code),
+            Vec::<&Node>::new(),
+            "should find nothing if there are no nodes of a kind"
+        );
+
+        assert_eq!(
+            tree.find_first(NodeKind::Link),
+            Some(&Node::link("b", None, vec![Node::text("c")])),
+            "should find the first node of a kind"
+        );
+
+        assert_eq!(
+            tree.find_first(NodeKind::Root),
+            Some(&tree),
+            "should include the node itself"
+        );
+
+        assert_eq!(
+            tree.find_first(NodeKind::Image),
+            None,
+            "should find no first node if there are no nodes of a kind"
+        );
+
+        assert_eq!(
+            tree.find_all_by(|node| matches!(node, Node::Link(link) if link.children.is_empty())),
+            vec![&Node::link("d", None, vec![])],
+            "should find all nodes matching a predicate"
+        );
+
+        let mut count = 0;
+        assert_eq!(
+            tree.find_first_by(|node| {
+                count += 1;
+                matches!(node, Node::Paragraph(_))
+            })
+            .map(Node::kind),
+            Some(NodeKind::Paragraph),
+            "should find the first node matching a predicate"
+        );
+        assert_eq!(count, 4, "should stop at the first match");
+    }
+
+    #[test]
+    fn visit_mut_actions() {
+        let mut tree = Node::Root(Root {
+            children: vec![
+                visit_paragraph(vec![visit_text("a")]),
+                visit_paragraph(vec![visit_text("b")]),
+                visit_paragraph(vec![visit_text("c")]),
+            ],
+            position: None,
+            data: None,
+        });
+        let mut seen = vec![];
+
+        visit_mut(&mut tree, |node, path| {
+            seen.push(format!("{}:{:?}", node.name(), path));
+            if node.to_string() == "a" && path.len() == 1 {
+                VisitAction::Skip
+            } else if node.to_string() == "b" && path.len() == 2 {
+                VisitAction::Stop
+            } else {
+                VisitAction::Continue
+            }
+        });
+
+        assert_eq!(
+            seen,
+            vec!["Root:[]", "Paragraph:[0]", "Paragraph:[1]", "Text:[1, 0]",],
+            "should support `Skip` and `Stop`, with paths"
+        );
+    }
+
+    #[test]
+    fn visit_mut_replace_children() {
+        let mut tree = Node::Root(Root {
+            children: vec![
+                visit_paragraph(vec![visit_text("a")]),
+                visit_paragraph(vec![visit_text("b")]),
+            ],
+            position: None,
+            data: None,
+        });
+        let mut seen = vec![];
+
+        visit_mut(&mut tree, |node, path| {
+            seen.push(node.name());
+
+            // Replace the children of the first paragraph.
+            if path == [0] {
+                if let Some(children) = node.children_mut() {
+                    *children = vec![
+                        visit_text("c"),
+                        Node::Strong(Strong {
+                            children: vec![visit_text("d")],
+                            position: None,
+                            marker: None,
+                            data: None,
+                        }),
+                    ];
+                }
+            }
+
+            // Turn text into uppercase.
+            if let Node::Text(text) = node {
+                text.value = text.value.to_uppercase();
+            }
+
+            VisitAction::Continue
+        });
+
+        assert_eq!(
+            seen,
+            vec![
+                "Root",
+                "Paragraph",
+                "Text",
+                "Strong",
+                "Text",
+                "Paragraph",
+                "Text"
+            ],
+            "should visit new children"
+        );
+        assert_eq!(tree.to_string(), "CDB", "should support mutation");
+    }
+
+    #[test]
+    fn eq_ignoring_positions() {
+        let a = Node::Paragraph(Paragraph {
+            children: vec![Node::Link(Link {
+                children: vec![Node::Text(Text {
+                    value: "a".into(),
+                    position: Some(Position::new(1, 2, 1, 1, 3, 2)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                url: "b".into(),
+                title: Some("c".into()),
+                data: None,
+                markers: vec![],
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+            data: None,
+        });
+
+        assert!(
+            a.eq_ignoring_positions(&Node::paragraph(vec![Node::link(
+                "b",
+                Some("c"),
+                vec![Node::text("a")]
+            )])),
+            "should ignore positions"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&Node::paragraph(vec![Node::link(
+                "b",
+                Some("c"),
+                vec![Node::text("A")]
+            )])),
+            "should compare values"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&Node::paragraph(vec![Node::link(
+                "B",
+                Some("c"),
+                vec![Node::text("a")]
+            )])),
+            "should compare urls"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&Node::paragraph(vec![Node::link(
+                "b",
+                None,
+                vec![Node::text("a")]
+            )])),
+            "should compare titles"
+        );
+        assert!(
+            !a.eq_ignoring_positions(&Node::paragraph(vec![Node::link("b", Some("c"), vec![])])),
+            "should compare children"
+        );
+        assert!(
+            !Node::definition("a", "b").eq_ignoring_positions(&Node::definition("c", "b")),
+            "should compare identifiers"
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let text = |value: &str, position: Option<Position>| {
+            Node::Text(Text {
+                value: value.into(),
+                position,
+                data: None,
+            })
+        };
+
+        let mut tree = Node::paragraph(vec![
+            text("a", Some(Position::new(1, 1, 0, 1, 2, 1))),
+            text("*", Some(Position::new(1, 2, 1, 1, 4, 3))),
+            text("b", Some(Position::new(1, 4, 3, 1, 5, 4))),
+        ]);
+        tree.normalize();
+        assert_eq!(
+            tree,
+            Node::paragraph(vec![text("a*b", Some(Position::new(1, 1, 0, 1, 5, 4)))]),
+            "should merge texts, and their positions"
+        );
+
+        let mut tree = Node::paragraph(vec![
+            text("a", Some(Position::new(1, 1, 0, 1, 2, 1))),
+            text("b", None),
+            text("c", Some(Position::new(1, 3, 2, 1, 4, 3))),
+        ]);
+        tree.normalize();
+        assert_eq!(
+            tree,
+            Node::paragraph(vec![text("abc", None)]),
+            "should drop the position if a text has none"
+        );
+
+        let mut tree = Node::root(vec![
+            Node::paragraph(vec![
+                Node::text("a"),
+                Node::break_node(),
+                Node::text("b"),
+                Node::strong(vec![Node::text("c"), Node::text("d")]),
+                Node::text("e"),
+                Node::text("f"),
+            ]),
+            Node::paragraph(vec![Node::text("g")]),
+        ]);
+        tree.normalize();
+        assert_eq!(
+            tree,
+            Node::root(vec![
+                Node::paragraph(vec![
+                    Node::text("a"),
+                    Node::break_node(),
+                    Node::text("b"),
+                    Node::strong(vec![Node::text("cd")]),
+                    Node::text("ef"),
+                ]),
+                Node::paragraph(vec![Node::text("g")]),
+            ]),
+            "should not merge across other nodes, and merge in descendants"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn data_serde() {
+        let mut node = Node::text("a");
+        assert!(
+            !serde_json::to_string(&node).unwrap().contains("data"),
+            "should not serialize missing `data`"
+        );
+
+        if let Node::Text(text) = &mut node {
+            text.data = Some(Box::new(Data::from([("b".into(), "c".into())])));
+        }
+        let json = serde_json::to_string(&node).unwrap();
+        assert!(
+            json.contains(r#""data":{"b":"c"}"#),
+            "should serialize `data`"
+        );
+        assert_eq!(
+            Node::Text(
+                serde_json::from_str::<Text>(r#"{"type":"text","value":"a","data":{"b":"c"}}"#)
+                    .unwrap()
+            ),
+            node,
+            "should deserialize `data`"
+        );
+    }
+
+    #[test]
+    fn relevel() {
+        let depths = |tree: &Node| {
+            tree.find_all(NodeKind::Heading)
+                .iter()
+                .map(|node| match node {
+                    Node::Heading(heading) => heading.depth,
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let tree = Node::root(vec![
+            Node::heading(1, vec![Node::text("a")]),
+            Node::heading(3, vec![Node::text("b")]),
+            Node::block_quote(vec![Node::heading(5, vec![Node::text("c")])]),
+            Node::heading(6, vec![Node::text("d")]),
+        ]);
+
+        let mut result = tree.clone();
+        relevel_headings(&mut result, 1);
+        assert_eq!(
+            depths(&result),
+            vec![2, 4, 6, 6],
+            "should shift depths down, in descendants, and clamp at 6"
+        );
+
+        let mut result = tree.clone();
+        relevel_headings(&mut result, -2);
+        assert_eq!(
+            depths(&result),
+            vec![1, 1, 3, 4],
+            "should shift depths up, and clamp at 1"
+        );
+
+        let mut result = tree.clone();
+        relevel_headings(&mut result, i8::MIN);
+        assert_eq!(
+            depths(&result),
+            vec![1, 1, 1, 1],
+            "should support `i8::MIN`"
+        );
+
+        let mut result = tree.clone();
+        relevel_headings(&mut result, i8::MAX);
+        assert_eq!(
+            depths(&result),
+            vec![6, 6, 6, 6],
+            "should support `i8::MAX`"
+        );
+
+        let mut result = tree.clone();
+        relevel_headings(&mut result, 0);
+        assert_eq!(result, tree, "should do nothing with `0`");
+
+        let mut result = tree.clone();
+        normalize_headings(&mut result);
+        assert_eq!(result, tree, "should do nothing if the smallest depth is 1");
+
+        let mut result = Node::root(vec![
+            Node::block_quote(vec![Node::heading(4, vec![])]),
+            Node::heading(3, vec![]),
+            Node::heading(6, vec![]),
+        ]);
+        normalize_headings(&mut result);
+        assert_eq!(
+            depths(&result),
+            vec![2, 1, 4],
+            "should make the smallest depth 1, and keep skipped levels"
+        );
+
+        let mut result = Node::root(vec![Node::paragraph(vec![Node::text("a")])]);
+        normalize_headings(&mut result);
+        assert_eq!(
+            result,
+            Node::root(vec![Node::paragraph(vec![Node::text("a")])]),
+            "should do nothing without headings"
+        );
+    }
+}
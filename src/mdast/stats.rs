@@ -0,0 +1,322 @@
+//! Count words, characters, nodes, and more in mdast.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::mdast::{stats::stats, NodeKind};
+//! use markdown::{to_mdast, ParseOptions};
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! let tree = to_mdast("# Hello, world!\n\nSee [here](a).", &ParseOptions::default())?;
+//! let result = stats(&tree);
+//!
+//! assert_eq!(result.words, 4);
+//! assert_eq!(result.count(NodeKind::Paragraph), 1);
+//! assert_eq!(result.links, 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::mdast::{Code, Node, NodeKind};
+use crate::util::identifier::id_cont;
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::time::Duration;
+
+/// Statistics of a document.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// Number of words in the text that a reader sees.
+    ///
+    /// Words are counted in each block (paragraph, heading, table cell, code,
+    /// math) on its own, in the text of [`Node::to_text()`][].
+    /// They are found with the word boundaries of
+    /// [Unicode text segmentation][uax29], so `can’t`, `3.14`, and `snake_case`
+    /// are one word, and each CJK ideograph is a word.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    pub words: usize,
+    /// Number of characters (Unicode scalar values) in the text that a reader
+    /// sees, as with [`Node::to_text()`][].
+    pub characters: usize,
+    /// Number of nodes of each kind, in the order they are first seen.
+    ///
+    /// Use [`Stats::count()`][] to get the number for one kind.
+    pub nodes: Vec<(NodeKind, usize)>,
+    /// Depth of the outline of headings: `0` without headings, `1` if there
+    /// are no headings in sections of other headings, and so on.
+    ///
+    /// Headings are nested like in a
+    /// [table of contents][crate::mdast::toc::toc]: skipped levels are not
+    /// filled in, so an `h3` right after an `h1` is at depth `2`.
+    pub outline_depth: usize,
+    /// Number of code (flow) nodes for each language, with `None` for code
+    /// without one.
+    pub code_languages: BTreeMap<Option<String>, usize>,
+    /// Number of links, including autolinks and link references.
+    pub links: usize,
+    /// Number of images, including image references.
+    pub images: usize,
+}
+
+impl Stats {
+    /// Get the number of nodes of a kind.
+    #[must_use]
+    pub fn count(&self, kind: NodeKind) -> usize {
+        self.nodes
+            .iter()
+            .find(|(other, _)| *other == kind)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Estimate how long it takes to read the document, at
+    /// `words_per_minute` (such as `200`).
+    ///
+    /// Returns a zero duration if `words_per_minute` is `0`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::mdast::stats::stats;
+    /// use markdown::{to_mdast, ParseOptions};
+    /// use std::time::Duration;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let tree = to_mdast(&"word ".repeat(300), &ParseOptions::default())?;
+    ///
+    /// assert_eq!(stats(&tree).reading_time(200), Duration::from_secs(90));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reading_time(&self, words_per_minute: usize) -> Duration {
+        // Computed in `u64`, so that it does not overflow on 32-bit targets.
+        Duration::from_millis(
+            (self.words as u64)
+                .saturating_mul(60_000)
+                .checked_div(words_per_minute as u64)
+                .unwrap_or(0),
+        )
+    }
+}
+
+/// Get the statistics of `tree`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::mdast::stats::stats;
+/// use markdown::{to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a\n\n### b\n\n```rs\nfn c() {}\n```", &ParseOptions::default())?;
+/// let result = stats(&tree);
+///
+/// assert_eq!(result.outline_depth, 2);
+/// assert_eq!(result.code_languages.get(&Some("rs".into())), Some(&1));
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn stats(tree: &Node) -> Stats {
+    let mut result = Stats {
+        characters: tree.to_text().chars().count(),
+        ..Stats::default()
+    };
+    // Depths of the headings that can still contain later headings.
+    let mut outline: Vec<u8> = vec![];
+
+    for node in tree {
+        let kind = node.kind();
+        if let Some(entry) = result.nodes.iter_mut().find(|(other, _)| *other == kind) {
+            entry.1 += 1;
+        } else {
+            result.nodes.push((kind, 1));
+        }
+
+        match node {
+            Node::Paragraph(_) | Node::Heading(_) | Node::TableCell(_) | Node::Math(_) => {
+                result.words += count_words(&node.to_text());
+            }
+            Node::Code(Code { value, lang, .. }) => {
+                result.words += count_words(value);
+                *result.code_languages.entry(lang.clone()).or_insert(0) += 1;
+            }
+            Node::Link(_) | Node::LinkReference(_) => result.links += 1,
+            Node::Image(_) | Node::ImageReference(_) => result.images += 1,
+            _ => {}
+        }
+
+        if let Node::Heading(heading) = node {
+            while outline
+                .last()
+                .map_or(false, |depth| *depth >= heading.depth)
+            {
+                outline.pop();
+            }
+            outline.push(heading.depth);
+            result.outline_depth = result.outline_depth.max(outline.len());
+        }
+    }
+
+    result
+}
+
+/// Word break class of a character, simplified from Unicode text
+/// segmentation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum WordBreak {
+    /// Letters, except ideographs, hiragana, and katakana.
+    Letter,
+    /// Digits and other numbers.
+    Numeric,
+    /// Katakana, which forms words with other katakana.
+    Katakana,
+    /// Ideographs and hiragana, which are each a word.
+    Ideographic,
+    /// Connectors, such as `_`, which join letters, numbers, and katakana.
+    ExtendNumLet,
+    /// Characters that join letters, such as `:`.
+    MidLetter,
+    /// Characters that join numbers, such as `,`.
+    MidNum,
+    /// Characters that join letters or numbers, such as `.` and `'`.
+    MidNumLet,
+    /// Marks and format characters, which belong to the character before.
+    Extend,
+    /// Anything else, such as whitespace and other punctuation.
+    Other,
+}
+
+/// Count the words in `value`.
+fn count_words(value: &str) -> usize {
+    let classes = value.chars().map(word_break).collect::<Vec<_>>();
+    let mut count = 0;
+    // Class of the last character in the current segment, if it can be
+    // continued.
+    let mut previous: Option<WordBreak> = None;
+    // Whether the current segment is already counted.
+    let mut counted = false;
+    let mut index = 0;
+
+    while index < classes.len() {
+        let class = classes[index];
+
+        let next = match class {
+            // Belongs to whatever comes before.
+            WordBreak::Extend => previous,
+            WordBreak::Letter
+            | WordBreak::Numeric
+            | WordBreak::Katakana
+            | WordBreak::ExtendNumLet => {
+                let joined = matches!(
+                    (previous, class),
+                    (
+                        Some(WordBreak::Letter | WordBreak::Numeric | WordBreak::ExtendNumLet),
+                        WordBreak::Letter | WordBreak::Numeric | WordBreak::ExtendNumLet,
+                    ) | (
+                        Some(WordBreak::Katakana | WordBreak::ExtendNumLet),
+                        WordBreak::Katakana | WordBreak::ExtendNumLet,
+                    )
+                );
+
+                if !joined {
+                    counted = false;
+                }
+
+                Some(class)
+            }
+            // Each ideograph is a segment.
+            WordBreak::Ideographic => {
+                counted = false;
+                Some(class)
+            }
+            WordBreak::MidLetter | WordBreak::MidNum | WordBreak::MidNumLet => {
+                // Only joins the same kind of character on both sides.
+                let after = classes[index + 1..]
+                    .iter()
+                    .find(|class| **class != WordBreak::Extend)
+                    .copied();
+                let joins = match (previous, class) {
+                    (Some(WordBreak::Letter), WordBreak::MidLetter | WordBreak::MidNumLet) => {
+                        after == Some(WordBreak::Letter)
+                    }
+                    (Some(WordBreak::Numeric), WordBreak::MidNum | WordBreak::MidNumLet) => {
+                        after == Some(WordBreak::Numeric)
+                    }
+                    _ => false,
+                };
+
+                if joins {
+                    previous
+                } else {
+                    None
+                }
+            }
+            WordBreak::Other => None,
+        };
+
+        // Segments are words if they have a letter, number, or ideograph.
+        if !counted
+            && matches!(
+                class,
+                WordBreak::Letter
+                    | WordBreak::Numeric
+                    | WordBreak::Katakana
+                    | WordBreak::Ideographic
+            )
+        {
+            count += 1;
+            counted = true;
+        }
+
+        previous = next;
+        index += 1;
+    }
+
+    count
+}
+
+/// Get the word break class of a character.
+fn word_break(char: char) -> WordBreak {
+    match char {
+        '\u{3005}'
+        | '\u{3006}'
+        | '\u{3007}'
+        | '\u{3021}'..='\u{3029}'
+        | '\u{3038}'..='\u{303B}'
+        | '\u{3041}'..='\u{309F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{3FFFF}' => WordBreak::Ideographic,
+        '\u{30A0}'..='\u{30FF}'
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{32D0}'..='\u{32FE}'
+        | '\u{3300}'..='\u{3357}'
+        | '\u{FF66}'..='\u{FF9D}' => WordBreak::Katakana,
+        '_'
+        | '\u{203F}'
+        | '\u{2040}'
+        | '\u{2054}'
+        | '\u{FE33}'
+        | '\u{FE34}'
+        | '\u{FE4D}'..='\u{FE4F}'
+        | '\u{FF3F}' => WordBreak::ExtendNumLet,
+        ':' | '\u{00B7}' | '\u{0387}' | '\u{055F}' | '\u{05F4}' | '\u{2027}' | '\u{FE13}'
+        | '\u{FE55}' | '\u{FF1A}' => WordBreak::MidLetter,
+        ',' | ';' | '\u{037E}' | '\u{0589}' | '\u{060C}' | '\u{060D}' | '\u{066C}' | '\u{07F8}'
+        | '\u{2044}' | '\u{FE10}' | '\u{FE14}' | '\u{FE50}' | '\u{FE54}' | '\u{FF0C}'
+        | '\u{FF1B}' => WordBreak::MidNum,
+        '.' | '\'' | '\u{2018}' | '\u{2019}' | '\u{2024}' | '\u{FE52}' | '\u{FF07}'
+        | '\u{FF0E}' => WordBreak::MidNumLet,
+        '\u{00AD}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FE00}'..='\u{FE0F}' => {
+            WordBreak::Extend
+        }
+        _ if char.is_numeric() => WordBreak::Numeric,
+        _ if char.is_alphabetic() => WordBreak::Letter,
+        // Combining marks (such as a virama) continue identifiers, but are
+        // not letters or numbers.
+        _ if id_cont(char, false) => WordBreak::Extend,
+        _ => WordBreak::Other,
+    }
+}
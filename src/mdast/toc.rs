@@ -0,0 +1,207 @@
+//! Make a table of contents from the headings in mdast.
+//!
+//! Slugs are made like the [`heading_ids`][crate::CompileOptions::heading_ids]
+//! option of [`mdast_to_html()`][crate::mdast_to_html] makes `id`s, so the
+//! links in a table of contents point to the headings in the HTML.
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::mdast::toc::{toc, toc_to_list};
+//! use markdown::{mdast_to_markdown, to_mdast, ParseOptions};
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! let tree = to_mdast("# Alpha\n\n## Bravo\n\n# Alpha", &ParseOptions::default())?;
+//! let entries = toc(&tree, 6);
+//!
+//! assert_eq!(entries[1].slug, "alpha-1");
+//! assert_eq!(
+//!     mdast_to_markdown(&toc_to_list(&entries))?,
+//!     "- [Alpha](#alpha)\n  - [Bravo](#bravo)\n- [Alpha](#alpha-1)\n"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::mdast::{
+    build::{link, list, list_item, paragraph, text},
+    Node,
+};
+use crate::unist::Position;
+use crate::util::slug::{self, Slugger, Slugify};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// Entry in a table of contents.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TocEntry {
+    /// Depth of the heading, from `1` to `6`.
+    pub depth: u8,
+    /// Text of the heading, as with [`Node::to_text()`][].
+    pub text: String,
+    /// Unique slug of the heading, without `#`.
+    pub slug: String,
+    /// Positional info of the heading.
+    pub position: Option<Position>,
+    /// Entries of the headings in this section with a greater depth.
+    pub children: Vec<TocEntry>,
+}
+
+/// Make a table of contents from the headings in `tree`.
+///
+/// Headings deeper than `max_depth` are not included.
+/// Headings without a slug (such as headings without text) are not included
+/// either, as they cannot be linked to.
+///
+/// Each entry contains the entries of the deeper headings that come after it,
+/// up to the next heading that is not deeper.
+/// Skipped levels are not filled in: an `h3` right after an `h1` is a child of
+/// that `h1`.
+///
+/// To make slugs in a different way, use [`toc_with_slugify()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::mdast::toc::toc;
+/// use markdown::{to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a\n\n### b\n\n## c\n\n# d", &ParseOptions::default())?;
+/// let entries = toc(&tree, 2);
+///
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].children[0].text, "c");
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn toc(tree: &Node, max_depth: u8) -> Vec<TocEntry> {
+    toc_impl(tree, max_depth, None)
+}
+
+/// Make a table of contents from the headings in `tree`, with a custom
+/// function to make slugs.
+///
+/// Like [`toc()`][], but `slugify` is used to make slugs, like the
+/// [`slugify`][crate::Options::slugify] option.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::mdast::toc::toc_with_slugify;
+/// use markdown::{to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a\n\n# a", &ParseOptions::default())?;
+/// let entries = toc_with_slugify(&tree, 6, &|value| format!("x-{}", value));
+///
+/// assert_eq!(entries[0].slug, "x-a");
+/// assert_eq!(entries[1].slug, "x-a-1");
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn toc_with_slugify(tree: &Node, max_depth: u8, slugify: &Slugify) -> Vec<TocEntry> {
+    toc_impl(tree, max_depth, Some(slugify))
+}
+
+/// Turn a table of contents into an unordered list of links.
+///
+/// Each item contains a paragraph with a link to `#` and the slug, and a
+/// nested list of the children of the entry, if there are any.
+/// The result can be added to a tree, such as after its first heading.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::mdast::toc::{toc, toc_to_list};
+/// use markdown::{mdast_to_html, to_mdast, Options, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let tree = to_mdast("# a\n\n## b", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     mdast_to_html(&toc_to_list(&toc(&tree, 6)), &Options::default())?,
+///     "<ul>\n<li><a href=\"#a\">a</a>\n<ul>\n<li><a href=\"#b\">b</a></li>\n</ul>\n</li>\n</ul>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn toc_to_list(entries: &[TocEntry]) -> Node {
+    list(
+        false,
+        entries
+            .iter()
+            .map(|entry| {
+                let mut children = vec![paragraph(vec![link(
+                    &format!("#{}", entry.slug),
+                    vec![text(&entry.text)],
+                )])];
+
+                if !entry.children.is_empty() {
+                    children.push(toc_to_list(&entry.children));
+                }
+
+                list_item(children)
+            })
+            .collect(),
+    )
+}
+
+/// Make a table of contents.
+fn toc_impl(tree: &Node, max_depth: u8, slugify: Option<&Slugify>) -> Vec<TocEntry> {
+    let mut slugger = Slugger::default();
+    // Entries that can still get children, from shallow to deep.
+    let mut stack: Vec<TocEntry> = vec![];
+    let mut result = vec![];
+
+    for node in tree {
+        if let Node::Heading(heading) = node {
+            // Slugs of all headings are made, so that later ones are unique
+            // like in HTML.
+            let slug = if let Some(slug) = slug::heading(heading, slugify, &mut slugger) {
+                slug
+            } else {
+                continue;
+            };
+
+            if heading.depth > max_depth {
+                continue;
+            }
+
+            while stack
+                .last()
+                .map_or(false, |entry| entry.depth >= heading.depth)
+            {
+                close(&mut stack, &mut result);
+            }
+
+            stack.push(TocEntry {
+                depth: heading.depth,
+                text: heading.children.iter().map(Node::to_text).collect(),
+                slug,
+                position: heading.position.clone(),
+                children: vec![],
+            });
+        }
+    }
+
+    while !stack.is_empty() {
+        close(&mut stack, &mut result);
+    }
+
+    result
+}
+
+/// Add the deepest entry on `stack` to its parent, or to `result` if it has
+/// none.
+fn close(stack: &mut Vec<TocEntry>, result: &mut Vec<TocEntry>) {
+    let entry = stack.pop().unwrap();
+
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(entry);
+    } else {
+        result.push(entry);
+    }
+}
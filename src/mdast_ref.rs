@@ -0,0 +1,1392 @@
+//! markdown syntax tree that borrows from the input: [mdast][].
+//!
+//! The nodes here are like the ones in [`mdast`][], but their values (such
+//! as the value of text and code, URLs, titles, and labels) are [`Cow`][]s.
+//! Values that are written verbatim in the input, such as text without
+//! character escapes and character references, borrow from it, and only
+//! values that are decoded or pieced together are owned.
+//! This cuts down on allocations for big documents.
+//!
+//! Use [`to_mdast_ref()`][crate::to_mdast_ref] to get a tree, and
+//! [`Node::into_owned()`][] to turn it into an [`mdast::Node`][].
+//!
+//! ## Examples
+//!
+//! ```
+//! use markdown::{mdast_ref::Node, to_mdast_ref, ParseOptions};
+//! use std::borrow::Cow;
+//! # fn main() -> Result<(), markdown::message::Message> {
+//!
+//! let tree = to_mdast_ref("a *b* c&amp;d", &ParseOptions::default())?;
+//! let paragraph = &tree.children().unwrap()[0];
+//! let children = paragraph.children().unwrap();
+//!
+//! if let Node::Text(text) = &children[0] {
+//!     assert!(matches!(text.value, Cow::Borrowed("a ")));
+//! }
+//! if let Node::Text(text) = &children[2] {
+//!     assert!(matches!(text.value, Cow::Owned(_)));
+//!     assert_eq!(text.value, " c&d");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [mdast]: https://github.com/syntax-tree/mdast
+
+use crate::mdast::{self, AlignKind, AttributeContent, Data, ReferenceKind, Stop};
+use crate::unist::Position;
+use alloc::{borrow::Cow, boxed::Box, vec, vec::Vec};
+
+/// Nodes, like [`mdast::Node`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Node<'a> {
+    // Document:
+    /// Root.
+    Root(Root<'a>),
+
+    // Container:
+    /// Block quote.
+    BlockQuote(BlockQuote<'a>),
+    /// Footnote definition.
+    FootnoteDefinition(FootnoteDefinition<'a>),
+    /// MDX: JSX element (container).
+    MdxJsxFlowElement(MdxJsxFlowElement<'a>),
+    /// List.
+    List(List<'a>),
+
+    // Frontmatter:
+    /// MDX.js ESM.
+    MdxjsEsm(MdxjsEsm<'a>),
+    /// Toml.
+    Toml(Toml<'a>),
+    /// Yaml.
+    Yaml(Yaml<'a>),
+
+    // Phrasing:
+    /// Break.
+    Break(mdast::Break),
+    /// Code (phrasing).
+    InlineCode(InlineCode<'a>),
+    /// Math (phrasing).
+    InlineMath(InlineMath<'a>),
+    /// Delete.
+    Delete(Delete<'a>),
+    /// Emphasis.
+    Emphasis(Emphasis<'a>),
+    /// MDX: expression (text).
+    MdxTextExpression(MdxTextExpression<'a>),
+    /// Footnote reference.
+    FootnoteReference(FootnoteReference<'a>),
+    /// Html (phrasing).
+    Html(Html<'a>),
+    /// Image.
+    Image(Image<'a>),
+    /// Image reference.
+    ImageReference(ImageReference<'a>),
+    /// MDX: JSX element (text).
+    MdxJsxTextElement(MdxJsxTextElement<'a>),
+    /// Link.
+    Link(Link<'a>),
+    /// Link reference.
+    LinkReference(LinkReference<'a>),
+    /// Strong
+    Strong(Strong<'a>),
+    /// Text.
+    Text(Text<'a>),
+
+    // Flow:
+    /// Code (flow).
+    Code(Code<'a>),
+    /// Math (flow).
+    Math(Math<'a>),
+    /// MDX: expression (flow).
+    MdxFlowExpression(MdxFlowExpression<'a>),
+    /// Heading.
+    Heading(Heading<'a>),
+    /// Table.
+    Table(Table<'a>),
+    /// Thematic break.
+    ThematicBreak(mdast::ThematicBreak),
+
+    // Table content.
+    /// Table row.
+    TableRow(TableRow<'a>),
+
+    // Row content.
+    /// Table cell.
+    TableCell(TableCell<'a>),
+
+    // List content.
+    /// List item.
+    ListItem(ListItem<'a>),
+
+    // Content.
+    /// Definition.
+    Definition(Definition<'a>),
+    /// Paragraph.
+    Paragraph(Paragraph<'a>),
+}
+
+impl<'a> Node<'a> {
+    /// Get the children of a parent node.
+    ///
+    /// Returns `None` for literals and other nodes that cannot have children.
+    #[must_use]
+    pub fn children(&self) -> Option<&Vec<Node<'a>>> {
+        match self {
+            // Parent.
+            Node::Root(x) => Some(&x.children),
+            Node::Paragraph(x) => Some(&x.children),
+            Node::Heading(x) => Some(&x.children),
+            Node::BlockQuote(x) => Some(&x.children),
+            Node::List(x) => Some(&x.children),
+            Node::ListItem(x) => Some(&x.children),
+            Node::Emphasis(x) => Some(&x.children),
+            Node::Strong(x) => Some(&x.children),
+            Node::Link(x) => Some(&x.children),
+            Node::LinkReference(x) => Some(&x.children),
+            Node::FootnoteDefinition(x) => Some(&x.children),
+            Node::Table(x) => Some(&x.children),
+            Node::TableRow(x) => Some(&x.children),
+            Node::TableCell(x) => Some(&x.children),
+            Node::Delete(x) => Some(&x.children),
+            Node::MdxJsxFlowElement(x) => Some(&x.children),
+            Node::MdxJsxTextElement(x) => Some(&x.children),
+            // Non-parent.
+            Node::MdxjsEsm(_)
+            | Node::Toml(_)
+            | Node::Yaml(_)
+            | Node::Break(_)
+            | Node::InlineCode(_)
+            | Node::InlineMath(_)
+            | Node::MdxTextExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::Html(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::Text(_)
+            | Node::Code(_)
+            | Node::Math(_)
+            | Node::MdxFlowExpression(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => None,
+        }
+    }
+
+    /// Get the children of a parent node, mutably.
+    ///
+    /// Returns `None` for literals and other nodes that cannot have children.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<Node<'a>>> {
+        match self {
+            // Parent.
+            Node::Root(x) => Some(&mut x.children),
+            Node::Paragraph(x) => Some(&mut x.children),
+            Node::Heading(x) => Some(&mut x.children),
+            Node::BlockQuote(x) => Some(&mut x.children),
+            Node::List(x) => Some(&mut x.children),
+            Node::ListItem(x) => Some(&mut x.children),
+            Node::Emphasis(x) => Some(&mut x.children),
+            Node::Strong(x) => Some(&mut x.children),
+            Node::Link(x) => Some(&mut x.children),
+            Node::LinkReference(x) => Some(&mut x.children),
+            Node::FootnoteDefinition(x) => Some(&mut x.children),
+            Node::Table(x) => Some(&mut x.children),
+            Node::TableRow(x) => Some(&mut x.children),
+            Node::TableCell(x) => Some(&mut x.children),
+            Node::Delete(x) => Some(&mut x.children),
+            Node::MdxJsxFlowElement(x) => Some(&mut x.children),
+            Node::MdxJsxTextElement(x) => Some(&mut x.children),
+            // Non-parent.
+            Node::MdxjsEsm(_)
+            | Node::Toml(_)
+            | Node::Yaml(_)
+            | Node::Break(_)
+            | Node::InlineCode(_)
+            | Node::InlineMath(_)
+            | Node::MdxTextExpression(_)
+            | Node::FootnoteReference(_)
+            | Node::Html(_)
+            | Node::Image(_)
+            | Node::ImageReference(_)
+            | Node::Text(_)
+            | Node::Code(_)
+            | Node::Math(_)
+            | Node::MdxFlowExpression(_)
+            | Node::ThematicBreak(_)
+            | Node::Definition(_) => None,
+        }
+    }
+
+    /// Get the position of a node, if it has one.
+    #[must_use]
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            Node::Root(x) => x.position.as_ref(),
+            Node::BlockQuote(x) => x.position.as_ref(),
+            Node::FootnoteDefinition(x) => x.position.as_ref(),
+            Node::MdxJsxFlowElement(x) => x.position.as_ref(),
+            Node::List(x) => x.position.as_ref(),
+            Node::MdxjsEsm(x) => x.position.as_ref(),
+            Node::Toml(x) => x.position.as_ref(),
+            Node::Yaml(x) => x.position.as_ref(),
+            Node::Break(x) => x.position.as_ref(),
+            Node::InlineCode(x) => x.position.as_ref(),
+            Node::InlineMath(x) => x.position.as_ref(),
+            Node::Delete(x) => x.position.as_ref(),
+            Node::Emphasis(x) => x.position.as_ref(),
+            Node::MdxTextExpression(x) => x.position.as_ref(),
+            Node::FootnoteReference(x) => x.position.as_ref(),
+            Node::Html(x) => x.position.as_ref(),
+            Node::Image(x) => x.position.as_ref(),
+            Node::ImageReference(x) => x.position.as_ref(),
+            Node::MdxJsxTextElement(x) => x.position.as_ref(),
+            Node::Link(x) => x.position.as_ref(),
+            Node::LinkReference(x) => x.position.as_ref(),
+            Node::Strong(x) => x.position.as_ref(),
+            Node::Text(x) => x.position.as_ref(),
+            Node::Code(x) => x.position.as_ref(),
+            Node::Math(x) => x.position.as_ref(),
+            Node::MdxFlowExpression(x) => x.position.as_ref(),
+            Node::Heading(x) => x.position.as_ref(),
+            Node::Table(x) => x.position.as_ref(),
+            Node::ThematicBreak(x) => x.position.as_ref(),
+            Node::TableRow(x) => x.position.as_ref(),
+            Node::TableCell(x) => x.position.as_ref(),
+            Node::ListItem(x) => x.position.as_ref(),
+            Node::Definition(x) => x.position.as_ref(),
+            Node::Paragraph(x) => x.position.as_ref(),
+        }
+    }
+
+    /// Get the position of a node, mutably, if it has one.
+    pub fn position_mut(&mut self) -> Option<&mut Position> {
+        match self {
+            Node::Root(x) => x.position.as_mut(),
+            Node::BlockQuote(x) => x.position.as_mut(),
+            Node::FootnoteDefinition(x) => x.position.as_mut(),
+            Node::MdxJsxFlowElement(x) => x.position.as_mut(),
+            Node::List(x) => x.position.as_mut(),
+            Node::MdxjsEsm(x) => x.position.as_mut(),
+            Node::Toml(x) => x.position.as_mut(),
+            Node::Yaml(x) => x.position.as_mut(),
+            Node::Break(x) => x.position.as_mut(),
+            Node::InlineCode(x) => x.position.as_mut(),
+            Node::InlineMath(x) => x.position.as_mut(),
+            Node::Delete(x) => x.position.as_mut(),
+            Node::Emphasis(x) => x.position.as_mut(),
+            Node::MdxTextExpression(x) => x.position.as_mut(),
+            Node::FootnoteReference(x) => x.position.as_mut(),
+            Node::Html(x) => x.position.as_mut(),
+            Node::Image(x) => x.position.as_mut(),
+            Node::ImageReference(x) => x.position.as_mut(),
+            Node::MdxJsxTextElement(x) => x.position.as_mut(),
+            Node::Link(x) => x.position.as_mut(),
+            Node::LinkReference(x) => x.position.as_mut(),
+            Node::Strong(x) => x.position.as_mut(),
+            Node::Text(x) => x.position.as_mut(),
+            Node::Code(x) => x.position.as_mut(),
+            Node::Math(x) => x.position.as_mut(),
+            Node::MdxFlowExpression(x) => x.position.as_mut(),
+            Node::Heading(x) => x.position.as_mut(),
+            Node::Table(x) => x.position.as_mut(),
+            Node::ThematicBreak(x) => x.position.as_mut(),
+            Node::TableRow(x) => x.position.as_mut(),
+            Node::TableCell(x) => x.position.as_mut(),
+            Node::ListItem(x) => x.position.as_mut(),
+            Node::Definition(x) => x.position.as_mut(),
+            Node::Paragraph(x) => x.position.as_mut(),
+        }
+    }
+
+    /// Set (or clear) the position of a node.
+    pub fn position_set(&mut self, position: Option<Position>) {
+        match self {
+            Node::Root(x) => x.position = position,
+            Node::BlockQuote(x) => x.position = position,
+            Node::FootnoteDefinition(x) => x.position = position,
+            Node::MdxJsxFlowElement(x) => x.position = position,
+            Node::List(x) => x.position = position,
+            Node::MdxjsEsm(x) => x.position = position,
+            Node::Toml(x) => x.position = position,
+            Node::Yaml(x) => x.position = position,
+            Node::Break(x) => x.position = position,
+            Node::InlineCode(x) => x.position = position,
+            Node::InlineMath(x) => x.position = position,
+            Node::Delete(x) => x.position = position,
+            Node::Emphasis(x) => x.position = position,
+            Node::MdxTextExpression(x) => x.position = position,
+            Node::FootnoteReference(x) => x.position = position,
+            Node::Html(x) => x.position = position,
+            Node::Image(x) => x.position = position,
+            Node::ImageReference(x) => x.position = position,
+            Node::MdxJsxTextElement(x) => x.position = position,
+            Node::Link(x) => x.position = position,
+            Node::LinkReference(x) => x.position = position,
+            Node::Strong(x) => x.position = position,
+            Node::Text(x) => x.position = position,
+            Node::Code(x) => x.position = position,
+            Node::Math(x) => x.position = position,
+            Node::MdxFlowExpression(x) => x.position = position,
+            Node::Heading(x) => x.position = position,
+            Node::Table(x) => x.position = position,
+            Node::ThematicBreak(x) => x.position = position,
+            Node::TableRow(x) => x.position = position,
+            Node::TableCell(x) => x.position = position,
+            Node::ListItem(x) => x.position = position,
+            Node::Definition(x) => x.position = position,
+            Node::Paragraph(x) => x.position = position,
+        }
+    }
+
+    /// Get the source of a node: the part of `value` at its position.
+    ///
+    /// Like [`mdast::Node::source()`][], so `value` must be the document
+    /// that this node was parsed from, with positions that count bytes.
+    /// Returns `None` if the node has no position, or if its position is not
+    /// in `value`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast_ref, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let value = "```js\na\n```";
+    /// let tree = to_mdast_ref(value, &ParseOptions::default())?;
+    ///
+    /// assert_eq!(tree.children().unwrap()[0].source(value), Some(value));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn source<'b>(&self, value: &'b str) -> Option<&'b str> {
+        let position = self.position()?;
+        value.get(position.start.offset..position.end.offset)
+    }
+
+    /// Turn this node into an [`mdast::Node`][], copying borrowed values.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_mdast, to_mdast_ref, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let value = "# a *b*\n\n[c](d 'e')";
+    /// let tree = to_mdast_ref(value, &ParseOptions::default())?;
+    ///
+    /// assert_eq!(tree.into_owned(), to_mdast(value, &ParseOptions::default())?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> mdast::Node {
+        // Parents with their children that are not yet turned, without
+        // recursion, so that deep trees do not overflow.
+        let mut stack = vec![shallow_into_owned(self)];
+
+        loop {
+            let (_, children) = stack.last_mut().expect("expected parent");
+
+            if let Some(child) = children.next() {
+                stack.push(shallow_into_owned(child));
+            } else {
+                let (node, _) = stack.pop().expect("expected parent");
+
+                if let Some((parent, _)) = stack.last_mut() {
+                    parent.children_mut().expect("expected parent").push(node);
+                } else {
+                    return node;
+                }
+            }
+        }
+    }
+}
+
+impl<'a> From<Node<'a>> for mdast::Node {
+    fn from(node: Node<'a>) -> Self {
+        node.into_owned()
+    }
+}
+
+/// Turn a node into an [`mdast::Node`][] without children, and get its
+/// children.
+#[allow(clippy::too_many_lines)]
+fn shallow_into_owned(node: Node) -> (mdast::Node, vec::IntoIter<Node>) {
+    let (node, children) = match node {
+        Node::Root(x) => (
+            mdast::Node::Root(mdast::Root {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+        Node::BlockQuote(x) => (
+            mdast::Node::BlockQuote(mdast::BlockQuote {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+        Node::FootnoteDefinition(x) => (
+            mdast::Node::FootnoteDefinition(mdast::FootnoteDefinition {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                identifier: x.identifier.into_owned(),
+                label: x.label.map(Cow::into_owned),
+            }),
+            x.children,
+        ),
+        Node::MdxJsxFlowElement(x) => (
+            mdast::Node::MdxJsxFlowElement(mdast::MdxJsxFlowElement {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                name: x.name.map(Cow::into_owned),
+                attributes: x.attributes,
+            }),
+            x.children,
+        ),
+        Node::List(x) => (
+            mdast::Node::List(mdast::List {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                ordered: x.ordered,
+                start: x.start,
+                spread: x.spread,
+            }),
+            x.children,
+        ),
+        Node::MdxjsEsm(x) => (
+            mdast::Node::MdxjsEsm(mdast::MdxjsEsm {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                stops: x.stops,
+            }),
+            vec![],
+        ),
+        Node::Toml(x) => (
+            mdast::Node::Toml(mdast::Toml {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+            }),
+            vec![],
+        ),
+        Node::Yaml(x) => (
+            mdast::Node::Yaml(mdast::Yaml {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+            }),
+            vec![],
+        ),
+        Node::Break(x) => (mdast::Node::Break(x), vec![]),
+        Node::InlineCode(x) => (
+            mdast::Node::InlineCode(mdast::InlineCode {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                sequence_size: x.sequence_size,
+                padding: x.padding,
+                markers: x.markers,
+            }),
+            vec![],
+        ),
+        Node::InlineMath(x) => (
+            mdast::Node::InlineMath(mdast::InlineMath {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+            }),
+            vec![],
+        ),
+        Node::Delete(x) => (
+            mdast::Node::Delete(mdast::Delete {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+        Node::Emphasis(x) => (
+            mdast::Node::Emphasis(mdast::Emphasis {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                marker: x.marker,
+            }),
+            x.children,
+        ),
+        Node::MdxTextExpression(x) => (
+            mdast::Node::MdxTextExpression(mdast::MdxTextExpression {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                stops: x.stops,
+            }),
+            vec![],
+        ),
+        Node::FootnoteReference(x) => (
+            mdast::Node::FootnoteReference(mdast::FootnoteReference {
+                position: x.position,
+                data: x.data,
+                identifier: x.identifier.into_owned(),
+                label: x.label.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::Html(x) => (
+            mdast::Node::Html(mdast::Html {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+            }),
+            vec![],
+        ),
+        Node::Image(x) => (
+            mdast::Node::Image(mdast::Image {
+                position: x.position,
+                data: x.data,
+                alt: x.alt.into_owned(),
+                url: x.url.into_owned(),
+                title: x.title.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::ImageReference(x) => (
+            mdast::Node::ImageReference(mdast::ImageReference {
+                position: x.position,
+                data: x.data,
+                alt: x.alt.into_owned(),
+                reference_kind: x.reference_kind,
+                identifier: x.identifier.into_owned(),
+                label: x.label.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::MdxJsxTextElement(x) => (
+            mdast::Node::MdxJsxTextElement(mdast::MdxJsxTextElement {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                name: x.name.map(Cow::into_owned),
+                attributes: x.attributes,
+            }),
+            x.children,
+        ),
+        Node::Link(x) => (
+            mdast::Node::Link(mdast::Link {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                url: x.url.into_owned(),
+                title: x.title.map(Cow::into_owned),
+                markers: x.markers,
+            }),
+            x.children,
+        ),
+        Node::LinkReference(x) => (
+            mdast::Node::LinkReference(mdast::LinkReference {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                reference_kind: x.reference_kind,
+                identifier: x.identifier.into_owned(),
+                label: x.label.map(Cow::into_owned),
+            }),
+            x.children,
+        ),
+        Node::Strong(x) => (
+            mdast::Node::Strong(mdast::Strong {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                marker: x.marker,
+            }),
+            x.children,
+        ),
+        Node::Text(x) => (
+            mdast::Node::Text(mdast::Text {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+            }),
+            vec![],
+        ),
+        Node::Code(x) => (
+            mdast::Node::Code(mdast::Code {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                lang: x.lang.map(Cow::into_owned),
+                meta: x.meta.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::Math(x) => (
+            mdast::Node::Math(mdast::Math {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                meta: x.meta.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::MdxFlowExpression(x) => (
+            mdast::Node::MdxFlowExpression(mdast::MdxFlowExpression {
+                value: x.value.into_owned(),
+                position: x.position,
+                data: x.data,
+                stops: x.stops,
+            }),
+            vec![],
+        ),
+        Node::Heading(x) => (
+            mdast::Node::Heading(mdast::Heading {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                depth: x.depth,
+                setext: x.setext,
+                markers: x.markers,
+            }),
+            x.children,
+        ),
+        Node::Table(x) => (
+            mdast::Node::Table(mdast::Table {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                align: x.align,
+            }),
+            x.children,
+        ),
+        Node::ThematicBreak(x) => (mdast::Node::ThematicBreak(x), vec![]),
+        Node::TableRow(x) => (
+            mdast::Node::TableRow(mdast::TableRow {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+        Node::TableCell(x) => (
+            mdast::Node::TableCell(mdast::TableCell {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+        Node::ListItem(x) => (
+            mdast::Node::ListItem(mdast::ListItem {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+                spread: x.spread,
+                checked: x.checked,
+                marker: x.marker,
+                number: x.number,
+                indent: x.indent,
+            }),
+            x.children,
+        ),
+        Node::Definition(x) => (
+            mdast::Node::Definition(mdast::Definition {
+                position: x.position,
+                data: x.data,
+                url: x.url.into_owned(),
+                title: x.title.map(Cow::into_owned),
+                identifier: x.identifier.into_owned(),
+                label: x.label.map(Cow::into_owned),
+            }),
+            vec![],
+        ),
+        Node::Paragraph(x) => (
+            mdast::Node::Paragraph(mdast::Paragraph {
+                children: Vec::with_capacity(x.children.len()),
+                position: x.position,
+                data: x.data,
+            }),
+            x.children,
+        ),
+    };
+
+    (node, children.into_iter())
+}
+
+/// Document.
+///
+/// Like [`mdast::Root`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Root<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Paragraph.
+///
+/// Like [`mdast::Paragraph`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Paragraph<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Heading.
+///
+/// Like [`mdast::Heading`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Heading<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Rank (between `1` and `6`, both including).
+    pub depth: u8,
+    /// Whether the heading is underlined (setext, `a\n=`) instead of
+    /// starting with number signs (atx, `# a`).
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub setext: bool,
+    /// Positional info of the markers: the opening and closing (if any)
+    /// sequences of number signs (atx), or the sequence of the underline
+    /// (setext).
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    pub markers: Vec<Position>,
+}
+
+/// Block quote.
+///
+/// Like [`mdast::BlockQuote`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockQuote<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// List.
+///
+/// Like [`mdast::List`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct List<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Ordered (`true`) or unordered (`false`).
+    pub ordered: bool,
+    /// Starting number of the list.
+    /// `None` when unordered.
+    pub start: Option<u32>,
+    /// One or more of its children are separated with a blank line from its
+    /// siblings (when `true`), or not (when `false`).
+    ///
+    /// A list is loose in `CommonMark` (its paragraphs are wrapped in `<p>`)
+    /// when it is spread or when one of its items is spread.
+    pub spread: bool,
+}
+
+/// List item.
+///
+/// Like [`mdast::ListItem`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListItem<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// The item contains two or more children separated by a blank line
+    /// (when `true`), or not (when `false`).
+    pub spread: bool,
+    /// GFM: whether the item is done (when `true`), not done (when `false`),
+    /// or indeterminate or not applicable (`None`).
+    pub checked: Option<bool>,
+    /// Marker used in the source (`*`, `+`, or `-` in unordered lists, `.` or
+    /// `)` in ordered lists), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+    /// Number of the item in the source (such as `3` for `3. a`), if known
+    /// and ordered.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub number: Option<u32>,
+    /// Size of the prefix of the item in the source, from the start of the
+    /// marker to where content starts (such as `4` for `-   a`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub indent: Option<usize>,
+}
+
+/// Html (flow or phrasing).
+///
+/// Like [`mdast::Html`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Html<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Code (flow).
+///
+/// Like [`mdast::Code`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Code<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// The language of computer code being marked up.
+    pub lang: Option<Cow<'a, str>>,
+    /// Custom info relating to the node.
+    pub meta: Option<Cow<'a, str>>,
+}
+
+/// Math (flow).
+///
+/// Like [`mdast::Math`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Math<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Custom info relating to the node.
+    pub meta: Option<Cow<'a, str>>,
+}
+
+/// Definition.
+///
+/// Like [`mdast::Definition`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Definition<'a> {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: Cow<'a, str>,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<Cow<'a, str>>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with
+    /// [`normalize_identifier`][crate::normalize_identifier], which the parser
+    /// does.
+    pub identifier: Cow<'a, str>,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// Text.
+///
+/// Like [`mdast::Text`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Text<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Emphasis.
+///
+/// Like [`mdast::Emphasis`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Emphasis<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Marker used in the source (`*` or `_`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+}
+
+/// Strong.
+///
+/// Like [`mdast::Strong`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Strong<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Marker used in the source (`*` or `_`), if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub marker: Option<char>,
+}
+
+/// Code (phrasing).
+///
+/// Like [`mdast::InlineCode`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InlineCode<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Number of grave accents in the sequences used in the source, if known.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub sequence_size: Option<usize>,
+    /// Whether the source had a space (or line ending) between the value and
+    /// both sequences.
+    ///
+    /// Used when serializing to markdown, not when compiling to HTML.
+    pub padding: bool,
+    /// Positional info of the markers: the opening and closing sequences of
+    /// grave accents.
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    pub markers: Vec<Position>,
+}
+
+/// Math (phrasing).
+///
+/// Like [`mdast::InlineMath`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InlineMath<'a> {
+    // Text.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Link.
+///
+/// Like [`mdast::Link`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Link<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: Cow<'a, str>,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<Cow<'a, str>>,
+    /// Positional info of the markers: the opening and closing brackets
+    /// and parens of a resource (`[a](b)`), or the angle brackets of an
+    /// autolink (`<c>`).
+    /// GFM autolink literals (`www.d.e`) have no markers.
+    ///
+    /// Only added by the parser with
+    /// [`mdast_marker_positions`][crate::ParseOptions::mdast_marker_positions].
+    pub markers: Vec<Position>,
+}
+
+/// Image.
+///
+/// Like [`mdast::Image`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image<'a> {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    pub alt: Cow<'a, str>,
+    // Resource.
+    /// URL to the referenced resource.
+    pub url: Cow<'a, str>,
+    /// Advisory info for the resource, such as something that would be
+    /// appropriate for a tooltip.
+    pub title: Option<Cow<'a, str>>,
+}
+
+/// Link reference.
+///
+/// Like [`mdast::LinkReference`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinkReference<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Reference.
+    /// Explicitness of a reference.
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with
+    /// [`normalize_identifier`][crate::normalize_identifier], which the parser
+    /// does.
+    pub identifier: Cow<'a, str>,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// Image reference.
+///
+/// Like [`mdast::ImageReference`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageReference<'a> {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Alternative.
+    /// Equivalent content for environments that cannot represent the node as
+    /// intended.
+    pub alt: Cow<'a, str>,
+    // Reference.
+    /// Explicitness of a reference.
+    pub reference_kind: ReferenceKind,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with
+    /// [`normalize_identifier`][crate::normalize_identifier], which the parser
+    /// does.
+    pub identifier: Cow<'a, str>,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// GFM: footnote definition.
+///
+/// Like [`mdast::FootnoteDefinition`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FootnoteDefinition<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with
+    /// [`normalize_identifier`][crate::normalize_identifier], which the parser
+    /// does.
+    pub identifier: Cow<'a, str>,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// GFM: footnote reference.
+///
+/// Like [`mdast::FootnoteReference`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FootnoteReference<'a> {
+    // Void.
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Association.
+    /// Value that can match another node.
+    /// `identifier` is a source value: character escapes and character references
+    /// are *not* parsed.
+    /// Its value must be normalized with
+    /// [`normalize_identifier`][crate::normalize_identifier], which the parser
+    /// does.
+    pub identifier: Cow<'a, str>,
+    /// `label` is a string value: it works just like `title` on a link or a
+    /// `lang` on code: character escapes and character references are parsed.
+    /// It is kept as authored: it is not normalized.
+    ///
+    /// To normalize a value, collapse markdown whitespace (`[\t\n\r ]+`) to a
+    /// space, trim the optional initial and/or final space, and perform
+    /// case-folding.
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// GFM: table.
+///
+/// Like [`mdast::Table`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Table<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // Extra.
+    /// Represents how cells in columns are aligned.
+    pub align: Vec<AlignKind>,
+}
+
+/// GFM: table row.
+///
+/// Like [`mdast::TableRow`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableRow<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// GFM: table cell.
+///
+/// Like [`mdast::TableCell`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableCell<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// GFM: delete.
+///
+/// Like [`mdast::Delete`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delete<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Frontmatter: yaml.
+///
+/// Like [`mdast::Yaml`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Yaml<'a> {
+    // Void.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// Frontmatter: toml.
+///
+/// Like [`mdast::Toml`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Toml<'a> {
+    // Void.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+}
+
+/// MDX: ESM.
+///
+/// Like [`mdast::MdxjsEsm`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MdxjsEsm<'a> {
+    // Literal.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (flow).
+///
+/// Like [`mdast::MdxFlowExpression`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MdxFlowExpression<'a> {
+    // Literal.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: expression (text).
+///
+/// Like [`mdast::MdxTextExpression`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MdxTextExpression<'a> {
+    // Literal.
+    /// Content model.
+    pub value: Cow<'a, str>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+
+    // Custom data on where each slice of `value` came from.
+    pub stops: Vec<Stop>,
+}
+
+/// MDX: JSX element (container).
+///
+/// Like [`mdast::MdxJsxFlowElement`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MdxJsxFlowElement<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<Cow<'a, str>>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
+
+/// MDX: JSX element (text).
+///
+/// Like [`mdast::MdxJsxTextElement`][], but borrowing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MdxJsxTextElement<'a> {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node<'a>>,
+    /// Positional info.
+    pub position: Option<Position>,
+    /// Extra info, for plugins and utilities to use.
+    pub data: Option<Box<Data>>,
+    // JSX element.
+    /// Name.
+    ///
+    /// Fragments have no name.
+    pub name: Option<Cow<'a, str>>,
+    /// Attributes.
+    pub attributes: Vec<AttributeContent>,
+}
@@ -0,0 +1,1243 @@
+//! Turn a markdown syntax tree into an HTML syntax tree.
+//!
+//! This mirrors `mdast_to_html`: serializing the result with `hast_to_html`
+//! gives the same HTML.
+
+use crate::hast;
+use crate::mdast::{
+    AlignKind, Code, Definition, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
+    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math, Node,
+    ReferenceKind, Table, Text,
+};
+use crate::mdast_to_html::collect;
+use crate::message;
+use crate::to_html::{
+    code_wrapper, heading_rank, join_class, math_wrapper, parse_attributes, WrapperElement,
+};
+use crate::unist::Position;
+use crate::util::{
+    constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
+    gfm_tagfilter::gfm_tagfilter,
+    normalize_identifier::normalize_identifier,
+    sanitize_uri::{normalize, normalize_with_protocols},
+    slug::{self, Slugger, Slugify},
+    whitespace::collapse as collapse_whitespace,
+};
+use crate::{CompileOptions, Options};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Compilation state.
+#[allow(clippy::struct_excessive_bools)]
+struct State<'a> {
+    /// Configuration.
+    options: &'a CompileOptions,
+    /// Function to turn the text of a heading into a slug.
+    slugify: Option<&'a Slugify>,
+    /// Line ending to use.
+    line_ending: &'a str,
+    /// Definitions in the tree.
+    definitions: Vec<&'a Definition>,
+    /// Definitions passed in options, used after those in the tree.
+    option_definitions: &'a BTreeMap<String, (String, Option<String>)>,
+    /// Identifiers of the GFM footnote definitions in the tree.
+    gfm_footnote_identifiers: Vec<String>,
+    /// Compiled GFM footnote definitions.
+    gfm_footnote_definitions: Vec<(String, Vec<hast::Node>)>,
+    /// GFM footnote calls, with how often they are called.
+    gfm_footnote_definition_calls: Vec<(String, usize)>,
+    /// Whether paragraphs are in a tight list item, and thus not wrapped.
+    tight: bool,
+    /// Whether we are in a container (block quote, list, footnote
+    /// definition).
+    container: bool,
+    /// Whether we are in phrasing content (paragraphs, headings, cells).
+    phrasing: bool,
+    /// Whether we are in a link.
+    link_inside: bool,
+    /// Whether the next paragraph starts with a GFM task list item check.
+    gfm_task_list_item_check: Option<bool>,
+    /// Alignment of the current GFM table.
+    table_align: &'a [AlignKind],
+    /// Whether we are in the head row of a GFM table.
+    table_head: bool,
+    /// Alignment of the current GFM table cell.
+    table_cell_align: AlignKind,
+    /// Slugs of headings seen so far.
+    slugger: Slugger,
+    /// Elements to wrap code (flow) in.
+    code_wrapper: Vec<WrapperElement>,
+    /// Elements to wrap math (flow) in.
+    math_wrapper: Vec<WrapperElement>,
+}
+
+/// Nodes compiled into a parent.
+struct Parent {
+    /// Compiled nodes.
+    children: Vec<hast::Node>,
+    /// Whether the nodes start the HTML, instead of following an opening tag.
+    start: bool,
+}
+
+impl Parent {
+    /// Create a parent.
+    fn new(start: bool) -> Parent {
+        Parent {
+            children: vec![],
+            start,
+        }
+    }
+
+    /// Add a node.
+    fn push(&mut self, node: hast::Node) {
+        self.children.push(node);
+    }
+
+    /// Add generated text, which has no position.
+    fn push_text(&mut self, value: &str) {
+        self.children.push(hast::Node::Text(hast::Text {
+            value: value.into(),
+            position: None,
+        }));
+    }
+
+    /// Add a line ending if needed (as in, there’s no eol/eof already).
+    fn line_ending_if_needed(&mut self, line_ending: &str) {
+        let mut index = self.children.len();
+        let mut needed = !self.start;
+
+        while index > 0 {
+            index -= 1;
+            match &self.children[index] {
+                hast::Node::Text(hast::Text { value, .. })
+                | hast::Node::Raw(hast::Raw { value, .. }) => {
+                    if value.is_empty() {
+                        continue;
+                    }
+                    needed = !value.ends_with(['\n', '\r']);
+                }
+                hast::Node::Element(_) | hast::Node::Root(_) => needed = true,
+            }
+            break;
+        }
+
+        if needed {
+            self.push_text(line_ending);
+        }
+    }
+
+    /// Remove line endings at the end.
+    fn trim_line_endings(&mut self) {
+        while let Some(
+            hast::Node::Text(hast::Text { value, .. }) | hast::Node::Raw(hast::Raw { value, .. }),
+        ) = self.children.last_mut()
+        {
+            value.truncate(value.trim_end_matches(['\n', '\r']).len());
+
+            if !value.is_empty() {
+                break;
+            }
+
+            self.children.pop();
+        }
+    }
+}
+
+/// Turn a markdown syntax tree into an HTML syntax tree.
+pub fn compile<'a>(tree: &'a Node, options: &'a Options) -> Result<hast::Node, message::Message> {
+    let mut definitions = vec![];
+    let mut gfm_footnote_identifiers = vec![];
+    let mut line_ending = None;
+    collect(
+        tree,
+        &mut definitions,
+        &mut gfm_footnote_identifiers,
+        &mut line_ending,
+    );
+
+    let mut state = State {
+        options: &options.compile,
+        slugify: options.slugify.as_deref(),
+        line_ending: line_ending.unwrap_or_else(|| options.compile.default_line_ending.as_str()),
+        definitions,
+        option_definitions: &options.parse.definitions,
+        gfm_footnote_identifiers,
+        gfm_footnote_definitions: vec![],
+        gfm_footnote_definition_calls: vec![],
+        tight: false,
+        container: false,
+        phrasing: false,
+        link_inside: false,
+        gfm_task_list_item_check: None,
+        table_align: &[],
+        table_head: false,
+        table_cell_align: AlignKind::None,
+        slugger: Slugger::default(),
+        code_wrapper: code_wrapper(&options.compile),
+        math_wrapper: math_wrapper(),
+    };
+    let mut root = Parent::new(true);
+
+    one(&mut state, tree, &mut root)?;
+
+    if !state.gfm_footnote_definition_calls.is_empty() {
+        footnote_section(&mut state, &mut root);
+    }
+
+    match options.compile.trailing_newline {
+        Some(true) => root.line_ending_if_needed(state.line_ending),
+        Some(false) => root.trim_line_endings(),
+        None => {}
+    }
+
+    Ok(hast::Node::Root(hast::Root {
+        children: root.children,
+        position: tree.position().cloned(),
+    }))
+}
+
+/// Compile a node.
+fn one<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    match node {
+        Node::Root(_) => all(state, node, parent)?,
+        Node::BlockQuote(_) => {
+            let tight = state.tight;
+            let container = state.container;
+            state.tight = false;
+            state.container = true;
+            parent.line_ending_if_needed(state.line_ending);
+            let mut block_quote = Parent::new(false);
+            all(state, node, &mut block_quote)?;
+            block_quote.line_ending_if_needed(state.line_ending);
+            parent.push(element(
+                "blockquote",
+                source_position(state, node.position()),
+                block_quote.children,
+                node.position(),
+            ));
+            state.tight = tight;
+            state.container = container;
+        }
+        Node::Break(_) => {
+            parent.push(element("br", vec![], vec![], node.position()));
+            parent.push_text(if state.options.collapse_whitespace {
+                " "
+            } else {
+                state.line_ending
+            });
+        }
+        Node::Code(node) => code(state, node, parent),
+        Node::Definition(_) | Node::Toml(_) | Node::Yaml(_) => {}
+        Node::Delete(_) => wrap(state, node, "del", parent)?,
+        Node::Emphasis(_) => wrap(state, node, "em", parent)?,
+        Node::FootnoteDefinition(node) => footnote_definition(state, node)?,
+        Node::FootnoteReference(node) => footnote_reference(state, node, parent),
+        Node::Heading(node) => heading(state, node, parent)?,
+        Node::Html(node) => html(state, node, parent),
+        Node::Image(node) => image(state, node, parent),
+        Node::ImageReference(node) => image_reference(state, node, parent),
+        Node::InlineCode(InlineCode {
+            value, position, ..
+        }) => {
+            raw_text(value, None, position.as_ref(), parent);
+        }
+        Node::InlineMath(InlineMath {
+            value, position, ..
+        }) => {
+            raw_text(
+                value,
+                Some("language-math math-inline"),
+                position.as_ref(),
+                parent,
+            );
+        }
+        Node::Link(node) => link(state, node, parent)?,
+        Node::LinkReference(node) => link_reference(state, node, parent)?,
+        Node::List(node) => list(state, node, parent)?,
+        Node::ListItem(node) => list_item(state, node, parent)?,
+        Node::Math(Math {
+            value, position, ..
+        }) => {
+            raw_flow(
+                state,
+                value,
+                &state.math_wrapper,
+                Some("language-math math-display"),
+                position.as_ref(),
+                parent,
+            );
+        }
+        Node::Paragraph(_) => paragraph(state, node, parent)?,
+        Node::Strong(_) => wrap(state, node, "strong", parent)?,
+        Node::Table(node) => table(state, node, parent)?,
+        Node::TableRow(_) => table_row(state, node, parent)?,
+        Node::TableCell(_) => table_cell(state, node, parent)?,
+        Node::Text(Text {
+            value, position, ..
+        }) => {
+            parent.push(hast::Node::Text(hast::Text {
+                value: text(state, value).into_owned(),
+                position: position.clone(),
+            }));
+        }
+        Node::ThematicBreak(_) => {
+            parent.line_ending_if_needed(state.line_ending);
+
+            if let Some(html) = &state.options.thematic_break_html {
+                parent.push(hast::Node::Raw(hast::Raw {
+                    value: html.clone(),
+                    position: node.position().cloned(),
+                }));
+            } else {
+                parent.push(element(
+                    "hr",
+                    source_position(state, node.position()),
+                    vec![],
+                    node.position(),
+                ));
+            }
+        }
+        Node::MdxFlowExpression(_) | Node::MdxjsEsm(_) | Node::MdxTextExpression(_) => {
+            unknown(state, node)?;
+        }
+        Node::MdxJsxFlowElement(_) | Node::MdxJsxTextElement(_) => {
+            unknown(state, node)?;
+            all(state, node, parent)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Compile the children of a node.
+fn all<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    if let Some(children) = node.children() {
+        for child in children {
+            one(state, child, parent)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create an element.
+fn element(
+    tag_name: &str,
+    attributes: Vec<(String, String)>,
+    children: Vec<hast::Node>,
+    position: Option<&Position>,
+) -> hast::Node {
+    hast::Node::Element(hast::Element {
+        children,
+        position: position.cloned(),
+        tag_name: tag_name.into(),
+        attributes,
+    })
+}
+
+/// Compile a node with children in an element.
+fn wrap<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    tag_name: &str,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let mut children = Parent::new(false);
+    all(state, node, &mut children)?;
+    parent.push(element(
+        tag_name,
+        vec![],
+        children.children,
+        node.position(),
+    ));
+    Ok(())
+}
+
+/// Compile a paragraph.
+///
+/// In tight list items, paragraphs are not wrapped in `<p>`, and neither are
+/// paragraphs outside containers without `wrap_paragraphs`.
+fn paragraph<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let wrap = !state.tight && (state.options.wrap_paragraphs || state.container);
+
+    if !state.tight {
+        parent.line_ending_if_needed(state.line_ending);
+    }
+
+    let mut paragraph = Parent::new(false);
+    let target = if wrap { &mut paragraph } else { &mut *parent };
+
+    if let Some(checked) = state.gfm_task_list_item_check.take() {
+        gfm_task_list_item_check(state, checked, target);
+    }
+
+    state.phrasing = true;
+    all(state, node, target)?;
+    state.phrasing = false;
+
+    if wrap {
+        parent.push(element(
+            "p",
+            source_position(state, node.position()),
+            paragraph.children,
+            node.position(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Compile a heading.
+fn heading<'a>(
+    state: &mut State<'a>,
+    node: &'a Heading,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let tag_name = format!(
+        "h{}",
+        heading_rank(usize::from(node.depth), state.options.heading_offset)
+    );
+    let mut attributes = source_position(state, node.position.as_ref());
+
+    if state.options.heading_ids {
+        if let Some(value) = slug::heading(node, state.slugify, &mut state.slugger) {
+            attributes.push(("id".into(), value));
+        }
+    }
+
+    parent.line_ending_if_needed(state.line_ending);
+    let mut children = Parent::new(false);
+    state.phrasing = true;
+    for child in &node.children {
+        one(state, child, &mut children)?;
+    }
+    state.phrasing = false;
+    parent.push(element(
+        &tag_name,
+        attributes,
+        children.children,
+        node.position.as_ref(),
+    ));
+    Ok(())
+}
+
+/// Compile code (flow).
+fn code(state: &State, node: &Code, parent: &mut Parent) {
+    let class = node.lang.as_ref().map(|lang| format!("language-{}", lang));
+    raw_flow(
+        state,
+        &node.value,
+        &state.code_wrapper,
+        class.as_deref(),
+        node.position.as_ref(),
+        parent,
+    );
+}
+
+/// Compile code (flow) or math (flow), in the elements of `wrapper`.
+fn raw_flow(
+    state: &State,
+    value: &str,
+    wrapper: &[WrapperElement],
+    class: Option<&str>,
+    position: Option<&Position>,
+    parent: &mut Parent,
+) {
+    let mut code = Parent::new(false);
+    if !value.is_empty() {
+        code.push_text(&format!("{}{}", value, state.line_ending));
+    }
+
+    // Build the elements from the inside out.
+    let mut children = code.children;
+    let mut index = wrapper.len();
+    while index > 0 {
+        index -= 1;
+        let wrapper_element = &wrapper[index];
+        let mut attributes = wrapper_element.attributes.clone();
+        if index == 0 {
+            attributes.append(&mut source_position(state, position));
+        }
+        let class = if index == wrapper.len() - 1 {
+            join_class(wrapper_element.class.as_deref(), class)
+        } else {
+            wrapper_element.class.clone()
+        };
+        if let Some(class) = class {
+            attributes.push(("class".into(), class));
+        }
+        children = vec![element(
+            &wrapper_element.tag_name,
+            attributes,
+            children,
+            if index == 0 { position } else { None },
+        )];
+    }
+
+    parent.line_ending_if_needed(state.line_ending);
+    parent.push(children.pop().expect("expected element"));
+}
+
+/// Compile code (text) or math (text).
+///
+/// Line endings are turned into spaces, as browsers would show them anyway.
+fn raw_text(value: &str, class: Option<&str>, position: Option<&Position>, parent: &mut Parent) {
+    let mut code = Parent::new(false);
+    code.push_text(&value.replace("\r\n", " ").replace(['\r', '\n'], " "));
+    parent.push(element(
+        "code",
+        class.map_or_else(Vec::new, |class| vec![("class".into(), class.into())]),
+        code.children,
+        position,
+    ));
+}
+
+/// Compile HTML.
+///
+/// Raw with `allow_dangerous_html`, text otherwise.
+fn html(state: &State, node: &Html, parent: &mut Parent) {
+    if state.options.strip_comments
+        && node
+            .value
+            .trim_start_matches(['\t', ' '])
+            .starts_with("<!--")
+    {
+        return;
+    }
+
+    if !state.phrasing {
+        parent.line_ending_if_needed(state.line_ending);
+    }
+
+    let position = node.position.clone();
+
+    parent.push(if !state.options.allow_dangerous_html {
+        hast::Node::Text(hast::Text {
+            value: node.value.clone(),
+            position,
+        })
+    } else if state.options.gfm_tagfilter {
+        hast::Node::Raw(hast::Raw {
+            value: gfm_tagfilter(&node.value),
+            position,
+        })
+    } else {
+        hast::Node::Raw(hast::Raw {
+            value: node.value.clone(),
+            position,
+        })
+    });
+}
+
+/// Compile a link.
+fn link<'a>(
+    state: &mut State<'a>,
+    node: &'a Link,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    anchor(
+        state,
+        &node.url,
+        node.title.as_deref(),
+        &node.children,
+        node.position.as_ref(),
+        parent,
+    )
+}
+
+/// Compile a link reference.
+///
+/// Turned into a link if there is a definition for it, and into the text it
+/// was written as otherwise.
+fn link_reference<'a>(
+    state: &mut State<'a>,
+    node: &'a LinkReference,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    if let Some((url, title)) = definition(state, &node.identifier) {
+        anchor(
+            state,
+            url,
+            title,
+            &node.children,
+            node.position.as_ref(),
+            parent,
+        )?;
+    } else {
+        parent.push_text("[");
+        for child in &node.children {
+            one(state, child, parent)?;
+        }
+        parent.push_text(&format!(
+            "]{}",
+            reference_suffix(node.reference_kind, &node.identifier, node.label.as_deref())
+        ));
+    }
+
+    Ok(())
+}
+
+/// Compile an `a` element.
+///
+/// Links cannot contain links, so in links, only the children are compiled
+/// (this happens with GFM autolink literals in links).
+fn anchor<'a>(
+    state: &mut State<'a>,
+    destination: &str,
+    title: Option<&str>,
+    children: &'a [Node],
+    position: Option<&Position>,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let link_inside = state.link_inside;
+    let mut attributes = vec![("href".into(), url(state, destination, false))];
+    if let Some(title) = title {
+        attributes.push(("title".into(), title.into()));
+    }
+
+    let mut anchor = Parent::new(false);
+    let target = if link_inside {
+        &mut *parent
+    } else {
+        &mut anchor
+    };
+
+    state.link_inside = true;
+    for child in children {
+        one(state, child, target)?;
+    }
+    state.link_inside = link_inside;
+
+    if !link_inside {
+        parent.push(element("a", attributes, anchor.children, position));
+    }
+
+    Ok(())
+}
+
+/// Get the value of text, collapsed with `collapse_whitespace`.
+fn text<'b>(state: &State, value: &'b str) -> Cow<'b, str> {
+    if state.options.collapse_whitespace {
+        collapse_whitespace(value)
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Compile an image.
+fn image(state: &State, node: &Image, parent: &mut Parent) {
+    image_element(
+        state,
+        &node.url,
+        &node.alt,
+        node.title.as_deref(),
+        node.position.as_ref(),
+        parent,
+    );
+}
+
+/// Compile an image reference.
+///
+/// Turned into an image if there is a definition for it, and into the text
+/// it was written as otherwise.
+fn image_reference(state: &State, node: &ImageReference, parent: &mut Parent) {
+    if let Some((url, title)) = definition(state, &node.identifier) {
+        image_element(state, url, &node.alt, title, node.position.as_ref(), parent);
+    } else {
+        parent.push(hast::Node::Text(hast::Text {
+            value: format!(
+                "![{}]{}",
+                text(state, &node.alt),
+                reference_suffix(node.reference_kind, &node.identifier, node.label.as_deref())
+            ),
+            position: node.position.clone(),
+        }));
+    }
+}
+
+/// Compile an `img` element.
+fn image_element(
+    state: &State,
+    destination: &str,
+    alt: &str,
+    title: Option<&str>,
+    position: Option<&Position>,
+    parent: &mut Parent,
+) {
+    let mut attributes = vec![
+        ("src".into(), url(state, destination, true)),
+        ("alt".into(), text(state, alt).into_owned()),
+    ];
+    if let Some(title) = title {
+        attributes.push(("title".into(), title.into()));
+    }
+    parent.push(element("img", attributes, vec![], position));
+}
+
+/// Get the end of an unresolved reference (`[b]` in `[a][b]`, `[]` in
+/// `[a][]`, or nothing in `[a]`).
+fn reference_suffix(kind: ReferenceKind, identifier: &str, label: Option<&str>) -> String {
+    match kind {
+        ReferenceKind::Full => format!("[{}]", label.unwrap_or(identifier)),
+        ReferenceKind::Collapsed => "[]".into(),
+        ReferenceKind::Shortcut => String::new(),
+    }
+}
+
+/// Compile a list.
+fn list<'a>(
+    state: &mut State<'a>,
+    node: &'a List,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let tight = state.tight;
+    let container = state.container;
+    let tag_name = if node.ordered { "ol" } else { "ul" };
+    state.container = true;
+    state.tight = !node.spread
+        && !node
+            .children
+            .iter()
+            .any(|child| matches!(child, Node::ListItem(item) if item.spread));
+
+    let mut attributes = source_position(state, node.position.as_ref());
+    if node.ordered && !state.options.renumber_ordered_lists {
+        if let Some(start) = node.start {
+            if start != 1 {
+                attributes.push(("start".into(), start.to_string()));
+            }
+        }
+    }
+
+    parent.line_ending_if_needed(state.line_ending);
+    let mut items = Parent::new(false);
+    for child in &node.children {
+        one(state, child, &mut items)?;
+    }
+    items.push_text(state.line_ending);
+    parent.push(element(
+        tag_name,
+        attributes,
+        items.children,
+        node.position.as_ref(),
+    ));
+
+    state.tight = tight;
+    state.container = container;
+    Ok(())
+}
+
+/// Compile a list item.
+fn list_item<'a>(
+    state: &mut State<'a>,
+    node: &'a ListItem,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let children = node
+        .children
+        .iter()
+        .filter(|child| !matches!(child, Node::Definition(_) | Node::FootnoteDefinition(_)))
+        .collect::<Vec<_>>();
+
+    parent.line_ending_if_needed(state.line_ending);
+    let mut item = Parent::new(false);
+
+    if let Some(checked) = node.checked {
+        if matches!(children.first(), Some(Node::Paragraph(_))) {
+            state.gfm_task_list_item_check = Some(checked);
+        } else {
+            gfm_task_list_item_check(state, checked, &mut item);
+        }
+    }
+
+    let mut seen = false;
+    for child in &node.children {
+        // Tight paragraphs are not wrapped, so they need a line ending when
+        // they follow something.
+        if seen && state.tight && matches!(child, Node::Paragraph(_)) {
+            item.line_ending_if_needed(state.line_ending);
+        }
+        seen = seen || !matches!(child, Node::Definition(_) | Node::FootnoteDefinition(_));
+        one(state, child, &mut item)?;
+    }
+
+    let tight_paragraph = state.tight && matches!(children.last(), Some(Node::Paragraph(_)));
+
+    if !tight_paragraph && !children.is_empty() {
+        item.line_ending_if_needed(state.line_ending);
+    }
+
+    parent.push(element(
+        "li",
+        source_position(state, node.position.as_ref()),
+        item.children,
+        node.position.as_ref(),
+    ));
+    Ok(())
+}
+
+/// Compile a GFM task list item check.
+fn gfm_task_list_item_check(state: &State, checked: bool, parent: &mut Parent) {
+    let mut attributes = vec![("type".into(), "checkbox".into())];
+    if !state.options.gfm_task_list_item_checkable {
+        attributes.push(("disabled".into(), String::new()));
+    }
+    if checked {
+        attributes.push(("checked".into(), String::new()));
+    }
+    parent.push(element("input", attributes, vec![], None));
+    parent.push_text(" ");
+}
+
+/// Compile a GFM table.
+///
+/// The head and body get the positions of their rows.
+fn table<'a>(
+    state: &mut State<'a>,
+    node: &'a Table,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let align = state.table_align;
+    let head = state.table_head;
+    state.table_align = &node.align;
+    parent.line_ending_if_needed(state.line_ending);
+    let mut table = Parent::new(false);
+
+    if let Some(row) = node.children.first() {
+        table.line_ending_if_needed(state.line_ending);
+        let mut thead = Parent::new(false);
+        state.table_head = true;
+        one(state, row, &mut thead)?;
+        state.table_head = false;
+        thead.line_ending_if_needed(state.line_ending);
+        table.push(element("thead", vec![], thead.children, row.position()));
+    }
+
+    if node.children.len() > 1 {
+        let rows = &node.children[1..];
+        table.line_ending_if_needed(state.line_ending);
+        let mut tbody = Parent::new(false);
+        for row in rows {
+            one(state, row, &mut tbody)?;
+        }
+        tbody.line_ending_if_needed(state.line_ending);
+        let position = match (rows[0].position(), rows.last().and_then(Node::position)) {
+            (Some(start), Some(end)) => Some(Position {
+                start: start.start.clone(),
+                end: end.end.clone(),
+            }),
+            _ => None,
+        };
+        table.push(element("tbody", vec![], tbody.children, position.as_ref()));
+    }
+
+    table.line_ending_if_needed(state.line_ending);
+    parent.push(element(
+        "table",
+        source_position(state, node.position.as_ref()),
+        table.children,
+        node.position.as_ref(),
+    ));
+    state.table_align = align;
+    state.table_head = head;
+    Ok(())
+}
+
+/// Compile a GFM table row.
+///
+/// Cells beyond the alignment of the table are dropped, missing cells are
+/// added.
+/// Without alignment (outside a table), all cells are kept.
+fn table_row<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let align = state.table_align;
+    let head = state.table_head;
+    let cells: &'a [Node] = node.children().map_or(&[], |children| children);
+    let size = if align.is_empty() {
+        cells.len()
+    } else {
+        align.len()
+    };
+
+    parent.line_ending_if_needed(state.line_ending);
+    let mut row = Parent::new(false);
+
+    let mut index = 0;
+    while index < size {
+        let align = align.get(index).copied().unwrap_or(AlignKind::None);
+
+        if let Some(cell) = cells.get(index) {
+            state.table_cell_align = align;
+            one(state, cell, &mut row)?;
+            state.table_cell_align = AlignKind::None;
+        } else {
+            row.line_ending_if_needed(state.line_ending);
+            row.push(element(
+                if head { "th" } else { "td" },
+                table_cell_attributes(align),
+                vec![],
+                None,
+            ));
+        }
+
+        index += 1;
+    }
+
+    row.line_ending_if_needed(state.line_ending);
+    parent.push(element("tr", vec![], row.children, node.position()));
+    Ok(())
+}
+
+/// Compile a GFM table cell.
+fn table_cell<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    parent: &mut Parent,
+) -> Result<(), message::Message> {
+    let align = state.table_cell_align;
+    let head = state.table_head;
+    parent.line_ending_if_needed(state.line_ending);
+    let mut cell = Parent::new(false);
+    state.phrasing = true;
+    all(state, node, &mut cell)?;
+    state.phrasing = false;
+    parent.push(element(
+        if head { "th" } else { "td" },
+        table_cell_attributes(align),
+        cell.children,
+        node.position(),
+    ));
+    Ok(())
+}
+
+/// Get the attributes of a GFM table cell.
+fn table_cell_attributes(align: AlignKind) -> Vec<(String, String)> {
+    let value = match align {
+        AlignKind::Left => "left",
+        AlignKind::Right => "right",
+        AlignKind::Center => "center",
+        AlignKind::None => return vec![],
+    };
+    vec![("align".into(), value.into())]
+}
+
+/// Compile a GFM footnote definition.
+///
+/// The result is stored, to be used in the footnote section if the definition
+/// is called.
+fn footnote_definition<'a>(
+    state: &mut State<'a>,
+    node: &'a FootnoteDefinition,
+) -> Result<(), message::Message> {
+    let tight = state.tight;
+    let container = state.container;
+    let mut value = Parent::new(true);
+    state.tight = false;
+    state.container = true;
+    for child in &node.children {
+        one(state, child, &mut value)?;
+    }
+    state.tight = tight;
+    state.container = container;
+    state
+        .gfm_footnote_definitions
+        .push((normalize_identifier(&node.identifier), value.children));
+    Ok(())
+}
+
+/// Compile a GFM footnote call.
+///
+/// Turned into the text it was written as if there is no definition for it.
+fn footnote_reference(state: &mut State, node: &FootnoteReference, parent: &mut Parent) {
+    let id = normalize_identifier(&node.identifier);
+
+    if !state.gfm_footnote_identifiers.contains(&id) {
+        parent.push(hast::Node::Text(hast::Text {
+            value: format!("[^{}]", node.label.as_deref().unwrap_or(&node.identifier)),
+            position: node.position.clone(),
+        }));
+        return;
+    }
+
+    let safe_id = normalize(&id.to_lowercase());
+    let mut call_index = 0;
+
+    // See if this has been called before.
+    while call_index < state.gfm_footnote_definition_calls.len() {
+        if state.gfm_footnote_definition_calls[call_index].0 == id {
+            break;
+        }
+        call_index += 1;
+    }
+
+    // New.
+    if call_index == state.gfm_footnote_definition_calls.len() {
+        state.gfm_footnote_definition_calls.push((id, 0));
+    }
+
+    // Increment.
+    state.gfm_footnote_definition_calls[call_index].1 += 1;
+
+    let prefix = gfm_footnote_clobber_prefix(state);
+    let mut reference_id = format!("{}fnref-{}", prefix, safe_id);
+    if state.gfm_footnote_definition_calls[call_index].1 > 1 {
+        reference_id.push('-');
+        reference_id.push_str(
+            &state.gfm_footnote_definition_calls[call_index]
+                .1
+                .to_string(),
+        );
+    }
+
+    let mut call = Parent::new(false);
+    call.push_text(&(call_index + 1).to_string());
+    parent.push(element(
+        "sup",
+        vec![],
+        vec![element(
+            "a",
+            vec![
+                ("href".into(), format!("#{}fn-{}", prefix, safe_id)),
+                ("id".into(), reference_id),
+                ("data-footnote-ref".into(), String::new()),
+                ("aria-describedby".into(), "footnote-label".into()),
+            ],
+            call.children,
+            None,
+        )],
+        node.position.as_ref(),
+    ));
+}
+
+/// Compile the footnote section.
+fn footnote_section(state: &mut State, parent: &mut Parent) {
+    let options = state.options;
+    let mut label_attributes = vec![("id".into(), "footnote-label".into())];
+    if let Some(ref value) = options.gfm_footnote_label_attributes {
+        label_attributes.append(&mut parse_attributes(value));
+    } else {
+        label_attributes.push(("class".into(), "sr-only".into()));
+    }
+    let mut label = Parent::new(false);
+    label.push_text(options.gfm_footnote_label.as_deref().unwrap_or("Footnotes"));
+
+    let mut items = Parent::new(false);
+    let mut index = 0;
+    while index < state.gfm_footnote_definition_calls.len() {
+        items.push_text(state.line_ending);
+        footnote_item(state, index, &mut items);
+        index += 1;
+    }
+    items.push_text(state.line_ending);
+
+    let mut section = Parent::new(false);
+    section.push(element(
+        options
+            .gfm_footnote_label_tag_name
+            .as_deref()
+            .unwrap_or("h2"),
+        label_attributes,
+        label.children,
+        None,
+    ));
+    section.push_text(state.line_ending);
+    section.push(element("ol", vec![], items.children, None));
+    section.push_text(state.line_ending);
+
+    parent.line_ending_if_needed(state.line_ending);
+    parent.push(element(
+        "section",
+        vec![
+            ("data-footnotes".into(), String::new()),
+            ("class".into(), "footnotes".into()),
+        ],
+        section.children,
+        None,
+    ));
+    parent.push_text(state.line_ending);
+}
+
+/// Compile a footnote item from a call.
+fn footnote_item(state: &State, index: usize, parent: &mut Parent) {
+    let (id, count) = &state.gfm_footnote_definition_calls[index];
+    let safe_id = normalize(&id.to_lowercase());
+    let prefix = gfm_footnote_clobber_prefix(state);
+    let mut value = state
+        .gfm_footnote_definitions
+        .iter()
+        .find(|definition| &definition.0 == id)
+        .map_or_else(Vec::new, |definition| definition.1.clone());
+
+    // Create one or more backreferences.
+    let label = state
+        .options
+        .gfm_footnote_back_label
+        .as_deref()
+        .unwrap_or("Back to content");
+    let mut backreferences = Parent::new(false);
+    let mut reference_index = 0;
+    while reference_index < *count {
+        if reference_index != 0 {
+            backreferences.push_text(" ");
+        }
+        let mut href = format!("#{}fnref-{}", prefix, safe_id);
+        if reference_index != 0 {
+            href.push('-');
+            href.push_str(&(reference_index + 1).to_string());
+        }
+        let mut children = Parent::new(false);
+        children.push_text("↩");
+        if reference_index != 0 {
+            let mut number = Parent::new(false);
+            number.push_text(&(reference_index + 1).to_string());
+            children.push(element("sup", vec![], number.children, None));
+        }
+        backreferences.push(element(
+            "a",
+            vec![
+                ("href".into(), href),
+                ("data-footnote-backref".into(), String::new()),
+                ("aria-label".into(), label.into()),
+                ("class".into(), "data-footnote-backref".into()),
+            ],
+            children.children,
+            None,
+        ));
+        reference_index += 1;
+    }
+
+    // Find the last node that is not a line ending.
+    let mut end = value.len();
+    while end > 0
+        && matches!(
+            &value[end - 1],
+            hast::Node::Text(hast::Text { value, .. }) | hast::Node::Raw(hast::Raw { value, .. })
+                if value.trim_end_matches(['\n', '\r']).is_empty()
+        )
+    {
+        end -= 1;
+    }
+
+    let mut item = Parent::new(false);
+    item.push_text(state.line_ending);
+
+    // Add the backreferences to the last paragraph, if there is one.
+    match value.get_mut(end.wrapping_sub(1)) {
+        Some(hast::Node::Element(paragraph)) if paragraph.tag_name == "p" => {
+            paragraph.children.push(hast::Node::Text(hast::Text {
+                value: " ".into(),
+                position: None,
+            }));
+            paragraph.children.append(&mut backreferences.children);
+            item.children.append(&mut value);
+        }
+        _ => {
+            item.children.append(&mut value);
+            item.line_ending_if_needed(state.line_ending);
+            item.children.append(&mut backreferences.children);
+        }
+    }
+
+    item.line_ending_if_needed(state.line_ending);
+    parent.push(element(
+        "li",
+        vec![("id".into(), format!("{}fn-{}", prefix, safe_id))],
+        item.children,
+        None,
+    ));
+}
+
+/// Handle a node that cannot be turned into HTML: MDX.
+///
+/// Errors, unless `skip_unknown_nodes` is on.
+fn unknown(state: &State, node: &Node) -> Result<(), message::Message> {
+    if state.options.skip_unknown_nodes {
+        Ok(())
+    } else {
+        Err(message::Message {
+            place: node
+                .position()
+                .map(|position| Box::new(message::Place::Position(position.clone()))),
+            reason: format!("Cannot turn `{}` into HTML", node.name()),
+            rule_id: Box::new("unknown-node".into()),
+            source: Box::new("markdown-rs".into()),
+        })
+    }
+}
+
+/// Find the URL and title of the definition for an identifier.
+fn definition<'a>(state: &State<'a>, identifier: &str) -> Option<(&'a str, Option<&'a str>)> {
+    let id = normalize_identifier(identifier);
+    state
+        .definitions
+        .iter()
+        .find(|definition| normalize_identifier(&definition.identifier) == id)
+        .map(|definition| (definition.url.as_str(), definition.title.as_deref()))
+        .or_else(|| {
+            state
+                .option_definitions
+                .iter()
+                .find(|(label, _)| normalize_identifier(label) == id)
+                .map(|(_, (url, title))| (url.as_str(), title.as_deref()))
+        })
+}
+
+/// Normalize a URL, dropping dangerous protocols unless
+/// `allow_dangerous_protocol` is on.
+fn url(state: &State, value: &str, image: bool) -> String {
+    if state.options.allow_dangerous_protocol {
+        normalize(value)
+    } else {
+        normalize_with_protocols(
+            value,
+            if image {
+                &SAFE_PROTOCOL_SRC
+            } else {
+                &SAFE_PROTOCOL_HREF
+            },
+        )
+    }
+}
+
+/// Get the GFM footnote clobber prefix.
+fn gfm_footnote_clobber_prefix<'a>(state: &State<'a>) -> &'a str {
+    state
+        .options
+        .gfm_footnote_clobber_prefix
+        .as_deref()
+        .unwrap_or("user-content-")
+}
+
+/// Get the lines of a node as attributes, if `source_position` is on and the
+/// node has a position.
+fn source_position(state: &State, position: Option<&Position>) -> Vec<(String, String)> {
+    match position {
+        Some(position) if state.options.source_position => vec![
+            ("data-line".into(), position.start.line.to_string()),
+            ("data-end-line".into(), position.end.line.to_string()),
+        ],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,1542 @@
+//! Turn a syntax tree into a string of HTML.
+
+use crate::mdast::{
+    AlignKind, BlockQuote, Break, Code, Definition, Delete, Emphasis, FootnoteDefinition,
+    FootnoteReference, Heading, Html, Image, ImageReference, InlineCode, InlineMath, Link,
+    LinkReference, List, ListItem, Math, MdxFlowExpression, MdxJsxFlowElement, MdxJsxTextElement,
+    MdxTextExpression, MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell,
+    TableRow, Text, ThematicBreak, Toml, Yaml,
+};
+use crate::message;
+use crate::to_html::{
+    attribute, code_wrapper, heading_rank, join_class, math_wrapper, wrapper_open, WrapperElement,
+};
+use crate::unist::Position;
+use crate::util::{
+    constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
+    encode::{encode, encode_non_ascii},
+    gfm_tagfilter::gfm_tagfilter,
+    normalize_identifier::normalize_identifier,
+    sanitize_uri::{sanitize, sanitize_with_protocols},
+    slug::{self, Slugger, Slugify},
+    whitespace::collapse as collapse_whitespace,
+};
+use crate::{CompileOptions, Options};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Compilation state.
+#[allow(clippy::struct_excessive_bools)]
+struct State<'a> {
+    /// Configuration.
+    options: &'a CompileOptions,
+    /// Custom renderer.
+    renderer: Option<&'a dyn Renderer>,
+    /// Function to turn the text of a heading into a slug.
+    slugify: Option<&'a Slugify>,
+    /// Line ending to use.
+    line_ending: &'a str,
+    /// Definitions in the tree.
+    definitions: Vec<&'a Definition>,
+    /// Definitions passed in options, used after those in the tree.
+    option_definitions: &'a BTreeMap<String, (String, Option<String>)>,
+    /// Identifiers of the GFM footnote definitions in the tree.
+    gfm_footnote_identifiers: Vec<String>,
+    /// Compiled GFM footnote definitions.
+    gfm_footnote_definitions: Vec<(String, String)>,
+    /// GFM footnote calls, with how often they are called.
+    gfm_footnote_definition_calls: Vec<(String, usize)>,
+    /// Whether paragraphs are in a tight list item, and thus not wrapped.
+    tight: bool,
+    /// Whether we are in a container (block quote, list, footnote
+    /// definition).
+    container: bool,
+    /// Whether we are in phrasing content (paragraphs, headings, cells).
+    phrasing: bool,
+    /// Whether we are in a link.
+    link_inside: bool,
+    /// Whether the next paragraph starts with a GFM task list item check.
+    gfm_task_list_item_check: Option<bool>,
+    /// Alignment of the current GFM table.
+    table_align: &'a [AlignKind],
+    /// Whether we are in the head row of a GFM table.
+    table_head: bool,
+    /// Alignment of the current GFM table cell.
+    table_cell_align: AlignKind,
+    /// Slugs of headings seen so far.
+    slugger: Slugger,
+    /// Elements to wrap code (flow) in.
+    code_wrapper: Vec<WrapperElement>,
+    /// Elements to wrap math (flow) in.
+    math_wrapper: Vec<WrapperElement>,
+}
+
+/// Customize how nodes are turned into HTML by
+/// [`mdast_to_html_with_renderer()`][crate::mdast_to_html_with_renderer].
+///
+/// There is a method for each node type.
+/// They all compile the node the default way, so only the methods for nodes
+/// that should be handled differently have to be implemented.
+/// Use the [`RenderContext`][] to write HTML, to compile the children of a
+/// node, or to fall back to the default.
+///
+/// ## Errors
+///
+/// Errors returned by methods stop compilation, and are returned by
+/// `mdast_to_html_with_renderer()`.
+/// By default, MDX nodes error, unless `skip_unknown_nodes` is on.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{
+///     mdast::Code, mdast_to_html_with_renderer, message, to_mdast, Options, ParseOptions,
+///     RenderContext, Renderer,
+/// };
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// struct Highlight;
+///
+/// impl Renderer for Highlight {
+///     fn code(&self, node: &Code, context: &mut RenderContext) -> Result<(), message::Message> {
+///         context.line_ending_if_needed();
+///         context.push("<pre class=\"highlight\">");
+///         context.push_text(&node.value);
+///         context.push("</pre>");
+///         Ok(())
+///     }
+/// }
+///
+/// let tree = to_mdast("# a\n\n```\nb\n```", &ParseOptions::default())?;
+///
+/// assert_eq!(
+///     mdast_to_html_with_renderer(&tree, &Options::default(), &Highlight)?,
+///     "<h1>a</h1>\n<pre class=\"highlight\">b</pre>"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::missing_errors_doc)]
+pub trait Renderer {
+    /// Compile a root.
+    fn root(&self, _node: &Root, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a block quote.
+    fn block_quote(
+        &self,
+        _node: &BlockQuote,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a break.
+    fn break_node(
+        &self,
+        _node: &Break,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile code (flow).
+    fn code(&self, _node: &Code, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a definition, which by default results in nothing.
+    fn definition(
+        &self,
+        _node: &Definition,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile GFM strikethrough.
+    fn delete(&self, _node: &Delete, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile emphasis.
+    fn emphasis(
+        &self,
+        _node: &Emphasis,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a GFM footnote definition.
+    ///
+    /// Whatever is written here is used in the footnote section, if the
+    /// definition is called.
+    fn footnote_definition(
+        &self,
+        _node: &FootnoteDefinition,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a GFM footnote call.
+    fn footnote_reference(
+        &self,
+        _node: &FootnoteReference,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a heading.
+    fn heading(
+        &self,
+        _node: &Heading,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile HTML.
+    fn html(&self, _node: &Html, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an image.
+    fn image(&self, _node: &Image, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an image reference.
+    fn image_reference(
+        &self,
+        _node: &ImageReference,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile code (text).
+    fn inline_code(
+        &self,
+        _node: &InlineCode,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile math (text).
+    fn inline_math(
+        &self,
+        _node: &InlineMath,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a link.
+    fn link(&self, _node: &Link, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a link reference.
+    fn link_reference(
+        &self,
+        _node: &LinkReference,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a list.
+    fn list(&self, _node: &List, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a list item.
+    fn list_item(
+        &self,
+        _node: &ListItem,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile math (flow).
+    fn math(&self, _node: &Math, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an MDX expression (flow), which by default is an error.
+    fn mdx_flow_expression(
+        &self,
+        _node: &MdxFlowExpression,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an MDX JSX element (flow), which by default is an error.
+    fn mdx_jsx_flow_element(
+        &self,
+        _node: &MdxJsxFlowElement,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an MDX JSX element (text), which by default is an error.
+    fn mdx_jsx_text_element(
+        &self,
+        _node: &MdxJsxTextElement,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile an MDX expression (text), which by default is an error.
+    fn mdx_text_expression(
+        &self,
+        _node: &MdxTextExpression,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile MDX ESM, which by default is an error.
+    fn mdxjs_esm(
+        &self,
+        _node: &MdxjsEsm,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a paragraph.
+    fn paragraph(
+        &self,
+        _node: &Paragraph,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile strong.
+    fn strong(&self, _node: &Strong, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a GFM table.
+    fn table(&self, _node: &Table, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a GFM table row.
+    fn table_row(
+        &self,
+        _node: &TableRow,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a GFM table cell.
+    fn table_cell(
+        &self,
+        _node: &TableCell,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile text.
+    fn text(&self, _node: &Text, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile a thematic break.
+    fn thematic_break(
+        &self,
+        _node: &ThematicBreak,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile TOML frontmatter, which by default results in nothing.
+    fn toml(&self, _node: &Toml, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+
+    /// Compile YAML frontmatter, which by default results in nothing.
+    fn yaml(&self, _node: &Yaml, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.render_default()
+    }
+}
+
+/// Context passed to the methods of a [`Renderer`][].
+pub struct RenderContext<'a, 'b> {
+    /// Compilation state.
+    state: &'b mut State<'a>,
+    /// Node being compiled.
+    node: &'a Node,
+    /// HTML compiled so far.
+    result: &'b mut String,
+}
+
+impl RenderContext<'_, '_> {
+    /// Compile the node the default way.
+    ///
+    /// Its children are still compiled with the renderer.
+    ///
+    /// ## Errors
+    ///
+    /// Errors on MDX nodes, unless `skip_unknown_nodes` is on, and passes
+    /// through errors from the renderer for children.
+    pub fn render_default(&mut self) -> Result<(), message::Message> {
+        one_default(self.state, self.node, self.result)
+    }
+
+    /// Compile the children of the node.
+    ///
+    /// ## Errors
+    ///
+    /// Errors on MDX nodes, unless `skip_unknown_nodes` is on, and passes
+    /// through errors from the renderer.
+    pub fn render_children(&mut self) -> Result<(), message::Message> {
+        all(self.state, self.node, self.result)
+    }
+
+    /// Add HTML, as is.
+    pub fn push(&mut self, value: &str) {
+        self.result.push_str(value);
+    }
+
+    /// Add text, encoded.
+    pub fn push_text(&mut self, value: &str) {
+        self.result.push_str(&encode(value, true));
+    }
+
+    /// Add a line ending if needed (as in, there’s no eol/eof already).
+    pub fn line_ending_if_needed(&mut self) {
+        line_ending_if_needed(self.state, self.result);
+    }
+
+    /// Sanitize a URL, so it can be used in `href` (or `src` if `image`).
+    ///
+    /// Dangerous protocols are dropped unless `allow_dangerous_protocol` is on.
+    pub fn sanitize_url(&self, value: &str, image: bool) -> String {
+        url(self.state, value, image)
+    }
+
+    /// Configuration.
+    pub fn options(&self) -> &CompileOptions {
+        self.state.options
+    }
+}
+
+/// Turn a syntax tree into a string of HTML, with a renderer if one is given.
+pub fn compile<'a>(
+    tree: &'a Node,
+    options: &'a Options,
+    renderer: Option<&'a dyn Renderer>,
+) -> Result<String, message::Message> {
+    let mut definitions = vec![];
+    let mut gfm_footnote_identifiers = vec![];
+    let mut line_ending = None;
+    collect(
+        tree,
+        &mut definitions,
+        &mut gfm_footnote_identifiers,
+        &mut line_ending,
+    );
+
+    let mut state = State {
+        options: &options.compile,
+        renderer,
+        slugify: options.slugify.as_deref(),
+        line_ending: line_ending.unwrap_or_else(|| options.compile.default_line_ending.as_str()),
+        definitions,
+        option_definitions: &options.parse.definitions,
+        gfm_footnote_identifiers,
+        gfm_footnote_definitions: vec![],
+        gfm_footnote_definition_calls: vec![],
+        tight: false,
+        container: false,
+        phrasing: false,
+        link_inside: false,
+        gfm_task_list_item_check: None,
+        table_align: &[],
+        table_head: false,
+        table_cell_align: AlignKind::None,
+        slugger: Slugger::default(),
+        code_wrapper: code_wrapper(&options.compile),
+        math_wrapper: math_wrapper(),
+    };
+    let mut result = String::new();
+
+    one(&mut state, tree, &mut result)?;
+
+    if !state.gfm_footnote_definition_calls.is_empty() {
+        footnote_section(&mut state, &mut result);
+    }
+
+    match options.compile.trailing_newline {
+        Some(true) => line_ending_if_needed(&state, &mut result),
+        Some(false) => result.truncate(result.trim_end_matches(['\n', '\r']).len()),
+        None => {}
+    }
+
+    if options.compile.encode_non_ascii {
+        result = encode_non_ascii(&result);
+    }
+
+    Ok(result)
+}
+
+/// Find definitions, footnote definitions, and the first line ending used in
+/// the tree.
+pub(crate) fn collect<'a>(
+    node: &'a Node,
+    definitions: &mut Vec<&'a Definition>,
+    gfm_footnote_identifiers: &mut Vec<String>,
+    line_ending: &mut Option<&'static str>,
+) {
+    match node {
+        Node::Definition(definition) => definitions.push(definition),
+        Node::FootnoteDefinition(definition) => {
+            gfm_footnote_identifiers.push(normalize_identifier(&definition.identifier));
+        }
+        Node::Code(Code { value, .. })
+        | Node::Html(Html { value, .. })
+        | Node::InlineCode(InlineCode { value, .. })
+        | Node::InlineMath(InlineMath { value, .. })
+        | Node::Math(Math { value, .. })
+        | Node::Text(Text { value, .. })
+            if line_ending.is_none() =>
+        {
+            if let Some(index) = value.find(['\n', '\r']) {
+                *line_ending = Some(if value[index..].starts_with("\r\n") {
+                    "\r\n"
+                } else if value[index..].starts_with('\r') {
+                    "\r"
+                } else {
+                    "\n"
+                });
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            collect(child, definitions, gfm_footnote_identifiers, line_ending);
+        }
+    }
+}
+
+/// Compile a node, with the renderer if there is one.
+fn one<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    if let Some(renderer) = state.renderer {
+        render(
+            renderer,
+            &mut RenderContext {
+                state,
+                node,
+                result,
+            },
+        )
+    } else {
+        one_default(state, node, result)
+    }
+}
+
+/// Compile a node with the method of the renderer for its type.
+fn render(renderer: &dyn Renderer, context: &mut RenderContext) -> Result<(), message::Message> {
+    let node = context.node;
+    match node {
+        Node::Root(node) => renderer.root(node, context),
+        Node::BlockQuote(node) => renderer.block_quote(node, context),
+        Node::Break(node) => renderer.break_node(node, context),
+        Node::Code(node) => renderer.code(node, context),
+        Node::Definition(node) => renderer.definition(node, context),
+        Node::Delete(node) => renderer.delete(node, context),
+        Node::Emphasis(node) => renderer.emphasis(node, context),
+        Node::FootnoteDefinition(node) => renderer.footnote_definition(node, context),
+        Node::FootnoteReference(node) => renderer.footnote_reference(node, context),
+        Node::Heading(node) => renderer.heading(node, context),
+        Node::Html(node) => renderer.html(node, context),
+        Node::Image(node) => renderer.image(node, context),
+        Node::ImageReference(node) => renderer.image_reference(node, context),
+        Node::InlineCode(node) => renderer.inline_code(node, context),
+        Node::InlineMath(node) => renderer.inline_math(node, context),
+        Node::Link(node) => renderer.link(node, context),
+        Node::LinkReference(node) => renderer.link_reference(node, context),
+        Node::List(node) => renderer.list(node, context),
+        Node::ListItem(node) => renderer.list_item(node, context),
+        Node::Math(node) => renderer.math(node, context),
+        Node::MdxFlowExpression(node) => renderer.mdx_flow_expression(node, context),
+        Node::MdxJsxFlowElement(node) => renderer.mdx_jsx_flow_element(node, context),
+        Node::MdxJsxTextElement(node) => renderer.mdx_jsx_text_element(node, context),
+        Node::MdxTextExpression(node) => renderer.mdx_text_expression(node, context),
+        Node::MdxjsEsm(node) => renderer.mdxjs_esm(node, context),
+        Node::Paragraph(node) => renderer.paragraph(node, context),
+        Node::Strong(node) => renderer.strong(node, context),
+        Node::Table(node) => renderer.table(node, context),
+        Node::TableCell(node) => renderer.table_cell(node, context),
+        Node::TableRow(node) => renderer.table_row(node, context),
+        Node::Text(node) => renderer.text(node, context),
+        Node::ThematicBreak(node) => renderer.thematic_break(node, context),
+        Node::Toml(node) => renderer.toml(node, context),
+        Node::Yaml(node) => renderer.yaml(node, context),
+    }
+}
+
+/// Compile a node the default way.
+fn one_default<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    match node {
+        Node::Root(_) => all(state, node, result)?,
+        Node::BlockQuote(_) => {
+            let tight = state.tight;
+            let container = state.container;
+            state.tight = false;
+            state.container = true;
+            line_ending_if_needed(state, result);
+            result.push_str("<blockquote");
+            push_source_position(state, node.position(), result);
+            result.push('>');
+            all(state, node, result)?;
+            line_ending_if_needed(state, result);
+            result.push_str("</blockquote>");
+            state.tight = tight;
+            state.container = container;
+        }
+        Node::Break(_) => {
+            result.push_str("<br />");
+            result.push_str(if state.options.collapse_whitespace {
+                " "
+            } else {
+                state.line_ending
+            });
+        }
+        Node::Code(node) => code(state, node, result),
+        Node::Definition(_) | Node::Toml(_) | Node::Yaml(_) => {}
+        Node::Delete(_) => wrap(state, node, "del", result)?,
+        Node::Emphasis(_) => wrap(state, node, "em", result)?,
+        Node::FootnoteDefinition(node) => footnote_definition(state, node)?,
+        Node::FootnoteReference(node) => footnote_reference(state, node, result),
+        Node::Heading(node) => heading(state, node, result)?,
+        Node::Html(node) => html(state, node, result),
+        Node::Image(node) => image(state, node, result),
+        Node::ImageReference(node) => image_reference(state, node, result),
+        Node::InlineCode(InlineCode { value, .. }) => {
+            raw_text(state, value, None, result);
+        }
+        Node::InlineMath(InlineMath { value, .. }) => {
+            raw_text(state, value, Some("language-math math-inline"), result);
+        }
+        Node::Link(node) => link(state, node, result)?,
+        Node::LinkReference(node) => link_reference(state, node, result)?,
+        Node::List(node) => list(state, node, result)?,
+        Node::ListItem(node) => list_item(state, node, result)?,
+        Node::Math(Math {
+            value, position, ..
+        }) => {
+            raw_flow(
+                state,
+                value,
+                &state.math_wrapper,
+                Some("language-math math-display"),
+                position.as_ref(),
+                result,
+            );
+        }
+        Node::Paragraph(_) => paragraph(state, node, result)?,
+        Node::Strong(_) => wrap(state, node, "strong", result)?,
+        Node::Table(node) => table(state, node, result)?,
+        Node::TableRow(_) => table_row(state, node, result)?,
+        Node::TableCell(_) => table_cell(state, node, result)?,
+        Node::Text(Text { value, .. }) => result.push_str(&encode(&text(state, value), true)),
+        Node::ThematicBreak(_) => {
+            line_ending_if_needed(state, result);
+
+            if let Some(html) = &state.options.thematic_break_html {
+                result.push_str(html);
+            } else {
+                result.push_str("<hr");
+                push_source_position(state, node.position(), result);
+                result.push_str(" />");
+            }
+        }
+        Node::MdxFlowExpression(_) | Node::MdxjsEsm(_) | Node::MdxTextExpression(_) => {
+            unknown(state, node)?;
+        }
+        Node::MdxJsxFlowElement(_) | Node::MdxJsxTextElement(_) => {
+            unknown(state, node)?;
+            all(state, node, result)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Compile the children of a node.
+fn all<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    if let Some(children) = node.children() {
+        for child in children {
+            one(state, child, result)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Compile a node with children in an element.
+fn wrap<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    tag_name: &str,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    result.push('<');
+    result.push_str(tag_name);
+    result.push('>');
+    all(state, node, result)?;
+    result.push_str("</");
+    result.push_str(tag_name);
+    result.push('>');
+    Ok(())
+}
+
+/// Compile a paragraph.
+///
+/// In tight list items, paragraphs are not wrapped in `<p>`, and neither are
+/// paragraphs outside containers without `wrap_paragraphs`.
+fn paragraph<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let wrap = !state.tight && (state.options.wrap_paragraphs || state.container);
+
+    if !state.tight {
+        line_ending_if_needed(state, result);
+    }
+
+    if wrap {
+        result.push_str("<p");
+        push_source_position(state, node.position(), result);
+        result.push('>');
+    }
+
+    if let Some(checked) = state.gfm_task_list_item_check.take() {
+        gfm_task_list_item_check(state, checked, result);
+    }
+
+    state.phrasing = true;
+    all(state, node, result)?;
+    state.phrasing = false;
+
+    if wrap {
+        result.push_str("</p>");
+    }
+
+    Ok(())
+}
+
+/// Compile a heading.
+fn heading<'a>(
+    state: &mut State<'a>,
+    node: &'a Heading,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let rank = heading_rank(usize::from(node.depth), state.options.heading_offset).to_string();
+    line_ending_if_needed(state, result);
+    result.push_str("<h");
+    result.push_str(&rank);
+    push_source_position(state, node.position.as_ref(), result);
+    push_heading_id(state, node, result);
+    result.push('>');
+    state.phrasing = true;
+    for child in &node.children {
+        one(state, child, result)?;
+    }
+    state.phrasing = false;
+    result.push_str("</h");
+    result.push_str(&rank);
+    result.push('>');
+    Ok(())
+}
+
+/// Compile code (flow).
+fn code(state: &State, node: &Code, result: &mut String) {
+    let class = node
+        .lang
+        .as_ref()
+        .map(|lang| format!("language-{}", encode(lang, true)));
+    raw_flow(
+        state,
+        &node.value,
+        &state.code_wrapper,
+        class.as_deref(),
+        node.position.as_ref(),
+        result,
+    );
+}
+
+/// Compile code (flow) or math (flow), in the elements of `wrapper`.
+fn raw_flow(
+    state: &State,
+    value: &str,
+    wrapper: &[WrapperElement],
+    class: Option<&str>,
+    position: Option<&Position>,
+    result: &mut String,
+) {
+    line_ending_if_needed(state, result);
+    for (index, element) in wrapper.iter().enumerate() {
+        result.push_str(&wrapper_open(element, &state.options.quote));
+        if index == 0 {
+            push_source_position(state, position, result);
+        }
+        let own = element.class.as_deref().map(|class| encode(class, true));
+        let class = if index == wrapper.len() - 1 {
+            join_class(own.as_deref(), class)
+        } else {
+            own
+        };
+        if let Some(class) = class {
+            result.push_str(&attribute(&state.options.quote, "class", &class));
+        }
+        result.push('>');
+    }
+    if !value.is_empty() {
+        result.push_str(&encode(value, true));
+        result.push_str(state.line_ending);
+    }
+    for element in wrapper.iter().rev() {
+        result.push_str("</");
+        result.push_str(&element.tag_name);
+        result.push('>');
+    }
+}
+
+/// Compile code (text) or math (text).
+///
+/// Line endings are turned into spaces, as browsers would show them anyway.
+fn raw_text(state: &State, value: &str, class: Option<&str>, result: &mut String) {
+    result.push_str("<code");
+    if let Some(class) = class {
+        push_attribute(state, "class", class, result);
+    }
+    result.push('>');
+    let value = value.replace("\r\n", " ").replace(['\r', '\n'], " ");
+    result.push_str(&encode(&value, true));
+    result.push_str("</code>");
+}
+
+/// Compile HTML.
+///
+/// Passed through with `allow_dangerous_html`, encoded otherwise.
+fn html(state: &State, node: &Html, result: &mut String) {
+    if state.options.strip_comments
+        && node
+            .value
+            .trim_start_matches(['\t', ' '])
+            .starts_with("<!--")
+    {
+        return;
+    }
+
+    if !state.phrasing {
+        line_ending_if_needed(state, result);
+    }
+
+    if !state.options.allow_dangerous_html {
+        result.push_str(&encode(&node.value, true));
+    } else if state.options.gfm_tagfilter {
+        result.push_str(&gfm_tagfilter(&node.value));
+    } else {
+        result.push_str(&node.value);
+    }
+}
+
+/// Compile a link.
+fn link<'a>(
+    state: &mut State<'a>,
+    node: &'a Link,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    anchor(
+        state,
+        &node.url,
+        node.title.as_deref(),
+        &node.children,
+        result,
+    )
+}
+
+/// Compile a link reference.
+///
+/// Turned into a link if there is a definition for it, and into the text it
+/// was written as otherwise.
+fn link_reference<'a>(
+    state: &mut State<'a>,
+    node: &'a LinkReference,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    if let Some((url, title)) = definition(state, &node.identifier) {
+        anchor(state, url, title, &node.children, result)?;
+    } else {
+        result.push('[');
+        for child in &node.children {
+            one(state, child, result)?;
+        }
+        result.push(']');
+        reference_suffix(
+            node.reference_kind,
+            &node.identifier,
+            node.label.as_deref(),
+            result,
+        );
+    }
+
+    Ok(())
+}
+
+/// Compile an `a` element.
+///
+/// Links cannot contain links, so in links, only the children are compiled
+/// (this happens with GFM autolink literals in links).
+fn anchor<'a>(
+    state: &mut State<'a>,
+    destination: &str,
+    title: Option<&str>,
+    children: &'a [Node],
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let link_inside = state.link_inside;
+
+    if !link_inside {
+        result.push_str("<a");
+        push_attribute(state, "href", &url(state, destination, false), result);
+        if let Some(title) = title {
+            push_attribute(state, "title", &encode(title, true), result);
+        }
+        result.push('>');
+    }
+
+    state.link_inside = true;
+    for child in children {
+        one(state, child, result)?;
+    }
+    state.link_inside = link_inside;
+
+    if !link_inside {
+        result.push_str("</a>");
+    }
+
+    Ok(())
+}
+
+/// Get the value of text, collapsed with `collapse_whitespace`.
+fn text<'b>(state: &State, value: &'b str) -> Cow<'b, str> {
+    if state.options.collapse_whitespace {
+        collapse_whitespace(value)
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Compile an image.
+fn image(state: &State, node: &Image, result: &mut String) {
+    image_element(state, &node.url, &node.alt, node.title.as_deref(), result);
+}
+
+/// Compile an image reference.
+///
+/// Turned into an image if there is a definition for it, and into the text
+/// it was written as otherwise.
+fn image_reference(state: &State, node: &ImageReference, result: &mut String) {
+    if let Some((url, title)) = definition(state, &node.identifier) {
+        image_element(state, url, &node.alt, title, result);
+    } else {
+        result.push_str("![");
+        result.push_str(&encode(&text(state, &node.alt), true));
+        result.push(']');
+        reference_suffix(
+            node.reference_kind,
+            &node.identifier,
+            node.label.as_deref(),
+            result,
+        );
+    }
+}
+
+/// Compile an `img` element.
+fn image_element(
+    state: &State,
+    destination: &str,
+    alt: &str,
+    title: Option<&str>,
+    result: &mut String,
+) {
+    result.push_str("<img");
+    push_attribute(state, "src", &url(state, destination, true), result);
+    push_attribute(state, "alt", &encode(&text(state, alt), true), result);
+    if let Some(title) = title {
+        push_attribute(state, "title", &encode(title, true), result);
+    }
+    result.push_str(" />");
+}
+
+/// Compile the end of an unresolved reference (`[b]` in `[a][b]`, `[]` in
+/// `[a][]`, or nothing in `[a]`).
+fn reference_suffix(
+    kind: ReferenceKind,
+    identifier: &str,
+    label: Option<&str>,
+    result: &mut String,
+) {
+    match kind {
+        ReferenceKind::Full => {
+            result.push('[');
+            result.push_str(&encode(label.unwrap_or(identifier), true));
+            result.push(']');
+        }
+        ReferenceKind::Collapsed => result.push_str("[]"),
+        ReferenceKind::Shortcut => {}
+    }
+}
+
+/// Compile a list.
+fn list<'a>(
+    state: &mut State<'a>,
+    node: &'a List,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let tight = state.tight;
+    let container = state.container;
+    let tag_name = if node.ordered { "ol" } else { "ul" };
+    state.container = true;
+    state.tight = !node.spread
+        && !node
+            .children
+            .iter()
+            .any(|child| matches!(child, Node::ListItem(item) if item.spread));
+
+    line_ending_if_needed(state, result);
+    result.push('<');
+    result.push_str(tag_name);
+    push_source_position(state, node.position.as_ref(), result);
+    if node.ordered && !state.options.renumber_ordered_lists {
+        if let Some(start) = node.start {
+            if start != 1 {
+                push_attribute(state, "start", &start.to_string(), result);
+            }
+        }
+    }
+    result.push('>');
+    for child in &node.children {
+        one(state, child, result)?;
+    }
+    result.push_str(state.line_ending);
+    result.push_str("</");
+    result.push_str(tag_name);
+    result.push('>');
+
+    state.tight = tight;
+    state.container = container;
+    Ok(())
+}
+
+/// Compile a list item.
+fn list_item<'a>(
+    state: &mut State<'a>,
+    node: &'a ListItem,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let children = node
+        .children
+        .iter()
+        .filter(|child| !matches!(child, Node::Definition(_) | Node::FootnoteDefinition(_)))
+        .collect::<Vec<_>>();
+
+    line_ending_if_needed(state, result);
+    result.push_str("<li");
+    push_source_position(state, node.position.as_ref(), result);
+    result.push('>');
+
+    if let Some(checked) = node.checked {
+        if matches!(children.first(), Some(Node::Paragraph(_))) {
+            state.gfm_task_list_item_check = Some(checked);
+        } else {
+            gfm_task_list_item_check(state, checked, result);
+        }
+    }
+
+    let mut seen = false;
+    for child in &node.children {
+        // Tight paragraphs are not wrapped, so they need a line ending when
+        // they follow something.
+        if seen && state.tight && matches!(child, Node::Paragraph(_)) {
+            line_ending_if_needed(state, result);
+        }
+        seen = seen || !matches!(child, Node::Definition(_) | Node::FootnoteDefinition(_));
+        one(state, child, result)?;
+    }
+
+    let tight_paragraph = state.tight && matches!(children.last(), Some(Node::Paragraph(_)));
+
+    if !tight_paragraph && !children.is_empty() {
+        line_ending_if_needed(state, result);
+    }
+
+    result.push_str("</li>");
+    Ok(())
+}
+
+/// Compile a GFM task list item check.
+fn gfm_task_list_item_check(state: &State, checked: bool, result: &mut String) {
+    result.push_str("<input");
+    push_attribute(state, "type", "checkbox", result);
+    if !state.options.gfm_task_list_item_checkable {
+        push_attribute(state, "disabled", "", result);
+    }
+    if checked {
+        push_attribute(state, "checked", "", result);
+    }
+    result.push_str(" /> ");
+}
+
+/// Compile a GFM table.
+fn table<'a>(
+    state: &mut State<'a>,
+    node: &'a Table,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let align = state.table_align;
+    let head = state.table_head;
+    state.table_align = &node.align;
+    line_ending_if_needed(state, result);
+    result.push_str("<table");
+    push_source_position(state, node.position.as_ref(), result);
+    result.push('>');
+
+    if let Some(row) = node.children.first() {
+        line_ending_if_needed(state, result);
+        result.push_str("<thead>");
+        state.table_head = true;
+        one(state, row, result)?;
+        state.table_head = false;
+        line_ending_if_needed(state, result);
+        result.push_str("</thead>");
+    }
+
+    if node.children.len() > 1 {
+        line_ending_if_needed(state, result);
+        result.push_str("<tbody>");
+        for row in &node.children[1..] {
+            one(state, row, result)?;
+        }
+        line_ending_if_needed(state, result);
+        result.push_str("</tbody>");
+    }
+
+    line_ending_if_needed(state, result);
+    result.push_str("</table>");
+    state.table_align = align;
+    state.table_head = head;
+    Ok(())
+}
+
+/// Compile a GFM table row.
+///
+/// Cells beyond the alignment of the table are dropped, missing cells are
+/// added.
+/// Without alignment (outside a table), all cells are kept.
+fn table_row<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let align = state.table_align;
+    let head = state.table_head;
+    let cells: &'a [Node] = node.children().map_or(&[], |children| children);
+    let size = if align.is_empty() {
+        cells.len()
+    } else {
+        align.len()
+    };
+
+    line_ending_if_needed(state, result);
+    result.push_str("<tr>");
+
+    let mut index = 0;
+    while index < size {
+        let align = align.get(index).copied().unwrap_or(AlignKind::None);
+
+        if let Some(cell) = cells.get(index) {
+            state.table_cell_align = align;
+            one(state, cell, result)?;
+            state.table_cell_align = AlignKind::None;
+        } else {
+            line_ending_if_needed(state, result);
+            table_cell_open(state, align, head, result);
+            table_cell_close(head, result);
+        }
+
+        index += 1;
+    }
+
+    line_ending_if_needed(state, result);
+    result.push_str("</tr>");
+    Ok(())
+}
+
+/// Compile a GFM table cell.
+fn table_cell<'a>(
+    state: &mut State<'a>,
+    node: &'a Node,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let align = state.table_cell_align;
+    let head = state.table_head;
+    line_ending_if_needed(state, result);
+    table_cell_open(state, align, head, result);
+    state.phrasing = true;
+    all(state, node, result)?;
+    state.phrasing = false;
+    table_cell_close(head, result);
+    Ok(())
+}
+
+/// Compile the opening tag of a GFM table cell.
+fn table_cell_open(state: &State, align: AlignKind, head: bool, result: &mut String) {
+    result.push_str(if head { "<th" } else { "<td" });
+    match align {
+        AlignKind::Left => push_attribute(state, "align", "left", result),
+        AlignKind::Right => push_attribute(state, "align", "right", result),
+        AlignKind::Center => push_attribute(state, "align", "center", result),
+        AlignKind::None => {}
+    }
+    result.push('>');
+}
+
+/// Compile the closing tag of a GFM table cell.
+fn table_cell_close(head: bool, result: &mut String) {
+    result.push_str(if head { "</th>" } else { "</td>" });
+}
+
+/// Compile a GFM footnote definition.
+///
+/// The result is stored, to be used in the footnote section if the definition
+/// is called.
+fn footnote_definition<'a>(
+    state: &mut State<'a>,
+    node: &'a FootnoteDefinition,
+) -> Result<(), message::Message> {
+    let tight = state.tight;
+    let container = state.container;
+    let mut value = String::new();
+    state.tight = false;
+    state.container = true;
+    for child in &node.children {
+        one(state, child, &mut value)?;
+    }
+    state.tight = tight;
+    state.container = container;
+    state
+        .gfm_footnote_definitions
+        .push((normalize_identifier(&node.identifier), value));
+    Ok(())
+}
+
+/// Compile a GFM footnote call.
+///
+/// Turned into the text it was written as if there is no definition for it.
+fn footnote_reference(state: &mut State, node: &FootnoteReference, result: &mut String) {
+    let id = normalize_identifier(&node.identifier);
+
+    if !state.gfm_footnote_identifiers.contains(&id) {
+        result.push_str("[^");
+        result.push_str(&encode(
+            node.label.as_deref().unwrap_or(&node.identifier),
+            true,
+        ));
+        result.push(']');
+        return;
+    }
+
+    let safe_id = sanitize(&id.to_lowercase());
+    let mut call_index = 0;
+
+    // See if this has been called before.
+    while call_index < state.gfm_footnote_definition_calls.len() {
+        if state.gfm_footnote_definition_calls[call_index].0 == id {
+            break;
+        }
+        call_index += 1;
+    }
+
+    // New.
+    if call_index == state.gfm_footnote_definition_calls.len() {
+        state.gfm_footnote_definition_calls.push((id, 0));
+    }
+
+    // Increment.
+    state.gfm_footnote_definition_calls[call_index].1 += 1;
+
+    let prefix = gfm_footnote_clobber_prefix(state);
+    let mut reference_id = format!("{}fnref-{}", prefix, safe_id);
+    if state.gfm_footnote_definition_calls[call_index].1 > 1 {
+        reference_id.push('-');
+        reference_id.push_str(
+            &state.gfm_footnote_definition_calls[call_index]
+                .1
+                .to_string(),
+        );
+    }
+
+    result.push_str("<sup><a");
+    push_attribute(state, "href", &format!("#{}fn-{}", prefix, safe_id), result);
+    push_attribute(state, "id", &reference_id, result);
+    push_attribute(state, "data-footnote-ref", "", result);
+    push_attribute(state, "aria-describedby", "footnote-label", result);
+    result.push('>');
+    result.push_str(&(call_index + 1).to_string());
+    result.push_str("</a></sup>");
+}
+
+/// Compile the footnote section.
+fn footnote_section(state: &mut State, result: &mut String) {
+    let options = state.options;
+    let tag_name = options
+        .gfm_footnote_label_tag_name
+        .as_ref()
+        .map_or_else(|| "h2".into(), |value| encode(value, true));
+
+    line_ending_if_needed(state, result);
+    result.push_str("<section");
+    push_attribute(state, "data-footnotes", "", result);
+    push_attribute(state, "class", "footnotes", result);
+    result.push_str("><");
+    result.push_str(&tag_name);
+    push_attribute(state, "id", "footnote-label", result);
+    if let Some(ref value) = options.gfm_footnote_label_attributes {
+        result.push(' ');
+        result.push_str(value);
+    } else {
+        push_attribute(state, "class", "sr-only", result);
+    }
+    result.push('>');
+    if let Some(ref value) = options.gfm_footnote_label {
+        result.push_str(&encode(value, true));
+    } else {
+        result.push_str("Footnotes");
+    }
+    result.push_str("</");
+    result.push_str(&tag_name);
+    result.push('>');
+    result.push_str(state.line_ending);
+    result.push_str("<ol>");
+
+    let mut index = 0;
+    while index < state.gfm_footnote_definition_calls.len() {
+        footnote_item(state, index, result);
+        index += 1;
+    }
+
+    result.push_str(state.line_ending);
+    result.push_str("</ol>");
+    result.push_str(state.line_ending);
+    result.push_str("</section>");
+    result.push_str(state.line_ending);
+}
+
+/// Compile a footnote item from a call.
+fn footnote_item(state: &State, index: usize, result: &mut String) {
+    let (id, count) = &state.gfm_footnote_definition_calls[index];
+    let safe_id = sanitize(&id.to_lowercase());
+    let prefix = gfm_footnote_clobber_prefix(state);
+    let value = state
+        .gfm_footnote_definitions
+        .iter()
+        .find(|definition| &definition.0 == id)
+        .map_or("", |definition| definition.1.as_str());
+
+    result.push_str(state.line_ending);
+    result.push_str("<li");
+    push_attribute(state, "id", &format!("{}fn-{}", prefix, safe_id), result);
+    result.push('>');
+    result.push_str(state.line_ending);
+
+    // Create one or more backreferences.
+    let label = if let Some(ref value) = state.options.gfm_footnote_back_label {
+        encode(value, true)
+    } else {
+        "Back to content".into()
+    };
+    let mut backreferences = String::new();
+    let mut reference_index = 0;
+    while reference_index < *count {
+        if reference_index != 0 {
+            backreferences.push(' ');
+        }
+        let mut href = format!("#{}fnref-{}", prefix, safe_id);
+        if reference_index != 0 {
+            href.push('-');
+            href.push_str(&(reference_index + 1).to_string());
+        }
+        backreferences.push_str("<a");
+        push_attribute(state, "href", &href, &mut backreferences);
+        push_attribute(state, "data-footnote-backref", "", &mut backreferences);
+        push_attribute(state, "aria-label", &label, &mut backreferences);
+        push_attribute(state, "class", "data-footnote-backref", &mut backreferences);
+        backreferences.push_str(">↩");
+        if reference_index != 0 {
+            backreferences.push_str("<sup>");
+            backreferences.push_str(&(reference_index + 1).to_string());
+            backreferences.push_str("</sup>");
+        }
+        backreferences.push_str("</a>");
+        reference_index += 1;
+    }
+
+    // Add the backreferences to the last paragraph, if there is one.
+    let end = value.trim_end_matches(['\n', '\r']).len();
+    if value[..end].ends_with("</p>") {
+        result.push_str(&value[..end - 4]);
+        result.push(' ');
+        result.push_str(&backreferences);
+        result.push_str(&value[end - 4..]);
+    } else {
+        result.push_str(value);
+        line_ending_if_needed(state, result);
+        result.push_str(&backreferences);
+    }
+
+    line_ending_if_needed(state, result);
+    result.push_str("</li>");
+}
+
+/// Handle a node that cannot be turned into HTML: MDX.
+///
+/// Errors, unless `skip_unknown_nodes` is on.
+fn unknown(state: &State, node: &Node) -> Result<(), message::Message> {
+    if state.options.skip_unknown_nodes {
+        Ok(())
+    } else {
+        Err(message::Message {
+            place: node
+                .position()
+                .map(|position| Box::new(message::Place::Position(position.clone()))),
+            reason: format!("Cannot turn `{}` into HTML", node.name()),
+            rule_id: Box::new("unknown-node".into()),
+            source: Box::new("markdown-rs".into()),
+        })
+    }
+}
+
+/// Find the URL and title of the definition for an identifier.
+fn definition<'a>(state: &State<'a>, identifier: &str) -> Option<(&'a str, Option<&'a str>)> {
+    let id = normalize_identifier(identifier);
+    state
+        .definitions
+        .iter()
+        .find(|definition| normalize_identifier(&definition.identifier) == id)
+        .map(|definition| (definition.url.as_str(), definition.title.as_deref()))
+        .or_else(|| {
+            state
+                .option_definitions
+                .iter()
+                .find(|(label, _)| normalize_identifier(label) == id)
+                .map(|(_, (url, title))| (url.as_str(), title.as_deref()))
+        })
+}
+
+/// Sanitize a URL, dropping dangerous protocols unless
+/// `allow_dangerous_protocol` is on.
+fn url(state: &State, value: &str, image: bool) -> String {
+    if state.options.allow_dangerous_protocol {
+        sanitize(value)
+    } else {
+        sanitize_with_protocols(
+            value,
+            if image {
+                &SAFE_PROTOCOL_SRC
+            } else {
+                &SAFE_PROTOCOL_HREF
+            },
+        )
+    }
+}
+
+/// Get the encoded clobber prefix of GFM footnotes.
+fn gfm_footnote_clobber_prefix(state: &State) -> String {
+    if let Some(ref value) = state.options.gfm_footnote_clobber_prefix {
+        encode(value, true)
+    } else {
+        "user-content-".into()
+    }
+}
+
+/// Push an attribute, with a value that is already encoded.
+fn push_attribute(state: &State, name: &str, value: &str, result: &mut String) {
+    result.push_str(&attribute(&state.options.quote, name, value));
+}
+
+/// Push an `id` for a heading, if `heading_ids` is on.
+fn push_heading_id(state: &mut State, node: &Heading, result: &mut String) {
+    if !state.options.heading_ids {
+        return;
+    }
+
+    if let Some(value) = slug::heading(node, state.slugify, &mut state.slugger) {
+        push_attribute(state, "id", &encode(&value, true), result);
+    }
+}
+
+/// Push the lines of a node as attributes, if `source_position` is on and
+/// the node has a position.
+fn push_source_position(state: &State, position: Option<&Position>, result: &mut String) {
+    if state.options.source_position {
+        if let Some(position) = position {
+            push_attribute(state, "data-line", &position.start.line.to_string(), result);
+            push_attribute(
+                state,
+                "data-end-line",
+                &position.end.line.to_string(),
+                result,
+            );
+        }
+    }
+}
+
+/// Add a line ending if needed (as in, there’s no eol/eof already).
+fn line_ending_if_needed(state: &State, result: &mut String) {
+    if !matches!(result.as_bytes().last(), None | Some(b'\n' | b'\r')) {
+        result.push_str(state.line_ending);
+    }
+}
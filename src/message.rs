@@ -44,3 +44,49 @@ impl fmt::Display for Place {
         }
     }
 }
+
+/// Error when turning markdown from a reader into something.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ReadError {
+    /// The reader could not be read, or did not contain UTF-8.
+    Io(std::io::Error),
+    /// The markdown could not be parsed (which can only happen with MDX).
+    Message(Message),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "Cannot read markdown: {}", error),
+            ReadError::Message(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::Message(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ReadError {
+    fn from(error: std::io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Message> for ReadError {
+    fn from(message: Message) -> Self {
+        ReadError::Message(message)
+    }
+}
@@ -1,13 +1,182 @@
 //! Turn bytes of markdown into events.
 
+use crate::cst;
 use crate::event::{Event, Point};
+use crate::mdast;
 use crate::message;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::subtokenize;
+use crate::to_html::{
+    compile as to_html, compile_with_source_map, option_definitions, Definition, Mapping,
+};
+use crate::to_mdast::compile as to_mdast;
 use crate::tokenizer::Tokenizer;
-use crate::util::location::Location;
-use crate::ParseOptions;
-use alloc::{string::String, vec, vec::Vec};
+use crate::util::{location::Location, normalize_identifier::normalize_identifier};
+use crate::{Options, ParseOptions};
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+
+/// Reusable parser, configured once.
+///
+/// Use this when turning many documents into HTML or syntax trees with the
+/// same configuration, instead of passing options to each call of
+/// [`to_html_with_options()`][crate::to_html_with_options] or
+/// [`to_mdast()`][crate::to_mdast].
+/// The [`definitions`][crate::ParseOptions::definitions] in options are
+/// prepared once, in [`Parser::new()`][], instead of for each document,
+/// which helps when there are many of them.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{Options, Parser};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let parser = Parser::new(Options::gfm());
+///
+/// assert_eq!(parser.to_html("~a~")?, "<p><del>a</del></p>");
+/// assert_eq!(parser.to_html("b")?, "<p>b</p>");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Parser {
+    /// Configuration.
+    options: Options,
+    /// Normalized identifiers of the definitions in options.
+    identifiers: Vec<String>,
+    /// Definitions in options, prepared for compiling to HTML.
+    definitions: Vec<Definition>,
+}
+
+impl Parser {
+    /// Create a parser with configuration.
+    pub fn new(options: Options) -> Self {
+        Self {
+            identifiers: option_identifiers(&options.parse),
+            definitions: option_definitions(&options.parse.definitions),
+            options,
+        }
+    }
+
+    /// Get the configuration.
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Turn markdown into HTML.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_html_with_options()`][crate::to_html_with_options].
+    pub fn to_html(&self, value: &str) -> Result<String, message::Message> {
+        let (events, parse_state) = self.parse(value)?;
+        Ok(to_html(
+            &events,
+            parse_state.bytes,
+            &self.definitions,
+            &self.options.compile,
+            self.options.slugify.as_deref(),
+        ))
+    }
+
+    /// Turn markdown into HTML and a source map.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_html_and_source_map()`][crate::to_html_and_source_map].
+    pub fn to_html_and_source_map(
+        &self,
+        value: &str,
+    ) -> Result<(String, Vec<Mapping>), message::Message> {
+        let (events, parse_state) = self.parse(value)?;
+        Ok(compile_with_source_map(
+            &events,
+            parse_state.bytes,
+            &self.definitions,
+            &self.options.compile,
+            self.options.slugify.as_deref(),
+        ))
+    }
+
+    /// Turn markdown into a syntax tree.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_mdast()`][crate::to_mdast].
+    pub fn to_mdast(&self, value: &str) -> Result<mdast::Node, message::Message> {
+        let (events, _) = self.parse(value)?;
+        to_mdast(&events, value, &self.options.parse)
+    }
+
+    /// Turn markdown into events.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_events()`][crate::to_events].
+    pub fn to_events(&self, value: &str) -> Result<Vec<Event>, message::Message> {
+        let (events, _) = self.parse(value)?;
+        Ok(events)
+    }
+
+    /// Turn events of markdown into a syntax tree.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`events_to_mdast()`][crate::events_to_mdast].
+    pub fn events_to_mdast(
+        &self,
+        events: &[Event],
+        value: &str,
+    ) -> Result<mdast::Node, message::Message> {
+        to_mdast(events, value, &self.options.parse)
+    }
+
+    /// Turn markdown into a concrete syntax tree.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_cst()`][crate::to_cst].
+    pub fn to_cst(&self, value: &str) -> Result<Vec<cst::Node>, message::Message> {
+        let (events, _) = self.parse(value)?;
+        Ok(cst::from_events(events))
+    }
+
+    /// Turn markdown into a syntax tree and HTML, parsing it once.
+    ///
+    /// ## Errors
+    ///
+    /// This errors when MDX is turned on and there is a syntax error, see
+    /// [`to_mdast_and_html()`][crate::to_mdast_and_html].
+    pub fn to_mdast_and_html(
+        &self,
+        value: &str,
+    ) -> Result<(mdast::Node, String), message::Message> {
+        let (events, parse_state) = self.parse(value)?;
+        let node = to_mdast(&events, value, &self.options.parse)?;
+        let html = to_html(
+            &events,
+            parse_state.bytes,
+            &self.definitions,
+            &self.options.compile,
+            self.options.slugify.as_deref(),
+        );
+        Ok((node, html))
+    }
+
+    /// Turn markdown into events, with the prepared definitions.
+    fn parse<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+        parse_with_identifiers(value, &self.options.parse, Cow::Borrowed(&self.identifiers))
+    }
+}
 
 /// Info needed, in all content types, when parsing markdown.
 ///
@@ -23,16 +192,50 @@ pub struct ParseState<'a> {
     pub bytes: &'a [u8],
     /// Set of defined definition identifiers.
     pub definitions: Vec<String>,
+    /// Set of identifiers of the definitions passed in options, which are
+    /// defined in every document.
+    pub option_definitions: Cow<'a, [String]>,
     /// Set of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
 }
 
+impl ParseState<'_> {
+    /// Whether there is a definition with the normalized identifier `id`,
+    /// in the document or in options.
+    pub fn is_defined(&self, id: &str) -> bool {
+        self.definitions.iter().any(|definition| definition == id)
+            || self
+                .option_definitions
+                .iter()
+                .any(|definition| definition == id)
+    }
+}
+
 /// Turn a string of markdown into events.
 ///
 /// Passes the bytes back so the compiler can access the source.
 pub fn parse<'a>(
     value: &'a str,
     options: &'a ParseOptions,
+) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
+    parse_with_identifiers(value, options, Cow::Owned(option_identifiers(options)))
+}
+
+/// Get the normalized identifiers of the definitions passed in options.
+fn option_identifiers(options: &ParseOptions) -> Vec<String> {
+    options
+        .definitions
+        .keys()
+        .map(|label| normalize_identifier(label))
+        .collect()
+}
+
+/// Turn a string of markdown into events, with `identifiers` (from
+/// [`option_identifiers`][]) defined.
+fn parse_with_identifiers<'a>(
+    value: &'a str,
+    options: &'a ParseOptions,
+    identifiers: Cow<'a, [String]>,
 ) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
     let bytes = value.as_bytes();
 
@@ -45,6 +248,7 @@ pub fn parse<'a>(
             None
         },
         definitions: vec![],
+        option_definitions: identifiers,
         gfm_footnote_definitions: vec![],
     };
 
@@ -1,25 +1,32 @@
 //! Turn events into a string of HTML.
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
+use crate::to_text::compile as to_text;
 use crate::util::{
-    character_reference::decode as decode_character_reference,
+    character_reference::{
+        decode as decode_character_reference, parse as decode_character_references,
+    },
     constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
-    encode::encode,
+    encode::{encode, encode_non_ascii},
     gfm_tagfilter::gfm_tagfilter,
     infer::{gfm_table_align, list_loose},
     normalize_identifier::normalize_identifier,
+    raw_text::decode as decode_raw_text,
     sanitize_uri::{sanitize, sanitize_with_protocols},
     skip,
     slice::{Position, Slice},
+    slug::{slug, Slugger, Slugify},
+    whitespace::collapse as collapse_whitespace,
 };
-use crate::{CompileOptions, LineEnding};
+use crate::{CompileOptions, LineEnding, Quote};
 use alloc::{
+    collections::BTreeMap,
     format,
     string::{String, ToString},
     vec,
     vec::Vec,
 };
-use core::str;
+use core::{ops::Range, str};
 
 /// Link, image, or footnote call.
 /// Resource or reference.
@@ -56,9 +63,28 @@ struct Media {
     title: Option<String>,
 }
 
+/// Mapping from a range of HTML to the range of markdown it came from.
+///
+/// See [`to_html_and_source_map()`][crate::to_html_and_source_map].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mapping {
+    /// Byte range in the HTML, from the start of the opening tag to the end
+    /// of the closing tag.
+    pub output: Range<usize>,
+    /// Byte range in the markdown.
+    ///
+    /// Empty (at the end of the markdown) for generated content, such as the
+    /// GFM footnote section.
+    pub input: Range<usize>,
+    /// Name of the construct (such as `Paragraph` or `ListItem`).
+    ///
+    /// `None` for generated content.
+    pub name: Option<Name>,
+}
+
 /// Representation of a definition.
-#[derive(Debug)]
-struct Definition {
+#[derive(Clone, Debug)]
+pub(crate) struct Definition {
     /// Identifier.
     id: String,
     /// The destination (url).
@@ -73,7 +99,6 @@ struct Definition {
 
 /// Context used to compile markdown.
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug)]
 struct CompileContext<'a> {
     // Static info.
     /// List of events.
@@ -82,6 +107,8 @@ struct CompileContext<'a> {
     bytes: &'a [u8],
     /// Configuration.
     options: &'a CompileOptions,
+    /// Function to turn the text of a heading into a slug.
+    slugify: Option<&'a Slugify>,
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
     /// Rank of heading (atx).
@@ -92,10 +119,21 @@ struct CompileContext<'a> {
     raw_flow_seen_data: Option<bool>,
     /// Number of raw (flow) fences.
     raw_flow_fences_count: Option<usize>,
+    /// Class (encoded) to add to the inner element of raw (flow), such as
+    /// `language-js`, when it is closed.
+    raw_flow_class: Option<String>,
     /// Whether we are in code (text).
     raw_text_inside: bool,
     /// Whether we are in image text.
     image_alt_inside: bool,
+    /// Whether we are in an HTML comment that is stripped.
+    html_comment_inside: bool,
+    /// Whether we are in a paragraph or heading (setext) text, where line
+    /// endings are collapsed with `collapse_whitespace`.
+    collapse_line_endings: bool,
+    /// Whether we are in a string (such as a destination) or HTML (text),
+    /// where whitespace is not collapsed with `collapse_whitespace`.
+    collapse_skip: bool,
     /// Marker of character reference.
     character_reference_marker: Option<u8>,
     /// Whether we are expecting the first list item marker.
@@ -106,8 +144,8 @@ struct CompileContext<'a> {
     tight_stack: Vec<bool>,
     /// List of definitions.
     definitions: Vec<Definition>,
-    /// List of definitions.
-    gfm_footnote_definitions: Vec<(String, String)>,
+    /// List of definitions, with the mappings of what is in them.
+    gfm_footnote_definitions: Vec<(String, String, Vec<Mapping>)>,
     gfm_footnote_definition_calls: Vec<(String, usize)>,
     gfm_footnote_definition_stack: Vec<(usize, usize)>,
     /// Whether we are in a GFM table head.
@@ -116,6 +154,8 @@ struct CompileContext<'a> {
     gfm_table_align: Option<Vec<AlignKind>>,
     /// Current GFM table column.
     gfm_table_column: usize,
+    /// Slugs of headings seen so far.
+    slugger: Slugger,
     // Fields used to influance the current compilation.
     /// Ignore the next line ending.
     slurp_one_line_ending: bool,
@@ -124,9 +164,18 @@ struct CompileContext<'a> {
     // Configuration
     /// Line ending to use.
     line_ending_default: LineEnding,
+    /// Elements to wrap code (flow) in.
+    code_wrapper: Vec<WrapperElement>,
+    /// Elements to wrap math (flow) in.
+    math_wrapper: Vec<WrapperElement>,
     // Intermediate results.
     /// Stack of buffers.
     buffers: Vec<String>,
+    /// Mappings, with the number of buffers when they started, if a source
+    /// map is made.
+    mappings: Option<Vec<(usize, Mapping)>>,
+    /// Indices of mappings that are not yet ended.
+    mapping_stack: Vec<usize>,
     /// Current event index.
     index: usize,
 }
@@ -137,7 +186,9 @@ impl<'a> CompileContext<'a> {
         events: &'a [Event],
         bytes: &'a [u8],
         options: &'a CompileOptions,
+        slugify: Option<&'a Slugify>,
         line_ending: LineEnding,
+        source_map: bool,
     ) -> CompileContext<'a> {
         CompileContext {
             events,
@@ -146,6 +197,7 @@ impl<'a> CompileContext<'a> {
             heading_setext_buffer: None,
             raw_flow_seen_data: None,
             raw_flow_fences_count: None,
+            raw_flow_class: None,
             raw_text_inside: false,
             character_reference_marker: None,
             list_expect_first_marker: None,
@@ -157,14 +209,23 @@ impl<'a> CompileContext<'a> {
             gfm_table_in_head: false,
             gfm_table_align: None,
             gfm_table_column: 0,
+            slugger: Slugger::default(),
             tight_stack: vec![],
             slurp_one_line_ending: false,
             image_alt_inside: false,
+            html_comment_inside: false,
+            collapse_line_endings: false,
+            collapse_skip: false,
             encode_html: true,
             line_ending_default: line_ending,
+            code_wrapper: code_wrapper(options),
+            math_wrapper: math_wrapper(),
             buffers: vec![String::new()],
+            mappings: if source_map { Some(vec![]) } else { None },
+            mapping_stack: vec![],
             index: 0,
             options,
+            slugify,
         }
     }
 
@@ -185,6 +246,196 @@ impl<'a> CompileContext<'a> {
         last_buf.push_str(value);
     }
 
+    /// Push an attribute, with a value that is already encoded.
+    fn push_attribute(&mut self, name: &str, value: &str) {
+        let attribute = attribute(&self.options.quote, name, value);
+        self.push(&attribute);
+    }
+
+    /// Find the enter and exit events of a construct.
+    ///
+    /// The construct is the closest `name` that was entered at or before the
+    /// current event.
+    fn construct_range(&self, name: &Name) -> (usize, usize) {
+        let mut enter = self.index;
+        while !(self.events[enter].kind == Kind::Enter && self.events[enter].name == *name) {
+            enter -= 1;
+        }
+
+        let mut exit = enter + 1;
+        let mut balance = 0;
+        loop {
+            if self.events[exit].kind == Kind::Enter {
+                balance += 1;
+            } else if balance == 0 {
+                break;
+            } else {
+                balance -= 1;
+            }
+            exit += 1;
+        }
+
+        (enter, exit)
+    }
+
+    /// Add the lines of a block construct as attributes, if
+    /// `source_position` is on.
+    fn push_source_position(&mut self, name: &Name) {
+        if !self.options.source_position {
+            return;
+        }
+
+        let (enter, exit) = self.construct_range(name);
+        let start = self.events[enter].point.line.to_string();
+        let end = self.events[exit].point.line.to_string();
+        self.push_attribute("data-line", &start);
+        self.push_attribute("data-end-line", &end);
+    }
+
+    /// Start a mapping of a block construct, if a source map is made.
+    ///
+    /// The construct is the closest `name` that was entered at or before the
+    /// current event.
+    fn mapping_start(&mut self, name: &Name) {
+        if self.mappings.is_some() {
+            let (enter, exit) = self.construct_range(name);
+            let input = self.events[enter].point.index..self.events[exit].point.index;
+            self.mapping_push(input, Some(name.clone()));
+        }
+    }
+
+    /// Start a mapping of generated content, if a source map is made.
+    fn mapping_start_generated(&mut self) {
+        if self.mappings.is_some() {
+            self.mapping_push(self.bytes.len()..self.bytes.len(), None);
+        }
+    }
+
+    /// Start a mapping at the end of the last buffer.
+    fn mapping_push(&mut self, input: Range<usize>, name: Option<Name>) {
+        let start = self
+            .buffers
+            .last()
+            .expect("at least one buffer should exist")
+            .len();
+        let depth = self.buffers.len();
+        let mappings = self.mappings.as_mut().unwrap();
+        self.mapping_stack.push(mappings.len());
+        mappings.push((
+            depth,
+            Mapping {
+                output: start..start,
+                input,
+                name,
+            },
+        ));
+    }
+
+    /// End the last started mapping, if a source map is made.
+    fn mapping_end(&mut self) {
+        if let Some(mappings) = self.mappings.as_mut() {
+            let index = self.mapping_stack.pop().expect("expected started mapping");
+            mappings[index].1.output.end = self
+                .buffers
+                .last()
+                .expect("at least one buffer should exist")
+                .len();
+        }
+    }
+
+    /// Get the elements to wrap the current code (flow) or math (flow) in.
+    fn raw_flow_wrapper(&self) -> &[WrapperElement] {
+        if matches!(
+            self.events[self.index].name,
+            Name::MathFlow | Name::MathFlowFence
+        ) {
+            &self.math_wrapper
+        } else {
+            &self.code_wrapper
+        }
+    }
+
+    /// Open the elements of code (flow) or math (flow) of `name`.
+    ///
+    /// Note: the last element is not closed, so that its class can be
+    /// added later, with
+    /// [`push_raw_flow_start_close`][Self::push_raw_flow_start_close].
+    fn push_raw_flow_start(&mut self, name: &Name) {
+        let last = self.raw_flow_wrapper().len() - 1;
+        let mut index = 0;
+
+        while index <= last {
+            if index != 0 {
+                self.push(">");
+            }
+
+            let value = wrapper_open(&self.raw_flow_wrapper()[index], &self.options.quote);
+            self.push(&value);
+
+            if index == 0 {
+                self.push_source_position(name);
+            }
+
+            if index != last {
+                if let Some(class) = self.raw_flow_wrapper()[index].class.as_deref() {
+                    let class = encode(class, true);
+                    self.push_attribute("class", &class);
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    /// Add the class of the last element of code (flow) or math (flow),
+    /// joined with the encoded `class`, and close it.
+    fn push_raw_flow_start_close(&mut self, class: Option<&str>) {
+        let own = self
+            .raw_flow_wrapper()
+            .last()
+            .expect("expected element")
+            .class
+            .as_deref()
+            .map(|class| encode(class, true));
+
+        if let Some(class) = join_class(own.as_deref(), class) {
+            self.push_attribute("class", &class);
+        }
+
+        self.push(">");
+    }
+
+    /// Close the elements of code (flow) or math (flow).
+    fn push_raw_flow_end(&mut self) {
+        let mut index = self.raw_flow_wrapper().len();
+
+        while index > 0 {
+            index -= 1;
+            let value = format!("</{}>", self.raw_flow_wrapper()[index].tag_name);
+            self.push(&value);
+        }
+    }
+
+    /// Add an `id` to a heading, if `heading_ids` is on.
+    fn push_heading_id(&mut self, name: &Name) {
+        if !self.options.heading_ids {
+            return;
+        }
+
+        let (enter, exit) = self.construct_range(name);
+        let text = to_text(&self.events[enter..=exit], self.bytes);
+        let value = if let Some(slugify) = self.slugify {
+            slugify(&text)
+        } else {
+            slug(&text)
+        };
+
+        if !value.is_empty() {
+            let value = encode(&self.slugger.unique(&value), true);
+            self.push_attribute("id", &value);
+        }
+    }
+
     /// Add a line ending.
     fn line_ending(&mut self) {
         let eol = self.line_ending_default.as_str().to_string();
@@ -203,8 +454,146 @@ impl<'a> CompileContext<'a> {
     }
 }
 
+/// Get the indices of the first and last events of the top-level blocks
+/// that overlap `lines` (1-indexed, end exclusive).
+///
+/// Blocks that straddle the start or end of `lines` are included whole.
+/// When the last block is the last in the document, the final line ending
+/// is included too.
+fn select_lines(events: &[Event], lines: &Range<usize>) -> Option<(usize, usize)> {
+    let mut first: Option<usize> = None;
+    let mut last = 0;
+    let mut last_in_document = 0;
+    let mut index = 0;
+    let mut depth = 0;
+    let mut start = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            if depth == 0 {
+                start = index;
+            }
+
+            depth += 1;
+        } else {
+            depth -= 1;
+
+            if depth == 0
+                && !matches!(
+                    event.name,
+                    Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+                )
+            {
+                last_in_document = index;
+
+                if events[start].point.line < lines.end && event.point.line >= lines.start {
+                    first.get_or_insert(start);
+                    last = index;
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    first.map(|first| {
+        if last == last_in_document {
+            (first, events.len() - 1)
+        } else {
+            (first, last)
+        }
+    })
+}
+
+/// Prepare definitions passed in options for [`compile`][], by normalizing
+/// their identifiers and encoding their titles.
+pub(crate) fn option_definitions(
+    definitions: &BTreeMap<String, (String, Option<String>)>,
+) -> Vec<Definition> {
+    definitions
+        .iter()
+        .map(|(label, (destination, title))| Definition {
+            id: normalize_identifier(label),
+            destination: Some(destination.clone()),
+            title: title.as_ref().map(|title| encode(title, true)),
+        })
+        .collect()
+}
+
 /// Turn events and bytes into a string of HTML.
-pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> String {
+///
+/// `definitions` (see [`option_definitions`][]) are used after the
+/// definitions in the document.
+pub(crate) fn compile(
+    events: &[Event],
+    bytes: &[u8],
+    definitions: &[Definition],
+    options: &CompileOptions,
+    slugify: Option<&Slugify>,
+) -> String {
+    compile_impl(events, bytes, definitions, options, slugify, false, None).0
+}
+
+/// Turn events and bytes into a string of HTML, of the top-level blocks
+/// that overlap `lines` (see [`select_lines`][]).
+///
+/// The whole document is compiled, so that headings and footnote calls are
+/// counted as in the whole document, but only the HTML of those blocks is
+/// kept.
+/// The footnote section is added if a footnote definition that is called
+/// is in those blocks.
+pub(crate) fn compile_range(
+    events: &[Event],
+    bytes: &[u8],
+    definitions: &[Definition],
+    options: &CompileOptions,
+    slugify: Option<&Slugify>,
+    lines: &Range<usize>,
+) -> String {
+    if let Some(range) = select_lines(events, lines) {
+        compile_impl(
+            events,
+            bytes,
+            definitions,
+            options,
+            slugify,
+            false,
+            Some(range),
+        )
+        .0
+    } else {
+        String::new()
+    }
+}
+
+/// Turn events and bytes into a string of HTML, and a source map of the
+/// blocks in it.
+pub(crate) fn compile_with_source_map(
+    events: &[Event],
+    bytes: &[u8],
+    definitions: &[Definition],
+    options: &CompileOptions,
+    slugify: Option<&Slugify>,
+) -> (String, Vec<Mapping>) {
+    compile_impl(events, bytes, definitions, options, slugify, true, None)
+}
+
+/// Turn events and bytes into a string of HTML, and, if `source_map` is on,
+/// a source map.
+///
+/// When `range` is given (the indices of the first and last events to keep),
+/// the HTML of other events is dropped.
+fn compile_impl(
+    events: &[Event],
+    bytes: &[u8],
+    definitions: &[Definition],
+    options: &CompileOptions,
+    slugify: Option<&Slugify>,
+    source_map: bool,
+    range: Option<(usize, usize)>,
+) -> (String, Vec<Mapping>) {
     let mut index = 0;
     let mut line_ending_inferred = None;
 
@@ -228,7 +617,14 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
     let line_ending_default =
         line_ending_inferred.unwrap_or_else(|| options.default_line_ending.clone());
 
-    let mut context = CompileContext::new(events, bytes, options, line_ending_default);
+    let mut context = CompileContext::new(
+        events,
+        bytes,
+        options,
+        slugify,
+        line_ending_default,
+        source_map,
+    );
     let mut definition_indices = vec![];
     let mut index = 0;
     let mut definition_inside = false;
@@ -265,6 +661,10 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         index += 1;
     }
 
+    // Definitions passed in options come after those in the document, so
+    // that those in the document take precedence.
+    context.definitions.extend_from_slice(definitions);
+
     let mut index = 0;
     let jump_default = (events.len(), events.len());
     let mut definition_index = 0;
@@ -272,7 +672,22 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         .get(definition_index)
         .unwrap_or(&jump_default);
 
+    let mut dropping = false;
+
     while index < events.len() {
+        // Events outside `range` are compiled in a buffer that is dropped.
+        let drop = range.map_or(false, |(first, last)| index < first || index > last);
+
+        if drop != dropping {
+            if drop {
+                context.buffer();
+            } else {
+                context.resume();
+            }
+
+            dropping = drop;
+        }
+
         if index == jump.0 {
             index = jump.1 + 1;
             definition_index += 1;
@@ -285,23 +700,123 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         }
     }
 
+    if dropping {
+        context.resume();
+    }
+
     // No section to generate.
-    if !context.gfm_footnote_definition_calls.is_empty() {
+    if !context.gfm_footnote_definition_calls.is_empty()
+        && range.map_or(true, |(first, last)| {
+            has_called_gfm_footnote_definition(&context, first, last)
+        })
+    {
         generate_footnote_section(&mut context);
     }
 
+    if options.trailing_newline == Some(true) {
+        context.line_ending_if_needed();
+    }
+
     debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
-    context
+    let mut result = context
         .buffers
         .first()
         .expect("expected 1 final buffer")
-        .into()
+        .as_str();
+
+    if options.trailing_newline == Some(false) {
+        result = result.trim_end_matches(['\n', '\r']);
+    }
+
+    let mut mappings = context
+        .mappings
+        .take()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_, mut mapping)| {
+            mapping.output.start = mapping.output.start.min(result.len());
+            mapping.output.end = mapping.output.end.min(result.len());
+            mapping
+        })
+        .collect::<Vec<_>>();
+
+    if options.encode_non_ascii {
+        if !mappings.is_empty() {
+            encode_non_ascii_mappings(result, &mut mappings);
+        }
+
+        (encode_non_ascii(result), mappings)
+    } else {
+        (result.into(), mappings)
+    }
+}
+
+/// Move the output ranges of `mappings` into `value` to where they are once
+/// non-ASCII characters in it are encoded.
+fn encode_non_ascii_mappings(value: &str, mappings: &mut [Mapping]) {
+    let mut offsets = mappings
+        .iter()
+        .flat_map(|mapping| [mapping.output.start, mapping.output.end])
+        .collect::<Vec<_>>();
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let mut moved = vec![];
+    let mut chars = value.char_indices().peekable();
+    let mut shift = 0;
+
+    for offset in offsets {
+        while let Some((index, char)) = chars.peek() {
+            if *index >= offset {
+                break;
+            }
+
+            if !char.is_ascii() {
+                shift += format!("&#x{:X};", *char as u32).len() - char.len_utf8();
+            }
+
+            chars.next();
+        }
+
+        moved.push((offset, offset + shift));
+    }
+
+    let find = |offset: usize| {
+        let index = moved
+            .binary_search_by_key(&offset, |d| d.0)
+            .expect("expected offset");
+        moved[index].1
+    };
+
+    for mapping in mappings {
+        mapping.output = find(mapping.output.start)..find(mapping.output.end);
+    }
 }
 
 /// Handle the event at `index`.
 fn handle(context: &mut CompileContext, index: usize) {
     context.index = index;
 
+    if context.options.collapse_whitespace {
+        let enter = context.events[index].kind == Kind::Enter;
+
+        match context.events[index].name {
+            Name::Paragraph | Name::HeadingSetextText => context.collapse_line_endings = enter,
+            Name::CodeFencedFenceInfo
+            | Name::CodeFencedFenceMeta
+            | Name::DefinitionDestinationString
+            | Name::DefinitionLabelString
+            | Name::DefinitionTitleString
+            | Name::GfmFootnoteDefinitionLabelString
+            | Name::HtmlText
+            | Name::MathFlowFenceMeta
+            | Name::ReferenceString
+            | Name::ResourceDestinationString
+            | Name::ResourceTitleString => context.collapse_skip = enter,
+            _ => {}
+        }
+    }
+
     if context.events[index].kind == Kind::Enter {
         enter(context);
     } else {
@@ -452,26 +967,38 @@ fn on_enter_buffer(context: &mut CompileContext) {
 fn on_enter_block_quote(context: &mut CompileContext) {
     context.tight_stack.push(false);
     context.line_ending_if_needed();
-    context.push("<blockquote>");
+    context.mapping_start(&Name::BlockQuote);
+    context.push("<blockquote");
+    context.push_source_position(&Name::BlockQuote);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
 fn on_enter_code_indented(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
-    context.push("<pre><code>");
+    context.mapping_start(&Name::CodeIndented);
+    context.push_raw_flow_start(&Name::CodeIndented);
+    let class = context
+        .options
+        .code_indented_class
+        .as_deref()
+        .map(|value| encode(value, true));
+    context.push_raw_flow_start_close(class.as_deref());
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`CodeFenced`][Name::CodeFenced],[`MathFlow`][Name::MathFlow]}.
 fn on_enter_raw_flow(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
+    let name = context.events[context.index].name.clone();
+    context.mapping_start(&name);
     // Note that no `>` is used, which is added later (due to info)
-    context.push("<pre><code");
+    context.push_raw_flow_start(&name);
     context.raw_flow_fences_count = Some(0);
 
     if context.events[context.index].name == Name::MathFlow {
-        context.push(" class=\"language-math math-display\"");
+        context.raw_flow_class = Some("language-math math-display".into());
     }
 }
 
@@ -481,7 +1008,7 @@ fn on_enter_raw_text(context: &mut CompileContext) {
     if !context.image_alt_inside {
         context.push("<code");
         if context.events[context.index].name == Name::MathText {
-            context.push(" class=\"language-math math-inline\"");
+            context.push_attribute("class", "language-math math-inline");
         }
         context.push(">");
     }
@@ -548,7 +1075,10 @@ fn on_enter_gfm_table(context: &mut CompileContext) {
     let align = gfm_table_align(context.events, context.index);
     context.gfm_table_align = Some(align);
     context.line_ending_if_needed();
-    context.push("<table>");
+    context.mapping_start(&Name::GfmTable);
+    context.push("<table");
+    context.push_source_position(&Name::GfmTable);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableBody`][Name::GfmTableBody].
@@ -575,9 +1105,9 @@ fn on_enter_gfm_table_cell(context: &mut CompileContext) {
         }
 
         match value {
-            AlignKind::Left => context.push(" align=\"left\""),
-            AlignKind::Right => context.push(" align=\"right\""),
-            AlignKind::Center => context.push(" align=\"center\""),
+            AlignKind::Left => context.push_attribute("align", "left"),
+            AlignKind::Right => context.push_attribute("align", "right"),
+            AlignKind::Center => context.push_attribute("align", "center"),
             AlignKind::None => {}
         }
 
@@ -601,15 +1131,20 @@ fn on_enter_gfm_table_row(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<input type=\"checkbox\" ");
+        context.push("<input");
+        context.push_attribute("type", "checkbox");
         if !context.options.gfm_task_list_item_checkable {
-            context.push("disabled=\"\" ");
+            context.push_attribute("disabled", "");
         }
     }
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`HtmlFlow`][Name::HtmlFlow].
 fn on_enter_html_flow(context: &mut CompileContext) {
+    if on_enter_html_comment(context) {
+        return;
+    }
+
     context.line_ending_if_needed();
     if context.options.allow_dangerous_html {
         context.encode_html = false;
@@ -618,11 +1153,39 @@ fn on_enter_html_flow(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:[`HtmlText`][Name::HtmlText].
 fn on_enter_html_text(context: &mut CompileContext) {
+    if on_enter_html_comment(context) {
+        return;
+    }
+
     if context.options.allow_dangerous_html {
         context.encode_html = false;
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
+///
+/// Starts dropping the HTML if it is a comment and comments are stripped.
+fn on_enter_html_comment(context: &mut CompileContext) -> bool {
+    if !context.options.strip_comments {
+        return false;
+    }
+
+    let mut index = context.events[context.index].point.index;
+
+    // Skip initial whitespace of HTML (flow).
+    while matches!(context.bytes.get(index), Some(b'\t' | b' ')) {
+        index += 1;
+    }
+
+    if context.bytes[index..].starts_with(b"<!--") {
+        context.html_comment_inside = true;
+        context.buffer();
+        true
+    } else {
+        false
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Image`][Name::Image].
 fn on_enter_image(context: &mut CompileContext) {
     context.media_stack.push(Media {
@@ -655,11 +1218,14 @@ fn on_enter_list(context: &mut CompileContext) {
     context.line_ending_if_needed();
 
     // Note: no `>`.
-    context.push(if context.events[context.index].name == Name::ListOrdered {
+    let name = context.events[context.index].name.clone();
+    context.mapping_start(&name);
+    context.push(if name == Name::ListOrdered {
         "<ol"
     } else {
         "<ul"
     });
+    context.push_source_position(&name);
     context.list_expect_first_marker = Some(true);
 }
 
@@ -671,17 +1237,27 @@ fn on_enter_list_item_marker(context: &mut CompileContext) {
 
     context.line_ending_if_needed();
 
-    context.push("<li>");
+    context.mapping_start(&Name::ListItem);
+    context.push("<li");
+    context.push_source_position(&Name::ListItem);
+    context.push(">");
     context.list_expect_first_marker = Some(false);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
 fn on_enter_paragraph(context: &mut CompileContext) {
-    let tight = context.tight_stack.last().unwrap_or(&false);
+    let tight = *context.tight_stack.last().unwrap_or(&false);
 
     if !tight {
         context.line_ending_if_needed();
-        context.push("<p>");
+    }
+
+    context.mapping_start(&Name::Paragraph);
+
+    if !tight && (context.options.wrap_paragraphs || !context.tight_stack.is_empty()) {
+        context.push("<p");
+        context.push_source_position(&Name::Paragraph);
+        context.push(">");
     }
 }
 
@@ -717,6 +1293,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) {
         )
         .as_str(),
         false,
+        true,
     );
 }
 
@@ -731,6 +1308,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
         )
         .as_str(),
         false,
+        false,
     );
 }
 
@@ -754,6 +1332,7 @@ fn on_exit_block_quote(context: &mut CompileContext) {
     context.line_ending_if_needed();
     context.slurp_one_line_ending = false;
     context.push("</blockquote>");
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarker`][Name::CharacterReferenceMarker].
@@ -790,15 +1369,18 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFlowChunk`][Name::CodeFlowChunk],[`MathFlowChunk`][Name::MathFlowChunk]}.
 fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(true);
-    context.push(&encode(
-        &Slice::from_position(
-            context.bytes,
-            &Position::from_exit_event(context.events, context.index),
-        )
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+    );
+    let value = if context.options.preserve_tabs_in_code && slice.before > 0 {
+        // Virtual spaces before are the rest of a tab.
+        format!("\t{}", slice.as_str())
+    } else {
         // Must serialize to get virtual spaces.
-        .serialize(),
-        context.encode_html,
-    ));
+        slice.serialize()
+    };
+    context.push(&encode(&value, context.encode_html));
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFencedFence`][Name::CodeFencedFence],[`MathFlowFence`][Name::MathFlowFence]}.
@@ -808,7 +1390,8 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
         .expect("expected `raw_flow_fences_count`");
 
     if count == 0 {
-        context.push(">");
+        let class = context.raw_flow_class.take();
+        context.push_raw_flow_start_close(class.as_deref());
         context.slurp_one_line_ending = true;
     }
 
@@ -820,9 +1403,7 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
 /// Note: math (flow) does not support `info`.
 fn on_exit_raw_flow_fence_info(context: &mut CompileContext) {
     let value = context.resume();
-    context.push(" class=\"language-");
-    context.push(&value);
-    context.push("\"");
+    context.raw_flow_class = Some(format!("language-{}", value));
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
@@ -854,7 +1435,8 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
         context.line_ending_if_needed();
     }
 
-    context.push("</code></pre>");
+    context.push_raw_flow_end();
+    context.mapping_end();
 
     if let Some(count) = context.raw_flow_fences_count.take() {
         if count < 2 {
@@ -868,48 +1450,9 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
 fn on_exit_raw_text(context: &mut CompileContext) {
     let result = context.resume();
-    // To do: share with `to_mdast`.
-    let mut bytes = result.as_bytes().to_vec();
-
-    // If we are in a GFM table, we need to decode escaped pipes.
-    // This is a rather weird GFM feature.
-    if context.gfm_table_align.is_some() {
-        let mut index = 0;
-        let mut len = bytes.len();
-
-        while index < len {
-            if index + 1 < len && bytes[index] == b'\\' && bytes[index + 1] == b'|' {
-                bytes.remove(index);
-                len -= 1;
-            }
-
-            index += 1;
-        }
-    }
-
-    let mut trim = false;
-    let mut index = 0;
-    let mut end = bytes.len();
-
-    if end > 2 && bytes[index] == b' ' && bytes[end - 1] == b' ' {
-        index += 1;
-        end -= 1;
-        while index < end && !trim {
-            if bytes[index] != b' ' {
-                trim = true;
-                break;
-            }
-            index += 1;
-        }
-    }
-
-    if trim {
-        bytes.remove(0);
-        bytes.pop();
-    }
-
+    let value = decode_raw_text(&result, context.gfm_table_align.is_some());
     context.raw_text_inside = false;
-    context.push(str::from_utf8(&bytes).unwrap());
+    context.push(&value);
 
     if !context.image_alt_inside {
         context.push("</code>");
@@ -933,14 +1476,17 @@ fn on_exit_drop_slurp(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeTextData`][Name::CodeTextData],[`Data`][Name::Data],[`CharacterEscapeValue`][Name::CharacterEscapeValue]}.
 fn on_exit_data(context: &mut CompileContext) {
-    context.push(&encode(
-        Slice::from_position(
-            context.bytes,
-            &Position::from_exit_event(context.events, context.index),
-        )
-        .as_str(),
-        context.encode_html,
-    ));
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+    );
+    let value = slice.as_str();
+
+    if context.options.collapse_whitespace && !context.raw_text_inside && !context.collapse_skip {
+        context.push(&encode(&collapse_whitespace(value), context.encode_html));
+    } else {
+        context.push(&encode(value, context.encode_html));
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`Definition`][Name::Definition].
@@ -1003,6 +1549,7 @@ fn on_exit_gfm_autolink_literal_email(context: &mut CompileContext) {
         )
         .as_str(),
         true,
+        true,
     );
 }
 
@@ -1017,6 +1564,7 @@ fn on_exit_gfm_autolink_literal_mailto(context: &mut CompileContext) {
         )
         .as_str(),
         true,
+        true,
     );
 }
 
@@ -1031,6 +1579,7 @@ fn on_exit_gfm_autolink_literal_protocol(context: &mut CompileContext) {
         )
         .as_str(),
         true,
+        false,
     );
 }
 
@@ -1045,6 +1594,7 @@ fn on_exit_gfm_autolink_literal_www(context: &mut CompileContext) {
         )
         .as_str(),
         true,
+        false,
     );
 }
 
@@ -1059,6 +1609,7 @@ fn on_exit_gfm_autolink_literal_xmpp(context: &mut CompileContext) {
         )
         .as_str(),
         true,
+        false,
     );
 }
 
@@ -1092,31 +1643,23 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
         return;
     }
 
-    context.push("<sup><a href=\"#");
-    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
-    } else {
-        context.push("user-content-");
-    }
-    context.push("fn-");
-    context.push(&safe_id);
-    context.push("\" id=\"");
-    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
-    } else {
-        context.push("user-content-");
-    }
-    context.push("fnref-");
-    context.push(&safe_id);
+    let prefix = gfm_footnote_clobber_prefix(context);
+    let mut reference_id = format!("{}fnref-{}", prefix, safe_id);
     if context.gfm_footnote_definition_calls[call_index].1 > 1 {
-        context.push("-");
-        context.push(
+        reference_id.push('-');
+        reference_id.push_str(
             &context.gfm_footnote_definition_calls[call_index]
                 .1
                 .to_string(),
         );
     }
-    context.push("\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">");
+
+    context.push("<sup><a");
+    context.push_attribute("href", &format!("#{}fn-{}", prefix, safe_id));
+    context.push_attribute("id", &reference_id);
+    context.push_attribute("data-footnote-ref", "");
+    context.push_attribute("aria-describedby", "footnote-label");
+    context.push(">");
 
     context.push(&(call_index + 1).to_string());
     context.push("</a></sup>");
@@ -1135,16 +1678,33 @@ fn on_exit_gfm_footnote_definition_prefix(context: &mut CompileContext) {
     context.resume();
     // Capture everything until end of definition.
     context.buffer();
+    context.mapping_start(&Name::GfmFootnoteDefinition);
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmFootnoteDefinition`][Name::GfmFootnoteDefinition].
 fn on_exit_gfm_footnote_definition(context: &mut CompileContext) {
+    // Take the mappings of the definition and what is in it: they are
+    // placed when the definition is used.
+    let mut mappings = vec![];
+    if let Some(index) = context.mapping_stack.last().copied() {
+        context.mapping_end();
+        mappings = context
+            .mappings
+            .as_mut()
+            .unwrap()
+            .split_off(index)
+            .into_iter()
+            .map(|d| d.1)
+            .collect();
+    }
+
     let value = context.resume();
     let indices = context.gfm_footnote_definition_stack.pop().unwrap();
     context.tight_stack.pop();
     context.gfm_footnote_definitions.push((
         normalize_identifier(Slice::from_indices(context.bytes, indices.0, indices.1).as_str()),
         value,
+        mappings,
     ));
 }
 
@@ -1160,6 +1720,7 @@ fn on_exit_gfm_table(context: &mut CompileContext) {
     context.gfm_table_align = None;
     context.line_ending_if_needed();
     context.push("</table>");
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmTableBody`][Name::GfmTableBody].
@@ -1214,14 +1775,14 @@ fn on_exit_gfm_table_row(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_exit_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("/>");
+        context.push(" />");
     }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemValueChecked`][Name::GfmTaskListItemValueChecked].
 fn on_exit_gfm_task_list_item_value_checked(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("checked=\"\" ");
+        context.push_attribute("checked", "");
     }
 }
 
@@ -1233,8 +1794,9 @@ fn on_exit_heading_atx(context: &mut CompileContext) {
         .expect("`heading_atx_rank` must be set in headings");
 
     context.push("</h");
-    context.push(&rank.to_string());
+    context.push(&heading_rank(rank, context.options.heading_offset).to_string());
     context.push(">");
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`HeadingAtxSequence`][Name::HeadingAtxSequence].
@@ -1248,8 +1810,11 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
         .len();
         context.line_ending_if_needed();
         context.heading_atx_rank = Some(rank);
+        context.mapping_start(&Name::HeadingAtx);
         context.push("<h");
-        context.push(&rank.to_string());
+        context.push(&heading_rank(rank, context.options.heading_offset).to_string());
+        context.push_source_position(&Name::HeadingAtx);
+        context.push_heading_id(&Name::HeadingAtx);
         context.push(">");
     }
 }
@@ -1275,20 +1840,37 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
         .expect("`heading_atx_rank` must be set in headings");
     let position = Position::from_exit_event(context.events, context.index);
     let head = context.bytes[position.start.index];
-    let rank = if head == b'-' { "2" } else { "1" };
+    let rank = heading_rank(
+        if head == b'-' { 2 } else { 1 },
+        context.options.heading_offset,
+    )
+    .to_string();
 
     context.line_ending_if_needed();
+    context.mapping_start(&Name::HeadingSetext);
     context.push("<h");
-    context.push(rank);
+    context.push(&rank);
+    context.push_source_position(&Name::HeadingSetext);
+    context.push_heading_id(&Name::HeadingSetext);
     context.push(">");
     context.push(&text);
     context.push("</h");
-    context.push(rank);
+    context.push(&rank);
     context.push(">");
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) {
+    if context.html_comment_inside {
+        context.html_comment_inside = false;
+        context.resume();
+
+        if context.events[context.index].name == Name::HtmlFlow {
+            context.slurp_one_line_ending = true;
+        }
+    }
+
     context.encode_html = true;
 }
 
@@ -1332,6 +1914,11 @@ fn on_exit_line_ending(context: &mut CompileContext) {
                 || context.events[context.index - 2].name == Name::GfmFootnoteDefinition))
     {
         context.slurp_one_line_ending = false;
+    } else if context.options.collapse_whitespace
+        && context.collapse_line_endings
+        && !context.collapse_skip
+    {
+        context.push(" ");
     } else {
         context.push(&encode(
             Slice::from_position(
@@ -1353,6 +1940,7 @@ fn on_exit_list(context: &mut CompileContext) {
     } else {
         "</ul>"
     });
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`ListItem`][Name::ListItem].
@@ -1382,11 +1970,12 @@ fn on_exit_list_item(context: &mut CompileContext) {
     }
 
     context.push("</li>");
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`ListItemValue`][Name::ListItemValue].
 fn on_exit_list_item_value(context: &mut CompileContext) {
-    if context.list_expect_first_marker.unwrap() {
+    if context.list_expect_first_marker.unwrap() && !context.options.renumber_ordered_lists {
         let slice = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
@@ -1394,9 +1983,7 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
         let value = slice.as_str().parse::<u32>().ok().unwrap();
 
         if value != 1 {
-            context.push(" start=\"");
-            context.push(&value.to_string());
-            context.push("\"");
+            context.push_attribute("start", &value.to_string());
         }
     }
 }
@@ -1442,21 +2029,19 @@ fn on_exit_media(context: &mut CompileContext) {
         None
     };
 
-    if !is_in_image {
+    if is_in_image {
         if media.image {
-            context.push("<img src=\"");
-        } else {
-            context.push("<a href=\"");
-        };
-
+            context.push(&label);
+        }
+    } else {
         let destination = if let Some(index) = definition_index {
             context.definitions[index].destination.as_ref()
         } else {
             media.destination.as_ref()
         };
 
-        if let Some(destination) = destination {
-            let url = if context.options.allow_dangerous_protocol {
+        let url = if let Some(destination) = destination {
+            if context.options.allow_dangerous_protocol {
                 sanitize(destination)
             } else {
                 sanitize_with_protocols(
@@ -1467,21 +2052,19 @@ fn on_exit_media(context: &mut CompileContext) {
                         &SAFE_PROTOCOL_HREF
                     },
                 )
-            };
-            context.push(&url);
-        }
+            }
+        } else {
+            String::new()
+        };
 
         if media.image {
-            context.push("\" alt=\"");
+            context.push("<img");
+            context.push_attribute("src", &url);
+            context.push_attribute("alt", &label);
+        } else {
+            context.push("<a");
+            context.push_attribute("href", &url);
         };
-    }
-
-    if media.image {
-        context.push(&label);
-    }
-
-    if !is_in_image {
-        context.push("\"");
 
         let title = if let Some(index) = definition_index {
             context.definitions[index].title.clone()
@@ -1490,9 +2073,7 @@ fn on_exit_media(context: &mut CompileContext) {
         };
 
         if let Some(title) = title {
-            context.push(" title=\"");
-            context.push(&title);
-            context.push("\"");
+            context.push_attribute("title", &title);
         };
 
         if media.image {
@@ -1517,9 +2098,11 @@ fn on_exit_paragraph(context: &mut CompileContext) {
 
     if *tight {
         context.slurp_one_line_ending = true;
-    } else {
+    } else if context.options.wrap_paragraphs || !context.tight_stack.is_empty() {
         context.push("</p>");
     }
+
+    context.mapping_end();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`ReferenceString`][Name::ReferenceString].
@@ -1554,23 +2137,67 @@ fn on_exit_strong(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_exit_thematic_break(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<hr />");
+    context.mapping_start(&Name::ThematicBreak);
+
+    if let Some(html) = &context.options.thematic_break_html {
+        context.push(html);
+    } else {
+        context.push("<hr");
+        context.push_source_position(&Name::ThematicBreak);
+        context.push(" />");
+    }
+
+    context.mapping_end();
+}
+
+/// Check whether the events from `first` to `last` (inclusive) contain a GFM
+/// footnote definition that is called.
+fn has_called_gfm_footnote_definition(context: &CompileContext, first: usize, last: usize) -> bool {
+    let mut index = first;
+
+    while index <= last {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit && event.name == Name::GfmFootnoteDefinitionLabelString {
+            let indices = Position::from_exit_event(context.events, index).to_indices();
+            let id = normalize_identifier(
+                Slice::from_indices(context.bytes, indices.0, indices.1).as_str(),
+            );
+
+            if context
+                .gfm_footnote_definition_calls
+                .iter()
+                .any(|(call, _)| *call == id)
+            {
+                return true;
+            }
+        }
+
+        index += 1;
+    }
+
+    false
 }
 
 /// Generate a footnote section.
 fn generate_footnote_section(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<section data-footnotes=\"\" class=\"footnotes\"><");
+    context.mapping_start_generated();
+    context.push("<section");
+    context.push_attribute("data-footnotes", "");
+    context.push_attribute("class", "footnotes");
+    context.push("><");
     if let Some(ref value) = context.options.gfm_footnote_label_tag_name {
         context.push(&encode(value, context.encode_html));
     } else {
         context.push("h2");
     }
-    context.push(" id=\"footnote-label\" ");
+    context.push_attribute("id", "footnote-label");
     if let Some(ref value) = context.options.gfm_footnote_label_attributes {
+        context.push(" ");
         context.push(value);
     } else {
-        context.push("class=\"sr-only\"");
+        context.push_attribute("class", "sr-only");
     }
     context.push(">");
     if let Some(ref value) = context.options.gfm_footnote_label {
@@ -1598,6 +2225,7 @@ fn generate_footnote_section(context: &mut CompileContext) {
     context.push("</ol>");
     context.line_ending();
     context.push("</section>");
+    context.mapping_end();
     context.line_ending();
 }
 
@@ -1621,16 +2249,12 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         "expected definition"
     );
 
+    let prefix = gfm_footnote_clobber_prefix(context);
     context.line_ending();
-    context.push("<li id=\"");
-    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-        context.push(&encode(value, context.encode_html));
-    } else {
-        context.push("user-content-");
-    }
-    context.push("fn-");
-    context.push(&safe_id);
-    context.push("\">");
+    let start = context.buffers.last().unwrap().len();
+    context.push("<li");
+    context.push_attribute("id", &format!("{}fn-{}", prefix, safe_id));
+    context.push(">");
     context.line_ending();
 
     // Create one or more backreferences.
@@ -1640,25 +2264,23 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         if reference_index != 0 {
             backreferences.push(' ');
         }
-        backreferences.push_str("<a href=\"#");
-        if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
-            backreferences.push_str(&encode(value, context.encode_html));
-        } else {
-            backreferences.push_str("user-content-");
-        }
-        backreferences.push_str("fnref-");
-        backreferences.push_str(&safe_id);
+        let mut href = format!("#{}fnref-{}", prefix, safe_id);
         if reference_index != 0 {
-            backreferences.push('-');
-            backreferences.push_str(&(reference_index + 1).to_string());
+            href.push('-');
+            href.push_str(&(reference_index + 1).to_string());
         }
-        backreferences.push_str("\" data-footnote-backref=\"\" aria-label=\"");
-        if let Some(ref value) = context.options.gfm_footnote_back_label {
-            backreferences.push_str(&encode(value, context.encode_html));
+        let label = if let Some(ref value) = context.options.gfm_footnote_back_label {
+            encode(value, context.encode_html)
         } else {
-            backreferences.push_str("Back to content");
-        }
-        backreferences.push_str("\" class=\"data-footnote-backref\">↩");
+            "Back to content".into()
+        };
+        let quote = &context.options.quote;
+        backreferences.push_str("<a");
+        backreferences.push_str(&attribute(quote, "href", &href));
+        backreferences.push_str(&attribute(quote, "data-footnote-backref", ""));
+        backreferences.push_str(&attribute(quote, "aria-label", &label));
+        backreferences.push_str(&attribute(quote, "class", "data-footnote-backref"));
+        backreferences.push_str(">↩");
         if reference_index != 0 {
             backreferences.push_str("<sup>");
             backreferences.push_str(&(reference_index + 1).to_string());
@@ -1670,6 +2292,8 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     }
 
     let value = context.gfm_footnote_definitions[definition_index].1.clone();
+    let mut mappings = context.gfm_footnote_definitions[definition_index].2.clone();
+    let offset = context.buffers.last().unwrap().len();
     let bytes = value.as_bytes();
     let mut byte_index = bytes.len();
     // Move back past EOL.
@@ -1688,6 +2312,17 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         && bytes[byte_index - 1] == b'>'
     {
         let (before, after) = bytes.split_at(byte_index - 4);
+        let size = 1 + backreferences.len();
+
+        for mapping in &mut mappings {
+            if mapping.output.start > before.len() {
+                mapping.output.start += size;
+            }
+            if mapping.output.end > before.len() {
+                mapping.output.end += size;
+            }
+        }
+
         let mut result = String::new();
         result.push_str(str::from_utf8(before).unwrap());
         result.push(' ');
@@ -1701,6 +2336,21 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     }
     context.line_ending_if_needed();
     context.push("</li>");
+
+    // The first mapping is of the definition itself, which is the item.
+    if let Some(all) = context.mappings.as_mut() {
+        let depth = context.buffers.len();
+        let end = context.buffers.last().unwrap().len();
+
+        for (index, mut mapping) in mappings.into_iter().enumerate() {
+            mapping.output = if index == 0 {
+                start..end
+            } else {
+                mapping.output.start + offset..mapping.output.end + offset
+            };
+            all.push((depth, mapping));
+        }
+    }
 }
 
 /// Generate an autolink (used by unicode autolinks and GFM autolink literals).
@@ -1709,7 +2359,9 @@ fn generate_autolink(
     protocol: Option<&str>,
     value: &str,
     is_gfm_literal: bool,
+    is_email: bool,
 ) {
+    let obfuscate = is_email && context.options.obfuscate_email_autolinks;
     let mut is_in_link = false;
     let mut index = 0;
 
@@ -1722,26 +2374,264 @@ fn generate_autolink(
     }
 
     if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
-        context.push("<a href=\"");
+        context.push("<a");
         let url = if let Some(protocol) = protocol {
             format!("{}{}", protocol, value)
         } else {
             value.into()
         };
 
-        let url = if context.options.allow_dangerous_protocol {
+        // Email addresses are always `mailto:` and ASCII, so they are fine to
+        // encode as character references instead of sanitizing them.
+        let url = if obfuscate {
+            encode_character_references(&url)
+        } else if context.options.allow_dangerous_protocol {
             sanitize(&url)
         } else {
             sanitize_with_protocols(&url, &SAFE_PROTOCOL_HREF)
         };
 
-        context.push(&url);
-        context.push("\">");
+        context.push_attribute("href", &url);
+        context.push(">");
     }
 
-    context.push(&encode(value, context.encode_html));
+    if obfuscate {
+        context.push(&encode_character_references(value));
+    } else {
+        context.push(&encode(value, context.encode_html));
+    }
 
     if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
         context.push("</a>");
     }
 }
+
+/// Encode every character as a character reference, alternating between
+/// decimal and hexadecimal.
+///
+/// This is used to obfuscate email addresses: they look the same in browsers,
+/// but are harder to find for naïve harvesters.
+fn encode_character_references(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() * 6);
+
+    for (index, char) in value.chars().enumerate() {
+        let value = if index % 2 == 0 {
+            format!("&#{};", char as u32)
+        } else {
+            format!("&#x{:x};", char as u32)
+        };
+        result.push_str(&value);
+    }
+
+    result
+}
+
+/// Get the encoded clobber prefix of GFM footnotes.
+fn gfm_footnote_clobber_prefix(context: &CompileContext) -> String {
+    if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
+        encode(value, context.encode_html)
+    } else {
+        "user-content-".into()
+    }
+}
+
+/// Serialize an attribute (` name="value"`), with a value that is already
+/// encoded.
+///
+/// Values are encoded for double quotes already (`"` as `&quot;`), so only
+/// single quotes need to be encoded here.
+pub(crate) fn attribute(quote: &Quote, name: &str, value: &str) -> String {
+    let value = if *quote == Quote::Single {
+        value.replace('\'', "&#39;")
+    } else {
+        value.into()
+    };
+    let quote = quote.as_str();
+    format!(" {}={}{}{}", name, quote, value, quote)
+}
+
+/// Element to wrap code (flow) or math (flow) in.
+#[derive(Debug)]
+pub(crate) struct WrapperElement {
+    /// Tag name.
+    pub tag_name: String,
+    /// Attributes other than `class`, with values that are not encoded.
+    pub attributes: Vec<(String, String)>,
+    /// Values of all `class` attributes, joined with spaces, not encoded.
+    pub class: Option<String>,
+}
+
+/// Get the elements to wrap code (flow) in, from outer to inner.
+///
+/// See [`code_fence_wrapper`][CompileOptions::code_fence_wrapper].
+/// Elements with invalid tag names, and attributes with invalid names, are
+/// ignored.
+pub(crate) fn code_wrapper(options: &CompileOptions) -> Vec<WrapperElement> {
+    let wrapper = options
+        .code_fence_wrapper
+        .iter()
+        .flatten()
+        .filter_map(|element| wrapper_element(element))
+        .collect::<Vec<_>>();
+
+    if wrapper.is_empty() {
+        math_wrapper()
+    } else {
+        wrapper
+    }
+}
+
+/// Get the elements to wrap math (flow) in, which are also the default
+/// elements for code (flow): `<pre><code>`.
+pub(crate) fn math_wrapper() -> Vec<WrapperElement> {
+    vec![
+        WrapperElement {
+            tag_name: "pre".into(),
+            attributes: vec![],
+            class: None,
+        },
+        WrapperElement {
+            tag_name: "code".into(),
+            attributes: vec![],
+            class: None,
+        },
+    ]
+}
+
+/// Serialize the start of a wrapper element (`<div id="a"`), without its
+/// class and without `>`.
+pub(crate) fn wrapper_open(element: &WrapperElement, quote: &Quote) -> String {
+    let mut result = format!("<{}", element.tag_name);
+    for (name, value) in &element.attributes {
+        result.push_str(&attribute(quote, name, &encode(value, true)));
+    }
+    result
+}
+
+/// Parse an element of `code_fence_wrapper` (`div class="code"`).
+fn wrapper_element(value: &str) -> Option<WrapperElement> {
+    let value = value.trim();
+    let index = value
+        .find(|char: char| char.is_ascii_whitespace())
+        .unwrap_or(value.len());
+    let tag_name = &value[..index];
+
+    if !tag_name.starts_with(|char: char| char.is_ascii_alphabetic())
+        || !tag_name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-')
+    {
+        return None;
+    }
+
+    let mut attributes = vec![];
+    let mut class = None;
+
+    for (name, value) in parse_attributes(&value[index..]) {
+        if name
+            .chars()
+            .any(|char| char.is_control() || matches!(char, '"' | '\'' | '<' | '>' | '/'))
+        {
+            continue;
+        }
+
+        if name.eq_ignore_ascii_case("class") {
+            class = join_class(class.as_deref(), Some(value.trim()));
+        } else {
+            attributes.push((name, value));
+        }
+    }
+
+    Some(WrapperElement {
+        tag_name: tag_name.to_ascii_lowercase(),
+        attributes,
+        class,
+    })
+}
+
+/// Join two space-separated lists of classes.
+pub(crate) fn join_class(left: Option<&str>, right: Option<&str>) -> Option<String> {
+    let classes = [left, right]
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>();
+
+    if classes.is_empty() {
+        None
+    } else {
+        Some(classes.join(" "))
+    }
+}
+
+/// Parse attributes written as HTML, such as `class="a" hidden`.
+///
+/// Character references in values are decoded.
+pub(crate) fn parse_attributes(value: &str) -> Vec<(String, String)> {
+    let bytes = value.as_bytes();
+    let mut attributes = vec![];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index].is_ascii_whitespace() || bytes[index] == b'=' {
+            index += 1;
+            continue;
+        }
+
+        let name_start = index;
+        while index < bytes.len() && !bytes[index].is_ascii_whitespace() && bytes[index] != b'=' {
+            index += 1;
+        }
+        let name = &value[name_start..index];
+
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+
+        let mut attribute_value = String::new();
+
+        if index < bytes.len() && bytes[index] == b'=' {
+            index += 1;
+            while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+                index += 1;
+            }
+
+            let (start, end) = if index < bytes.len() && matches!(bytes[index], b'"' | b'\'') {
+                let quote = bytes[index];
+                let start = index + 1;
+                index = start;
+                while index < bytes.len() && bytes[index] != quote {
+                    index += 1;
+                }
+                let end = index;
+                index += 1;
+                (start, end)
+            } else {
+                let start = index;
+                while index < bytes.len() && !bytes[index].is_ascii_whitespace() {
+                    index += 1;
+                }
+                (start, index)
+            };
+
+            attribute_value = decode_character_references(&value[start..end]);
+        }
+
+        attributes.push((name.into(), attribute_value));
+    }
+
+    attributes
+}
+
+/// Get the rank of a heading (`1` for `h1`) of `depth`, shifted by
+/// [`heading_offset`][CompileOptions::heading_offset], from `1` to `6`.
+pub(crate) fn heading_rank(depth: usize, offset: i8) -> usize {
+    let shift = usize::from(offset.unsigned_abs());
+    let rank = if offset < 0 {
+        depth.saturating_sub(shift)
+    } else {
+        depth.saturating_add(shift)
+    };
+    rank.clamp(1, 6)
+}
@@ -0,0 +1,1495 @@
+//! Turn a syntax tree into markdown.
+
+use crate::mdast::{
+    AlignKind, AttributeContent, AttributeValue, BlockQuote, BreakKind, Code, Definition,
+    FootnoteDefinition, Heading, Image, ImageReference, InlineCode, Link, LinkReference, List,
+    ListItem, Math, MdxJsxFlowElement, MdxJsxTextElement, Node, ReferenceKind, Table,
+};
+use crate::message;
+use crate::util::{
+    char::{classify_opt, Kind as CharacterKind},
+    normalize_identifier::normalize_identifier,
+};
+use crate::SerializeOptions;
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Serialization state.
+#[derive(Debug)]
+struct State<'a> {
+    /// Configuration.
+    options: &'a SerializeOptions,
+    /// Marker of the thematic break in `options`.
+    rule_marker: char,
+    /// Marker that emphasis or strong cannot use, because it is the only child
+    /// of emphasis or strong that uses it.
+    attention_avoid: Option<char>,
+    /// Character after the current phrasing node, if known.
+    after: Option<char>,
+    /// Size of the first word of the phrasing node after the current one, if
+    /// a line ending can be added before it.
+    after_word: Option<usize>,
+    /// Whether we are in a GFM table cell, where pipes must be escaped.
+    table_cell: bool,
+    /// Whether we are in an ATX heading, which cannot contain line endings.
+    heading_atx: bool,
+    /// Whether line endings can be added to text, to wrap it.
+    wrap: bool,
+    /// Size of the prefixes (such as `> `) of the containers we are in.
+    indent: usize,
+    /// Bullets before the current node on its line, when it is the first
+    /// thing in the first item of unordered lists.
+    bullets: Vec<char>,
+}
+
+/// Turn a syntax tree into markdown.
+pub fn serialize(tree: &Node, options: &SerializeOptions) -> Result<String, message::Message> {
+    if !matches!(options.bullet, '*' | '+' | '-') {
+        return Err(invalid("bullet", options.bullet, "`*`, `+`, or `-`"));
+    }
+
+    if let Some(bullet_other) = options.bullet_other {
+        if !matches!(bullet_other, '*' | '+' | '-') || bullet_other == options.bullet {
+            return Err(invalid(
+                "bullet_other",
+                bullet_other,
+                "`*`, `+`, or `-`, different from `bullet`",
+            ));
+        }
+    }
+
+    if !matches!(options.emphasis, '*' | '_') {
+        return Err(invalid("emphasis", options.emphasis, "`*` or `_`"));
+    }
+
+    if !matches!(options.strong, '*' | '_') {
+        return Err(invalid("strong", options.strong, "`*` or `_`"));
+    }
+
+    if !matches!(options.fence, '`' | '~') {
+        return Err(invalid("fence", options.fence, "`` ` `` or `~`"));
+    }
+
+    if options.fence_length < 3 {
+        return Err(invalid("fence_length", options.fence_length, "`3` or more"));
+    }
+
+    let rule_marker = if let Some(marker) = rule_marker(&options.rule) {
+        marker
+    } else {
+        return Err(invalid(
+            "rule",
+            &options.rule,
+            "three or more of `*`, `-`, or `_`",
+        ));
+    };
+
+    let mut state = State {
+        options,
+        rule_marker,
+        attention_avoid: None,
+        after: None,
+        after_word: None,
+        table_cell: false,
+        heading_atx: false,
+        wrap: false,
+        indent: 0,
+        bullets: Vec::new(),
+    };
+    let mut value = flow(&mut state, tree, None)?;
+
+    // HTML (flow) can end in a line ending already.
+    if !value.is_empty() && !value.ends_with('\n') {
+        value.push('\n');
+    }
+
+    Ok(value)
+}
+
+/// Serialize flow (block) content.
+///
+/// `previous` is the marker of a list of the same kind directly before
+/// `node`, which a list cannot use, as they would join.
+fn flow(
+    state: &mut State,
+    node: &Node,
+    previous: Option<char>,
+) -> Result<String, message::Message> {
+    if !matches!(node, Node::List(_)) {
+        state.bullets.clear();
+    }
+
+    let value = match node {
+        Node::Root(node) => flow_children(state, &node.children, false)?,
+        Node::BlockQuote(node) => block_quote(state, node)?,
+        Node::FootnoteDefinition(node) => footnote_definition(state, node)?,
+        Node::MdxJsxFlowElement(node) => mdx_jsx_flow_element(state, node)?,
+        Node::List(node) => list(state, node, previous)?,
+        Node::Code(node) => code(state, node),
+        Node::Math(node) => math(node),
+        Node::Heading(node) => heading(state, node)?,
+        Node::Table(node) => table(state, node)?,
+        Node::Definition(node) => definition(node),
+        Node::Paragraph(node) => {
+            let mut value = String::new();
+            state.wrap = state.options.line_width.is_some();
+            let result = phrasing_children(state, &node.children, &mut value);
+            state.wrap = false;
+            result?;
+            encode_trailing_whitespace(&mut value);
+            value
+        }
+        Node::ThematicBreak(_) => state.options.rule.clone(),
+        Node::Yaml(node) => format!("---\n{}\n---", node.value),
+        Node::Toml(node) => format!("+++\n{}\n+++", node.value),
+        Node::MdxjsEsm(node) => node.value.clone(),
+        Node::MdxFlowExpression(node) => format!("{{{}}}", node.value),
+        Node::ListItem(_) | Node::TableRow(_) | Node::TableCell(_) => {
+            return Err(unexpected(node, "flow"));
+        }
+        // Phrasing directly in flow.
+        _ => {
+            let mut value = String::new();
+            phrasing(state, node, &mut value)?;
+            value
+        }
+    };
+
+    Ok(value)
+}
+
+/// Serialize the children of a flow node.
+///
+/// Blocks are separated by a blank line, unless `tight` is `true`, in which
+/// case they are on their own line where possible.
+fn flow_children(
+    state: &mut State,
+    children: &[Node],
+    tight: bool,
+) -> Result<String, message::Message> {
+    let mut result = String::new();
+    let mut marker = None;
+    let mut index = 0;
+
+    while index < children.len() {
+        let child = &children[index];
+        let mut previous_marker = None;
+        let mut setext = false;
+
+        if index > 0 {
+            let previous = &children[index - 1];
+
+            // Two adjacent lists of the same kind would join together, so
+            // they need different markers.
+            if let (Node::List(a), Node::List(b)) = (previous, child) {
+                if a.ordered == b.ordered {
+                    previous_marker = marker;
+                }
+            }
+
+            // A list or paragraph would swallow what comes after it.
+            let blank = !tight
+                || matches!(previous, Node::List(_))
+                || matches!((previous, child), (Node::Paragraph(_), Node::Paragraph(_)));
+
+            // A thematic break made with dashes would turn a paragraph into a
+            // heading.
+            setext = !blank
+                && state.rule_marker == '-'
+                && matches!(
+                    (previous, child),
+                    (Node::Paragraph(_), Node::ThematicBreak(_))
+                );
+
+            result.push_str(if blank { "\n\n" } else { "\n" });
+        }
+
+        if let Node::List(list) = child {
+            marker = Some(list_marker(state, list, previous_marker));
+        }
+
+        if setext {
+            result.push_str("***");
+        } else {
+            result.push_str(&flow(state, child, previous_marker)?);
+        }
+
+        index += 1;
+    }
+
+    Ok(result)
+}
+
+/// Serialize a block quote.
+fn block_quote(state: &mut State, node: &BlockQuote) -> Result<String, message::Message> {
+    state.indent += 2;
+    let value = flow_children(state, &node.children, false);
+    state.indent -= 2;
+    Ok(indent(&value?, "> ", "> "))
+}
+
+/// Serialize a footnote definition.
+fn footnote_definition(
+    state: &mut State,
+    node: &FootnoteDefinition,
+) -> Result<String, message::Message> {
+    state.indent += 4;
+    let value = flow_children(state, &node.children, false);
+    state.indent -= 4;
+    let label = label(node.label.as_deref(), &node.identifier);
+    Ok(indent(&value?, &format!("[^{}]: ", label), "    "))
+}
+
+/// Get the marker to use for a list: `.` or `)` for ordered lists, and the
+/// bullet for unordered lists.
+///
+/// `previous` is the marker of a list of the same kind directly before it.
+fn list_marker(state: &State, node: &List, previous: Option<char>) -> char {
+    // Lists from markdown keep their marker, where possible.
+    let source = match node.children.first() {
+        Some(Node::ListItem(item)) => item.marker.filter(|marker| {
+            if node.ordered {
+                matches!(marker, '.' | ')')
+            } else {
+                matches!(marker, '*' | '+' | '-')
+            }
+        }),
+        _ => None,
+    };
+
+    if node.ordered {
+        return [source.unwrap_or('.'), '.', ')']
+            .iter()
+            .copied()
+            .find(|marker| Some(*marker) != previous)
+            .unwrap_or('.');
+    }
+
+    let bullet = state.options.bullet;
+    let other = state
+        .options
+        .bullet_other
+        .unwrap_or(if bullet == '-' { '*' } else { '-' });
+    let rest = ['*', '-', '+']
+        .iter()
+        .copied()
+        .find(|marker| *marker != bullet && *marker != other)
+        .unwrap_or(bullet);
+    // An item that starts with a thematic break made with the same marker
+    // would be a thematic break.
+    let rule = node.children.iter().any(|child| {
+        matches!(child, Node::ListItem(item) if matches!(item.children.first(), Some(Node::ThematicBreak(_))))
+    });
+    // An empty first item after two bullets that are the same (such as in
+    // `- - -`) would be a thematic break too.
+    let empty = matches!(
+        node.children.first(),
+        Some(Node::ListItem(item)) if item.children.is_empty() && item.checked.is_none()
+    );
+    let before = if empty && state.bullets.len() > 1 {
+        let last = state.bullets[state.bullets.len() - 1];
+        if state.bullets[state.bullets.len() - 2] == last && last != '+' {
+            Some(last)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    [source.unwrap_or(bullet), bullet, other, rest]
+        .iter()
+        .copied()
+        .find(|marker| {
+            Some(*marker) != previous
+                && Some(*marker) != before
+                && !(rule && *marker == state.rule_marker)
+        })
+        .unwrap_or(bullet)
+}
+
+/// Serialize a list.
+fn list(
+    state: &mut State,
+    node: &List,
+    previous: Option<char>,
+) -> Result<String, message::Message> {
+    let mut result = String::new();
+    let marker = list_marker(state, node, previous);
+    let mut bullets = core::mem::take(&mut state.bullets);
+    bullets.push(marker);
+    let mut number = node.start.unwrap_or(1);
+    let mut index = 0;
+
+    while index < node.children.len() {
+        let child = &node.children[index];
+
+        if index > 0 {
+            result.push_str(match &node.children[index - 1] {
+                // An item with one child is only spread when followed by
+                // two blank lines.
+                Node::ListItem(item) if item.spread && item.children.len() < 2 => "\n\n\n",
+                _ if node.spread => "\n\n",
+                _ => "\n",
+            });
+        }
+
+        // Items from markdown keep their number.
+        if let Node::ListItem(ListItem {
+            number: Some(value),
+            ..
+        }) = child
+        {
+            if node.ordered {
+                number = *value;
+            }
+        }
+
+        let marker = if node.ordered {
+            format!("{}{}", number, marker)
+        } else {
+            marker.to_string()
+        };
+
+        if let Node::ListItem(item) = child {
+            // Only a list directly in the first item, without a checkbox,
+            // is on the same line as the bullets before it.
+            if index == 0 && !node.ordered && item.checked.is_none() {
+                state.bullets = core::mem::take(&mut bullets);
+            }
+
+            let value = list_item(state, item, &marker);
+            state.bullets.clear();
+            result.push_str(&value?);
+        } else {
+            return Err(unexpected(child, "list"));
+        }
+
+        if state.options.increment_list_marker {
+            number += 1;
+        }
+
+        index += 1;
+    }
+
+    Ok(result)
+}
+
+/// Serialize a list item.
+fn list_item(state: &mut State, node: &ListItem, marker: &str) -> Result<String, message::Message> {
+    let mut value = String::new();
+
+    if let Some(checked) = node.checked {
+        value.push_str(if checked { "[x] " } else { "[ ] " });
+    }
+
+    // Items from markdown keep their indent, if it still works with the
+    // marker: more would be indented code.
+    let size = node
+        .indent
+        .filter(|size| *size > marker.len() && *size <= marker.len() + 4)
+        .unwrap_or(marker.len() + 1);
+
+    state.indent += size;
+    let result = flow_children(state, &node.children, !node.spread);
+    state.indent -= size;
+    value.push_str(&result?);
+
+    if value.is_empty() {
+        Ok(marker.into())
+    } else {
+        Ok(indent(
+            &value,
+            &format!("{:width$}", marker, width = size),
+            &" ".repeat(size),
+        ))
+    }
+}
+
+/// Serialize a heading.
+///
+/// Headings are written as ATX headings (`# a`), unless they were setext
+/// headings (`a\n=`), or contain line endings, which only setext headings
+/// can contain.
+/// Setext headings of rank 3 and higher do not exist, so those are always
+/// ATX.
+fn heading(state: &mut State, node: &Heading) -> Result<String, message::Message> {
+    let mut value = String::new();
+
+    let setext = node.depth < 3
+        && (has_line_ending(&node.children) || (node.setext && !node.children.is_empty()));
+
+    if setext {
+        phrasing_children(state, &node.children, &mut value)?;
+        encode_trailing_whitespace(&mut value);
+        let size = value
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count())
+            .max(1);
+        value.push('\n');
+        value.push_str(&(if node.depth == 1 { "=" } else { "-" }).repeat(size));
+    } else {
+        state.heading_atx = true;
+        let result = phrasing_children(state, &node.children, &mut value);
+        state.heading_atx = false;
+        result?;
+        encode_trailing_whitespace(&mut value);
+
+        // A closing sequence would be removed.
+        let text = value.trim_end_matches('#');
+        if text.len() < value.len() && (text.is_empty() || text.ends_with([' ', '\t'])) {
+            value.insert(text.len(), '\\');
+        }
+
+        let sequence = "#".repeat(node.depth.clamp(1, 6).into());
+
+        if value.is_empty() {
+            value.clone_from(&sequence);
+        } else {
+            value = format!("{} {}", sequence, value);
+        }
+
+        if state.options.close_atx {
+            value.push(' ');
+            value.push_str(&sequence);
+        }
+    }
+
+    Ok(value)
+}
+
+/// Serialize code (flow).
+///
+/// Code is always fenced, with a fence longer than any sequence in it.
+fn code(state: &State, node: &Code) -> String {
+    let mut info = String::new();
+
+    if let Some(lang) = &node.lang {
+        info.push_str(lang);
+
+        if let Some(meta) = &node.meta {
+            info.push(' ');
+            info.push_str(meta);
+        }
+    }
+
+    // Backticks cannot occur in the info of backtick fences.
+    let marker = if info.contains('`') {
+        '~'
+    } else {
+        state.options.fence
+    };
+    let fence = String::from(marker)
+        .repeat((longest_run(&node.value, marker) + 1).max(state.options.fence_length));
+    let info = encode_info(&info);
+
+    if node.value.is_empty() {
+        format!("{}{}\n{}", fence, info, fence)
+    } else {
+        format!("{}{}\n{}\n{}", fence, info, node.value, fence)
+    }
+}
+
+/// Serialize math (flow).
+fn math(node: &Math) -> String {
+    let fence = "$".repeat((longest_run(&node.value, '$') + 1).max(2));
+    let info = encode_info(node.meta.as_deref().unwrap_or_default());
+
+    if node.value.is_empty() {
+        format!("{}{}\n{}", fence, info, fence)
+    } else {
+        format!("{}{}\n{}\n{}", fence, info, node.value, fence)
+    }
+}
+
+/// Serialize a definition.
+fn definition(node: &Definition) -> String {
+    let label = label(node.label.as_deref(), &node.identifier);
+    let mut value = format!("[{}]: {}", label, destination(&node.url));
+
+    if let Some(title) = &node.title {
+        value.push(' ');
+        value.push_str(&encode_title(title));
+    }
+
+    value
+}
+
+/// Serialize a GFM table.
+fn table(state: &mut State, node: &Table) -> Result<String, message::Message> {
+    let mut rows = Vec::new();
+
+    for child in &node.children {
+        if let Node::TableRow(row) = child {
+            let mut cells = Vec::new();
+
+            for cell in &row.children {
+                if let Node::TableCell(cell) = cell {
+                    let mut value = String::new();
+                    state.table_cell = true;
+                    let result = phrasing_children(state, &cell.children, &mut value);
+                    state.table_cell = false;
+                    result?;
+                    encode_trailing_whitespace(&mut value);
+                    cells.push(value);
+                } else {
+                    return Err(unexpected(cell, "table row"));
+                }
+            }
+
+            rows.push(cells);
+        } else {
+            return Err(unexpected(child, "table"));
+        }
+    }
+
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < rows.len() {
+        if index > 0 {
+            result.push('\n');
+        }
+
+        result.push_str(&table_row(&rows[index]));
+
+        // Delimiter row, after the head row.
+        if index == 0 {
+            let mut cells = Vec::new();
+            let size = rows[0].len().max(node.align.len()).max(1);
+
+            while cells.len() < size {
+                cells.push(
+                    match node.align.get(cells.len()) {
+                        Some(AlignKind::Left) => ":--",
+                        Some(AlignKind::Right) => "--:",
+                        Some(AlignKind::Center) => ":-:",
+                        _ => "---",
+                    }
+                    .to_string(),
+                );
+            }
+
+            result.push('\n');
+            result.push_str(&table_row(&cells));
+        }
+
+        index += 1;
+    }
+
+    Ok(result)
+}
+
+/// Serialize a row of cells.
+fn table_row(cells: &[String]) -> String {
+    let mut result = String::from("|");
+
+    for cell in cells {
+        if !cell.is_empty() {
+            result.push(' ');
+            result.push_str(cell);
+        }
+
+        result.push_str(" |");
+    }
+
+    result
+}
+
+/// Serialize an MDX JSX element (flow).
+fn mdx_jsx_flow_element(
+    state: &mut State,
+    node: &MdxJsxFlowElement,
+) -> Result<String, message::Message> {
+    let name = node.name.as_deref().unwrap_or_default();
+
+    if node.children.is_empty() {
+        return Ok(mdx_jsx_tag(name, &node.attributes, true));
+    }
+
+    let value = flow_children(state, &node.children, false)?;
+
+    Ok(format!(
+        "{}\n{}\n</{}>",
+        mdx_jsx_tag(name, &node.attributes, false),
+        indent(&value, "  ", "  "),
+        name
+    ))
+}
+
+/// Serialize an MDX JSX element (text).
+fn mdx_jsx_text_element(
+    state: &mut State,
+    node: &MdxJsxTextElement,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let name = node.name.as_deref().unwrap_or_default();
+
+    if node.children.is_empty() {
+        result.push_str(&mdx_jsx_tag(name, &node.attributes, true));
+    } else {
+        result.push_str(&mdx_jsx_tag(name, &node.attributes, false));
+        phrasing_children(state, &node.children, result)?;
+        result.push_str("</");
+        result.push_str(name);
+        result.push('>');
+    }
+
+    Ok(())
+}
+
+/// Serialize an MDX JSX opening (or self-closing) tag.
+fn mdx_jsx_tag(name: &str, attributes: &[AttributeContent], self_closing: bool) -> String {
+    let mut result = format!("<{}", name);
+
+    for attribute in attributes {
+        result.push(' ');
+
+        match attribute {
+            AttributeContent::Expression { value, .. } => {
+                result.push('{');
+                result.push_str(value);
+                result.push('}');
+            }
+            AttributeContent::Property(property) => {
+                result.push_str(&property.name);
+
+                match &property.value {
+                    Some(AttributeValue::Literal(value)) => {
+                        result.push_str("=\"");
+                        result.push_str(&value.replace('&', "&amp;").replace('"', "&quot;"));
+                        result.push('"');
+                    }
+                    Some(AttributeValue::Expression(value)) => {
+                        result.push_str("={");
+                        result.push_str(&value.value);
+                        result.push('}');
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    if self_closing {
+        // Fragments cannot be self-closing.
+        if name.is_empty() && attributes.is_empty() {
+            result.push_str("></>");
+        } else {
+            result.push_str(" />");
+        }
+    } else {
+        result.push('>');
+    }
+
+    result
+}
+
+/// Serialize the children of a phrasing node.
+fn phrasing_children(
+    state: &mut State,
+    children: &[Node],
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let mut index = 0;
+
+    while index < children.len() {
+        let child = &children[index];
+        state.after = match children.get(index + 1) {
+            Some(Node::Text(text)) => text.value.chars().next(),
+            _ => None,
+        };
+        state.after_word = match children.get(index + 1) {
+            // These are not wrapped, so include all of them.
+            Some(
+                next @ (Node::FootnoteReference(_)
+                | Node::Image(_)
+                | Node::ImageReference(_)
+                | Node::LinkReference(_)),
+            ) => Some(next.to_string().chars().count() + 2),
+            // Include some markup, such as `_` or `[`.
+            Some(
+                next @ (Node::Delete(_) | Node::Emphasis(_) | Node::Link(_) | Node::Strong(_)),
+            ) => {
+                let value = next.to_string();
+                Some(value.split(' ').next().unwrap_or_default().chars().count() + 2)
+            }
+            _ => None,
+        };
+
+        // An exclamation mark before a link would turn it into an image.
+        if matches!(
+            child,
+            Node::Link(_) | Node::LinkReference(_) | Node::FootnoteReference(_)
+        ) && result.ends_with('!')
+            && !result.ends_with("\\!")
+        {
+            result.insert(result.len() - 1, '\\');
+        }
+
+        phrasing(state, child, result)?;
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Serialize phrasing (inline) content.
+fn phrasing(state: &mut State, node: &Node, result: &mut String) -> Result<(), message::Message> {
+    match node {
+        Node::Text(node) => escape(state, &node.value, result),
+        Node::Emphasis(node) => attention(state, &node.children, 1, node.marker, result)?,
+        Node::Strong(node) => attention(state, &node.children, 2, node.marker, result)?,
+        Node::Delete(node) => {
+            result.push_str("~~");
+            phrasing_children(state, &node.children, result)?;
+            result.push_str("~~");
+        }
+        Node::InlineCode(node) => inline_code(state, node, result),
+        Node::InlineMath(node) => raw_text(state, &node.value, '$', 1, false, result),
+        Node::Break(node) => {
+            result.push_str(if state.heading_atx {
+                " "
+            } else if node.kind == BreakKind::Trailing {
+                "  \n"
+            } else {
+                "\\\n"
+            });
+        }
+        Node::Html(node) => result.push_str(&node.value),
+        Node::Link(node) => link(state, node, result)?,
+        Node::LinkReference(node) => link_reference(state, node, result)?,
+        Node::Image(node) => image(state, node, result),
+        Node::ImageReference(node) => image_reference(state, node, result),
+        Node::FootnoteReference(node) => {
+            result.push_str("[^");
+            result.push_str(&label(node.label.as_deref(), &node.identifier));
+            result.push(']');
+        }
+        Node::MdxTextExpression(node) => {
+            result.push('{');
+            result.push_str(&node.value);
+            result.push('}');
+        }
+        Node::MdxJsxTextElement(node) => mdx_jsx_text_element(state, node, result)?,
+        _ => return Err(unexpected(node, "phrasing")),
+    }
+
+    Ok(())
+}
+
+/// Serialize emphasis (`size: 1`) or strong (`size: 2`).
+///
+/// The marker of the node is used if it has one, the marker from the options
+/// otherwise.
+/// Underscores cannot be used inside words (`a*b*c`), so asterisks are used
+/// there.
+/// Emphasis as the only child of emphasis or strong uses the other marker
+/// (`*_a_*`), as the sequences would otherwise merge.
+fn attention(
+    state: &mut State,
+    children: &[Node],
+    size: usize,
+    marker: Option<char>,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    let mut marker = match marker {
+        Some(marker) if marker == '*' || marker == '_' => marker,
+        _ if size == 1 => state.options.emphasis,
+        _ => state.options.strong,
+    };
+
+    if marker == '_'
+        && (classify_opt(result.chars().last()) == CharacterKind::Other
+            || classify_opt(state.after) == CharacterKind::Other)
+    {
+        marker = '*';
+    }
+
+    if state.attention_avoid == Some(marker) {
+        marker = if marker == '*' { '_' } else { '*' };
+    }
+
+    let sequence = String::from(marker).repeat(size);
+    let avoid = state.attention_avoid;
+
+    state.attention_avoid = if matches!(children, [Node::Emphasis(_)]) {
+        Some(marker)
+    } else {
+        None
+    };
+
+    result.push_str(&sequence);
+    let value = phrasing_children(state, children, result);
+    state.attention_avoid = avoid;
+    value?;
+    result.push_str(&sequence);
+    Ok(())
+}
+
+/// Serialize code (text).
+///
+/// Keeps the size of the sequences and the padding of `node`, where possible.
+fn inline_code(state: &State, node: &InlineCode, result: &mut String) {
+    let size = node.sequence_size.unwrap_or(1).max(1);
+
+    if state.table_cell {
+        raw_text(
+            state,
+            &node.value.replace('|', "\\|"),
+            '`',
+            size,
+            node.padding,
+            result,
+        );
+    } else {
+        raw_text(state, &node.value, '`', size, node.padding, result);
+    }
+}
+
+/// Serialize code (text) or math (text).
+///
+/// Uses a sequence of at least `size` `marker`s that does not occur in
+/// `value`, and pads `value` with spaces when `padding` is set or when it
+/// would otherwise be stripped or merge with the sequence.
+fn raw_text(
+    state: &State,
+    value: &str,
+    marker: char,
+    size: usize,
+    padding: bool,
+    result: &mut String,
+) {
+    // Line endings are kept if a letter follows, but turned into spaces
+    // otherwise, as they could start other constructs.
+    let mut chars = value.chars().peekable();
+    let mut value = String::with_capacity(value.len());
+
+    while let Some(char) = chars.next() {
+        if matches!(char, '\r' | '\n') {
+            if char == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+
+            if !state.table_cell
+                && !state.heading_atx
+                && chars.peek().map_or(false, char::is_ascii_alphabetic)
+            {
+                value.push('\n');
+            } else {
+                value.push(' ');
+            }
+        } else {
+            value.push(char);
+        }
+    }
+
+    let mut size = size;
+
+    while has_run(&value, marker, size) {
+        size += 1;
+    }
+
+    let sequence = String::from(marker).repeat(size);
+    let space = |c: char| c == ' ' || c == '\n';
+    let pad = (padding && value.contains(|c| !space(c)))
+        || value.starts_with(marker)
+        || value.ends_with(marker)
+        || (value.starts_with(space) && value.ends_with(space) && value.contains(|c| !space(c)));
+
+    result.push_str(&sequence);
+    if pad {
+        result.push(' ');
+    }
+    result.push_str(&value);
+    if pad {
+        result.push(' ');
+    }
+    result.push_str(&sequence);
+}
+
+/// Serialize a link.
+///
+/// Links whose text is their URL (or email address) are written as
+/// autolinks (`<https://example.com>`).
+fn link(state: &mut State, node: &Link, result: &mut String) -> Result<(), message::Message> {
+    if let Some(value) = autolink(node) {
+        result.push('<');
+        result.push_str(value);
+        result.push('>');
+        return Ok(());
+    }
+
+    result.push('[');
+    phrasing_children(state, &node.children, result)?;
+    result.push_str("](");
+    result.push_str(&destination(&node.url));
+
+    if let Some(title) = &node.title {
+        result.push(' ');
+        result.push_str(&encode_title(title));
+    }
+
+    result.push(')');
+    Ok(())
+}
+
+/// Serialize a link reference.
+fn link_reference(
+    state: &mut State,
+    node: &LinkReference,
+    result: &mut String,
+) -> Result<(), message::Message> {
+    // Line endings would change the label.
+    let wrap = state.wrap;
+    let mut value = String::new();
+    state.wrap = false;
+    let done = phrasing_children(state, &node.children, &mut value);
+    state.wrap = wrap;
+    done?;
+
+    // The text is part of the label for collapsed and shortcut references,
+    // so it has to use the markers from the source.
+    if !matches_identifier(&value, &node.identifier) {
+        for marker in ['*', '_'] {
+            let options = SerializeOptions {
+                emphasis: marker,
+                strong: marker,
+                ..state.options.clone()
+            };
+            let mut other = State {
+                options: &options,
+                rule_marker: state.rule_marker,
+                attention_avoid: None,
+                after: None,
+                after_word: None,
+                table_cell: state.table_cell,
+                heading_atx: state.heading_atx,
+                wrap: false,
+                indent: state.indent,
+                bullets: Vec::new(),
+            };
+            let mut text = String::new();
+            phrasing_children(&mut other, &node.children, &mut text)?;
+
+            if matches_identifier(&text, &node.identifier) {
+                value = text;
+                break;
+            }
+        }
+    }
+
+    let plain = matches!(node.children.as_slice(), [Node::Text(text)] if Some(&text.value) == node.label.as_ref());
+    reference(
+        node.reference_kind,
+        node.label.as_deref(),
+        &node.identifier,
+        &value,
+        plain,
+        result,
+    );
+    Ok(())
+}
+
+/// Serialize an image.
+fn image(state: &mut State, node: &Image, result: &mut String) {
+    // Line endings would change the alt.
+    let wrap = state.wrap;
+    result.push_str("![");
+    state.wrap = false;
+    escape(state, &node.alt, result);
+    state.wrap = wrap;
+    result.push_str("](");
+    result.push_str(&destination(&node.url));
+
+    if let Some(title) = &node.title {
+        result.push(' ');
+        result.push_str(&encode_title(title));
+    }
+
+    result.push(')');
+}
+
+/// Serialize an image reference.
+fn image_reference(state: &mut State, node: &ImageReference, result: &mut String) {
+    // Line endings would change the alt and label.
+    let wrap = state.wrap;
+    let mut value = String::new();
+    state.wrap = false;
+    escape(state, &node.alt, &mut value);
+    state.wrap = wrap;
+    result.push('!');
+    reference(
+        node.reference_kind,
+        node.label.as_deref(),
+        &node.identifier,
+        &value,
+        Some(&node.alt) == node.label.as_ref(),
+        result,
+    );
+}
+
+/// Serialize a reference: its `text`, and then the label (`[label]`, `[]`, or
+/// nothing, depending on `kind`).
+///
+/// Collapsed and shortcut references are only used if `text` matches
+/// `identifier`.
+/// When `plain` is `true`, `text` is known to be the same as `label`, so
+/// the label can be used as the text instead.
+fn reference(
+    kind: ReferenceKind,
+    label: Option<&str>,
+    identifier: &str,
+    text: &str,
+    plain: bool,
+    result: &mut String,
+) {
+    let written = written_label(label, identifier);
+    let text = match &written {
+        Some(written) if plain && !matches_identifier(text, identifier) => written,
+        _ => text,
+    };
+
+    result.push('[');
+    result.push_str(text);
+    result.push(']');
+
+    if !matches_identifier(text, identifier) || kind == ReferenceKind::Full {
+        result.push('[');
+        result.push_str(written.as_deref().unwrap_or(identifier));
+        result.push(']');
+    } else if kind == ReferenceKind::Collapsed {
+        result.push_str("[]");
+    }
+}
+
+/// Serialize the label of a definition or reference.
+///
+/// Labels in the tree are the text of the source: character escapes and
+/// references are decoded, and markup is removed.
+/// When the label does not match `identifier` anymore, `identifier` is used
+/// instead.
+fn label(label: Option<&str>, identifier: &str) -> String {
+    written_label(label, identifier).unwrap_or_else(|| identifier.into())
+}
+
+/// Serialize a label, if it matches `identifier`.
+fn written_label(label: Option<&str>, identifier: &str) -> Option<String> {
+    let value = encode_punctuation(label?, "\\[]");
+
+    if matches_identifier(&value, identifier) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Check if a label, as written, matches `identifier`.
+fn matches_identifier(value: &str, identifier: &str) -> bool {
+    normalize_identifier(value) == identifier
+}
+
+/// Get the value of an autolink, if `node` can be one.
+fn autolink(node: &Link) -> Option<&str> {
+    if node.title.is_some() || node.children.len() != 1 {
+        return None;
+    }
+
+    let value = if let Node::Text(text) = &node.children[0] {
+        text.value.as_str()
+    } else {
+        return None;
+    };
+
+    if value
+        .contains(|c: char| c.is_ascii_whitespace() || c.is_ascii_control() || c == '<' || c == '>')
+    {
+        return None;
+    }
+
+    if value == node.url && has_scheme(value) {
+        return Some(value);
+    }
+
+    if node.url.strip_prefix("mailto:") == Some(value) && is_email(value) {
+        return Some(value);
+    }
+
+    None
+}
+
+/// Check if `value` starts with a URL scheme (such as `https:`).
+fn has_scheme(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() && index <= 32 {
+        let byte = bytes[index];
+
+        if byte == b':' {
+            return index > 1;
+        }
+
+        let ok = if index == 0 {
+            byte.is_ascii_alphabetic()
+        } else {
+            byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.')
+        };
+
+        if !ok {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
+/// Check if `value` is an email address as accepted by email autolinks.
+fn is_email(value: &str) -> bool {
+    if let Some((local, domain)) = value.split_once('@') {
+        !local.is_empty()
+            && local
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+/=?^_`{|}~.-".contains(&byte))
+            && domain.split('.').all(|label| {
+                !label.is_empty()
+                    && label.len() <= 63
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .bytes()
+                        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+            })
+    } else {
+        false
+    }
+}
+
+/// Serialize a destination.
+///
+/// Uses the enclosed form (`<a b>`) when the raw form (`a`) cannot be used.
+fn destination(url: &str) -> String {
+    let mut balance = 0;
+    let mut raw = !url.is_empty();
+
+    for char in url.chars() {
+        match char {
+            '(' => balance += 1,
+            ')' if balance == 0 => raw = false,
+            ')' => balance -= 1,
+            '<' => raw = false,
+            _ if char.is_ascii_whitespace() || char.is_ascii_control() => raw = false,
+            _ => {}
+        }
+    }
+
+    if raw && balance == 0 {
+        encode_punctuation(url, "\\&")
+    } else {
+        format!("<{}>", encode_punctuation(url, "\\&<>"))
+    }
+}
+
+/// Serialize a title.
+fn encode_title(title: &str) -> String {
+    format!("\"{}\"", encode_punctuation(title, "\\&\""))
+}
+
+/// Serialize the info of a fenced code or math.
+fn encode_info(info: &str) -> String {
+    encode_punctuation(info, "\\&")
+}
+
+/// Escape certain punctuation in raw values (destinations, titles, info) with
+/// backslashes.
+///
+/// Ampersands are only escaped when they could start a character reference.
+fn encode_punctuation(value: &str, characters: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if characters.contains(char)
+            && (char != '&'
+                || chars
+                    .peek()
+                    .map_or(false, |next| *next == '#' || next.is_ascii_alphanumeric()))
+        {
+            result.push('\\');
+        }
+
+        result.push(char);
+    }
+
+    result
+}
+
+/// Escape text, so that it is not seen as syntax.
+///
+/// Escapes characters that can start constructs anywhere, and characters
+/// that can start constructs at the start of a line when after a line
+/// ending (or at the start of `result`).
+/// Whitespace at the start and end of lines is encoded as character
+/// references, as it would otherwise be stripped.
+/// When wrapping, spaces between words are turned into line endings where
+/// lines would otherwise be longer than `line_width`.
+fn escape(state: &State, value: &str, result: &mut String) {
+    let bytes = value.as_bytes();
+    let mut at_break = result.is_empty() || result.ends_with('\n');
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        let next = bytes.get(index + 1).copied();
+        let mut skip = 0;
+        let replace = match byte {
+            b'\t' | b' '
+                if at_break || {
+                    let mut after = index + 1;
+                    while after < bytes.len() && matches!(bytes[after], b'\t' | b' ') {
+                        after += 1;
+                    }
+                    after < bytes.len() && matches!(bytes[after], b'\n' | b'\r')
+                } =>
+            {
+                Some(if byte == b' ' { "&#x20;" } else { "&#x9;" })
+            }
+            b' ' if wrap(state, value, index, start, result) => Some("\n"),
+            // Blank lines would end the paragraph.
+            b'\n' if state.heading_atx || at_break => Some("&#xA;"),
+            b'\\' | b'*' | b'_' | b'`' | b'~' | b'<' | b'[' | b']' => Some(""),
+            b'|' if state.table_cell || at_break => Some(""),
+            b'&' if next.map_or(false, |next| next == b'#' || next.is_ascii_alphanumeric()) => {
+                Some("")
+            }
+            b'#' if at_break => Some(""),
+            b'>' | b'-' | b'+' | b'=' if at_break && !state.heading_atx => Some(""),
+            b':' if at_break && !state.heading_atx && next == Some(b'-') => Some(""),
+            b'0'..=b'9' if at_break && !state.heading_atx => {
+                // Ordered list item markers.
+                let mut after = index;
+                while after < bytes.len() && bytes[after].is_ascii_digit() {
+                    after += 1;
+                }
+                if after - index < 10 && after < bytes.len() && matches!(bytes[after], b'.' | b')')
+                {
+                    skip = after - index;
+                }
+                None
+            }
+            _ => None,
+        };
+
+        if skip > 0 {
+            // Escape the `.` or `)` after the digits.
+            result.push_str(&value[start..index + skip]);
+            result.push('\\');
+            start = index + skip;
+            index += skip;
+        } else if let Some(replace) = replace {
+            result.push_str(&value[start..index]);
+
+            if replace.is_empty() {
+                result.push('\\');
+                start = index;
+            } else {
+                result.push_str(replace);
+                start = index + 1;
+            }
+        }
+
+        at_break = (matches!(byte, b'\n' | b'\r') && replace.is_none()) || replace == Some("\n");
+        index += 1;
+    }
+
+    result.push_str(&value[start..]);
+}
+
+/// Check whether to wrap at the space at `index` in `value`.
+///
+/// Only spaces between words are used, as whitespace around line endings
+/// would be stripped.
+/// Spaces at the end of text are used when a node that cannot start other
+/// constructs (such as emphasis) comes after it.
+/// `start` is where the part of `value` that is not yet in `result` starts.
+fn wrap(state: &State, value: &str, index: usize, start: usize, result: &str) -> bool {
+    let width = if let (true, Some(width)) = (state.wrap, state.options.line_width) {
+        width
+    } else {
+        return false;
+    };
+    let bytes = value.as_bytes();
+    let before = if index == 0 {
+        result.chars().last()
+    } else {
+        Some(bytes[index - 1] as char)
+    };
+
+    if before.map_or(true, |char| matches!(char, '\t' | '\n' | '\r' | ' ')) {
+        return false;
+    }
+
+    let mut end = index + 1;
+
+    while end < bytes.len() && !matches!(bytes[end], b'\t' | b'\n' | b'\r' | b' ') {
+        end += 1;
+    }
+
+    let word = if end > index + 1 {
+        value[index + 1..end].chars().count()
+    } else if end == bytes.len() {
+        // Space at the end, before another node.
+        if let Some(size) = state.after_word {
+            size
+        } else {
+            return false;
+        }
+    } else {
+        return false;
+    };
+
+    let line = result.rsplit('\n').next().unwrap_or_default();
+    let column = state.indent + line.chars().count() + value[start..index].chars().count();
+
+    column + 1 + word > width
+}
+
+/// Encode whitespace at the end of `value`, which would otherwise be
+/// stripped.
+fn encode_trailing_whitespace(value: &mut String) {
+    let size = value.len() - value.trim_end_matches([' ', '\t']).len();
+
+    if size > 0 {
+        let tail = value.split_off(value.len() - size);
+        for char in tail.chars() {
+            value.push_str(if char == ' ' { "&#x20;" } else { "&#x9;" });
+        }
+    }
+}
+
+/// Prefix each line in `value`.
+///
+/// Uses `first` for the first line, and `rest` for the other lines.
+/// Prefixes are trimmed on blank lines.
+fn indent(value: &str, first: &str, rest: &str) -> String {
+    let mut result = String::new();
+
+    for (index, line) in value.split('\n').enumerate() {
+        let prefix = if index == 0 { first } else { rest };
+
+        if index > 0 {
+            result.push('\n');
+        }
+
+        if line.is_empty() {
+            result.push_str(prefix.trim_end());
+        } else {
+            result.push_str(prefix);
+            result.push_str(line);
+        }
+    }
+
+    result
+}
+
+/// Check if there are line endings in phrasing content.
+fn has_line_ending(children: &[Node]) -> bool {
+    children.iter().any(|child| match child {
+        Node::Break(_) => true,
+        Node::Text(node) => node.value.contains(['\r', '\n']),
+        _ => child
+            .children()
+            .map_or(false, |children| has_line_ending(children)),
+    })
+}
+
+/// Get the size of the longest run of `marker` in `value`.
+fn longest_run(value: &str, marker: char) -> usize {
+    let mut longest = 0;
+    let mut size = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            size += 1;
+            longest = longest.max(size);
+        } else {
+            size = 0;
+        }
+    }
+
+    longest
+}
+
+/// Check if there is a run of exactly `size` `marker`s in `value`.
+fn has_run(value: &str, marker: char, size: usize) -> bool {
+    let mut current = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            current += 1;
+        } else {
+            if current == size {
+                return true;
+            }
+            current = 0;
+        }
+    }
+
+    current == size
+}
+
+/// Get the marker of a thematic break, if `value` is one.
+fn rule_marker(value: &str) -> Option<char> {
+    let marker = value
+        .chars()
+        .next()
+        .filter(|marker| matches!(marker, '*' | '-' | '_'))?;
+    let mut size = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            size += 1;
+        } else if !matches!(char, '\t' | ' ') {
+            return None;
+        }
+    }
+
+    if size < 3 {
+        None
+    } else {
+        Some(marker)
+    }
+}
+
+/// Create an error for an option with a value that cannot be used.
+fn invalid(name: &str, value: impl core::fmt::Display, expected: &str) -> message::Message {
+    message::Message {
+        place: None,
+        reason: format!(
+            "Cannot serialize with `{}` as `{}`, expected {}",
+            value, name, expected
+        ),
+        rule_id: Box::new("invalid-option".into()),
+        source: Box::new("markdown-rs".into()),
+    }
+}
+
+/// Create an error for a node that cannot occur in a place.
+fn unexpected(node: &Node, place: &str) -> message::Message {
+    message::Message {
+        place: node
+            .position()
+            .map(|position| Box::new(message::Place::Position(position.clone()))),
+        reason: format!("Unexpected node `{}` in {}", node.name(), place),
+        rule_id: Box::new("unexpected-node".into()),
+        source: Box::new("markdown-rs".into()),
+    }
+}
@@ -2,12 +2,14 @@
 
 use crate::event::{Event, Kind, Name};
 use crate::mdast::{
-    AttributeContent, AttributeValue, AttributeValueExpression, BlockQuote, Break, Code,
-    Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
-    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
-    MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression,
-    MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text,
-    ThematicBreak, Toml, Yaml,
+    self, AttributeContent, AttributeValue, AttributeValueExpression, Break, BreakKind,
+    MdxJsxAttribute, ReferenceKind, ThematicBreak,
+};
+use crate::mdast_ref::{
+    BlockQuote, Code, Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading,
+    Html, Image, ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
+    MdxFlowExpression, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node,
+    Paragraph, Root, Strong, Table, TableCell, TableRow, Text, Toml, Yaml,
 };
 use crate::message;
 use crate::unist::{Point, Position};
@@ -16,26 +18,25 @@ use crate::util::{
         decode as decode_character_reference, parse as parse_character_reference,
     },
     infer::{gfm_table_align, list_item_loose, list_loose},
+    location::Encoder,
     mdx_collect::{collect, Result as CollectResult},
     normalize_identifier::normalize_identifier,
-    slice::{Position as SlicePosition, Slice},
+    raw_text::{decode as decode_raw_text, padded as raw_text_padded},
+    slice::{substring, Position as SlicePosition, Slice},
 };
+use crate::{ParseOptions, PositionEncoding};
 use alloc::{
-    boxed::Box,
-    format,
-    string::{String, ToString},
-    vec,
-    vec::Vec,
+    borrow::Cow, boxed::Box, collections::BTreeMap, format, string::String, vec, vec::Vec,
 };
-use core::str;
+use core::mem;
 
 /// A reference to something.
 #[derive(Debug)]
-struct Reference {
+struct Reference<'a> {
     #[allow(clippy::struct_field_names)]
     reference_kind: Option<ReferenceKind>,
     identifier: String,
-    label: String,
+    label: Cow<'a, str>,
 }
 
 /// Info on a tag.
@@ -70,13 +71,13 @@ struct JsxTag {
     end: Point,
 }
 
-impl Reference {
-    fn new() -> Reference {
+impl<'a> Reference<'a> {
+    fn new() -> Reference<'a> {
         Reference {
             // Assume shortcut: removed on a resource, changed on a reference.
             reference_kind: Some(ReferenceKind::Shortcut),
             identifier: String::new(),
-            label: String::new(),
+            label: Cow::Borrowed(""),
         }
     }
 }
@@ -84,10 +85,12 @@ impl Reference {
 /// Context used to compile markdown.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
-struct CompileContext<'a> {
+struct CompileContext<'e, 'a> {
     // Static info.
     /// List of events.
-    events: &'a [Event],
+    events: &'e [Event],
+    /// Input value.
+    value: &'a str,
     /// List of bytes.
     bytes: &'a [u8],
     // Fields used by handlers to track the things they need to track to
@@ -98,21 +101,34 @@ struct CompileContext<'a> {
     heading_setext_text_after: bool,
     jsx_tag_stack: Vec<JsxTag>,
     jsx_tag: Option<JsxTag>,
-    media_reference_stack: Vec<Reference>,
+    media_reference_stack: Vec<Reference<'a>>,
     raw_flow_fence_seen: bool,
+    /// Whether to add positional info to nodes.
+    positions: bool,
+    /// Whether to add positional info of markers to nodes.
+    marker_positions: bool,
     // Intermediate results.
     /// Primary tree and buffers.
-    trees: Vec<(Node, Vec<usize>, Vec<usize>)>,
+    ///
+    /// Each is a stack of nodes that are open (the root first), which are
+    /// added to their parent when they close, and a stack of the indices of
+    /// the events that opened them.
+    trees: Vec<(Vec<Node<'a>>, Vec<usize>)>,
     /// Current event index.
     index: usize,
 }
 
-impl<'a> CompileContext<'a> {
+impl<'e, 'a> CompileContext<'e, 'a> {
     /// Create a new compile context.
-    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+    fn new(
+        events: &'e [Event],
+        value: &'a str,
+        positions: bool,
+        marker_positions: bool,
+    ) -> CompileContext<'e, 'a> {
         let tree = Node::Root(Root {
             children: vec![],
-            position: Some(Position {
+            position: positions.then(|| Position {
                 start: if events.is_empty() {
                     Point::new(1, 1, 0)
                 } else {
@@ -124,11 +140,13 @@ impl<'a> CompileContext<'a> {
                     events[events.len() - 1].point.to_unist()
                 },
             }),
+            data: None,
         });
 
         CompileContext {
             events,
-            bytes,
+            value,
+            bytes: value.as_bytes(),
             character_reference_marker: 0,
             gfm_table_inside: false,
             hard_break_after: false,
@@ -137,7 +155,9 @@ impl<'a> CompileContext<'a> {
             jsx_tag: None,
             media_reference_stack: vec![],
             raw_flow_fence_seen: false,
-            trees: vec![(tree, vec![], vec![])],
+            positions,
+            marker_positions,
+            trees: vec![(vec![tree], vec![])],
             index: 0,
         }
     }
@@ -145,75 +165,126 @@ impl<'a> CompileContext<'a> {
     /// Push a buffer.
     fn buffer(&mut self) {
         self.trees.push((
-            Node::Paragraph(Paragraph {
+            vec![Node::Paragraph(Paragraph {
                 children: vec![],
                 position: None,
-            }),
-            vec![],
+                data: None,
+            })],
             vec![],
         ));
     }
 
-    /// Pop a buffer, returning its value.
-    fn resume(&mut self) -> Node {
-        if let Some((node, stack_a, stack_b)) = self.trees.pop() {
+    /// Pop a buffer, returning its node.
+    fn resume(&mut self) -> Node<'a> {
+        if let Some((mut stack_a, stack_b)) = self.trees.pop() {
             debug_assert_eq!(
                 stack_a.len(),
-                0,
-                "expected stack (nodes in tree) to be drained"
+                1,
+                "expected stack (open nodes) to be drained"
             );
             debug_assert_eq!(
                 stack_b.len(),
                 0,
                 "expected stack (opening events) to be drained"
             );
-            node
+            stack_a.swap_remove(0)
         } else {
             unreachable!("Cannot resume w/o buffer")
         }
     }
 
-    fn tail_mut(&mut self) -> &mut Node {
-        let (tree, stack, _) = self.trees.last_mut().expect("Cannot get tail w/o tree");
-        delve_mut(tree, stack)
+    /// Pop a buffer, returning its value.
+    fn resume_value(&mut self) -> Cow<'a, str> {
+        value(&self.resume())
+    }
+
+    /// Get the positions of markers (`names`) in the construct entered at
+    /// the current event, if marker positions are on.
+    ///
+    /// Markers of nested media (such as an image in a link) are skipped.
+    fn markers(&self, names: &[Name]) -> Vec<Position> {
+        let mut markers = vec![];
+
+        if !self.marker_positions {
+            return markers;
+        }
+
+        let name = &self.events[self.index].name;
+        let mut index = self.index + 1;
+        let mut depth = 0;
+
+        while index < self.events.len() {
+            let event = &self.events[index];
+
+            if event.name == *name
+                || matches!(event.name, Name::GfmFootnoteCall | Name::Image | Name::Link)
+            {
+                if event.kind == Kind::Enter {
+                    depth += 1;
+                } else if depth == 0 {
+                    break;
+                } else {
+                    depth -= 1;
+                }
+            } else if depth == 0 && event.kind == Kind::Enter && names.contains(&event.name) {
+                markers.push(Position {
+                    start: event.point.to_unist(),
+                    end: self.events[index + 1].point.to_unist(),
+                });
+            }
+
+            index += 1;
+        }
+
+        markers
+    }
+
+    fn tail_mut(&mut self) -> &mut Node<'a> {
+        let (stack, _) = self.trees.last_mut().expect("Cannot get tail w/o tree");
+        stack.last_mut().expect("Cannot get tail w/o node")
     }
 
-    fn tail_penultimate_mut(&mut self) -> &mut Node {
-        let (tree, stack, _) = self.trees.last_mut().expect("Cannot get tail w/o tree");
-        delve_mut(tree, &stack[0..(stack.len() - 1)])
+    fn tail_penultimate_mut(&mut self) -> &mut Node<'a> {
+        let (stack, _) = self.trees.last_mut().expect("Cannot get tail w/o tree");
+        let index = stack.len() - 2;
+        &mut stack[index]
     }
 
-    fn tail_push(&mut self, mut child: Node) {
-        if child.position().is_none() {
+    fn tail_push(&mut self, mut child: Node<'a>) {
+        if self.positions && child.position().is_none() {
             child.position_set(Some(position_from_event(&self.events[self.index])));
         }
 
-        let (tree, stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
-        let node = delve_mut(tree, stack);
-        let children = node.children_mut().expect("Cannot push to non-parent");
-        let index = children.len();
-        children.push(child);
-        stack.push(index);
+        let (stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
+        stack.push(child);
         event_stack.push(self.index);
     }
 
     fn tail_push_again(&mut self) {
-        let (tree, stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
-        let node = delve_mut(tree, stack);
-        let children = node.children().expect("Cannot push to non-parent");
-        stack.push(children.len() - 1);
+        let (stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
+        let node = stack.last_mut().expect("Cannot get tail w/o node");
+        let children = node.children_mut().expect("Cannot push to non-parent");
+        let child = children.pop().expect("Cannot push again w/o child");
+        stack.push(child);
         event_stack.push(self.index);
     }
 
     fn tail_pop(&mut self) -> Result<(), message::Message> {
         let ev = &self.events[self.index];
-        let end = ev.point.to_unist();
-        let (tree, stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
-        let node = delve_mut(tree, stack);
-        let pos = node.position_mut().expect("Cannot pop manually added node");
-        pos.end = end;
+        let (stack, event_stack) = self.trees.last_mut().expect("Cannot get tail w/o tree");
+        let mut node = stack.pop().expect("Cannot pop w/o node");
 
-        stack.pop().unwrap();
+        if self.positions {
+            let pos = node.position_mut().expect("Cannot pop manually added node");
+            pos.end = ev.point.to_unist();
+        }
+
+        stack
+            .last_mut()
+            .expect("Cannot pop root")
+            .children_mut()
+            .expect("Cannot push to non-parent")
+            .push(node);
         let left_index = event_stack.pop().unwrap();
         let left = &self.events[left_index];
         if left.name != ev.name {
@@ -224,9 +295,28 @@ impl<'a> CompileContext<'a> {
     }
 }
 
-/// Turn events and bytes into a syntax tree.
-pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message> {
-    let mut context = CompileContext::new(events, bytes);
+/// Turn events and the input value into a syntax tree.
+pub fn compile(
+    events: &[Event],
+    value: &str,
+    options: &ParseOptions,
+) -> Result<mdast::Node, message::Message> {
+    Ok(compile_ref(events, value, options)?.into_owned())
+}
+
+/// Turn events and the input value into a syntax tree that borrows from the
+/// value.
+pub fn compile_ref<'a>(
+    events: &[Event],
+    value: &'a str,
+    options: &ParseOptions,
+) -> Result<Node<'a>, message::Message> {
+    let mut context = CompileContext::new(
+        events,
+        value,
+        options.mdast_positions,
+        options.mdast_marker_positions,
+    );
 
     let mut index = 0;
     while index < events.len() {
@@ -235,16 +325,98 @@ pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message>
     }
 
     debug_assert_eq!(context.trees.len(), 1, "expected 1 final tree");
-    let (tree, _, event_stack) = context.trees.pop().unwrap();
+    let (mut stack, event_stack) = context.trees.pop().unwrap();
 
     if let Some(index) = event_stack.last() {
         let event = &events[*index];
         on_mismatch_error(&mut context, None, event)?;
     }
 
+    let mut tree = stack.swap_remove(0);
+
+    if (options.mdast_positions || options.mdast_marker_positions)
+        && options.position_encoding != PositionEncoding::Utf8
+    {
+        encode_positions(
+            &mut tree,
+            &Encoder::new(value.as_bytes(), options.position_encoding),
+        );
+    }
+
     Ok(tree)
 }
 
+/// Get messages for definitions in `tree` that have the same identifier as
+/// an earlier definition, and are thus not used.
+pub fn duplicate_definitions(tree: &mdast::Node) -> Vec<message::Message> {
+    let mut seen: BTreeMap<String, &mdast::Definition> = BTreeMap::new();
+    let mut messages = vec![];
+
+    for node in tree {
+        if let mdast::Node::Definition(definition) = node {
+            let identifier = normalize_identifier(&definition.identifier);
+
+            if let Some(first) = seen.get(&identifier) {
+                messages.push(message::Message {
+                    place: definition
+                        .position
+                        .as_ref()
+                        .map(|position| Box::new(message::Place::Position(position.clone()))),
+                    reason: format!(
+                        "Unexpected duplicate definition `{}`, expected unique identifiers{}",
+                        definition.label.as_ref().unwrap_or(&definition.identifier),
+                        first
+                            .position
+                            .as_ref()
+                            .map_or(String::new(), |position| format!(
+                                " (the first definition, at {}:{}, is used)",
+                                position.start.line, position.start.column
+                            ))
+                    ),
+                    rule_id: Box::new("duplicate-definition".into()),
+                    source: Box::new("markdown-rs".into()),
+                });
+            } else {
+                seen.insert(identifier, definition);
+            }
+        }
+    }
+
+    messages
+}
+
+/// Turn the positions in a tree, which count bytes, into positions that
+/// count other units.
+fn encode_positions(tree: &mut Node, encoder: &Encoder) {
+    // Nodes to do, without recursion, so that deep trees do not overflow.
+    let mut stack = vec![tree];
+
+    while let Some(node) = stack.pop() {
+        if let Some(position) = node.position_mut() {
+            position.start = encoder.encode(&position.start);
+            position.end = encoder.encode(&position.end);
+        }
+
+        let markers = match node {
+            Node::Heading(node) => Some(&mut node.markers),
+            Node::InlineCode(node) => Some(&mut node.markers),
+            Node::Link(node) => Some(&mut node.markers),
+            _ => None,
+        };
+
+        if let Some(markers) = markers {
+            for marker in markers {
+                marker.start = encoder.encode(&marker.start);
+                marker.end = encoder.encode(&marker.end);
+            }
+        }
+
+        if let Some(children) = node.children_mut() {
+            stack.extend(children.iter_mut());
+        }
+    }
+}
+
 /// Handle the event at `index`.
 fn handle(context: &mut CompileContext, index: usize) -> Result<(), message::Message> {
     context.index = index;
@@ -412,6 +584,8 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::LabelText => on_exit_label_text(context),
         Name::LineEnding => on_exit_line_ending(context)?,
         Name::ListItem => on_exit_list_item(context)?,
+        Name::ListItemMarker => on_exit_list_item_marker(context),
+        Name::ListItemPrefix => on_exit_list_item_prefix(context),
         Name::ListItemValue => on_exit_list_item_value(context),
         Name::MdxEsm | Name::MdxFlowExpression | Name::MdxTextExpression => {
             on_exit_mdx_esm_or_expression(context)?;
@@ -452,8 +626,9 @@ fn on_enter_data(context: &mut CompileContext) {
         context.tail_push_again();
     } else {
         context.tail_push(Node::Text(Text {
-            value: String::new(),
+            value: Cow::Borrowed(""),
             position: None,
+            data: None,
         }));
     }
 }
@@ -461,10 +636,12 @@ fn on_enter_data(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`Autolink`][Name::Autolink].
 fn on_enter_autolink(context: &mut CompileContext) {
     context.tail_push(Node::Link(Link {
-        url: String::new(),
+        url: Cow::Borrowed(""),
         title: None,
         children: vec![],
         position: None,
+        data: None,
+        markers: context.markers(&[Name::AutolinkMarker]),
     }));
 }
 
@@ -473,6 +650,7 @@ fn on_enter_block_quote(context: &mut CompileContext) {
     context.tail_push(Node::BlockQuote(BlockQuote {
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -481,8 +659,9 @@ fn on_enter_code_fenced(context: &mut CompileContext) {
     context.tail_push(Node::Code(Code {
         lang: None,
         meta: None,
-        value: String::new(),
+        value: Cow::Borrowed(""),
         position: None,
+        data: None,
     }));
 }
 
@@ -494,9 +673,18 @@ fn on_enter_code_indented(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeText`][Name::CodeText].
 fn on_enter_code_text(context: &mut CompileContext) {
+    let start = context.events[context.index].point.index;
+    let size = context.bytes[start..]
+        .iter()
+        .take_while(|byte| **byte == b'`')
+        .count();
     context.tail_push(Node::InlineCode(InlineCode {
-        value: String::new(),
+        value: Cow::Borrowed(""),
         position: None,
+        sequence_size: Some(size),
+        padding: false,
+        data: None,
+        markers: context.markers(&[Name::CodeTextSequence]),
     }));
     context.buffer();
 }
@@ -504,8 +692,9 @@ fn on_enter_code_text(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`MathText`][Name::MathText].
 fn on_enter_math_text(context: &mut CompileContext) {
     context.tail_push(Node::InlineMath(InlineMath {
-        value: String::new(),
+        value: Cow::Borrowed(""),
         position: None,
+        data: None,
     }));
     context.buffer();
 }
@@ -520,9 +709,10 @@ fn on_enter_mdx_esm(context: &mut CompileContext) {
         &[Name::MdxEsm],
     );
     context.tail_push(Node::MdxjsEsm(MdxjsEsm {
-        value: result.value,
+        value: Cow::Owned(result.value),
         position: None,
         stops: result.stops,
+        data: None,
     }));
     context.buffer();
 }
@@ -537,9 +727,10 @@ fn on_enter_mdx_flow_expression(context: &mut CompileContext) {
         &[Name::MdxFlowExpression],
     );
     context.tail_push(Node::MdxFlowExpression(MdxFlowExpression {
-        value: result.value,
+        value: Cow::Owned(result.value),
         position: None,
         stops: result.stops,
+        data: None,
     }));
     context.buffer();
 }
@@ -554,9 +745,10 @@ fn on_enter_mdx_text_expression(context: &mut CompileContext) {
         &[Name::MdxTextExpression],
     );
     context.tail_push(Node::MdxTextExpression(MdxTextExpression {
-        value: result.value,
+        value: Cow::Owned(result.value),
         position: None,
         stops: result.stops,
+        data: None,
     }));
     context.buffer();
 }
@@ -564,11 +756,12 @@ fn on_enter_mdx_text_expression(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`Definition`][Name::Definition].
 fn on_enter_definition(context: &mut CompileContext) {
     context.tail_push(Node::Definition(Definition {
-        url: String::new(),
-        identifier: String::new(),
+        url: Cow::Borrowed(""),
+        identifier: Cow::Borrowed(""),
         label: None,
         title: None,
         position: None,
+        data: None,
     }));
 }
 
@@ -577,6 +770,8 @@ fn on_enter_emphasis(context: &mut CompileContext) {
     context.tail_push(Node::Emphasis(Emphasis {
         children: vec![],
         position: None,
+        marker: Some(context.bytes[context.events[context.index].point.index] as char),
+        data: None,
     }));
 }
 
@@ -589,9 +784,10 @@ fn on_enter_gfm_autolink_literal(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmFootnoteCall`][Name::GfmFootnoteCall].
 fn on_enter_gfm_footnote_call(context: &mut CompileContext) {
     context.tail_push(Node::FootnoteReference(FootnoteReference {
-        identifier: String::new(),
+        identifier: Cow::Borrowed(""),
         label: None,
         position: None,
+        data: None,
     }));
     context.media_reference_stack.push(Reference::new());
 }
@@ -599,10 +795,11 @@ fn on_enter_gfm_footnote_call(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmFootnoteDefinition`][Name::GfmFootnoteDefinition].
 fn on_enter_gfm_footnote_definition(context: &mut CompileContext) {
     context.tail_push(Node::FootnoteDefinition(FootnoteDefinition {
-        identifier: String::new(),
+        identifier: Cow::Borrowed(""),
         label: None,
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -611,6 +808,7 @@ fn on_enter_gfm_strikethrough(context: &mut CompileContext) {
     context.tail_push(Node::Delete(Delete {
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -621,6 +819,7 @@ fn on_enter_gfm_table(context: &mut CompileContext) {
         align,
         children: vec![],
         position: None,
+        data: None,
     }));
     context.gfm_table_inside = true;
 }
@@ -630,6 +829,7 @@ fn on_enter_gfm_table_row(context: &mut CompileContext) {
     context.tail_push(Node::TableRow(TableRow {
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -638,12 +838,22 @@ fn on_enter_gfm_table_cell(context: &mut CompileContext) {
     context.tail_push(Node::TableCell(TableCell {
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`HardBreakEscape`][Name::HardBreakEscape].
+/// Handle [`Enter`][Kind::Enter]:{[`HardBreakEscape`][Name::HardBreakEscape],[`HardBreakTrailing`][Name::HardBreakTrailing]}.
 fn on_enter_hard_break(context: &mut CompileContext) {
-    context.tail_push(Node::Break(Break { position: None }));
+    let kind = if context.events[context.index].name == Name::HardBreakTrailing {
+        BreakKind::Trailing
+    } else {
+        BreakKind::Escape
+    };
+    context.tail_push(Node::Break(Break {
+        position: None,
+        kind,
+        data: None,
+    }));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`Frontmatter`][Name::Frontmatter].
@@ -652,13 +862,15 @@ fn on_enter_frontmatter(context: &mut CompileContext) {
     let byte = context.bytes[index];
     let node = if byte == b'+' {
         Node::Toml(Toml {
-            value: String::new(),
+            value: Cow::Borrowed(""),
             position: None,
+            data: None,
         })
     } else {
         Node::Yaml(Yaml {
-            value: String::new(),
+            value: Cow::Borrowed(""),
             position: None,
+            data: None,
         })
     };
 
@@ -692,28 +904,40 @@ fn on_enter_strong(context: &mut CompileContext) {
     context.tail_push(Node::Strong(Strong {
         children: vec![],
         position: None,
+        marker: Some(context.bytes[context.events[context.index].point.index] as char),
+        data: None,
     }));
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_enter_thematic_break(context: &mut CompileContext) {
-    context.tail_push(Node::ThematicBreak(ThematicBreak { position: None }));
+    context.tail_push(Node::ThematicBreak(ThematicBreak {
+        position: None,
+        data: None,
+    }));
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`HeadingAtx`][Name::HeadingAtx].
+/// Handle [`Enter`][Kind::Enter]:{[`HeadingAtx`][Name::HeadingAtx],[`HeadingSetext`][Name::HeadingSetext]}.
 fn on_enter_heading(context: &mut CompileContext) {
     context.tail_push(Node::Heading(Heading {
         depth: 0, // Will be set later.
+        setext: context.events[context.index].name == Name::HeadingSetext,
         children: vec![],
         position: None,
+        data: None,
+        markers: context.markers(&[
+            Name::HeadingAtxSequence,
+            Name::HeadingSetextUnderlineSequence,
+        ]),
     }));
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_enter_html(context: &mut CompileContext) {
     context.tail_push(Node::Html(Html {
-        value: String::new(),
+        value: Cow::Borrowed(""),
         position: None,
+        data: None,
     }));
     context.buffer();
 }
@@ -721,10 +945,11 @@ fn on_enter_html(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`Image`][Name::Image].
 fn on_enter_image(context: &mut CompileContext) {
     context.tail_push(Node::Image(Image {
-        url: String::new(),
+        url: Cow::Borrowed(""),
         title: None,
-        alt: String::new(),
+        alt: Cow::Borrowed(""),
         position: None,
+        data: None,
     }));
     context.media_reference_stack.push(Reference::new());
 }
@@ -732,10 +957,12 @@ fn on_enter_image(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`Link`][Name::Link].
 fn on_enter_link(context: &mut CompileContext) {
     context.tail_push(Node::Link(Link {
-        url: String::new(),
+        url: Cow::Borrowed(""),
         title: None,
         children: vec![],
         position: None,
+        data: None,
+        markers: context.markers(&[Name::LabelMarker, Name::ResourceMarker]),
     }));
     context.media_reference_stack.push(Reference::new());
 }
@@ -751,6 +978,7 @@ fn on_enter_list(context: &mut CompileContext) {
         start: None,
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -761,8 +989,12 @@ fn on_enter_list_item(context: &mut CompileContext) {
     context.tail_push(Node::ListItem(ListItem {
         spread,
         checked: None,
+        marker: None,
+        number: None,
+        indent: None,
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -770,8 +1002,9 @@ fn on_enter_list_item(context: &mut CompileContext) {
 fn on_enter_math_flow(context: &mut CompileContext) {
     context.tail_push(Node::Math(Math {
         meta: None,
-        value: String::new(),
+        value: Cow::Borrowed(""),
         position: None,
+        data: None,
     }));
 }
 
@@ -917,6 +1150,7 @@ fn on_enter_paragraph(context: &mut CompileContext) {
     context.tail_push(Node::Paragraph(Paragraph {
         children: vec![],
         position: None,
+        data: None,
     }));
 }
 
@@ -933,8 +1167,9 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) -> Result<(), message
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     );
+    let input = context.value;
     if let Node::Link(link) = context.tail_mut() {
-        link.url.push_str(value.as_str());
+        append(input, &mut link.url, value.as_str());
     } else {
         unreachable!("expected link on stack");
     }
@@ -949,8 +1184,9 @@ fn on_exit_autolink_email(context: &mut CompileContext) -> Result<(), message::M
         &SlicePosition::from_exit_event(context.events, context.index),
     );
     if let Node::Link(link) = context.tail_mut() {
-        link.url.push_str("mailto:");
-        link.url.push_str(value.as_str());
+        let url = link.url.to_mut();
+        url.push_str("mailto:");
+        url.push_str(value.as_str());
     } else {
         unreachable!("expected link on stack");
     }
@@ -983,7 +1219,7 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
             .expect("expected to parse only valid named references");
 
     if let Node::Text(node) = context.tail_mut() {
-        node.value.push_str(value.as_str());
+        node.value.to_mut().push_str(value.as_str());
     } else {
         unreachable!("expected text on stack");
     }
@@ -993,7 +1229,7 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo].
 fn on_exit_code_fenced_fence_info(context: &mut CompileContext) {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
     if let Node::Code(node) = context.tail_mut() {
         node.lang = Some(value);
     } else {
@@ -1003,7 +1239,7 @@ fn on_exit_code_fenced_fence_info(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta],[`MathFlowFenceMeta`][Name::MathFlowFenceMeta]}.
 fn on_exit_raw_flow_fence_meta(context: &mut CompileContext) {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
     match context.tail_mut() {
         Node::Code(node) => node.meta = Some(value),
         Node::Math(node) => node.meta = Some(value),
@@ -1025,7 +1261,7 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`MathFlow`][Name::MathFlow]}.
 fn on_exit_raw_flow(context: &mut CompileContext) -> Result<(), message::Message> {
-    let value = trim_eol(context.resume().to_string(), true, true);
+    let value = trim_eol(context.resume_value(), true, true);
 
     match context.tail_mut() {
         Node::Code(node) => node.value = value,
@@ -1040,7 +1276,7 @@ fn on_exit_raw_flow(context: &mut CompileContext) -> Result<(), message::Message
 
 /// Handle [`Exit`][Kind::Exit]:[`CodeIndented`][Name::CodeIndented].
 fn on_exit_code_indented(context: &mut CompileContext) -> Result<(), message::Message> {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
 
     if let Node::Code(node) = context.tail_mut() {
         node.value = trim_eol(value, false, true);
@@ -1054,34 +1290,18 @@ fn on_exit_code_indented(context: &mut CompileContext) -> Result<(), message::Me
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
 fn on_exit_raw_text(context: &mut CompileContext) -> Result<(), message::Message> {
-    let mut value = context.resume().to_string();
-
-    // To do: share with `to_html`.
-    // If we are in a GFM table, we need to decode escaped pipes.
-    // This is a rather weird GFM feature.
-    if context.gfm_table_inside {
-        let mut bytes = value.as_bytes().to_vec();
-        let mut index = 0;
-        let mut len = bytes.len();
-        let mut replace = false;
-
-        while index < len {
-            if index + 1 < len && bytes[index] == b'\\' && bytes[index + 1] == b'|' {
-                replace = true;
-                bytes.remove(index);
-                len -= 1;
-            }
-
-            index += 1;
-        }
-
-        if replace {
-            value = str::from_utf8(&bytes).unwrap().into();
-        }
-    }
+    let raw = context.resume_value();
+    let padding = raw_text_padded(&raw);
+    let value = match raw {
+        Cow::Borrowed(raw) => decode_raw_text(raw, context.gfm_table_inside),
+        Cow::Owned(raw) => Cow::Owned(decode_raw_text(&raw, context.gfm_table_inside).into()),
+    };
 
     match context.tail_mut() {
-        Node::InlineCode(node) => node.value = value,
+        Node::InlineCode(node) => {
+            node.value = value;
+            node.padding = padding;
+        }
         Node::InlineMath(node) => node.value = value,
         _ => unreachable!("expected inline code or math on stack for value"),
     }
@@ -1096,8 +1316,9 @@ fn on_exit_data(context: &mut CompileContext) -> Result<(), message::Message> {
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     );
+    let input = context.value;
     if let Node::Text(text) = context.tail_mut() {
-        text.value.push_str(value.as_str());
+        append(input, &mut text.value, value.as_str());
     } else {
         unreachable!("expected text on stack");
     }
@@ -1107,7 +1328,7 @@ fn on_exit_data(context: &mut CompileContext) -> Result<(), message::Message> {
 
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionDestinationString`][Name::DefinitionDestinationString].
 fn on_exit_definition_destination_string(context: &mut CompileContext) {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
     if let Node::Definition(node) = context.tail_mut() {
         node.url = value;
     } else {
@@ -1117,21 +1338,21 @@ fn on_exit_definition_destination_string(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`DefinitionLabelString`][Name::DefinitionLabelString],[`GfmFootnoteDefinitionLabelString`][Name::GfmFootnoteDefinitionLabelString]}.
 fn on_exit_definition_id(context: &mut CompileContext) {
-    let label = context.resume().to_string();
+    let label = context.resume_value();
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     );
-    let identifier = normalize_identifier(slice.as_str()).to_lowercase();
+    let identifier = normalize_identifier(slice.as_str());
 
     match context.tail_mut() {
         Node::Definition(node) => {
             node.label = Some(label);
-            node.identifier = identifier;
+            node.identifier = Cow::Owned(identifier);
         }
         Node::FootnoteDefinition(node) => {
             node.label = Some(label);
-            node.identifier = identifier;
+            node.identifier = Cow::Owned(identifier);
         }
         _ => unreachable!("expected definition or footnote definition on stack"),
     }
@@ -1139,7 +1360,7 @@ fn on_exit_definition_id(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionTitleString`][Name::DefinitionTitleString].
 fn on_exit_definition_title_string(context: &mut CompileContext) {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
     if let Node::Definition(node) = context.tail_mut() {
         node.title = Some(value);
     } else {
@@ -1154,7 +1375,7 @@ fn on_exit_drop(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) -> Result<(), message::Message> {
-    let value = trim_eol(context.resume().to_string(), true, true);
+    let value = trim_eol(context.resume_value(), true, true);
 
     match context.tail_mut() {
         Node::Yaml(node) => node.value = value,
@@ -1182,11 +1403,12 @@ fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), mess
         _ => None,
     };
 
+    let input = context.value;
     if let Node::Link(link) = context.tail_mut() {
         if let Some(prefix) = prefix {
-            link.url.push_str(prefix);
+            link.url.to_mut().push_str(prefix);
         }
-        link.url.push_str(value.as_str());
+        append(input, &mut link.url, value.as_str());
     } else {
         unreachable!("expected link on stack");
     }
@@ -1197,7 +1419,27 @@ fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), mess
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmTable`][Name::GfmTable].
+///
+/// Rows with fewer cells than the table has columns get empty cells, without
+/// positions, so that cells line up across rows.
 fn on_exit_gfm_table(context: &mut CompileContext) -> Result<(), message::Message> {
+    if let Node::Table(table) = context.tail_mut() {
+        let size = table.align.len();
+        for row in &mut table.children {
+            if let Node::TableRow(row) = row {
+                while row.children.len() < size {
+                    row.children.push(Node::TableCell(TableCell {
+                        children: vec![],
+                        position: None,
+                        data: None,
+                    }));
+                }
+            }
+        }
+    } else {
+        unreachable!("expected table on stack");
+    }
+
     on_exit(context)?;
     context.gfm_table_inside = false;
     Ok(())
@@ -1268,29 +1510,98 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`LabelText`][Name::LabelText].
 fn on_exit_label_text(context: &mut CompileContext) {
     let mut fragment = context.resume();
-    let label = fragment.to_string();
-    let children = fragment.children_mut().unwrap().split_off(0);
+    let children = mem::take(fragment.children_mut().unwrap());
+    let label = join(&children, value);
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     );
-    let identifier = normalize_identifier(slice.as_str()).to_lowercase();
+    let identifier = normalize_identifier(slice.as_str());
 
     let reference = context
         .media_reference_stack
         .last_mut()
         .expect("expected reference on media stack");
-    reference.label.clone_from(&label);
+    reference.label = label;
     reference.identifier = identifier;
 
     match context.tail_mut() {
         Node::Link(node) => node.children = children,
-        Node::Image(node) => node.alt = label,
+        Node::Image(node) => node.alt = join(&children, alt),
         Node::FootnoteReference(_) => {}
         _ => unreachable!("expected footnote refereence, image, or link on stack"),
     }
 }
 
+/// Get the plain text of the description of an image, which, unlike
+/// [`value()`][], includes the alt of images in it.
+fn alt<'a>(node: &Node<'a>) -> Cow<'a, str> {
+    match node {
+        Node::Image(Image { alt, .. }) | Node::ImageReference(ImageReference { alt, .. }) => {
+            alt.clone()
+        }
+        _ => node
+            .children()
+            .map_or_else(|| value(node), |children| join(children, alt)),
+    }
+}
+
+/// Get the value of a node, like [`ToString`][] on [`mdast::Node`][]:
+/// the value of literals, and the values of the children of parents joined
+/// together.
+fn value<'a>(node: &Node<'a>) -> Cow<'a, str> {
+    match node {
+        Node::MdxjsEsm(MdxjsEsm { value, .. })
+        | Node::Toml(Toml { value, .. })
+        | Node::Yaml(Yaml { value, .. })
+        | Node::InlineCode(InlineCode { value, .. })
+        | Node::InlineMath(InlineMath { value, .. })
+        | Node::MdxTextExpression(MdxTextExpression { value, .. })
+        | Node::Html(Html { value, .. })
+        | Node::Text(Text { value, .. })
+        | Node::Code(Code { value, .. })
+        | Node::Math(Math { value, .. })
+        | Node::MdxFlowExpression(MdxFlowExpression { value, .. }) => value.clone(),
+        _ => node
+            .children()
+            .map_or(Cow::Borrowed(""), |children| join(children, value)),
+    }
+}
+
+/// Get the values of `nodes` with `each`, joined together, borrowing if
+/// there is one node.
+fn join<'a>(nodes: &[Node<'a>], each: fn(&Node<'a>) -> Cow<'a, str>) -> Cow<'a, str> {
+    if nodes.len() == 1 {
+        each(&nodes[0])
+    } else {
+        Cow::Owned(nodes.iter().map(each).collect())
+    }
+}
+
+/// Add `value`, which is a part of `input`, to `target`.
+///
+/// The result borrows from `input` if `target` is empty or borrowed and right
+/// before `value` in `input`.
+fn append<'a>(input: &'a str, target: &mut Cow<'a, str>, value: &'a str) {
+    if target.is_empty() {
+        *target = Cow::Borrowed(value);
+        return;
+    }
+
+    if let Cow::Borrowed(current) = target {
+        let base = input.as_ptr() as usize;
+        let start = current.as_ptr() as usize;
+        let end = start + current.len();
+
+        if start >= base && end == value.as_ptr() as usize {
+            *target = Cow::Borrowed(&input[start - base..end - base + value.len()]);
+            return;
+        }
+    }
+
+    target.to_mut().push_str(value);
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`LineEnding`][Name::LineEnding].
 fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Message> {
     if context.heading_setext_text_after {
@@ -1298,14 +1609,16 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
     }
     // Line ending position after hard break is part of it.
     else if context.hard_break_after {
-        let end = context.events[context.index].point.to_unist();
-        let node = context.tail_mut();
-        let tail = node
-            .children_mut()
-            .expect("expected parent")
-            .last_mut()
-            .expect("expected tail (break)");
-        tail.position_mut().unwrap().end = end;
+        if context.positions {
+            let end = context.events[context.index].point.to_unist();
+            let node = context.tail_mut();
+            let tail = node
+                .children_mut()
+                .expect("expected parent")
+                .last_mut()
+                .expect("expected tail (break)");
+            tail.position_mut().unwrap().end = end;
+        }
         context.hard_break_after = false;
     }
     // Line ending is a part of nodes that accept phrasing.
@@ -1328,7 +1641,7 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
 
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) -> Result<(), message::Message> {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
 
     match context.tail_mut() {
         Node::Html(node) => node.value = value,
@@ -1354,7 +1667,7 @@ fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
 
         match siblings.last_mut().unwrap() {
             Node::FootnoteReference(node) => {
-                node.identifier = reference.identifier;
+                node.identifier = Cow::Owned(reference.identifier);
                 node.label = Some(reference.label);
             }
             Node::Image(_) => {
@@ -1362,10 +1675,11 @@ fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
                 if let Some(Node::Image(node)) = siblings.pop() {
                     siblings.push(Node::ImageReference(ImageReference {
                         reference_kind: kind,
-                        identifier: reference.identifier,
+                        identifier: Cow::Owned(reference.identifier),
                         label: Some(reference.label),
                         alt: node.alt,
                         position: node.position,
+                        data: None,
                     }));
                 } else {
                     unreachable!("impossible: it’s an image")
@@ -1376,10 +1690,11 @@ fn on_exit_media(context: &mut CompileContext) -> Result<(), message::Message> {
                 if let Some(Node::Link(node)) = siblings.pop() {
                     siblings.push(Node::LinkReference(LinkReference {
                         reference_kind: kind,
-                        identifier: reference.identifier,
+                        identifier: Cow::Owned(reference.identifier),
                         label: Some(reference.label),
                         children: node.children,
                         position: node.position,
+                        data: None,
                     }));
                 } else {
                     unreachable!("impossible: it’s a link")
@@ -1398,35 +1713,46 @@ fn on_exit_list_item(context: &mut CompileContext) -> Result<(), message::Messag
         if item.checked.is_some() {
             if let Some(Node::Paragraph(paragraph)) = item.children.first_mut() {
                 if let Some(Node::Text(text)) = paragraph.children.first_mut() {
-                    let mut point = text.position.as_ref().unwrap().start.clone();
+                    let mut point = text.position.as_ref().map(|d| d.start.clone());
                     let bytes = text.value.as_bytes();
                     let mut start = 0;
 
                     // Move past eol.
                     if matches!(bytes[0], b'\t' | b' ') {
-                        point.offset += 1;
-                        point.column += 1;
+                        if let Some(point) = point.as_mut() {
+                            point.offset += 1;
+                            point.column += 1;
+                        }
                         start += 1;
                     } else if matches!(bytes[0], b'\r' | b'\n') {
-                        point.line += 1;
-                        point.column = 1;
-                        point.offset += 1;
+                        if let Some(point) = point.as_mut() {
+                            point.line += 1;
+                            point.column = 1;
+                            point.offset += 1;
+                        }
                         start += 1;
                         // Move past the LF of CRLF.
                         if bytes.len() > 1 && bytes[0] == b'\r' && bytes[1] == b'\n' {
-                            point.offset += 1;
+                            if let Some(point) = point.as_mut() {
+                                point.offset += 1;
+                            }
                             start += 1;
                         }
                     }
 
                     // The whole text is whitespace: update the text.
-                    if start == bytes.len() {
+                    let end = bytes.len();
+                    if start == end {
                         paragraph.children.remove(0);
                     } else {
-                        text.value = str::from_utf8(&bytes[start..]).unwrap().into();
-                        text.position.as_mut().unwrap().start = point.clone();
+                        text.value = substring(mem::take(&mut text.value), start, end);
+                        if let Some(point) = &point {
+                            text.position.as_mut().unwrap().start = point.clone();
+                        }
+                    }
+                    if let Some(point) = point {
+                        paragraph.position.as_mut().unwrap().start = point;
                     }
-                    paragraph.position.as_mut().unwrap().start = point;
                 }
             }
         }
@@ -1447,6 +1773,12 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
     .parse()
     .expect("expected list value up to u8");
 
+    if let Node::ListItem(node) = context.tail_mut() {
+        node.number = Some(start);
+    } else {
+        unreachable!("expected list item on stack");
+    }
+
     if let Node::List(node) = context.tail_penultimate_mut() {
         debug_assert!(node.ordered, "expected list to be ordered");
         if node.start.is_none() {
@@ -1457,6 +1789,60 @@ fn on_exit_list_item_value(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`ListItemMarker`][Name::ListItemMarker].
+fn on_exit_list_item_marker(context: &mut CompileContext) {
+    let marker = context.bytes[context.events[context.index - 1].point.index];
+
+    if let Node::ListItem(node) = context.tail_mut() {
+        node.marker = Some(marker as char);
+    } else {
+        unreachable!("expected list item on stack");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`ListItemPrefix`][Name::ListItemPrefix].
+fn on_exit_list_item_prefix(context: &mut CompileContext) {
+    let mut start = context.index;
+    let mut marker = context.index;
+
+    while context.events[start].name != Name::ListItemPrefix
+        || context.events[start].kind != Kind::Enter
+    {
+        if context.events[start].name == Name::ListItemMarker
+            && context.events[start].kind == Kind::Exit
+        {
+            marker = start;
+        }
+
+        start -= 1;
+    }
+
+    // From the value or marker, to the whitespace after it.
+    let size = |end: usize| {
+        Slice::from_position(
+            context.bytes,
+            &SlicePosition {
+                start: &context.events[start + 1].point,
+                end: &context.events[end].point,
+            },
+        )
+        .len()
+    };
+    let mut indent = size(context.index);
+
+    // Like in the parser, when the first line is blank, there is no
+    // whitespace in the prefix, and content is indented by one more.
+    if indent == size(marker) {
+        indent += 1;
+    }
+
+    if let Node::ListItem(node) = context.tail_mut() {
+        node.indent = Some(indent);
+    } else {
+        unreachable!("expected list item on stack");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`MdxJsxFlowTag`][Name::MdxJsxFlowTag],[`MdxJsxTextTag`][Name::MdxJsxTextTag]}.
 fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Message> {
     let mut tag = context.jsx_tag.as_ref().expect("expected tag").clone();
@@ -1500,23 +1886,25 @@ fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Mess
     } else {
         let node = if context.events[context.index].name == Name::MdxJsxFlowTag {
             Node::MdxJsxFlowElement(MdxJsxFlowElement {
-                name: tag.name.clone(),
+                name: tag.name.clone().map(Cow::Owned),
                 attributes: tag.attributes.clone(),
                 children: vec![],
-                position: Some(Position {
+                position: context.positions.then(|| Position {
                     start: tag.start.clone(),
                     end: tag.end.clone(),
                 }),
+                data: None,
             })
         } else {
             Node::MdxJsxTextElement(MdxJsxTextElement {
-                name: tag.name.clone(),
+                name: tag.name.clone().map(Cow::Owned),
                 attributes: tag.attributes.clone(),
                 children: vec![],
-                position: Some(Position {
+                position: context.positions.then(|| Position {
                     start: tag.start.clone(),
                     end: tag.end.clone(),
                 }),
+                data: None,
             })
         };
 
@@ -1641,7 +2029,7 @@ fn on_exit_mdx_jsx_tag_attribute_value_literal(context: &mut CompileContext) {
         .last_mut()
     {
         node.value = Some(AttributeValue::Literal(parse_character_reference(
-            &value.to_string(),
+            &self::value(&value),
         )));
     } else {
         unreachable!("expected property")
@@ -1655,12 +2043,12 @@ fn on_exit_mdx_jsx_tag_self_closing_marker(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`ReferenceString`][Name::ReferenceString].
 fn on_exit_reference_string(context: &mut CompileContext) {
-    let label = context.resume().to_string();
+    let label = context.resume_value();
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
     );
-    let identifier = normalize_identifier(slice.as_str()).to_lowercase();
+    let identifier = normalize_identifier(slice.as_str());
     let reference = context
         .media_reference_stack
         .last_mut()
@@ -1672,7 +2060,7 @@ fn on_exit_reference_string(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`ResourceDestinationString`][Name::ResourceDestinationString].
 fn on_exit_resource_destination_string(context: &mut CompileContext) {
-    let value = context.resume().to_string();
+    let value = context.resume_value();
 
     match context.tail_mut() {
         Node::Link(node) => node.url = value,
@@ -1683,7 +2071,7 @@ fn on_exit_resource_destination_string(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`ResourceTitleString`][Name::ResourceTitleString].
 fn on_exit_resource_title_string(context: &mut CompileContext) {
-    let value = Some(context.resume().to_string());
+    let value = Some(context.resume_value());
 
     match context.tail_mut() {
         Node::Link(node) => node.title = value,
@@ -1701,19 +2089,8 @@ fn position_from_event(event: &Event) -> Position {
     }
 }
 
-/// Resolve the current stack on the tree.
-fn delve_mut<'tree>(mut node: &'tree mut Node, stack: &'tree [usize]) -> &'tree mut Node {
-    let mut stack_index = 0;
-    while stack_index < stack.len() {
-        let index = stack[stack_index];
-        node = &mut node.children_mut().expect("Cannot delve into non-parent")[index];
-        stack_index += 1;
-    }
-    node
-}
-
 /// Remove initial/final EOLs.
-fn trim_eol(value: String, at_start: bool, at_end: bool) -> String {
+fn trim_eol(value: Cow<str>, at_start: bool, at_end: bool) -> Cow<str> {
     let bytes = value.as_bytes();
     let mut start = 0;
     let mut end = bytes.len();
@@ -1741,7 +2118,7 @@ fn trim_eol(value: String, at_start: bool, at_end: bool) -> String {
     }
 
     if start > 0 || end < bytes.len() {
-        str::from_utf8(&bytes[start..end]).unwrap().into()
+        substring(value, start, end)
     } else {
         value
     }
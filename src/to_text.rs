@@ -0,0 +1,474 @@
+//! Turn events into a string of plain text.
+
+use crate::event::{Event, Kind, Name};
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    infer::list_loose,
+    slice::{Position, Slice},
+};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Context used to compile plain text.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug)]
+struct CompileContext<'a> {
+    // Static info.
+    /// List of events.
+    events: &'a [Event],
+    /// List of bytes.
+    bytes: &'a [u8],
+    // Fields used by handlers to track the things they need to track to
+    // compile plain text.
+    /// Number of constructs we are in that are ignored.
+    ignore_depth: usize,
+    /// Number of constructs we are in that contain phrasing with line endings.
+    phrasing_depth: usize,
+    /// Whether we are in code (text).
+    raw_text_inside: bool,
+    /// Whether we saw data in code (flow).
+    raw_flow_seen_data: bool,
+    /// Line endings in code (flow) that are not yet written.
+    raw_flow_line_endings: usize,
+    /// Marker of character reference.
+    character_reference_marker: Option<u8>,
+    /// Whether a GFM task list item check is checked.
+    gfm_task_list_item_checked: bool,
+    /// Whether we saw a row in the current GFM table.
+    gfm_table_seen_row: bool,
+    /// Current GFM table column.
+    gfm_table_column: usize,
+    /// Stack of containers: whether they are tight.
+    tight_stack: Vec<bool>,
+    /// Stack of lists: whether they are ordered, and their next number.
+    list_stack: Vec<(bool, Option<u32>)>,
+    /// Stack of list item indents.
+    indent_stack: Vec<usize>,
+    /// Whether we are at the start of a container.
+    container_start: bool,
+    /// Whether we are at the start of a line.
+    line_start: bool,
+    // Intermediate results.
+    /// Stack of buffers.
+    buffers: Vec<String>,
+    /// Current event index.
+    index: usize,
+}
+
+impl<'a> CompileContext<'a> {
+    /// Create a new compile context.
+    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+        CompileContext {
+            events,
+            bytes,
+            ignore_depth: 0,
+            phrasing_depth: 0,
+            raw_text_inside: false,
+            raw_flow_seen_data: false,
+            raw_flow_line_endings: 0,
+            character_reference_marker: None,
+            gfm_task_list_item_checked: false,
+            gfm_table_seen_row: false,
+            gfm_table_column: 0,
+            tight_stack: vec![],
+            list_stack: vec![],
+            indent_stack: vec![],
+            container_start: false,
+            line_start: false,
+            buffers: vec![String::new()],
+            index: 0,
+        }
+    }
+
+    /// Push a buffer.
+    fn buffer(&mut self) {
+        self.buffers.push(String::new());
+    }
+
+    /// Pop a buffer, returning its value.
+    fn resume(&mut self) -> String {
+        self.buffers.pop().expect("Cannot resume w/o buffer")
+    }
+
+    /// Push a str to the last buffer, indenting it if at the start of a line.
+    fn push(&mut self, value: &str) {
+        if self.line_start && !value.is_empty() {
+            self.line_start = false;
+            let indent = self.indent_stack.iter().sum();
+            self.push(&" ".repeat(indent));
+        }
+
+        let last_buf_opt = self.buffers.last_mut();
+        let last_buf = last_buf_opt.expect("at least one buffer should exist");
+        last_buf.push_str(value);
+    }
+
+    /// Push the value of the current exit event.
+    fn push_slice(&mut self) {
+        let slice = Slice::from_position(
+            self.bytes,
+            &Position::from_exit_event(self.events, self.index),
+        );
+        self.push(slice.as_str());
+    }
+
+    /// Add a line ending.
+    ///
+    /// The next line is indented when something is pushed to it.
+    fn line_ending(&mut self) {
+        self.line_start = false;
+        self.push("\n");
+        self.line_start = true;
+    }
+
+    /// Separate a block from what came before.
+    ///
+    /// Blocks are separated by a blank line, or by a line ending in tight
+    /// lists.
+    /// The first block in a container is not separated: it directly follows
+    /// the container’s prefix (if any).
+    fn block(&mut self) {
+        if self.container_start {
+            self.container_start = false;
+        } else if !self.buffers[0].is_empty() {
+            self.line_ending();
+
+            if !self.tight_stack.last().unwrap_or(&false) {
+                self.line_ending();
+            }
+        }
+    }
+}
+
+/// Turn events and bytes into a string of plain text.
+pub fn compile(events: &[Event], bytes: &[u8]) -> String {
+    let mut context = CompileContext::new(events, bytes);
+
+    while context.index < events.len() {
+        handle(&mut context);
+        context.index += 1;
+    }
+
+    debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
+    context.buffers.pop().expect("expected 1 final buffer")
+}
+
+/// Handle the event at `index`.
+fn handle(context: &mut CompileContext) {
+    let event = &context.events[context.index];
+
+    if ignore(&event.name) {
+        if event.kind == Kind::Enter {
+            context.ignore_depth += 1;
+        } else {
+            context.ignore_depth -= 1;
+        }
+    } else if context.ignore_depth == 0 {
+        if event.kind == Kind::Enter {
+            enter(context);
+        } else {
+            exit(context);
+        }
+    }
+}
+
+/// Check whether everything in a construct is ignored.
+fn ignore(name: &Name) -> bool {
+    matches!(
+        name,
+        Name::CodeFencedFence
+            | Name::Definition
+            | Name::Frontmatter
+            | Name::GfmFootnoteCall
+            | Name::GfmFootnoteDefinition
+            | Name::GfmTableDelimiterRow
+            | Name::HtmlFlow
+            | Name::HtmlText
+            | Name::MathFlowFence
+            | Name::MdxEsm
+            | Name::MdxFlowExpression
+            | Name::MdxJsxFlowTag
+            | Name::MdxJsxTextTag
+            | Name::MdxTextExpression
+            | Name::Reference
+            | Name::Resource
+    )
+}
+
+/// Handle [`Enter`][Kind::Enter].
+fn enter(context: &mut CompileContext) {
+    match context.events[context.index].name {
+        Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_enter_raw_flow(context),
+        Name::CodeText | Name::MathText => on_enter_raw_text(context),
+        Name::HeadingAtx | Name::ListItem => context.block(),
+        Name::BlockQuote => on_enter_block_quote(context),
+        Name::GfmTable => on_enter_gfm_table(context),
+        Name::GfmTableCell => on_enter_gfm_table_cell(context),
+        Name::GfmTableRow => on_enter_gfm_table_row(context),
+        Name::HeadingSetext | Name::Paragraph => on_enter_phrasing(context),
+        Name::HeadingSetextText => on_enter_heading_setext_text(context),
+        Name::ListOrdered | Name::ListUnordered => on_enter_list(context),
+        _ => {}
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit].
+fn exit(context: &mut CompileContext) {
+    match context.events[context.index].name {
+        Name::AutolinkEmail
+        | Name::AutolinkProtocol
+        | Name::CharacterEscapeValue
+        | Name::CodeTextData
+        | Name::Data
+        | Name::GfmAutolinkLiteralEmail
+        | Name::GfmAutolinkLiteralMailto
+        | Name::GfmAutolinkLiteralProtocol
+        | Name::GfmAutolinkLiteralWww
+        | Name::GfmAutolinkLiteralXmpp
+        | Name::MathTextData => context.push_slice(),
+        Name::BlockQuote => on_exit_block_quote(context),
+        Name::CharacterReferenceMarker => on_exit_character_reference_marker(context),
+        Name::CharacterReferenceMarkerNumeric => {
+            on_exit_character_reference_marker_numeric(context);
+        }
+        Name::CharacterReferenceMarkerHexadecimal => {
+            on_exit_character_reference_marker_hexadecimal(context);
+        }
+        Name::CharacterReferenceValue => on_exit_character_reference_value(context),
+        Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_exit_raw_flow(context),
+        Name::CodeFlowChunk | Name::MathFlowChunk => on_exit_raw_flow_chunk(context),
+        Name::CodeText | Name::MathText => on_exit_raw_text(context),
+        Name::GfmTaskListItemCheck => on_exit_gfm_task_list_item_check(context),
+        Name::GfmTaskListItemValueChecked => on_exit_gfm_task_list_item_value_checked(context),
+        Name::HeadingSetextText | Name::Paragraph => on_exit_phrasing(context),
+        Name::LineEnding => on_exit_line_ending(context),
+        Name::ListItem => on_exit_list_item(context),
+        Name::ListItemPrefix => on_exit_list_item_prefix(context),
+        Name::ListItemValue => on_exit_list_item_value(context),
+        Name::ListOrdered | Name::ListUnordered => on_exit_list(context),
+        _ => {}
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`BlockQuote`][Name::BlockQuote].
+fn on_enter_block_quote(context: &mut CompileContext) {
+    context.block();
+    context.tight_stack.push(false);
+    context.container_start = true;
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GfmTable`][Name::GfmTable].
+fn on_enter_gfm_table(context: &mut CompileContext) {
+    context.block();
+    context.gfm_table_seen_row = false;
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GfmTableCell`][Name::GfmTableCell].
+fn on_enter_gfm_table_cell(context: &mut CompileContext) {
+    if context.gfm_table_column > 0 {
+        context.push("\t");
+    }
+
+    context.gfm_table_column += 1;
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`GfmTableRow`][Name::GfmTableRow].
+fn on_enter_gfm_table_row(context: &mut CompileContext) {
+    if context.gfm_table_seen_row {
+        context.line_ending();
+    }
+
+    context.gfm_table_seen_row = true;
+    context.gfm_table_column = 0;
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`HeadingSetextText`][Name::HeadingSetextText].
+fn on_enter_heading_setext_text(context: &mut CompileContext) {
+    context.phrasing_depth += 1;
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
+fn on_enter_list(context: &mut CompileContext) {
+    context.block();
+    let loose = list_loose(context.events, context.index, true);
+    context.tight_stack.push(!loose);
+    context.list_stack.push((
+        context.events[context.index].name == Name::ListOrdered,
+        None,
+    ));
+    context.container_start = true;
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`HeadingSetext`][Name::HeadingSetext],[`Paragraph`][Name::Paragraph]}.
+fn on_enter_phrasing(context: &mut CompileContext) {
+    context.block();
+
+    if context.events[context.index].name == Name::Paragraph {
+        context.phrasing_depth += 1;
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
+fn on_enter_raw_flow(context: &mut CompileContext) {
+    context.block();
+    context.raw_flow_seen_data = false;
+    context.raw_flow_line_endings = 0;
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
+fn on_enter_raw_text(context: &mut CompileContext) {
+    context.raw_text_inside = true;
+    context.buffer();
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarker`][Name::CharacterReferenceMarker].
+fn on_exit_character_reference_marker(context: &mut CompileContext) {
+    context.character_reference_marker = Some(b'&');
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarkerHexadecimal`][Name::CharacterReferenceMarkerHexadecimal].
+fn on_exit_character_reference_marker_hexadecimal(context: &mut CompileContext) {
+    context.character_reference_marker = Some(b'x');
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarkerNumeric`][Name::CharacterReferenceMarkerNumeric].
+fn on_exit_character_reference_marker_numeric(context: &mut CompileContext) {
+    context.character_reference_marker = Some(b'#');
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceValue`][Name::CharacterReferenceValue].
+fn on_exit_character_reference_value(context: &mut CompileContext) {
+    let marker = context
+        .character_reference_marker
+        .take()
+        .expect("expected `character_reference_kind` to be set");
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+    );
+    let value = decode_character_reference(slice.as_str(), marker, true)
+        .expect("expected to parse only valid named references");
+
+    context.push(&value);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`BlockQuote`][Name::BlockQuote].
+fn on_exit_block_quote(context: &mut CompileContext) {
+    context.tight_stack.pop();
+    context.container_start = false;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
+fn on_exit_gfm_task_list_item_check(context: &mut CompileContext) {
+    context.push(if context.gfm_task_list_item_checked {
+        "[x]"
+    } else {
+        "[ ]"
+    });
+    context.gfm_task_list_item_checked = false;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemValueChecked`][Name::GfmTaskListItemValueChecked].
+fn on_exit_gfm_task_list_item_value_checked(context: &mut CompileContext) {
+    context.gfm_task_list_item_checked = true;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`LineEnding`][Name::LineEnding].
+fn on_exit_line_ending(context: &mut CompileContext) {
+    if context.raw_text_inside {
+        context.push(" ");
+    } else if context.phrasing_depth > 0 {
+        context.line_ending();
+    } else if context.raw_flow_seen_data {
+        context.raw_flow_line_endings += 1;
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`ListItem`][Name::ListItem].
+fn on_exit_list_item(context: &mut CompileContext) {
+    context.indent_stack.pop();
+    context.container_start = false;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`ListItemPrefix`][Name::ListItemPrefix].
+fn on_exit_list_item_prefix(context: &mut CompileContext) {
+    let (ordered, value) = context.list_stack.last_mut().unwrap();
+    let prefix = if *ordered {
+        let number = value.unwrap_or(1);
+        *value = Some(number + 1);
+        number.to_string() + ". "
+    } else {
+        "- ".into()
+    };
+
+    context.push(&prefix);
+    context.indent_stack.push(prefix.len());
+    context.container_start = true;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`ListItemValue`][Name::ListItemValue].
+///
+/// Only the first value in a list is used, later items count up from it.
+fn on_exit_list_item_value(context: &mut CompileContext) {
+    let (_, value) = context.list_stack.last_mut().unwrap();
+
+    if value.is_none() {
+        let slice = Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, context.index),
+        );
+        *value = slice.as_str().parse::<u32>().ok();
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
+fn on_exit_list(context: &mut CompileContext) {
+    context.tight_stack.pop();
+    context.list_stack.pop();
+    context.container_start = false;
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`HeadingSetextText`][Name::HeadingSetextText],[`Paragraph`][Name::Paragraph]}.
+fn on_exit_phrasing(context: &mut CompileContext) {
+    context.phrasing_depth -= 1;
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
+fn on_exit_raw_flow(context: &mut CompileContext) {
+    context.raw_flow_seen_data = false;
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`CodeFlowChunk`][Name::CodeFlowChunk],[`MathFlowChunk`][Name::MathFlowChunk]}.
+///
+/// Line endings are only written between data, so that the line endings
+/// around the code are not included.
+fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
+    while context.raw_flow_line_endings > 0 {
+        context.line_ending();
+        context.raw_flow_line_endings -= 1;
+    }
+
+    context.raw_flow_seen_data = true;
+    context.push_slice();
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`CodeText`][Name::CodeText],[`MathText`][Name::MathText]}.
+fn on_exit_raw_text(context: &mut CompileContext) {
+    let mut value = context.resume();
+    let bytes = value.as_bytes();
+
+    // Strip one space from both sides, if there is something else.
+    if bytes.len() > 2
+        && bytes[0] == b' '
+        && bytes[bytes.len() - 1] == b' '
+        && bytes.iter().any(|byte| *byte != b' ')
+    {
+        value = value[1..value.len() - 1].into();
+    }
+
+    context.raw_text_inside = false;
+    context.push(&value);
+}
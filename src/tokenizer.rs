@@ -18,7 +18,7 @@ use crate::subtokenize::Subresult;
 #[cfg(feature = "log")]
 use crate::util::char::format_byte_opt;
 
-use crate::util::{constant::TAB_SIZE, edit_map::EditMap};
+use crate::util::{constant::TAB_SIZE, edit_map::EditMap, scan};
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 /// Containers.
@@ -176,6 +176,8 @@ struct Progress {
     current: Option<u8>,
     /// Current place in the file.
     point: Point,
+    /// Current point after the last line ending (excluding jump).
+    line_start: Point,
 }
 
 /// A lot of shared fields used to tokenize things.
@@ -286,6 +288,8 @@ pub struct Tokenizer<'a> {
     consumed: bool,
     /// Stack of how to handle attempts.
     attempts: Vec<Attempt>,
+    /// Index of the end of the bytes currently being fed.
+    push_end: usize,
     /// Current byte.
     pub current: Option<u8>,
     /// Previous byte.
@@ -335,6 +339,7 @@ impl<'a> Tokenizer<'a> {
             line_start: point.clone(),
             consumed: true,
             attempts: vec![],
+            push_end: 0,
             point,
             stack: vec![],
             events: vec![],
@@ -457,6 +462,31 @@ impl<'a> Tokenizer<'a> {
         self.consumed = true;
     }
 
+    /// Consume the current byte, and then the run of plain bytes after it.
+    ///
+    /// Plain bytes are those that are not tabs, line endings, or in
+    /// `markers`, so that states which would consume them one by one can
+    /// skip ahead instead.
+    /// See [`scan::run`][] for how runs are found.
+    pub fn consume_run(&mut self, markers: &[u8]) {
+        self.consume();
+
+        if self.point.vs == 0 {
+            let bytes = self.parse_state.bytes;
+            let end = self.push_end.min(bytes.len());
+
+            if self.point.index < end {
+                let size = scan::run(&bytes[self.point.index..end], markers);
+
+                if size > 0 {
+                    self.point.index += size;
+                    self.point.column += size;
+                    self.previous = Some(bytes[self.point.index - 1]);
+                }
+            }
+        }
+    }
+
     /// Move to the next (virtual) byte.
     fn move_one(&mut self) {
         match byte_action(self.parse_state.bytes, &self.point) {
@@ -561,6 +591,7 @@ impl<'a> Tokenizer<'a> {
             previous: self.previous,
             current: self.current,
             point: self.point.clone(),
+            line_start: self.line_start.clone(),
             events_len: self.events.len(),
             stack_len: self.stack.len(),
         }
@@ -571,6 +602,7 @@ impl<'a> Tokenizer<'a> {
         self.previous = previous.previous;
         self.current = previous.current;
         self.point = previous.point;
+        self.line_start = previous.line_start;
         debug_assert!(
             self.events.len() >= previous.events_len,
             "expected to restore less events than before"
@@ -702,6 +734,7 @@ fn push_impl(
     );
 
     tokenizer.move_to(from);
+    tokenizer.push_end = to.0;
 
     loop {
         match state {
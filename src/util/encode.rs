@@ -1,6 +1,7 @@
 //! Encode HTML.
 
 use alloc::string::String;
+use core::fmt::Write;
 
 /// Encode dangerous html characters.
 ///
@@ -53,3 +54,33 @@ pub fn encode(value: &str, encode_html: bool) -> String {
 
     result
 }
+
+/// Encode non-ASCII characters as hexadecimal character references.
+///
+/// Characters outside the basic multilingual plane (such as emoji) become a
+/// single reference to their code point.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::encode::encode_non_ascii;
+///
+/// assert_eq!(encode_non_ascii("I <3 🦀"), "I <3 &#x1F980;");
+/// ```
+pub fn encode_non_ascii(value: &str) -> String {
+    if value.is_ascii() {
+        return value.into();
+    }
+
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        if char.is_ascii() {
+            result.push(char);
+        } else {
+            write!(result, "&#x{:X};", char as u32).expect("writing to a string cannot fail");
+        }
+    }
+
+    result
+}
@@ -89,18 +89,36 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
 }
 
 /// Figure out if an item is spread or not.
+///
+/// An item is spread when there is a blank line between two of its children:
+/// blank lines at the end of an item do not count.
 pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
     debug_assert!(
         matches!(events[index].name, Name::ListItem),
         "expected list item"
     );
     let mut balance = 0;
+    let mut blank = false;
 
     while index < events.len() {
         let event = &events[index];
 
         if event.kind == Kind::Enter {
             balance += 1;
+
+            // Content after a blank line.
+            if blank
+                && balance == 2
+                && !matches!(
+                    event.name,
+                    Name::BlankLineEnding
+                        | Name::BlockQuotePrefix
+                        | Name::LineEnding
+                        | Name::SpaceOrTab
+                )
+            {
+                return true;
+            }
         } else {
             balance -= 1;
 
@@ -126,7 +144,7 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
                 }
 
                 if !at_prefix {
-                    return true;
+                    blank = true;
                 }
             }
 
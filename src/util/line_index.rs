@@ -0,0 +1,220 @@
+//! Convert between byte offsets and points, as the parser makes them.
+
+use crate::unist::Point;
+use crate::util::constant::TAB_SIZE;
+use alloc::{vec, vec::Vec};
+
+/// Index of lines in a document, to convert between byte offsets and points
+/// (line and column), as the parser makes them in the positions of nodes.
+///
+/// Columns count bytes, except that tabs are expanded to the next tab stop
+/// (every 4 columns), like the parser does.
+/// Line endings are `\n`, `\r`, and `\r\n`, where `\r\n` counts as one: the
+/// `\n` of a `\r\n` has the same column as its `\r`.
+///
+/// This does not support points in other encodings
+/// (see [`position_encoding`][crate::ParseOptions::position_encoding]).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{unist::Point, LineIndex};
+///
+/// let index = LineIndex::new("a\tb *c*\r\nd");
+///
+/// assert_eq!(index.offset_to_point(4), Some(Point::new(1, 7, 4)));
+/// assert_eq!(index.point_to_offset(&Point::new(2, 1, 9)), Some(9));
+/// ```
+#[derive(Debug)]
+pub struct LineIndex<'a> {
+    /// Document.
+    bytes: &'a [u8],
+    /// List of byte indices where lines start.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Get an index for `value`.
+    #[must_use]
+    pub fn new(value: &'a str) -> Self {
+        let bytes = value.as_bytes();
+        let mut line_starts = vec![0];
+        let mut index = 0;
+
+        while index < bytes.len() {
+            if bytes[index] == b'\n'
+                || (bytes[index] == b'\r' && bytes.get(index + 1) != Some(&b'\n'))
+            {
+                line_starts.push(index + 1);
+            }
+
+            index += 1;
+        }
+
+        LineIndex { bytes, line_starts }
+    }
+
+    /// Get the point of `offset`.
+    ///
+    /// The end of the document (`offset` is its length) has a point too.
+    /// Returns `None` when `offset` is after that.
+    ///
+    /// A container (such as a list item) can take part of a tab, in which
+    /// case the parser makes the nodes in it start in the tab: the point of
+    /// the tab itself is returned for its offset.
+    #[must_use]
+    pub fn offset_to_point(&self, offset: usize) -> Option<Point> {
+        if offset > self.bytes.len() {
+            return None;
+        }
+
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let mut column = 1;
+        let mut index = self.line_starts[line - 1];
+
+        while index < offset {
+            column = self.next_column(index, column);
+            index += 1;
+        }
+
+        Some(Point::new(line, column, offset))
+    }
+
+    /// Get the offset of the `line` and `column` of `point`.
+    ///
+    /// The `offset` of `point` is ignored, so this can be used for points
+    /// that only have a line and column.
+    /// Columns in a tab (after where it starts) are at the tab.
+    /// Returns `None` when there is no such line, or when the line is
+    /// shorter than `column`.
+    #[must_use]
+    pub fn point_to_offset(&self, point: &Point) -> Option<usize> {
+        if point.line == 0 || point.line > self.line_starts.len() || point.column == 0 {
+            return None;
+        }
+
+        let end = self
+            .line_starts
+            .get(point.line)
+            .map_or(self.bytes.len(), |start| start - 1);
+        let mut index = self.line_starts[point.line - 1];
+        let mut column = 1;
+
+        while column < point.column && index < end {
+            let next = self.next_column(index, column);
+
+            if next > point.column {
+                return Some(index);
+            }
+
+            column = next;
+            index += 1;
+        }
+
+        if column == point.column {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Get the column after the byte at `index`, which is at `column`.
+    fn next_column(&self, index: usize, column: usize) -> usize {
+        match self.bytes[index] {
+            b'\t' => column + 1 + (TAB_SIZE - column % TAB_SIZE) % TAB_SIZE,
+            // The `\n` of a `\r\n` gets the column of the `\r`.
+            b'\r' if self.bytes.get(index + 1) == Some(&b'\n') => column,
+            _ => column + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_index() {
+        let index = LineIndex::new("ab\r\nc\rd\n");
+        let points = [
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (1, 3),
+            (2, 1),
+            (2, 2),
+            (3, 1),
+            (3, 2),
+            (4, 1),
+        ];
+
+        for (offset, (line, column)) in points.iter().enumerate() {
+            let point = Point::new(*line, *column, offset);
+            assert_eq!(index.offset_to_point(offset), Some(point.clone()));
+
+            // The `\n` of a `\r\n` is at the column of the `\r`.
+            if offset != 3 {
+                assert_eq!(index.point_to_offset(&point), Some(offset));
+            }
+        }
+
+        assert_eq!(index.offset_to_point(9), None, "after EOF");
+        assert_eq!(
+            index.point_to_offset(&Point::new(1, 4, 0)),
+            None,
+            "after a line"
+        );
+        assert_eq!(
+            index.point_to_offset(&Point::new(4, 2, 0)),
+            None,
+            "after EOF"
+        );
+        assert_eq!(index.point_to_offset(&Point::new(5, 1, 0)), None, "no line");
+        assert_eq!(
+            index.point_to_offset(&Point::new(0, 1, 0)),
+            None,
+            "line `0`"
+        );
+        assert_eq!(
+            index.point_to_offset(&Point::new(1, 0, 0)),
+            None,
+            "column `0`"
+        );
+    }
+
+    #[test]
+    fn test_line_index_tab() {
+        let index = LineIndex::new("a\tb *c*\n\t\td");
+
+        assert_eq!(
+            index.offset_to_point(2),
+            Some(Point::new(1, 5, 2)),
+            "after a tab"
+        );
+        assert_eq!(index.offset_to_point(4), Some(Point::new(1, 7, 4)), "`*`");
+        assert_eq!(
+            index.offset_to_point(10),
+            Some(Point::new(2, 9, 10)),
+            "after two tabs"
+        );
+        assert_eq!(index.point_to_offset(&Point::new(1, 7, 0)), Some(4), "`*`");
+        assert_eq!(
+            index.point_to_offset(&Point::new(1, 3, 0)),
+            Some(1),
+            "in a tab"
+        );
+        assert_eq!(
+            index.point_to_offset(&Point::new(2, 5, 0)),
+            Some(9),
+            "second tab"
+        );
+        assert_eq!(index.point_to_offset(&Point::new(2, 9, 0)), Some(10), "`d`");
+    }
+
+    #[test]
+    fn test_line_index_empty() {
+        let index = LineIndex::new("");
+        assert_eq!(index.offset_to_point(0), Some(Point::new(1, 1, 0)));
+        assert_eq!(index.point_to_offset(&Point::new(1, 1, 0)), Some(0));
+    }
+}
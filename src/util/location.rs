@@ -3,7 +3,9 @@
 //! * Convert between byte indices and unist points.
 //! * Convert between byte indices into a string which is built up of several
 //!   slices in a whole document, and byte indices into that whole document.
+//! * Convert points that count bytes to points that count other units.
 
+use crate::configuration::PositionEncoding;
 use crate::unist::Point;
 use alloc::{vec, vec::Vec};
 
@@ -107,6 +109,91 @@ impl Location {
     }
 }
 
+/// Index of characters that take up fewer units than bytes in a document,
+/// to convert points that count UTF-8 bytes to points in another
+/// [`PositionEncoding`][].
+#[derive(Debug)]
+pub struct Encoder {
+    /// List of byte indices where lines start.
+    line_starts: Vec<usize>,
+    /// List, where each value is the byte index after a character that takes
+    /// up fewer units than bytes (`0`), and the total number of bytes that
+    /// were not units up to there (`1`).
+    extra: Vec<(usize, usize)>,
+}
+
+impl Encoder {
+    /// Get an index for the given `bytes`.
+    #[must_use]
+    pub fn new(bytes: &[u8], encoding: PositionEncoding) -> Self {
+        let mut encoder = Self {
+            line_starts: vec![0],
+            extra: vec![],
+        };
+        let mut total = 0;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let byte = bytes[index];
+            let size = if byte < 0x80 {
+                1
+            } else if byte < 0xE0 {
+                2
+            } else if byte < 0xF0 {
+                3
+            } else {
+                4
+            };
+            let units = match encoding {
+                PositionEncoding::Utf8 => size,
+                PositionEncoding::Utf16 if size == 4 => 2,
+                _ => 1,
+            };
+
+            if byte == b'\n' || (byte == b'\r' && bytes.get(index + 1) != Some(&b'\n')) {
+                encoder.line_starts.push(index + 1);
+            }
+
+            index += size;
+
+            if units < size {
+                total += size - units;
+                encoder.extra.push((index, total));
+            }
+        }
+
+        encoder
+    }
+
+    /// Turn a `point` that counts bytes into one that counts units.
+    ///
+    /// The column is the number of units from the start of the line, plus
+    /// one, so a tab counts as one unit.
+    #[must_use]
+    pub fn encode(&self, point: &Point) -> Point {
+        let line_start = self.line_starts[self
+            .line_starts
+            .partition_point(|start| *start <= point.offset)
+            - 1];
+        let extra = self.extra_before(point.offset);
+        let units_before_line = line_start - self.extra_before(line_start);
+
+        Point::new(
+            point.line,
+            point.offset - extra - units_before_line + 1,
+            point.offset - extra,
+        )
+    }
+
+    /// Get the number of bytes that are not units before `index`.
+    fn extra_before(&self, index: usize) -> usize {
+        match self.extra.partition_point(|(end, _)| *end <= index) {
+            0 => 0,
+            at => self.extra[at - 1].1,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +279,7 @@ mod tests {
             "should support some points (4)"
         );
     }
+
     #[test]
     fn test_empty() {
         let location = Location::new("".as_bytes());
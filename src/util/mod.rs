@@ -9,11 +9,16 @@ pub mod gfm_tagfilter;
 pub mod identifier;
 pub mod infer;
 pub mod line_ending;
+pub mod line_index;
 pub mod location;
 pub mod mdx;
 pub mod mdx_collect;
 pub mod normalize_identifier;
+pub mod raw_text;
 pub mod sanitize_uri;
+pub mod scan;
 pub mod skip;
 pub mod slice;
+pub mod slug;
 pub mod unicode;
+pub mod whitespace;
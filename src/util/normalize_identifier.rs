@@ -2,13 +2,15 @@
 
 use alloc::string::String;
 
-/// Normalize an identifier, as found in [references][label_end] and
-/// [definitions][definition], so it can be compared when matching.
+/// Normalize an identifier, as found in references and definitions, so it can
+/// be compared when matching.
 ///
-/// This collapsed whitespace found in markdown (`\t`, `\r`, `\n`, and ` `)
+/// This collapses whitespace found in markdown (`\t`, `\r`, `\n`, and ` `)
 /// into one space, trims it (as in, dropping the first and last space), and
 /// then performs unicode case folding twice: first by lowercasing uppercase
 /// characters, and then uppercasing lowercase characters.
+/// Finally, the result is lowercased again, which is the form used for the
+/// `identifier` field of references and definitions in mdast.
 ///
 /// Some characters are considered “uppercase”, such as U+03F4 (`ϴ`), but if
 /// their lowercase counterpart (U+03B8 (`θ`)) is uppercased will result in a
@@ -25,22 +27,22 @@ use alloc::string::String;
 ///
 /// ## Examples
 ///
-/// ```rust ignore
-/// markdown::util::normalize_identifier::normalize_identifier;
+/// ```
+/// use markdown::normalize_identifier;
 ///
 /// assert_eq!(normalize_identifier(" a "), "a");
-/// assert_eq!(normalize_identifier("a\t\r\nb"), "a b");
+/// assert_eq!(normalize_identifier("a\t\r\n  b"), "a b");
 /// assert_eq!(normalize_identifier("ПРИВЕТ"), "привет");
 /// assert_eq!(normalize_identifier("Привет"), "привет");
 /// assert_eq!(normalize_identifier("привет"), "привет");
+/// assert_eq!(normalize_identifier("ẞ"), "ss");
+/// assert_eq!(normalize_identifier("SS"), "ss");
 /// ```
 ///
 /// ## References
 ///
 /// *   [`micromark-util-normalize-identifier` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-normalize-identifier)
-///
-/// [definition]: crate::construct::definition
-/// [label_end]: crate::construct::label_end
+#[must_use]
 pub fn normalize_identifier(value: &str) -> String {
     // Note: it’ll grow a bit smaller for consecutive whitespace.
     let mut result = String::with_capacity(value.len());
@@ -59,7 +61,7 @@ pub fn normalize_identifier(value: &str) -> String {
         }
         // First non-whitespace we see after whitespace.
         else if in_whitespace {
-            if start != 0 {
+            if !result.is_empty() {
                 result.push(' ');
             }
 
@@ -74,5 +76,5 @@ pub fn normalize_identifier(value: &str) -> String {
         result.push_str(&value[start..]);
     }
 
-    result.to_lowercase().to_uppercase()
+    result.to_lowercase().to_uppercase().to_lowercase()
 }
@@ -0,0 +1,72 @@
+//! Get the value of code (text) and math (text).
+
+use crate::util::slice::substring;
+use alloc::borrow::Cow;
+
+/// Get the value of code (text) or math (text) from the raw value between
+/// its sequences.
+///
+/// In GFM tables, escaped pipes (`\|`) are decoded (this is a rather weird
+/// GFM feature).
+///
+/// One space (or line ending) is then stripped from both sides, but only if
+/// both the first and last character are a space (or line ending), and there
+/// is something other than spaces (and line endings) in between.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::raw_text::decode;
+///
+/// assert_eq!(decode(" a ", false), "a");
+/// assert_eq!(decode(" a", false), " a");
+/// assert_eq!(decode("  ", false), "  ");
+/// assert_eq!(decode("\na\n", false), "a");
+/// assert_eq!(decode("a\\|b", true), "a|b");
+/// ```
+///
+/// ## References
+///
+/// *   [*§ 6.1 Code spans* in `CommonMark`](https://spec.commonmark.org/0.31/#code-spans)
+pub fn decode(value: &str, in_table: bool) -> Cow<'_, str> {
+    let mut value = if in_table && value.contains("\\|") {
+        Cow::Owned(value.replace("\\|", "|"))
+    } else {
+        Cow::Borrowed(value)
+    };
+
+    if padded(&value) {
+        let bytes = value.as_bytes();
+        let start = if bytes.starts_with(b"\r\n") { 2 } else { 1 };
+        let end = bytes.len() - if bytes.ends_with(b"\r\n") { 2 } else { 1 };
+        value = substring(value, start, end);
+    }
+
+    value
+}
+
+/// Check whether the raw value between the sequences of code (text) or math
+/// (text) is padded: one space (or line ending) is stripped from both sides.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::raw_text::padded;
+///
+/// assert!(padded(" a "));
+/// assert!(!padded(" a"));
+/// assert!(!padded("  "));
+/// ```
+pub fn padded(value: &str) -> bool {
+    let bytes = value.as_bytes();
+
+    bytes.len() > 2
+        && is_space(bytes[0])
+        && is_space(bytes[bytes.len() - 1])
+        && bytes.iter().any(|byte| !is_space(*byte))
+}
+
+/// Check whether `byte` counts as a space.
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\n' | b'\r')
+}
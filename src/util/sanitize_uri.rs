@@ -52,7 +52,15 @@ pub fn sanitize(value: &str) -> String {
 ///
 /// *   [`micromark-util-sanitize-uri` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-sanitize-uri)
 pub fn sanitize_with_protocols(value: &str, protocols: &[&str]) -> String {
-    let value = sanitize(value);
+    encode(&normalize_with_protocols(value, protocols), true)
+}
+
+/// Normalize a URL, and check protocols.
+///
+/// Like [`sanitize_with_protocols`][], but without encoding HTML characters,
+/// for use in a syntax tree, where encoding is left to the serializer.
+pub fn normalize_with_protocols(value: &str, protocols: &[&str]) -> String {
+    let value = normalize(value);
 
     let end = value.find(|c| matches!(c, '?' | '#' | '/'));
     let mut colon = value.find(|c| matches!(c, ':'));
@@ -99,7 +107,7 @@ pub fn sanitize_with_protocols(value: &str, protocols: &[&str]) -> String {
 ///
 /// [definition]: crate::construct::definition
 /// [label_end]: crate::construct::label_end
-fn normalize(value: &str) -> String {
+pub fn normalize(value: &str) -> String {
     let chars = value.chars().collect::<Vec<_>>();
     // Note: it’ll grow bigger for each non-ascii or non-safe character.
     let mut result = String::with_capacity(value.len());
@@ -0,0 +1,258 @@
+//! Find runs of plain bytes.
+//!
+//! Data (in text and string) and code (text) consume most bytes one at a
+//! time, only stopping at certain markers.
+//! These helpers find how many bytes can be consumed in one go instead.
+//!
+//! With the `swar` feature, bytes are checked eight at a time, as one 64-bit
+//! word (SWAR: SIMD within a register), which is faster on long runs.
+//! This uses no SIMD instructions.
+//! Otherwise, they are checked one by one.
+
+/// Get the size of the run of bytes at the start of `bytes` that are not a
+/// tab, line ending, or in `markers`.
+///
+/// Tabs and carriage returns are excluded as the tokenizer handles them
+/// specially.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::scan::run;
+///
+/// assert_eq!(run(b"abc*d", b"*"), 3);
+/// assert_eq!(run(b"ab\ncd", &[]), 2);
+/// ```
+pub fn run(bytes: &[u8], markers: &[u8]) -> usize {
+    #[cfg(test)]
+    {
+        match tests::MODE.with(core::cell::Cell::get) {
+            tests::Mode::Byte => return 0,
+            tests::Mode::Scalar => return run_scalar(bytes, markers),
+            tests::Mode::Wide => return run_wide(bytes, markers),
+            tests::Mode::Default => {}
+        }
+    }
+
+    #[cfg(feature = "swar")]
+    {
+        run_wide(bytes, markers)
+    }
+
+    #[cfg(not(feature = "swar"))]
+    {
+        run_scalar(bytes, markers)
+    }
+}
+
+/// Whether `byte` ends a run.
+fn stop(byte: u8, markers: &[u8]) -> bool {
+    matches!(byte, b'\t' | b'\n' | b'\r') || markers.contains(&byte)
+}
+
+/// Find a run, one byte at a time.
+fn run_scalar(bytes: &[u8], markers: &[u8]) -> usize {
+    let mut index = 0;
+
+    while index < bytes.len() && !stop(bytes[index], markers) {
+        index += 1;
+    }
+
+    index
+}
+
+/// Find a run, eight bytes at a time.
+///
+/// Checks whole words for any byte that stops a run, and finds the exact
+/// byte with [`run_scalar`][] from the first word that has one.
+#[cfg(any(feature = "swar", test))]
+fn run_wide(bytes: &[u8], markers: &[u8]) -> usize {
+    let mut index = 0;
+
+    while index + 8 <= bytes.len() {
+        let mut word = [0; 8];
+        word.copy_from_slice(&bytes[index..index + 8]);
+        let word = u64::from_le_bytes(word);
+        let mut found = has_byte(word, b'\t') | has_byte(word, b'\n') | has_byte(word, b'\r');
+
+        for marker in markers {
+            found |= has_byte(word, *marker);
+        }
+
+        if found != 0 {
+            break;
+        }
+
+        index += 8;
+    }
+
+    index + run_scalar(&bytes[index..], markers)
+}
+
+/// Check whether `word` contains `byte`: non-zero if so.
+///
+/// ## References
+///
+/// *   [*Determine if a word has a zero byte* in *Bit Twiddling Hacks*](https://graphics.stanford.edu/~seander/bithacks.html#ZeroInWord)
+#[cfg(any(feature = "swar", test))]
+fn has_byte(word: u64, byte: u8) -> u64 {
+    const LOW: u64 = 0x0101_0101_0101_0101;
+    const HIGH: u64 = 0x8080_8080_8080_8080;
+    let value = word ^ (LOW * u64::from(byte));
+    value.wrapping_sub(LOW) & !value & HIGH
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::{to_html_with_options, Constructs, Options, ParseOptions};
+    use alloc::{format, string::String, vec::Vec};
+    use core::cell::Cell;
+
+    /// How [`run`][] finds runs, so that the tokenizer can be checked with
+    /// each.
+    #[derive(Clone, Copy, Debug)]
+    pub enum Mode {
+        /// As configured with the `swar` feature.
+        Default,
+        /// Find no runs, so that bytes are consumed one at a time.
+        Byte,
+        /// With [`run_scalar`][].
+        Scalar,
+        /// With [`run_wide`][].
+        Wide,
+    }
+
+    std::thread_local! {
+        pub static MODE: Cell<Mode> = Cell::new(Mode::Default);
+    }
+
+    /// Get random numbers (xorshift), from a fixed seed.
+    fn random() -> impl FnMut() -> u64 {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run(b"", &[]), 0, "should support empty bytes");
+        assert_eq!(run(b"abc", &[]), 3, "should support bytes w/o stops");
+        assert_eq!(run(b"abc*d", b"*"), 3, "should stop at markers");
+        assert_eq!(
+            run(b"abcdefghijk\tl", &[]),
+            11,
+            "should stop at tabs after a word"
+        );
+        assert_eq!(run(b"a\r\nb", &[]), 1, "should stop at carriage returns");
+        assert_eq!(run(b"*a", b"*"), 0, "should stop at the start");
+    }
+
+    #[test]
+    fn test_run_fuzz() {
+        let markers = [b'*', b'_', b'[', b']', b'<', b'&', b'\\', b'`', 0xE2];
+        let mut next = random();
+        let mut index = 0;
+
+        while index < 10_000 {
+            let mut bytes = Vec::new();
+            let size = (next() % 64) as usize;
+
+            while bytes.len() < size {
+                let seed = next();
+                // Mostly plain bytes, sometimes a stop.
+                let byte = if seed % 16 == 0 {
+                    [b'\t', b'\n', b'\r', b'*', b'&', 0xE2][(seed >> 8) as usize % 6]
+                } else {
+                    b'a' + ((seed >> 8) % 26) as u8
+                };
+                bytes.push(byte);
+            }
+
+            let used = &markers[0..(next() % 9) as usize];
+            let expected = run_scalar(&bytes, used);
+
+            assert_eq!(run(&bytes, used), expected, "should match scalar");
+            assert_eq!(run_wide(&bytes, used), expected, "should match scalar");
+
+            index += 1;
+        }
+    }
+
+    #[test]
+    fn test_run_html() {
+        let options = Options {
+            parse: ParseOptions {
+                constructs: Constructs {
+                    math_flow: true,
+                    math_text: true,
+                    ..Constructs::gfm()
+                },
+                ..ParseOptions::gfm()
+            },
+            ..Options::gfm()
+        };
+        // Test files, with escaped line endings and tabs turned into real
+        // ones, as a corpus of markdown.
+        // They are not in the package, so they are used if they exist.
+        let mut values = [
+            "commonmark.rs",
+            "code_text.rs",
+            "gfm_autolink_literal.rs",
+            "math_text.rs",
+            "misc_tabs.rs",
+        ]
+        .iter()
+        .filter_map(|name| {
+            std::fs::read_to_string(format!("{}/tests/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+        })
+        .flat_map(|code| {
+            code.split("\n\n")
+                .map(|value| value.replace("\\n", "\n").replace("\\t", "\t"))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+        let alphabet = b"ab \t\r\n*_[]()<>&;\\`$~!:/@.#-|";
+        let mut next = random();
+
+        let mut index = 0;
+
+        while index < 3_000 {
+            let size = (next() % 128) as usize;
+            let mut value = String::new();
+
+            while value.len() < size {
+                let seed = next();
+                // Mostly plain text, sometimes syntax.
+                if seed % 4 == 0 {
+                    value.push(alphabet[(seed >> 8) as usize % alphabet.len()] as char);
+                } else {
+                    value.push(['a', 'b', ' ', 'é'][(seed >> 8) as usize % 4]);
+                }
+            }
+
+            values.push(value);
+            index += 1;
+        }
+
+        for value in &values {
+            let html = |mode| {
+                MODE.with(|cell| cell.set(mode));
+                let result = to_html_with_options(value, &options).unwrap();
+                MODE.with(|cell| cell.set(Mode::Default));
+                result
+            };
+            let expected = html(Mode::Byte);
+
+            assert_eq!(html(Mode::Scalar), expected, "scalar: {:?}", value);
+            assert_eq!(html(Mode::Wide), expected, "wide: {:?}", value);
+            assert_eq!(html(Mode::Default), expected, "default: {:?}", value);
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::event::{Event, Kind, Point};
 use crate::util::constant::TAB_SIZE;
-use alloc::{format, string::String, vec};
+use alloc::{borrow::Cow, format, string::String, vec};
 use core::str;
 
 /// A range between two points.
@@ -110,7 +110,7 @@ impl<'a> Slice<'a> {
     /// Turn the slice into a `&str`.
     ///
     /// > 👉 **Note**: cannot represent virtual spaces.
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'a str {
         str::from_utf8(self.bytes).unwrap()
     }
 
@@ -123,3 +123,12 @@ impl<'a> Slice<'a> {
         format!("{}{}{}", prefix, self.as_str(), suffix)
     }
 }
+
+/// Get the part of `value` from `start` to `end`, which borrows if `value`
+/// borrows.
+pub fn substring(value: Cow<str>, start: usize, end: usize) -> Cow<str> {
+    match value {
+        Cow::Borrowed(value) => Cow::Borrowed(&value[start..end]),
+        Cow::Owned(value) => Cow::Owned(value[start..end].into()),
+    }
+}
@@ -0,0 +1,124 @@
+//! Make slugs (such as for `id`s of headings).
+
+use crate::mdast::{Heading, Node};
+use crate::util::char::{classify, Kind};
+use alloc::{collections::BTreeMap, format, string::String};
+
+/// Signature of a function that turns the text of a heading into a slug.
+///
+/// Can be passed as `slugify` in [`Options`][crate::configuration::Options]
+/// to make slugs in a different way (such as by transliterating non-ASCII
+/// characters).
+/// Slugs are made unique afterwards.
+pub type Slugify = dyn Fn(&str) -> String;
+
+/// Turn `value` into a slug, like GitHub does.
+///
+/// Lowercases, turns spaces into dashes, and removes other whitespace,
+/// punctuation (except for `-` and `_`), symbols, and control characters.
+/// Other characters (such as letters, digits, and marks) are kept as-is.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::slug::slug;
+///
+/// assert_eq!(slug("Hello, World!"), "hello-world");
+/// assert_eq!(slug("Über _uns_"), "über-_uns_");
+/// ```
+///
+/// ## References
+///
+/// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+pub fn slug(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars().flat_map(char::to_lowercase) {
+        if char == ' ' {
+            result.push('-');
+        } else if char == '-'
+            || char == '_'
+            || (!char.is_control() && classify(char) == Kind::Other)
+        {
+            result.push(char);
+        }
+    }
+
+    result
+}
+
+/// Make a unique slug for a heading in a syntax tree.
+///
+/// Uses the `id` in the `data` of the heading if there is one, and otherwise
+/// its text, turned into a slug with `slugify` or [`slug()`][].
+/// Returns `None` if there is no slug (such as for a heading without text).
+pub fn heading(node: &Heading, slugify: Option<&Slugify>, slugger: &mut Slugger) -> Option<String> {
+    let value = if let Some(id) = node.data.as_ref().and_then(|data| data.get("id")) {
+        id.clone()
+    } else {
+        let text = node.children.iter().map(Node::to_text).collect::<String>();
+
+        if let Some(slugify) = slugify {
+            slugify(&text)
+        } else {
+            slug(&text)
+        }
+    };
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(slugger.unique(&value))
+    }
+}
+
+/// Make slugs unique.
+///
+/// The first occurrence of a slug is used as-is, later occurrences get a
+/// suffix (`-1`, `-2`, and so on).
+///
+/// Can be passed to [`slug_headings()`][crate::mdast::slug_headings].
+/// Use the same slugger for several documents to make slugs unique across
+/// them (such as for a site), or [`reset()`][Slugger::reset] it to start
+/// over.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::Slugger;
+///
+/// let mut slugger = Slugger::default();
+///
+/// assert_eq!(slugger.unique("a"), "a");
+/// assert_eq!(slugger.unique("a"), "a-1");
+///
+/// slugger.reset();
+///
+/// assert_eq!(slugger.unique("a"), "a");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Slugger {
+    /// Number of times each slug was seen.
+    occurrences: BTreeMap<String, usize>,
+}
+
+impl Slugger {
+    /// Make `value` unique, among the values passed before.
+    pub fn unique(&mut self, value: &str) -> String {
+        let mut result = String::from(value);
+
+        while self.occurrences.contains_key(&result) {
+            let count = self.occurrences.get_mut(value).unwrap();
+            *count += 1;
+            result = format!("{}-{}", value, count);
+        }
+
+        self.occurrences.insert(result.clone(), 0);
+        result
+    }
+
+    /// Forget the values passed before.
+    pub fn reset(&mut self) {
+        self.occurrences.clear();
+    }
+}
@@ -0,0 +1,61 @@
+//! Collapse whitespace.
+
+use alloc::{borrow::Cow, string::String};
+
+/// Collapse each run of spaces, tabs, and line endings to a single space.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::whitespace::collapse;
+///
+/// assert_eq!(collapse("a \t b\r\nc"), "a b c");
+/// assert_eq!(collapse("a b"), "a b");
+/// ```
+pub fn collapse(value: &str) -> Cow<'_, str> {
+    let bytes = value.as_bytes();
+    let mut index = 0;
+
+    // Find the first place where something has to change.
+    while index < bytes.len() {
+        if is_whitespace(bytes[index])
+            && (bytes[index] != b' '
+                || (index + 1 < bytes.len() && is_whitespace(bytes[index + 1])))
+        {
+            break;
+        }
+
+        index += 1;
+    }
+
+    if index == bytes.len() {
+        return Cow::Borrowed(value);
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut start = 0;
+
+    while index < bytes.len() {
+        if is_whitespace(bytes[index]) {
+            result.push_str(&value[start..index]);
+            result.push(' ');
+            index += 1;
+
+            while index < bytes.len() && is_whitespace(bytes[index]) {
+                index += 1;
+            }
+
+            start = index;
+        } else {
+            index += 1;
+        }
+    }
+
+    result.push_str(&value[start..]);
+    Cow::Owned(result)
+}
+
+/// Check whether `byte` is collapsed.
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b'\t' | b'\n' | b'\r' | b' ')
+}
@@ -0,0 +1,52 @@
+//! Bindings for JavaScript, through `wasm-bindgen`.
+//!
+//! Options are passed as JSON, which is deserialized into [`Options`][] (or
+//! [`ParseOptions`][]) with the same field names as in Rust, but camel-cased
+//! (`allowDangerousHtml`, `gfmTagfilter`, and so on).
+//! Fields that are missing get their default value.
+//! Functions that parse MDX expressions or ESM cannot be passed.
+
+use crate::{Options, ParseOptions};
+use alloc::string::{String, ToString};
+use wasm_bindgen::prelude::*;
+
+/// Turn markdown into HTML.
+///
+/// See [`to_html()`][crate::to_html] for more info.
+#[wasm_bindgen(js_name = toHtml)]
+pub fn to_html(value: &str) -> String {
+    crate::to_html(value)
+}
+
+/// Turn markdown into HTML, with configuration as a JSON string.
+///
+/// See [`to_html_with_options()`][crate::to_html_with_options] for more info.
+///
+/// ## Errors
+///
+/// Throws if `options` is not valid JSON for [`Options`][], or when
+/// compiling MDX and a syntax error is found.
+#[wasm_bindgen(js_name = toHtmlWithOptions)]
+pub fn to_html_with_options(value: &str, options: &str) -> Result<String, JsError> {
+    let options: Options =
+        serde_json::from_str(options).map_err(|error| JsError::new(&error.to_string()))?;
+    crate::to_html_with_options(value, &options)
+        .map_err(|message| JsError::new(&message.to_string()))
+}
+
+/// Turn markdown into a syntax tree, serialized as a JSON string.
+///
+/// See [`to_mdast()`][crate::to_mdast] for more info.
+///
+/// ## Errors
+///
+/// Throws if `options` is not valid JSON for [`ParseOptions`][], or when
+/// parsing MDX and a syntax error is found.
+#[wasm_bindgen(js_name = toMdastJson)]
+pub fn to_mdast_json(value: &str, options: &str) -> Result<String, JsError> {
+    let options: ParseOptions =
+        serde_json::from_str(options).map_err(|error| JsError::new(&error.to_string()))?;
+    let tree =
+        crate::to_mdast(value, &options).map_err(|message| JsError::new(&message.to_string()))?;
+    serde_json::to_string(&tree).map_err(|error| JsError::new(&error.to_string()))
+}
@@ -844,34 +844,45 @@ fn attention() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Emphasis(Emphasis {
                         children: vec![Node::Text(Text {
                             value: "alpha".into(),
-                            position: Some(Position::new(1, 4, 3, 1, 9, 8))
+                            position: Some(Position::new(1, 4, 3, 1, 9, 8)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 10, 9))
+                        position: Some(Position::new(1, 3, 2, 1, 10, 9)),
+                        marker: Some('*'),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 13, 12))
+                        position: Some(Position::new(1, 10, 9, 1, 13, 12)),
+                        data: None,
                     }),
                     Node::Strong(Strong {
                         children: vec![Node::Text(Text {
                             value: "bravo".into(),
-                            position: Some(Position::new(1, 15, 14, 1, 20, 19))
+                            position: Some(Position::new(1, 15, 14, 1, 20, 19)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 13, 12, 1, 22, 21))
+                        position: Some(Position::new(1, 13, 12, 1, 22, 21)),
+                        marker: Some('*'),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
-                        position: Some(Position::new(1, 22, 21, 1, 25, 24))
+                        position: Some(Position::new(1, 22, 21, 1, 25, 24)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 25, 24))
+                position: Some(Position::new(1, 1, 0, 1, 25, 24)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 25, 24))
+            position: Some(Position::new(1, 1, 0, 1, 25, 24)),
+            data: None,
         }),
         "should support attention as `Emphasis`, `Strong`s in mdast"
     );
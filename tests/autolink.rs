@@ -107,6 +107,30 @@ fn autolink() -> Result<(), message::Message> {
         "should support email autolinks (3)"
     );
 
+    assert_eq!(
+        to_html("<foo+bar@sub.example.co.uk>"),
+        "<p><a href=\"mailto:foo+bar@sub.example.co.uk\">foo+bar@sub.example.co.uk</a></p>",
+        "should support plus addressing and multiple domain labels in email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<first.middle.last@example.com>"),
+        "<p><a href=\"mailto:first.middle.last@example.com\">first.middle.last@example.com</a></p>",
+        "should support dots in the local part of email autolinks"
+    );
+
+    assert_eq!(
+        to_html("a <!#$%&'*+-./=?^_`{|}~@b.c>"),
+        "<p>a <a href=\"mailto:!#$%25&amp;'*+-./=?%5E_%60%7B%7C%7D~@b.c\">!#$%&amp;'*+-./=?^_`{|}~@b.c</a></p>",
+        "should support all atext in the local part of email autolinks"
+    );
+
+    assert_eq!(
+        to_html("<a\"b@c.d>"),
+        "<p>&lt;a&quot;b@c.d&gt;</p>",
+        "should not support a double quote in the local part of email autolinks"
+    );
+
     assert_eq!(
         to_html("<foo\\+@bar.example.com>"),
         "<p>&lt;foo+@bar.example.com&gt;</p>",
@@ -275,6 +299,33 @@ fn autolink() -> Result<(), message::Message> {
         "should support turning off autolinks"
     );
 
+    let obfuscate = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            obfuscate_email_autolinks: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("<a@b.c>", &obfuscate)?,
+        "<p><a href=\"&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x61;&#64;&#x62;&#46;&#x63;\">&#97;&#x40;&#98;&#x2e;&#99;</a></p>",
+        "should support obfuscating email autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a@b.c mailto:d@e.f", &obfuscate)?,
+        "<p><a href=\"&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x61;&#64;&#x62;&#46;&#x63;\">&#97;&#x40;&#98;&#x2e;&#99;</a> <a href=\"&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x64;&#64;&#x65;&#46;&#x66;\">&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x64;&#64;&#x65;&#46;&#x66;</a></p>",
+        "should support obfuscating GFM email autolink literals"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b> [c](mailto:d@e.f)", &obfuscate)?,
+        "<p><a href=\"https://a.b\">https://a.b</a> <a href=\"mailto:d@e.f\">c</a></p>",
+        "should not obfuscate other links"
+    );
+
     assert_eq!(
         to_mdast(
             "a <https://alpha.com> b <bravo@charlie.com> c.",
@@ -285,38 +336,49 @@ fn autolink() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "https://alpha.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "https://alpha.com".into(),
-                            position: Some(Position::new(1, 4, 3, 1, 21, 20))
+                            position: Some(Position::new(1, 4, 3, 1, 21, 20)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 22, 21))
+                        position: Some(Position::new(1, 3, 2, 1, 22, 21)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
-                        position: Some(Position::new(1, 22, 21, 1, 25, 24))
+                        position: Some(Position::new(1, 22, 21, 1, 25, 24)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "mailto:bravo@charlie.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "bravo@charlie.com".into(),
-                            position: Some(Position::new(1, 26, 25, 1, 43, 42))
+                            position: Some(Position::new(1, 26, 25, 1, 43, 42)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 25, 24, 1, 44, 43))
+                        position: Some(Position::new(1, 25, 24, 1, 44, 43)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
-                        position: Some(Position::new(1, 44, 43, 1, 47, 46))
+                        position: Some(Position::new(1, 44, 43, 1, 47, 46)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 47, 46))
+                position: Some(Position::new(1, 1, 0, 1, 47, 46)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 47, 46))
+            position: Some(Position::new(1, 1, 0, 1, 47, 46)),
+            data: None,
         }),
         "should support autolinks as `Link`s in mdast"
     );
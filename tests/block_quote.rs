@@ -2,7 +2,7 @@ use markdown::{
     mdast::{BlockQuote, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    Constructs, Options, ParseOptions, PositionEncoding,
 };
 use pretty_assertions::assert_eq;
 
@@ -225,16 +225,51 @@ fn block_quote() -> Result<(), message::Message> {
                 children: vec![Node::Paragraph(Paragraph {
                     children: vec![Node::Text(Text {
                         value: "a".into(),
-                        position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                        data: None,
                     }),],
-                    position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                    position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                    data: None,
                 })],
-                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+            data: None,
         }),
         "should support block quotes as `BlockQuote`s in mdast"
     );
 
     Ok(())
 }
+
+#[test]
+fn block_quote_deep() -> Result<(), message::Message> {
+    let depth = 50_000;
+    let value = "> ".repeat(depth) + "a";
+
+    assert_eq!(
+        to_html(&value).matches("<blockquote>").count(),
+        depth,
+        "should support deeply nested block quotes"
+    );
+
+    let tree = to_mdast(
+        &value,
+        &ParseOptions {
+            position_encoding: PositionEncoding::Utf16,
+            ..ParseOptions::default()
+        },
+    )?;
+
+    assert_eq!(
+        tree.iter_depth().map(|(depth, _)| depth).max(),
+        Some(depth + 2),
+        "should support deeply nested block quotes in mdast"
+    );
+
+    // Dropping nodes is recursive, so take the tree apart first.
+    tree.drop_iterative();
+
+    Ok(())
+}
@@ -109,11 +109,14 @@ fn character_escape() -> Result<(), message::Message> {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![Node::Text(Text {
                     value: "a * b".into(),
-                    position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                    data: None,
                 }),],
-                position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 7, 6))
+            position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+            data: None,
         }),
         "should support character escapes as `Text`s in mdast"
     );
@@ -220,11 +220,14 @@ fn character_reference() -> Result<(), message::Message> {
             children: vec![Node::Paragraph(Paragraph {
                 children: vec![Node::Text(Text {
                     value: "\u{a0} & © Æ Ď\n¾ ℋ ⅆ\n∲ ≧̸\n# Ӓ Ϡ �\n\" ആ ಫ".into(),
-                    position: Some(Position::new(1, 1, 0, 5, 23, 158))
+                    position: Some(Position::new(1, 1, 0, 5, 23, 158)),
+                    data: None,
                 }),],
-                position: Some(Position::new(1, 1, 0, 5, 23, 158))
+                position: Some(Position::new(1, 1, 0, 5, 23, 158)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 5, 23, 158))
+            position: Some(Position::new(1, 1, 0, 5, 23, 158)),
+            data: None,
         }),
         "should support character references as `Text`s in mdast"
     );
@@ -1,8 +1,9 @@
 use markdown::{
-    mdast::{Code, Node, Root},
-    message, to_html, to_html_with_options, to_mdast,
+    hast_to_html,
+    mdast::{BlockQuote, Code, Node, Paragraph, Root, Text},
+    mdast_to_html, message, to_hast, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -176,6 +177,30 @@ fn code_fenced() -> Result<(), message::Message> {
         "should support grave accents and tildes in the meta string of tilde fenced code"
     );
 
+    assert_eq!(
+        to_html("```rust`foo\na\n```"),
+        "<p>```rust`foo\na</p>\n<pre><code></code></pre>\n",
+        "should not support grave accents in the info string"
+    );
+
+    assert_eq!(
+        to_html("```` a`b\nc\n````"),
+        "<p>```` a`b\nc</p>\n<pre><code></code></pre>\n",
+        "should not support grave accents in the info string of longer fences"
+    );
+
+    assert_eq!(
+        to_html("````rust\n```\n````"),
+        "<pre><code class=\"language-rust\">```\n</code></pre>",
+        "should support the info string in longer fences"
+    );
+
+    assert_eq!(
+        to_html("~~~rust`foo\na\n~~~"),
+        "<pre><code class=\"language-rust`foo\">a\n</code></pre>",
+        "should support grave accents in the info string of tilde fenced code"
+    );
+
     assert_eq!(
         to_html("```\n``` aaa\n```"),
         "<pre><code>``` aaa\n</code></pre>",
@@ -261,6 +286,35 @@ fn code_fenced() -> Result<(), message::Message> {
         "should not support lazyness (3)"
     );
 
+    assert_eq!(
+        to_html("- a\n\n  ```\n  b\n    c\n  ```"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>b\n  c\n</code></pre>\n</li>\n</ul>",
+        "should strip the indent of list items from code in them"
+    );
+
+    assert_eq!(
+        to_html("1. a\n\n   ```\n   b\n   ```\n\n   - c\n\n     ```\n     d\n     ```"),
+        "<ol>\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n<ul>\n<li>\n<p>c</p>\n<pre><code>d\n</code></pre>\n</li>\n</ul>\n</li>\n</ol>",
+        "should strip the indent of ordered and nested list items from code in them"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n   ```\n   b\n  c\n   ```"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>b\nc\n</code></pre>\n</li>\n</ul>",
+        "should strip the indent of the fence, and the indent of list items, from code in them"
+    );
+
+    assert_eq!(
+        to_mdast("- a\n\n  ```\n  b\n    c\n  ```", &Default::default())?
+            .iter()
+            .find_map(|node| match node {
+                Node::Code(code) => Some(code.value.clone()),
+                _ => None,
+            }),
+        Some("b\n  c".into()),
+        "should strip the indent of list items from code in them in mdast"
+    );
+
     assert_eq!(
         to_html_with_options(
             "```",
@@ -289,9 +343,11 @@ fn code_fenced() -> Result<(), message::Message> {
                 lang: Some("js".into()),
                 meta: Some("extra".into()),
                 value: "console.log(1)\nconsole.log(2)".into(),
-                position: Some(Position::new(1, 1, 0, 4, 4, 45))
+                position: Some(Position::new(1, 1, 0, 4, 4, 45)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 4, 4, 45))
+            position: Some(Position::new(1, 1, 0, 4, 4, 45)),
+            data: None,
         }),
         "should support code (fenced) as `Code`s in mdast"
     );
@@ -303,9 +359,11 @@ fn code_fenced() -> Result<(), message::Message> {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 2, 4, 7))
+                position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 4, 7))
+            position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+            data: None,
         }),
         "should support code (fenced) w/o closing fence in mdast"
     );
@@ -317,9 +375,11 @@ fn code_fenced() -> Result<(), message::Message> {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 11))
+                position: Some(Position::new(1, 1, 0, 3, 4, 11)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 11))
+            position: Some(Position::new(1, 1, 0, 3, 4, 11)),
+            data: None,
         }),
         "should support code (fenced) w/o CR line endings"
     );
@@ -331,12 +391,273 @@ fn code_fenced() -> Result<(), message::Message> {
                 lang: None,
                 meta: None,
                 value: "asd".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 13))
+                position: Some(Position::new(1, 1, 0, 3, 4, 13)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 13))
+            position: Some(Position::new(1, 1, 0, 3, 4, 13)),
+            data: None,
         }),
         "should support code (fenced) w/o CR+LF line endings"
     );
 
     Ok(())
 }
+
+#[test]
+fn code_fenced_unclosed() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("```js\na\n\n# b\n\n> c\n"),
+        "<pre><code class=\"language-js\">a\n\n# b\n\n&gt; c\n</code></pre>\n",
+        "should include everything up to the eof in code w/o closing sequence"
+    );
+
+    assert_eq!(
+        to_html("> ```\n> a\n\n# b\n\n- c"),
+        "<blockquote>\n<pre><code>a\n</code></pre>\n</blockquote>\n<h1>b</h1>\n<ul>\n<li>c</li>\n</ul>",
+        "should not include content after a block quote in code w/o closing sequence in it"
+    );
+
+    assert_eq!(
+        to_html("> ```\n> a\nb"),
+        "<blockquote>\n<pre><code>a\n</code></pre>\n</blockquote>\n<p>b</p>",
+        "should not include a lazy line in code w/o closing sequence in a block quote"
+    );
+
+    assert_eq!(
+        to_html("- ```\n  a\n- b\n\n  ```"),
+        "<ul>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n<li>\n<p>b</p>\n<pre><code></code></pre>\n</li>\n</ul>",
+        "should not include the next list item in code w/o closing sequence in a list item"
+    );
+
+    assert_eq!(
+        to_html("1. ```\n   a\n2. b\n\n> ~~~\n- c"),
+        "<ol>\n<li>\n<pre><code>a\n</code></pre>\n</li>\n<li>b</li>\n</ol>\n<blockquote>\n<pre><code></code></pre>\n</blockquote>\n<ul>\n<li>c</li>\n</ul>",
+        "should not include the line ending before a new container in code w/o closing sequence"
+    );
+
+    assert_eq!(
+        to_mdast("```js\na\n\nb\n", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                lang: Some("js".into()),
+                meta: None,
+                value: "a\n\nb".into(),
+                position: Some(Position::new(1, 1, 0, 5, 1, 11)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 5, 1, 11)),
+            data: None,
+        }),
+        "should support code w/o closing sequence up to the eof in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("> ```js\n> a\n\nb", &Default::default())?,
+        Node::Root(Root {
+            children: vec![
+                Node::BlockQuote(BlockQuote {
+                    children: vec![Node::Code(Code {
+                        lang: Some("js".into()),
+                        meta: None,
+                        value: "a".into(),
+                        position: Some(Position::new(1, 3, 2, 2, 4, 11)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(1, 1, 0, 2, 4, 11)),
+                    data: None,
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(4, 1, 13, 4, 2, 14)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(4, 1, 13, 4, 2, 14)),
+                    data: None,
+                }),
+            ],
+            position: Some(Position::new(1, 1, 0, 4, 2, 14)),
+            data: None,
+        }),
+        "should support code w/o closing sequence up to the end of a block quote in mdast"
+    );
+
+    Ok(())
+}
+
+/// Get the `lang` and `meta` of the first code node in `value`.
+fn info(value: &str) -> Result<(Option<String>, Option<String>), message::Message> {
+    let tree = to_mdast(value, &Default::default())?;
+    match tree.children().and_then(|children| children.first()) {
+        Some(Node::Code(code)) => Ok((code.lang.clone(), code.meta.clone())),
+        _ => panic!("expected code"),
+    }
+}
+
+#[test]
+fn code_fenced_info() -> Result<(), message::Message> {
+    assert_eq!(
+        info("```js\na\n```")?,
+        (Some("js".into()), None),
+        "should support a `lang`"
+    );
+
+    assert_eq!(
+        info("```js a  b \na\n```")?,
+        (Some("js".into()), Some("a  b ".into())),
+        "should support `meta` as everything after the first whitespace"
+    );
+
+    assert_eq!(
+        info("```\tjs\ta\na\n```")?,
+        (Some("js".into()), Some("a".into())),
+        "should support tabs around `lang`"
+    );
+
+    assert_eq!(
+        info("```js \na\n```")?,
+        (Some("js".into()), None),
+        "should not support an empty `meta` after a trailing space"
+    );
+
+    assert_eq!(
+        info("```   \na\n```")?,
+        (None, None),
+        "should not support info strings of only whitespace"
+    );
+
+    assert_eq!(
+        info("```a&amp;b c&amp;d\\*\na\n```")?,
+        (Some("a&b".into()), Some("c&d*".into())),
+        "should decode character references and escapes in `lang` and `meta`"
+    );
+
+    assert_eq!(
+        info("~~~a`b\\`c d`e\na\n~~~")?,
+        (Some("a`b`c".into()), Some("d`e".into())),
+        "should support grave accents and escaped grave accents in tilde fences"
+    );
+
+    assert_eq!(
+        info("    a")?,
+        (None, None),
+        "should not support `lang` or `meta` in indented code"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_wrapper() -> Result<(), message::Message> {
+    let wrapper = |elements: &[&str]| Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_flow: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        compile: CompileOptions {
+            code_fence_wrapper: Some(elements.iter().map(|d| (*d).into()).collect()),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n    b", &wrapper(&["code"]))?,
+        "<code class=\"language-js\">a\n</code>\n<code>b\n</code>",
+        "should support one element, for fenced and indented code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```js\na\n```",
+            &wrapper(&["div class=\"code\"", " pre  data-a ", "code"])
+        )?,
+        "<div class=\"code\"><pre data-a=\"\"><code class=\"language-js\">a\n</code></pre></div>",
+        "should support several elements, with attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```js\na\n```",
+            &wrapper(&["div class=\"x\"", "code class=\"y\" class='z'"])
+        )?,
+        "<div class=\"x\"><code class=\"y z language-js\">a\n</code></div>",
+        "should merge classes into one attribute"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```", &wrapper(&["div\"><script>", "code", "1x"]))?,
+        "<code class=\"language-js\">a\n</code>",
+        "should ignore elements with invalid tag names"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```", &wrapper(&["<div>", ""]))?,
+        "<pre><code></code></pre>",
+        "should use `<pre><code>` if no element is valid"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```\n```",
+            &wrapper(&["code a\"><script>=b title=\"&amp;<>\""])
+        )?,
+        "<code title=\"&amp;&lt;&gt;\"></code>",
+        "should ignore attributes with invalid names, and encode values"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```", &wrapper(&[]))?,
+        "<pre><code></code></pre>",
+        "should use `<pre><code>` for an empty list"
+    );
+
+    assert_eq!(
+        to_html_with_options("$$\na\n$$", &wrapper(&["code"]))?,
+        "<pre><code class=\"language-math math-display\">a\n</code></pre>",
+        "should not affect math (flow)"
+    );
+
+    let mut options = wrapper(&["div class=\"code\"", "code"]);
+    options.compile.source_position = true;
+    options.compile.code_indented_class = Some("b".into());
+
+    assert_eq!(
+        to_html_with_options("    a", &options)?,
+        "<div data-line=\"1\" data-end-line=\"1\" class=\"code\"><code class=\"b\">a\n</code></div>",
+        "should add source positions to the outer element, and classes to the inner element"
+    );
+
+    for options in [
+        wrapper(&["div class=\"code\"", "code"]),
+        wrapper(&["div class='a&amp;b' hidden", "code class=\"c\" title=\"<\""]),
+    ] {
+        for value in [
+            "```js\na\n```",
+            "```\n```",
+            "    a",
+            "> ```\n> a\n\n$$\nb\n$$",
+        ] {
+            let html = to_html_with_options(value, &options)?;
+
+            assert_eq!(
+                mdast_to_html(&to_mdast(value, &options.parse)?, &options)?,
+                html,
+                "should match `to_html` in `mdast_to_html` for `{}`",
+                value
+            );
+
+            assert_eq!(
+                hast_to_html(&to_hast(value, &options)?, &options.compile),
+                html,
+                "should match `to_html` in `to_hast` for `{}`",
+                value
+            );
+        }
+    }
+
+    Ok(())
+}
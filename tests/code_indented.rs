@@ -183,7 +183,8 @@ fn code_indented() -> Result<(), message::Message> {
                 compile: CompileOptions {
                     allow_dangerous_html: true,
                     ..Default::default()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p>a <?\n?></p>",
@@ -202,6 +203,82 @@ fn code_indented() -> Result<(), message::Message> {
         "should support turning off code (indented, 9)"
     );
 
+    let class = Options {
+        compile: CompileOptions {
+            code_indented_class: Some("code-indented".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("    a\n\n```js\nb\n```\n\n```\nc\n```", &class)?,
+        "<pre><code class=\"code-indented\">a\n</code></pre>\n<pre><code class=\"language-js\">b\n</code></pre>\n<pre><code>c\n</code></pre>",
+        "should support `code_indented_class`, only for code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "    a",
+            &Options {
+                compile: CompileOptions {
+                    code_indented_class: Some("a\"b".into()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code class=\"a&quot;b\">a\n</code></pre>",
+        "should encode `code_indented_class`"
+    );
+
+    let tabs = Options {
+        compile: CompileOptions {
+            preserve_tabs_in_code: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for value in [
+        "\ta\tb\t\tc",
+        "  \ta\tb\t\tc",
+        "    a\tb\n    \tc",
+        "\t\ta\n\n\tb",
+        "```\n\ta\n  \tb\n```",
+    ] {
+        assert_eq!(
+            to_html_with_options(value, &tabs)?,
+            to_html(value),
+            "should keep tabs in code, except where the indent ends in them, whether `preserve_tabs_in_code` or not, for {:?}",
+            value
+        );
+    }
+
+    assert_eq!(
+        to_html("- a\n\n\t\tb\tc"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>  b\tc\n</code></pre>\n</li>\n</ul>",
+        "should turn the rest of a tab that the indent ends in into spaces by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n\t\tb\tc", &tabs)?,
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>\tb\tc\n</code></pre>\n</li>\n</ul>",
+        "should support `preserve_tabs_in_code` in list items"
+    );
+
+    assert_eq!(
+        to_html_with_options(">\t\ta\tb", &tabs)?,
+        "<blockquote>\n<pre><code>\ta\tb\n</code></pre>\n</blockquote>",
+        "should support `preserve_tabs_in_code` in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options(" - a\n\n  \t  ```\n\t  b\n   ```", &tabs)?,
+        to_html(" - a\n\n  \t  ```\n\t  b\n   ```"),
+        "should not change code if its indent does not end in a tab"
+    );
+
     assert_eq!(
         to_mdast(
             "\tconsole.log(1)\n    console.log(2)\n",
@@ -212,9 +289,11 @@ fn code_indented() -> Result<(), message::Message> {
                 lang: None,
                 meta: None,
                 value: "console.log(1)\nconsole.log(2)".into(),
-                position: Some(Position::new(1, 1, 0, 2, 19, 34))
+                position: Some(Position::new(1, 1, 0, 2, 19, 34)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 1, 35))
+            position: Some(Position::new(1, 1, 0, 3, 1, 35)),
+            data: None,
         }),
         "should support code (indented) as `Code`s in mdast"
     );
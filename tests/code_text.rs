@@ -1,6 +1,6 @@
 use markdown::{
     mdast::{InlineCode, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
@@ -162,6 +162,24 @@ fn code_text() -> Result<(), message::Message> {
         "should support an escaped initial grave accent"
     );
 
+    assert_eq!(
+        to_html("`a\r\nb`"),
+        "<p><code>a b</code></p>",
+        "should turn a carriage return + line feed into a space"
+    );
+
+    assert_eq!(
+        to_html("`a\rb`"),
+        "<p><code>a b</code></p>",
+        "should turn a carriage return into a space"
+    );
+
+    assert_eq!(
+        to_html("> `a\r\n> b`\r\n> `c\rd`"),
+        "<blockquote>\r\n<p><code>a b</code>\r\n<code>c d</code></p>\r\n</blockquote>",
+        "should turn carriage returns (+ line feeds) into spaces in containers"
+    );
+
     assert_eq!(
         to_html_with_options(
             "`a`",
@@ -187,23 +205,121 @@ fn code_text() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::InlineCode(InlineCode {
                         value: "alpha".into(),
-                        position: Some(Position::new(1, 3, 2, 1, 10, 9))
+                        position: Some(Position::new(1, 3, 2, 1, 10, 9)),
+                        sequence_size: Some(1),
+                        padding: false,
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " b.".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 13, 12))
+                        position: Some(Position::new(1, 10, 9, 1, 13, 12)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
         }),
         "should support code (text) as `InlineCode`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("` `` `", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: "``".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                    sequence_size: Some(1),
+                    padding: true,
+                    data: None,
+                    markers: vec![],
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+            data: None,
+        }),
+        "should strip padding from `InlineCode`s in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("`foo`", &Default::default())?.to_string(),
+        "foo",
+        "should support code in mdast (commonmark 328)"
+    );
+
+    assert_eq!(
+        to_mdast("`` foo ` bar ``", &Default::default())?.to_string(),
+        "foo ` bar",
+        "should support code w/ more accents in mdast (commonmark 329)"
+    );
+
+    assert_eq!(
+        to_mdast("` `` `", &Default::default())?.to_string(),
+        "``",
+        "should support code w/ fences inside, and padding, in mdast (commonmark 330)"
+    );
+
+    assert_eq!(
+        to_mdast("`  ``  `", &Default::default())?.to_string(),
+        " `` ",
+        "should strip only one space of padding in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("` a`", &Default::default())?.to_string(),
+        " a",
+        "should not strip unbalanced padding in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("` `\n`  `", &Default::default())?.to_string(),
+        " \n  ",
+        "should not strip padding w/o data in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("``\nfoo\n``", &Default::default())?.to_string(),
+        "foo",
+        "should strip line endings as padding in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("`\r\nfoo \r\n`", &Default::default())?.to_string(),
+        "foo ",
+        "should strip carriage return + line feeds as padding in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("`a\r\nb` `c\rd`", &Default::default())?.to_string(),
+        "a\r\nb c\rd",
+        "should keep carriage returns (+ line feeds) in mdast"
+    );
+
+    assert_eq!(
+        mdast_to_html(
+            &to_mdast("> `a\r\n> b`", &Default::default())?,
+            &Default::default()
+        )?,
+        "<blockquote>\r\n<p><code>a b</code></p>\r\n</blockquote>",
+        "should use line endings in code (text) when turning mdast into HTML"
+    );
+
+    assert_eq!(
+        to_mdast("| a |\n| - |\n| ` \\| ` |", &ParseOptions::gfm())?.to_string(),
+        "a|",
+        "should strip padding after decoding escaped pipes in tables in mdast"
+    );
+
     Ok(())
 }
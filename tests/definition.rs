@@ -1,10 +1,15 @@
 use markdown::{
-    mdast::{Definition, Node, Root},
-    message, to_html, to_html_with_options, to_mdast,
+    mdast::{
+        definitions, footnote_definitions, resolve_references, Definition, FootnoteDefinition,
+        Link, LinkReference, Node, Paragraph, ReferenceKind, Root, Text,
+    },
+    mdast_to_html, mdast_to_markdown, message, normalize_identifier, to_html, to_html_with_options,
+    to_mdast, to_mdast_with_messages,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
 
 #[test]
 fn definition() -> Result<(), message::Message> {
@@ -119,6 +124,12 @@ fn definition() -> Result<(), message::Message> {
         "should match w/ case-insensitive (2)"
     );
 
+    assert_eq!(
+        to_html("[a b]: /url\n\n[ab] [a b] [a\n  b]"),
+        "<p>[ab] <a href=\"/url\">a b</a> <a href=\"/url\">a\nb</a></p>",
+        "should keep whitespace between words of labels when matching"
+    );
+
     assert_eq!(
         to_html("[ı]: a\n\n[I]"),
         "<p><a href=\"a\">I</a></p>",
@@ -515,12 +526,431 @@ fn definition() -> Result<(), message::Message> {
                 identifier: "a".into(),
                 label: Some("a".into()),
                 title: Some("c".into()),
-                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
         }),
         "should support definitions as `Definition`s in mdast"
     );
 
     Ok(())
 }
+
+#[test]
+fn definition_options() -> Result<(), message::Message> {
+    let mut definitions = BTreeMap::new();
+    definitions.insert("Alpha  Bravo".into(), ("/a".into(), Some("\"b\"".into())));
+    definitions.insert("c".into(), ("/c".into(), None));
+    let options = Options {
+        parse: ParseOptions {
+            definitions,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("[alpha bravo] [d][C] ![c][]", &options)?,
+        "<p><a href=\"/a\" title=\"&quot;b&quot;\">alpha bravo</a> <a href=\"/c\">d</a> <img src=\"/c\" alt=\"c\" /></p>",
+        "should support definitions from options"
+    );
+
+    assert_eq!(
+        to_html_with_options("[c]\n\n[c]: /d", &options)?,
+        "<p><a href=\"/d\">c</a></p>\n",
+        "should prefer definitions in the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("[e]", &options)?,
+        "<p>[e]</p>",
+        "should not support references to other definitions"
+    );
+
+    assert_eq!(
+        to_mdast("[c]", &options.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::LinkReference(LinkReference {
+                    children: vec![Node::Text(Text {
+                        value: "c".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 3, 2)),
+                        data: None,
+                    })],
+                    reference_kind: ReferenceKind::Shortcut,
+                    identifier: "c".into(),
+                    label: Some("c".into()),
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+            data: None,
+        }),
+        "should support references to definitions from options in mdast"
+    );
+
+    assert_eq!(
+        mdast_to_html(&to_mdast("[Alpha bravo] [c]", &options.parse)?, &options)?,
+        "<p><a href=\"/a\" title=\"&quot;b&quot;\">Alpha bravo</a> <a href=\"/c\">c</a></p>",
+        "should support definitions from options in `mdast_to_html`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definition_index() -> Result<(), message::Message> {
+    let tree = to_mdast(
+        "> [Alpha  Bravo]: a\n\n* [alpha bravo]: b\n\n[ẞ]: c\n\n[^d]: e\n[^D]: f",
+        &ParseOptions::gfm(),
+    )?;
+    let index = definitions(&tree);
+
+    assert_eq!(
+        index.keys().collect::<Vec<_>>(),
+        vec!["alpha bravo", "ss"],
+        "should index definitions by normalized identifier"
+    );
+
+    assert_eq!(
+        index["alpha bravo"].url, "a",
+        "should use the first of several definitions"
+    );
+
+    assert_eq!(
+        index["ss"],
+        &Definition {
+            url: "c".into(),
+            identifier: "ss".into(),
+            label: Some("ẞ".into()),
+            title: None,
+            position: Some(Position::new(5, 1, 41, 5, 9, 49)),
+            data: None,
+        },
+        "should find definitions"
+    );
+
+    let mut seed = BTreeMap::new();
+    seed.insert("alpha bravo".into(), ("x".into(), None));
+    seed.insert("ss".into(), ("y".into(), None));
+    let references = to_mdast(
+        "[ALPHA bravo] [ẞ]",
+        &ParseOptions {
+            definitions: seed,
+            ..ParseOptions::gfm()
+        },
+    )?;
+    let identifiers = references.children().unwrap()[0]
+        .children()
+        .unwrap()
+        .iter()
+        .filter_map(|node| match node {
+            Node::LinkReference(reference) => Some(reference.identifier.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        identifiers
+            .iter()
+            .map(|identifier| index[*identifier].url.as_str())
+            .collect::<Vec<_>>(),
+        vec!["a", "c"],
+        "should find definitions by the identifier of references"
+    );
+
+    let index = footnote_definitions(&tree);
+
+    assert_eq!(
+        index.keys().collect::<Vec<_>>(),
+        vec!["d"],
+        "should index footnote definitions by normalized identifier"
+    );
+
+    assert_eq!(
+        index["d"].label,
+        Some("d".into()),
+        "should use the first of several footnote definitions"
+    );
+
+    assert_eq!(
+        definitions(&Node::Definition(Definition {
+            url: "a".into(),
+            identifier: "B  C".into(),
+            label: None,
+            title: None,
+            position: None,
+            data: None,
+        }))
+        .keys()
+        .collect::<Vec<_>>(),
+        vec!["b c"],
+        "should normalize identifiers of definitions made by hand"
+    );
+
+    assert_eq!(
+        footnote_definitions(&Node::Root(Root {
+            children: vec![],
+            position: None,
+            data: None,
+        })),
+        BTreeMap::<String, &FootnoteDefinition>::new(),
+        "should support trees w/o definitions"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definition_resolve_references() -> Result<(), message::Message> {
+    let value = "[a][B] [c][] [d] ![e][b] ![f][]\n\n[b]: g \"h\"\n[c]: i\n[d]: j\n[f]: k\n[B]: l";
+    let mut tree = to_mdast(value, &ParseOptions::default())?;
+    resolve_references(&mut tree, false, false);
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "[a](g \"h\") [c](i) [d](j) ![e](g \"h\") ![f](k)\n\n[b]: g \"h\"\n\n[c]: i\n\n[d]: j\n\n[f]: k\n\n[B]: l\n",
+        "should turn references into links and images, using the first matching definition"
+    );
+
+    assert_eq!(
+        mdast_to_html(&tree, &Options::default())?,
+        mdast_to_html(
+            &to_mdast(value, &ParseOptions::default())?,
+            &Options::default()
+        )?,
+        "should compile to the same HTML as the references"
+    );
+
+    let mut tree = to_mdast(value, &ParseOptions::default())?;
+    resolve_references(&mut tree, true, false);
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "[a](g \"h\") [c](i) [d](j) ![e](g \"h\") ![f](k)\n",
+        "should support `remove_definitions: true`"
+    );
+
+    let mut tree = to_mdast("> [![a][b]][c]\n\n[b]: d\n[c]: e", &ParseOptions::default())?;
+    resolve_references(&mut tree, true, false);
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "> [![a](d)](e)\n",
+        "should turn references in references and other nodes into links and images"
+    );
+
+    let mut tree = to_mdast("[a]\n\n[a]: b", &ParseOptions::default())?;
+    resolve_references(&mut tree, true, false);
+
+    assert_eq!(
+        tree,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(Link {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 3, 2)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                    data: None,
+                    url: "b".into(),
+                    title: None,
+                    markers: vec![],
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 7, 11)),
+            data: None,
+        }),
+        "should keep the position of references"
+    );
+
+    let mut unresolved = to_mdast(
+        "*[a][B]* [*c*][] [d] ![e][f] ![g][]\n\n[b]: x\n[c]: x\n[d]: x\n[f]: x\n[g]: x",
+        &ParseOptions::default(),
+    )?;
+    unresolved
+        .children_mut()
+        .unwrap()
+        .retain(|node| !matches!(node, Node::Definition(_)));
+    let mut tree = unresolved.clone();
+    resolve_references(&mut tree, false, false);
+
+    assert_eq!(
+        tree, unresolved,
+        "should keep unresolved references by default"
+    );
+
+    resolve_references(&mut tree, false, true);
+
+    assert_eq!(
+        mdast_to_html(&tree, &Options::default())?,
+        mdast_to_html(&unresolved, &Options::default())?,
+        "should support `unresolved_to_text: true`, like `mdast_to_html`"
+    );
+
+    assert!(
+        !tree
+            .iter()
+            .any(|node| matches!(node, Node::LinkReference(_) | Node::ImageReference(_))),
+        "should not keep unresolved references with `unresolved_to_text: true`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definition_duplicates() -> Result<(), message::Message> {
+    let report = ParseOptions {
+        report_duplicate_definitions: true,
+        ..ParseOptions::default()
+    };
+
+    assert_eq!(
+        to_mdast_with_messages("[a]: b\n[a]: c", &ParseOptions::default())?.1,
+        vec![],
+        "should not report duplicates by default"
+    );
+
+    assert_eq!(
+        to_mdast_with_messages("[a]: b\n[B]: c\n\n[a]", &report)?.1,
+        vec![],
+        "should not report unique definitions"
+    );
+
+    let (tree, messages) = to_mdast_with_messages("[a]: b\n[A]: c\n\n> [ a ]: d\n\n[a]", &report)?;
+
+    assert_eq!(
+        tree,
+        to_mdast("[a]: b\n[A]: c\n\n> [ a ]: d\n\n[a]", &report)?,
+        "should not change the tree"
+    );
+
+    assert_eq!(
+        messages,
+        vec![
+            message::Message {
+                place: Some(Box::new(message::Place::Position(Position::new(
+                    2, 1, 7, 2, 7, 13
+                )))),
+                reason: "Unexpected duplicate definition `A`, expected unique identifiers (the first definition, at 1:1, is used)".into(),
+                rule_id: Box::new("duplicate-definition".into()),
+                source: Box::new("markdown-rs".into()),
+            },
+            message::Message {
+                place: Some(Box::new(message::Place::Position(Position::new(
+                    4, 3, 17, 4, 11, 25
+                )))),
+                reason: "Unexpected duplicate definition ` a `, expected unique identifiers (the first definition, at 1:1, is used)".into(),
+                rule_id: Box::new("duplicate-definition".into()),
+                source: Box::new("markdown-rs".into()),
+            }
+        ],
+        "should report duplicates, case-insensitively and in containers"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n[A]: c\n\n[a]"),
+        "<p><a href=\"b\">a</a></p>",
+        "should still use the first definition"
+    );
+
+    assert_eq!(
+        to_mdast_with_messages(
+            "[a]: b\n[a]: c",
+            &ParseOptions {
+                mdast_positions: false,
+                ..ParseOptions {
+                    report_duplicate_definitions: true,
+                    ..ParseOptions::default()
+                }
+            }
+        )?
+        .1[0]
+            .to_string(),
+        "Unexpected duplicate definition `a`, expected unique identifiers (markdown-rs:duplicate-definition)",
+        "should report duplicates without positions"
+    );
+
+    Ok(())
+}
+
+/// Get the `identifier` and `label` of references and definitions in `node`.
+fn associations(node: &Node) -> Vec<(String, Option<String>)> {
+    let mut result = vec![];
+    let association = match node {
+        Node::Definition(x) => Some((&x.identifier, &x.label)),
+        Node::FootnoteDefinition(x) => Some((&x.identifier, &x.label)),
+        Node::FootnoteReference(x) => Some((&x.identifier, &x.label)),
+        Node::ImageReference(x) => Some((&x.identifier, &x.label)),
+        Node::LinkReference(x) => Some((&x.identifier, &x.label)),
+        _ => None,
+    };
+
+    if let Some((identifier, label)) = association {
+        result.push((identifier.clone(), label.clone()));
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            result.append(&mut associations(child));
+        }
+    }
+
+    result
+}
+
+#[test]
+fn definition_identifier() -> Result<(), message::Message> {
+    assert_eq!(
+        associations(&to_mdast(
+            "[Foo  BAR] ![Привет][] [ẞ] [^Alpha]\n\n[foo\nbar]: a\n[ПРИВЕТ]: b\n[SS]: c\n[^ALPHA]: d",
+            &ParseOptions::gfm()
+        )?),
+        vec![
+            ("foo bar".into(), Some("Foo  BAR".into())),
+            ("привет".into(), Some("Привет".into())),
+            ("ss".into(), Some("ẞ".into())),
+            ("alpha".into(), Some("Alpha".into())),
+            ("foo bar".into(), Some("foo\nbar".into())),
+            ("привет".into(), Some("ПРИВЕТ".into())),
+            ("ss".into(), Some("SS".into())),
+            ("alpha".into(), Some("ALPHA".into())),
+        ],
+        "should normalize identifiers, but keep labels as authored"
+    );
+
+    assert_eq!(
+        to_html("[ẞ]\n\n[ss]: a"),
+        "<p><a href=\"a\">ẞ</a></p>\n",
+        "should match `ẞ` and `ss` (case folding)"
+    );
+
+    assert_eq!(
+        to_html("[á]\n\n[a]: b"),
+        "<p>[á]</p>\n",
+        "should not match `á` and `a` (no diacritic folding)"
+    );
+
+    assert_eq!(
+        normalize_identifier("  Foo\t\nBAR "),
+        "foo bar",
+        "should expose the normalization (1)"
+    );
+
+    assert_eq!(
+        normalize_identifier("ẞ"),
+        normalize_identifier("SS"),
+        "should expose the normalization (2)"
+    );
+
+    Ok(())
+}
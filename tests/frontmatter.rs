@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Node, Root, Toml, Yaml},
+    mdast::{Node, Paragraph, Root, Text, Toml, Yaml},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -138,9 +138,11 @@ fn frontmatter() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Yaml(Yaml {
                 value: "a: b".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 12))
+                position: Some(Position::new(1, 1, 0, 3, 4, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 12))
+            position: Some(Position::new(1, 1, 0, 3, 4, 12)),
+            data: None,
         }),
         "should support yaml as `Yaml`s in mdast"
     );
@@ -150,12 +152,67 @@ fn frontmatter() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Toml(Toml {
                 value: "title = \"Jupyter\"".into(),
-                position: Some(Position::new(1, 1, 0, 3, 4, 25))
+                position: Some(Position::new(1, 1, 0, 3, 4, 25)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 25))
+            position: Some(Position::new(1, 1, 0, 3, 4, 25)),
+            data: None,
         }),
         "should support toml as `Toml`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("---\n---", &frontmatter.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Yaml(Yaml {
+                value: "".into(),
+                position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+            data: None,
+        }),
+        "should support empty yaml in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("+++\n+++\n", &frontmatter.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Toml(Toml {
+                value: "".into(),
+                position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 1, 8)),
+            data: None,
+        }),
+        "should support empty toml in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("---\na\n\nb\n---\nc", &frontmatter.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Yaml(Yaml {
+                    value: "a\n\nb".into(),
+                    position: Some(Position::new(1, 1, 0, 5, 4, 12)),
+                    data: None,
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "c".into(),
+                        position: Some(Position::new(6, 1, 13, 6, 2, 14)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(6, 1, 13, 6, 2, 14)),
+                    data: None,
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 6, 2, 14)),
+            data: None,
+        }),
+        "should support frontmatter w/ blank lines and content after it in mdast"
+    );
+
     Ok(())
 }
@@ -187,6 +187,36 @@ fn gfm_autolink_literal() -> Result<(), message::Message> {
         "should support two closing parens in a path"
     );
 
+    assert_eq!(
+        to_html_with_options("www.a.com/b)", &Options::gfm())?,
+        "<p><a href=\"http://www.a.com/b\">www.a.com/b</a>)</p>",
+        "should not include an unbalanced closing paren at the end"
+    );
+
+    assert_eq!(
+        to_html_with_options("(see www.a.com/b_(c)).", &Options::gfm())?,
+        "<p>(see <a href=\"http://www.a.com/b_(c)\">www.a.com/b_(c)</a>).</p>",
+        "should include balanced parens, but not an unbalanced closing paren"
+    );
+
+    assert_eq!(
+        to_html_with_options("www.a.com/b;", &Options::gfm())?,
+        "<p><a href=\"http://www.a.com/b\">www.a.com/b</a>;</p>",
+        "should not include a trailing semicolon"
+    );
+
+    assert_eq!(
+        to_html_with_options("www.a.com/b&copy;", &Options::gfm())?,
+        "<p><a href=\"http://www.a.com/b\">www.a.com/b</a>©</p>",
+        "should not include a trailing character reference"
+    );
+
+    assert_eq!(
+        to_html_with_options("www.a.com/b&copy;c", &Options::gfm())?,
+        "<p><a href=\"http://www.a.com/b&amp;copy;c\">www.a.com/b&amp;copy;c</a></p>",
+        "should include something that looks like a character reference, if followed by more path"
+    );
+
     assert_eq!(
         to_html_with_options("ftp://a/b/c.txt", &Options::gfm())?,
         "<p>ftp://a/b/c.txt</p>",
@@ -2749,80 +2779,143 @@ www.a/~
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "https://alpha.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "https://alpha.com".into(),
-                            position: Some(Position::new(1, 3, 2, 1, 20, 19))
+                            position: Some(Position::new(1, 3, 2, 1, 20, 19)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 20, 19))
+                        position: Some(Position::new(1, 3, 2, 1, 20, 19)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
-                        position: Some(Position::new(1, 20, 19, 1, 23, 22))
+                        position: Some(Position::new(1, 20, 19, 1, 23, 22)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "mailto:bravo@charlie.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "bravo@charlie.com".into(),
-                            position: Some(Position::new(1, 23, 22, 1, 40, 39))
+                            position: Some(Position::new(1, 23, 22, 1, 40, 39)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 23, 22, 1, 40, 39))
+                        position: Some(Position::new(1, 23, 22, 1, 40, 39)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " c ".into(),
-                        position: Some(Position::new(1, 40, 39, 1, 43, 42))
+                        position: Some(Position::new(1, 40, 39, 1, 43, 42)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "http://www.delta.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "www.delta.com".into(),
-                            position: Some(Position::new(1, 43, 42, 1, 56, 55))
+                            position: Some(Position::new(1, 43, 42, 1, 56, 55)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 43, 42, 1, 56, 55))
+                        position: Some(Position::new(1, 43, 42, 1, 56, 55)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " d ".into(),
-                        position: Some(Position::new(1, 56, 55, 1, 59, 58))
+                        position: Some(Position::new(1, 56, 55, 1, 59, 58)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "xmpp:echo@foxtrot.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "xmpp:echo@foxtrot.com".into(),
-                            position: Some(Position::new(1, 59, 58, 1, 80, 79))
+                            position: Some(Position::new(1, 59, 58, 1, 80, 79)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 59, 58, 1, 80, 79))
+                        position: Some(Position::new(1, 59, 58, 1, 80, 79)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " e ".into(),
-                        position: Some(Position::new(1, 80, 79, 1, 83, 82))
+                        position: Some(Position::new(1, 80, 79, 1, 83, 82)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "mailto:golf@hotel.com".into(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "mailto:golf@hotel.com".into(),
-                            position: Some(Position::new(1, 83, 82, 1, 104, 103))
+                            position: Some(Position::new(1, 83, 82, 1, 104, 103)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 83, 82, 1, 104, 103))
+                        position: Some(Position::new(1, 83, 82, 1, 104, 103)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " f.".into(),
-                        position: Some(Position::new(1, 104, 103, 1, 107, 106))
+                        position: Some(Position::new(1, 104, 103, 1, 107, 106)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 107, 106))
+                position: Some(Position::new(1, 1, 0, 1, 107, 106)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 107, 106))
+            position: Some(Position::new(1, 1, 0, 1, 107, 106)),
+            data: None,
         }),
         "should support GFM autolink literals as `Link`s in mdast"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "[https://a.com](https://b.com) [www.c.com](https://d.com) [e@f.com](https://g.com)",
+            &Options::gfm()
+        )?,
+        "<p><a href=\"https://b.com\">https://a.com</a> <a href=\"https://d.com\">www.c.com</a> <a href=\"https://g.com\">e@f.com</a></p>",
+        "should not support GFM autolink literals as the text of links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a *https://b.com* c](d) https://e.com", &Options::gfm())?,
+        "<p><a href=\"d\">a <em>https://b.com</em> c</a> <a href=\"https://e.com\">https://e.com</a></p>",
+        "should not support GFM autolink literals in the text of links, but after them"
+    );
+
+    assert_eq!(
+        to_mdast("[https://a.com](https://b.com)", &ParseOptions::gfm())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Link(Link {
+                    url: "https://b.com".into(),
+                    title: None,
+                    children: vec![Node::Text(Text {
+                        value: "https://a.com".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 15, 14)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 31, 30)),
+                    data: None,
+                    markers: vec![],
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 31, 30)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 31, 30)),
+            data: None,
+        }),
+        "should not nest links in mdast for a URL as the text of a link"
+    );
+
     Ok(())
 }
@@ -37,7 +37,8 @@ fn gfm_footnote() -> Result<(), message::Message> {
                     gfm_footnote_label: Some("Voetnoten".into()),
                     gfm_footnote_back_label: Some("Terug naar de inhoud".into()),
                     ..CompileOptions::gfm()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p>Noot.<sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
@@ -60,7 +61,8 @@ fn gfm_footnote() -> Result<(), message::Message> {
                 compile: CompileOptions {
                     gfm_footnote_label_tag_name: Some("h1".into()),
                     ..CompileOptions::gfm()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
@@ -83,7 +85,8 @@ fn gfm_footnote() -> Result<(), message::Message> {
                 compile: CompileOptions {
                     gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
                     ..CompileOptions::gfm()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
@@ -106,7 +109,8 @@ fn gfm_footnote() -> Result<(), message::Message> {
                 compile: CompileOptions {
                     gfm_footnote_clobber_prefix: Some("".into()),
                     ..CompileOptions::gfm()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>
@@ -1629,37 +1633,88 @@ multi-paragraph list items. <a href="#user-content-fnref-longnote" data-footnote
                     children: vec![Node::Paragraph(Paragraph {
                         children: vec![Node::Text(Text {
                             value: "b\nc".into(),
-                            position: Some(Position::new(1, 7, 6, 2, 6, 10))
+                            position: Some(Position::new(1, 7, 6, 2, 6, 10)),
+                            data: None,
                         })],
-                        position: Some(Position::new(1, 7, 6, 2, 6, 10))
+                        position: Some(Position::new(1, 7, 6, 2, 6, 10)),
+                        data: None,
                     })],
                     identifier: "a".into(),
                     label: Some("a".into()),
-                    position: Some(Position::new(1, 1, 0, 3, 1, 11))
+                    position: Some(Position::new(1, 1, 0, 3, 1, 11)),
+                    data: None,
                 }),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "d ".into(),
-                            position: Some(Position::new(4, 1, 12, 4, 3, 14))
+                            position: Some(Position::new(4, 1, 12, 4, 3, 14)),
+                            data: None,
                         }),
                         Node::FootnoteReference(FootnoteReference {
                             identifier: "a".into(),
                             label: Some("a".into()),
-                            position: Some(Position::new(4, 3, 14, 4, 7, 18))
+                            position: Some(Position::new(4, 3, 14, 4, 7, 18)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " e.".into(),
-                            position: Some(Position::new(4, 7, 18, 4, 10, 21))
+                            position: Some(Position::new(4, 7, 18, 4, 10, 21)),
+                            data: None,
                         })
                     ],
-                    position: Some(Position::new(4, 1, 12, 4, 10, 21))
+                    position: Some(Position::new(4, 1, 12, 4, 10, 21)),
+                    data: None,
                 })
             ],
-            position: Some(Position::new(1, 1, 0, 4, 10, 21))
+            position: Some(Position::new(1, 1, 0, 4, 10, 21)),
+            data: None,
         }),
         "should support GFM footnotes as `FootnoteDefinition`, `FootnoteReference`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("[^Alpha] [^b]\n\n[^ALPHA]: c", &ParseOptions::gfm())?,
+        Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![
+                        Node::FootnoteReference(FootnoteReference {
+                            identifier: "alpha".into(),
+                            label: Some("Alpha".into()),
+                            position: Some(Position::new(1, 1, 0, 1, 9, 8)),
+                            data: None,
+                        }),
+                        Node::Text(Text {
+                            value: " [^b]".into(),
+                            position: Some(Position::new(1, 9, 8, 1, 14, 13)),
+                            data: None,
+                        })
+                    ],
+                    position: Some(Position::new(1, 1, 0, 1, 14, 13)),
+                    data: None,
+                }),
+                Node::FootnoteDefinition(FootnoteDefinition {
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "c".into(),
+                            position: Some(Position::new(3, 11, 25, 3, 12, 26)),
+                            data: None,
+                        })],
+                        position: Some(Position::new(3, 11, 25, 3, 12, 26)),
+                        data: None,
+                    })],
+                    identifier: "alpha".into(),
+                    label: Some("ALPHA".into()),
+                    position: Some(Position::new(3, 1, 15, 3, 12, 26)),
+                    data: None,
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 3, 12, 26)),
+            data: None,
+        }),
+        "should keep labels as written, normalize identifiers, and not support footnote references w/o definitions in mdast"
+    );
+
     Ok(())
 }
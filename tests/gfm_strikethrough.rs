@@ -412,23 +412,29 @@ u ~**xxx**~ zzz
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Delete(Delete {
                         children: vec![Node::Text(Text {
                             value: "alpha".into(),
-                            position: Some(Position::new(1, 5, 4, 1, 10, 9))
+                            position: Some(Position::new(1, 5, 4, 1, 10, 9)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " b.".into(),
-                        position: Some(Position::new(1, 12, 11, 1, 15, 14))
+                        position: Some(Position::new(1, 12, 11, 1, 15, 14)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 15, 14))
+                position: Some(Position::new(1, 1, 0, 1, 15, 14)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 15, 14))
+            position: Some(Position::new(1, 1, 0, 1, 15, 14)),
+            data: None,
         }),
         "should support GFM strikethrough as `Delete`s in mdast"
     );
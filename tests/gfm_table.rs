@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{AlignKind, InlineCode, Node, Root, Table, TableCell, TableRow, Text},
+    mdast::{AlignKind, InlineCode, Node, Root, Strong, Table, TableCell, TableRow, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -1856,88 +1856,130 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "none".into(),
-                                    position: Some(Position::new(1, 3, 2, 1, 7, 6))
+                                    position: Some(Position::new(1, 3, 2, 1, 7, 6)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(1, 1, 0, 1, 8, 7))
+                                position: Some(Position::new(1, 1, 0, 1, 8, 7)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "left".into(),
-                                    position: Some(Position::new(1, 10, 9, 1, 14, 13))
+                                    position: Some(Position::new(1, 10, 9, 1, 14, 13)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(1, 8, 7, 1, 15, 14))
+                                position: Some(Position::new(1, 8, 7, 1, 15, 14)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "right".into(),
-                                    position: Some(Position::new(1, 17, 16, 1, 22, 21))
+                                    position: Some(Position::new(1, 17, 16, 1, 22, 21)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(1, 15, 14, 1, 23, 22))
+                                position: Some(Position::new(1, 15, 14, 1, 23, 22)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "center".into(),
-                                    position: Some(Position::new(1, 25, 24, 1, 31, 30))
+                                    position: Some(Position::new(1, 25, 24, 1, 31, 30)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(1, 23, 22, 1, 33, 32))
+                                position: Some(Position::new(1, 23, 22, 1, 33, 32)),
+                                data: None,
                             }),
                         ],
-                        position: Some(Position::new(1, 1, 0, 1, 33, 32))
+                        position: Some(Position::new(1, 1, 0, 1, 33, 32)),
+                        data: None,
                     }),
                     Node::TableRow(TableRow {
-                        children: vec![Node::TableCell(TableCell {
-                            children: vec![Node::Text(Text {
-                                value: "a".into(),
-                                position: Some(Position::new(3, 3, 57, 3, 4, 58))
-                            }),],
-                            position: Some(Position::new(3, 1, 55, 3, 6, 60))
-                        }),],
-                        position: Some(Position::new(3, 1, 55, 3, 6, 60))
+                        children: vec![
+                            Node::TableCell(TableCell {
+                                children: vec![Node::Text(Text {
+                                    value: "a".into(),
+                                    position: Some(Position::new(3, 3, 57, 3, 4, 58)),
+                                    data: None,
+                                }),],
+                                position: Some(Position::new(3, 1, 55, 3, 6, 60)),
+                                data: None,
+                            }),
+                            Node::TableCell(TableCell {
+                                children: vec![],
+                                position: None,
+                                data: None,
+                            }),
+                            Node::TableCell(TableCell {
+                                children: vec![],
+                                position: None,
+                                data: None,
+                            }),
+                            Node::TableCell(TableCell {
+                                children: vec![],
+                                position: None,
+                                data: None,
+                            }),
+                        ],
+                        position: Some(Position::new(3, 1, 55, 3, 6, 60)),
+                        data: None,
                     }),
                     Node::TableRow(TableRow {
                         children: vec![
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "b".into(),
-                                    position: Some(Position::new(4, 3, 63, 4, 4, 64))
+                                    position: Some(Position::new(4, 3, 63, 4, 4, 64)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(4, 1, 61, 4, 5, 65))
+                                position: Some(Position::new(4, 1, 61, 4, 5, 65)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "c".into(),
-                                    position: Some(Position::new(4, 7, 67, 4, 8, 68))
+                                    position: Some(Position::new(4, 7, 67, 4, 8, 68)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(4, 5, 65, 4, 9, 69))
+                                position: Some(Position::new(4, 5, 65, 4, 9, 69)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "d".into(),
-                                    position: Some(Position::new(4, 11, 71, 4, 12, 72))
+                                    position: Some(Position::new(4, 11, 71, 4, 12, 72)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(4, 9, 69, 4, 13, 73))
+                                position: Some(Position::new(4, 9, 69, 4, 13, 73)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "e".into(),
-                                    position: Some(Position::new(4, 15, 75, 4, 16, 76))
+                                    position: Some(Position::new(4, 15, 75, 4, 16, 76)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(4, 13, 73, 4, 17, 77))
+                                position: Some(Position::new(4, 13, 73, 4, 17, 77)),
+                                data: None,
                             }),
                             Node::TableCell(TableCell {
                                 children: vec![Node::Text(Text {
                                     value: "f".into(),
-                                    position: Some(Position::new(4, 19, 79, 4, 20, 80))
+                                    position: Some(Position::new(4, 19, 79, 4, 20, 80)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(4, 17, 77, 4, 22, 82))
+                                position: Some(Position::new(4, 17, 77, 4, 22, 82)),
+                                data: None,
                             }),
                         ],
-                        position: Some(Position::new(4, 1, 61, 4, 22, 82))
+                        position: Some(Position::new(4, 1, 61, 4, 22, 82)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 4, 22, 82))
+                position: Some(Position::new(1, 1, 0, 4, 22, 82)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 4, 22, 82))
+            position: Some(Position::new(1, 1, 0, 4, 22, 82)),
+            data: None,
         }),
         "should support GFM tables as `Table`, `TableRow`, `TableCell`s in mdast"
     );
@@ -1951,18 +1993,101 @@ normal escape: <a href="https://github.com/github/cmark-gfm/issues/277">https://
                     children: vec![Node::TableCell(TableCell {
                         children: vec![Node::InlineCode(InlineCode {
                             value: "a|b".into(),
-                            position: Some(Position::new(1, 3, 2, 1, 9, 8))
+                            position: Some(Position::new(1, 3, 2, 1, 9, 8)),
+                            sequence_size: Some(1),
+                            padding: false,
+                            data: None,
+                            markers: vec![],
                         }),],
-                        position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                        position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                        data: None,
                     }),],
-                    position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                    position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                    data: None,
                 }),],
-                position: Some(Position::new(1, 1, 0, 2, 6, 16))
+                position: Some(Position::new(1, 1, 0, 2, 6, 16)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 6, 16))
+            position: Some(Position::new(1, 1, 0, 2, 6, 16)),
+            data: None,
         }),
         "should support weird pipe escapes in code in tables"
     );
 
+    assert_eq!(
+        to_mdast(
+            "| a | b |\n| - | - |\n| **c** \\| d |",
+            &ParseOptions::gfm()
+        )?,
+        Node::Root(Root {
+            children: vec![Node::Table(Table {
+                align: vec![AlignKind::None, AlignKind::None],
+                children: vec![
+                    Node::TableRow(TableRow {
+                        children: vec![
+                            Node::TableCell(TableCell {
+                                children: vec![Node::Text(Text {
+                                    value: "a".into(),
+                                    position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                                    data: None,
+                                }),],
+                                position: Some(Position::new(1, 1, 0, 1, 5, 4)),
+                                data: None,
+                            }),
+                            Node::TableCell(TableCell {
+                                children: vec![Node::Text(Text {
+                                    value: "b".into(),
+                                    position: Some(Position::new(1, 7, 6, 1, 8, 7)),
+                                    data: None,
+                                }),],
+                                position: Some(Position::new(1, 5, 4, 1, 10, 9)),
+                                data: None,
+                            }),
+                        ],
+                        position: Some(Position::new(1, 1, 0, 1, 10, 9)),
+                        data: None,
+                    }),
+                    Node::TableRow(TableRow {
+                        children: vec![
+                            Node::TableCell(TableCell {
+                                children: vec![
+                                    Node::Strong(Strong {
+                                        children: vec![Node::Text(Text {
+                                            value: "c".into(),
+                                            position: Some(Position::new(3, 5, 24, 3, 6, 25)),
+                                            data: None,
+                                        }),],
+                                        position: Some(Position::new(3, 3, 22, 3, 8, 27)),
+                                        marker: Some('*'),
+                                        data: None,
+                                    }),
+                                    Node::Text(Text {
+                                        value: " | d".into(),
+                                        position: Some(Position::new(3, 8, 27, 3, 13, 32)),
+                                        data: None,
+                                    }),
+                                ],
+                                position: Some(Position::new(3, 1, 20, 3, 15, 34)),
+                                data: None,
+                            }),
+                            Node::TableCell(TableCell {
+                                children: vec![],
+                                position: None,
+                                data: None,
+                            }),
+                        ],
+                        position: Some(Position::new(3, 1, 20, 3, 15, 34)),
+                        data: None,
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 3, 15, 34)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 15, 34)),
+            data: None,
+        }),
+        "should support inline content and escaped pipes in cells, and add missing cells, in mdast"
+    );
+
     Ok(())
 }
@@ -167,5 +167,53 @@ javascript:/*--></title></style></textarea></script></xmp><svg/onload='+/"/+/onm
         "should handle things like GitHub"
     );
 
+    assert_eq!(
+        to_html_with_options(
+            "<iframe></iframe> <noembed></noembed> <noframes></noframes> <plaintext></plaintext> <script></script> <style></style> <textarea></textarea> <title></title> <xmp></xmp>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    gfm_tagfilter: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "&lt;iframe>&lt;/iframe> &lt;noembed>&lt;/noembed> &lt;noframes>&lt;/noframes> &lt;plaintext>&lt;/plaintext> &lt;script>&lt;/script> &lt;style>&lt;/style> &lt;textarea>&lt;/textarea> &lt;title>&lt;/title> &lt;xmp>&lt;/xmp>",
+        "should filter all disallowed tags (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <iframe></iframe> <noembed></noembed> <noframes></noframes> <plaintext></plaintext> <script></script> <style></style> <textarea></textarea> <title></title> <xmp></xmp>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    gfm_tagfilter: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a &lt;iframe>&lt;/iframe> &lt;noembed>&lt;/noembed> &lt;noframes>&lt;/noframes> &lt;plaintext>&lt;/plaintext> &lt;script>&lt;/script> &lt;style>&lt;/style> &lt;textarea>&lt;/textarea> &lt;title>&lt;/title> &lt;xmp>&lt;/xmp></p>",
+        "should filter all disallowed tags (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <scripts> <titlebar> <xmps/> <stylesheet> <b>c</b> <Iframe>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    gfm_tagfilter: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a <scripts> <titlebar> <xmps/> <stylesheet> <b>c</b> &lt;Iframe></p>",
+        "should not filter other tags"
+    );
+
     Ok(())
 }
@@ -34,7 +34,8 @@ fn gfm_task_list_item() -> Result<(), message::Message> {
                 compile: CompileOptions {
                     gfm_task_list_item_checkable: true,
                     ..CompileOptions::gfm()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>",
@@ -264,44 +265,64 @@ Text.</li>
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "a".into(),
-                                position: Some(Position::new(1, 7, 6, 1, 8, 7))
+                                position: Some(Position::new(1, 7, 6, 1, 8, 7)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(1, 7, 6, 1, 8, 7))
+                            position: Some(Position::new(1, 7, 6, 1, 8, 7)),
+                            data: None,
                         })],
-                        position: Some(Position::new(1, 1, 0, 1, 8, 7))
+                        position: Some(Position::new(1, 1, 0, 1, 8, 7)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(false),
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "b".into(),
-                                position: Some(Position::new(2, 7, 14, 2, 8, 15))
+                                position: Some(Position::new(2, 7, 14, 2, 8, 15)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(2, 7, 14, 2, 8, 15))
+                            position: Some(Position::new(2, 7, 14, 2, 8, 15)),
+                            data: None,
                         })],
-                        position: Some(Position::new(2, 1, 8, 2, 8, 15))
+                        position: Some(Position::new(2, 1, 8, 2, 8, 15)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "c".into(),
-                                position: Some(Position::new(3, 3, 18, 3, 4, 19))
+                                position: Some(Position::new(3, 3, 18, 3, 4, 19)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(3, 3, 18, 3, 4, 19))
+                            position: Some(Position::new(3, 3, 18, 3, 4, 19)),
+                            data: None,
                         })],
-                        position: Some(Position::new(3, 1, 16, 3, 4, 19))
+                        position: Some(Position::new(3, 1, 16, 3, 4, 19)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 3, 4, 19))
+                position: Some(Position::new(1, 1, 0, 3, 4, 19)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 19))
+            position: Some(Position::new(1, 1, 0, 3, 4, 19)),
+            data: None,
         }),
         "should support task list items as `checked` fields on `ListItem`s in mdast"
     );
@@ -319,47 +340,69 @@ Text.</li>
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "a".into(),
-                                position: Some(Position::new(2, 1, 7, 2, 4, 10))
+                                position: Some(Position::new(2, 1, 7, 2, 4, 10)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(2, 1, 7, 2, 4, 10))
+                            position: Some(Position::new(2, 1, 7, 2, 4, 10)),
+                            data: None,
                         })],
-                        position: Some(Position::new(1, 1, 0, 2, 4, 10))
+                        position: Some(Position::new(1, 1, 0, 2, 4, 10)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(false),
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "  b".into(),
-                                position: Some(Position::new(3, 7, 17, 3, 10, 20))
+                                position: Some(Position::new(3, 7, 17, 3, 10, 20)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(3, 7, 17, 3, 10, 20))
+                            position: Some(Position::new(3, 7, 17, 3, 10, 20)),
+                            data: None,
                         })],
-                        position: Some(Position::new(3, 1, 11, 3, 10, 20))
+                        position: Some(Position::new(3, 1, 11, 3, 10, 20)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Emphasis(Emphasis {
                                 children: vec![Node::Text(Text {
                                     value: "c".into(),
-                                    position: Some(Position::new(5, 2, 30, 5, 3, 31))
+                                    position: Some(Position::new(5, 2, 30, 5, 3, 31)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(5, 1, 29, 5, 4, 32))
+                                position: Some(Position::new(5, 1, 29, 5, 4, 32)),
+                                marker: Some('*'),
+                                data: None,
                             })],
-                            position: Some(Position::new(5, 1, 29, 5, 4, 32))
+                            position: Some(Position::new(5, 1, 29, 5, 4, 32)),
+                            data: None,
                         })],
-                        position: Some(Position::new(4, 1, 21, 5, 4, 32))
+                        position: Some(Position::new(4, 1, 21, 5, 4, 32)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 5, 4, 32))
+                position: Some(Position::new(1, 1, 0, 5, 4, 32)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 5, 4, 32))
+            position: Some(Position::new(1, 1, 0, 5, 4, 32)),
+            data: None,
         }),
         "should handle lots of whitespace after checkbox, and non-text"
     );
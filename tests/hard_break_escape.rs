@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Break, Node, Paragraph, Root, Text},
+    mdast::{Break, BreakKind, Node, NodeKind, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -44,6 +44,39 @@ fn hard_break_escape() -> Result<(), message::Message> {
         "should not support escape hard breaks at the end of a heading"
     );
 
+    assert_eq!(
+        to_html("# a\\\nb"),
+        "<h1>a\\</h1>\n<p>b</p>",
+        "should not support escape hard breaks at the end of an ATX heading followed by more"
+    );
+
+    assert_eq!(
+        to_html("# a\\ #"),
+        "<h1>a\\</h1>",
+        "should not support escape hard breaks before the closing sequence of an ATX heading"
+    );
+
+    assert_eq!(
+        to_html("a\\\nb\n==="),
+        "<h1>a<br />\nb</h1>",
+        "should support escape hard breaks in setext heading text"
+    );
+
+    assert_eq!(
+        to_html("a\\\n---"),
+        "<h2>a\\</h2>",
+        "should not support escape hard breaks at the end of setext heading text"
+    );
+
+    assert_eq!(
+        to_mdast("a\\\nb\n===", &Default::default())?
+            .children()
+            .and_then(|children| children[0].children())
+            .map(|children| children.iter().map(Node::kind).collect::<Vec<_>>()),
+        Some(vec![NodeKind::Text, NodeKind::Break, NodeKind::Text]),
+        "should support escape hard breaks in setext heading text (mdast)"
+    );
+
     assert_eq!(
         to_html_with_options(
             "a\\\nb",
@@ -69,19 +102,25 @@ fn hard_break_escape() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1)),
+                        data: None,
                     }),
                     Node::Break(Break {
-                        position: Some(Position::new(1, 2, 1, 2, 1, 3))
+                        position: Some(Position::new(1, 2, 1, 2, 1, 3)),
+                        kind: BreakKind::Escape,
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: "b.".into(),
-                        position: Some(Position::new(2, 1, 3, 2, 3, 5))
+                        position: Some(Position::new(2, 1, 3, 2, 3, 5)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 2, 3, 5))
+                position: Some(Position::new(1, 1, 0, 2, 3, 5)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 3, 5))
+            position: Some(Position::new(1, 1, 0, 2, 3, 5)),
+            data: None,
         }),
         "should support hard break (escape) as `Break`s in mdast"
     );
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Break, Node, Paragraph, Root, Text},
+    mdast::{Break, BreakKind, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -128,6 +128,41 @@ fn hard_break_trailing() -> Result<(), message::Message> {
         "should support turning off hard break (trailing)"
     );
 
+    let no_trailing = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                hard_break_trailing: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &no_trailing)?,
+        "<p>a\nb<br />\nc</p>",
+        "should support hard break (escape) when turning off hard break (trailing)"
+    );
+
+    assert_eq!(
+        to_mdast("a  \nb", &no_trailing.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a\nb".into(),
+                    position: Some(Position::new(1, 1, 0, 2, 2, 5)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 2, 5)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 2, 5)),
+            data: None,
+        }),
+        "should trim trailing spaces as whitespace when turning off hard break (trailing)"
+    );
+
     assert_eq!(
         to_mdast("a  \nb.", &Default::default())?,
         Node::Root(Root {
@@ -135,22 +170,42 @@ fn hard_break_trailing() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1)),
+                        data: None,
                     }),
                     Node::Break(Break {
-                        position: Some(Position::new(1, 2, 1, 2, 1, 4))
+                        position: Some(Position::new(1, 2, 1, 2, 1, 4)),
+                        kind: BreakKind::Trailing,
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: "b.".into(),
-                        position: Some(Position::new(2, 1, 4, 2, 3, 6))
+                        position: Some(Position::new(2, 1, 4, 2, 3, 6)),
+                        data: None,
                     }),
                 ],
-                position: Some(Position::new(1, 1, 0, 2, 3, 6))
+                position: Some(Position::new(1, 1, 0, 2, 3, 6)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 3, 6))
+            position: Some(Position::new(1, 1, 0, 2, 3, 6)),
+            data: None,
         }),
         "should support hard break (trailing) as `Break`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("a   \r\nb", &Default::default())?
+            .children()
+            .unwrap()[0]
+            .children()
+            .unwrap()[1],
+        Node::Break(Break {
+            position: Some(Position::new(1, 2, 1, 2, 1, 6)),
+            kind: BreakKind::Trailing,
+            data: None,
+        }),
+        "should include all trailing spaces and the line ending in the position of a `Break`"
+    );
+
     Ok(())
 }
@@ -229,13 +229,18 @@ fn heading_atx() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Heading(Heading {
                 depth: 2,
+                setext: false,
                 children: vec![Node::Text(Text {
                     value: "alpha".into(),
-                    position: Some(Position::new(1, 4, 3, 1, 9, 8))
+                    position: Some(Position::new(1, 4, 3, 1, 9, 8)),
+                    data: None,
                 }),],
-                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                data: None,
+                markers: vec![],
             })],
-            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+            data: None,
         }),
         "should support heading (atx) as `Heading`s in mdast"
     );
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Heading, Node, Root, Text},
+    mdast::{BlockQuote, Break, BreakKind, Heading, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -110,6 +110,24 @@ fn heading_setext() -> Result<(), message::Message> {
         "should not support whitespace in the underline (2)"
     );
 
+    assert_eq!(
+        to_html("Foo\n==\t="),
+        "<p>Foo\n==\t=</p>",
+        "should not support whitespace in the underline (3)"
+    );
+
+    assert_eq!(
+        to_html("Foo\n===\t"),
+        "<h1>Foo</h1>",
+        "should support a tab after the underline"
+    );
+
+    assert_eq!(
+        to_html("Foo\n---\t \t\nbar"),
+        "<h2>Foo</h2>\n<p>bar</p>",
+        "should support tabs and spaces after the underline, before a line ending"
+    );
+
     assert_eq!(
         to_html("Foo  \n-----"),
         "<h2>Foo</h2>",
@@ -267,6 +285,48 @@ fn heading_setext() -> Result<(), message::Message> {
         "should not support lazyness (2)"
     );
 
+    assert_eq!(
+        to_html("> a\n> b\n==="),
+        "<blockquote>\n<p>a\nb\n===</p>\n</blockquote>",
+        "should not support lazyness (3)"
+    );
+
+    assert_eq!(
+        to_html("> a\n   ==="),
+        "<blockquote>\n<p>a\n===</p>\n</blockquote>",
+        "should not support lazyness (4)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> ==="),
+        "<blockquote>\n<blockquote>\n<p>a\n===</p>\n</blockquote>\n</blockquote>",
+        "should not support lazyness in nested block quotes (1)"
+    );
+
+    assert_eq!(
+        to_html("> > a\n==="),
+        "<blockquote>\n<blockquote>\n<p>a\n===</p>\n</blockquote>\n</blockquote>",
+        "should not support lazyness in nested block quotes (2)"
+    );
+
+    assert_eq!(
+        to_html("- > a\n  ==="),
+        "<ul>\n<li>\n<blockquote>\n<p>a\n===</p>\n</blockquote>\n</li>\n</ul>",
+        "should not support lazyness in block quotes in list items"
+    );
+
+    assert_eq!(
+        to_html("> a\n===\n> ==="),
+        "<blockquote>\n<h1>a\n===</h1>\n</blockquote>",
+        "should support a non-lazy underline after a lazy line"
+    );
+
+    assert_eq!(
+        to_html("> a\nb\n> ---"),
+        "<blockquote>\n<h2>a\nb</h2>\n</blockquote>",
+        "should support a non-lazy underline after a lazy paragraph continuation"
+    );
+
     assert_eq!(
         to_html("a\n- ==="),
         "<p>a</p>\n<ul>\n<li>===</li>\n</ul>",
@@ -302,16 +362,165 @@ fn heading_setext() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Heading(Heading {
                 depth: 1,
+                setext: true,
                 children: vec![Node::Text(Text {
                     value: "alpha\nbravo".into(),
-                    position: Some(Position::new(1, 1, 0, 2, 6, 11))
+                    position: Some(Position::new(1, 1, 0, 2, 6, 11)),
+                    data: None,
                 }),],
-                position: Some(Position::new(1, 1, 0, 3, 3, 14))
+                position: Some(Position::new(1, 1, 0, 3, 3, 14)),
+                data: None,
+                markers: vec![],
             })],
-            position: Some(Position::new(1, 1, 0, 3, 3, 14))
+            position: Some(Position::new(1, 1, 0, 3, 3, 14)),
+            data: None,
         }),
         "should support heading (atx) as `Heading`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("> a\n===", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a\n===".into(),
+                        position: Some(Position::new(1, 3, 2, 2, 4, 7)),
+                        data: None,
+                    }),],
+                    position: Some(Position::new(1, 3, 2, 2, 4, 7)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 4, 7)),
+            data: None,
+        }),
+        "should not support a lazy underline in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn heading_setext_thematic_break() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("foo\n---"),
+        "<h2>foo</h2>",
+        "should prefer a setext underline over a thematic break after a paragraph"
+    );
+
+    assert_eq!(
+        to_html("foo\n\n---"),
+        "<p>foo</p>\n<hr />",
+        "should prefer a thematic break after a blank line"
+    );
+
+    assert_eq!(
+        to_html("foo   \n---"),
+        "<h2>foo</h2>",
+        "should prefer a setext underline after trailing spaces"
+    );
+
+    assert_eq!(
+        to_html("foo\t\n---"),
+        "<h2>foo</h2>",
+        "should prefer a setext underline after a trailing tab"
+    );
+
+    assert_eq!(
+        to_html("foo\n   ---   "),
+        "<h2>foo</h2>",
+        "should prefer a setext underline w/ whitespace around it"
+    );
+
+    assert_eq!(
+        to_html("foo\n---\t\nbar"),
+        "<h2>foo</h2>\n<p>bar</p>",
+        "should prefer a setext underline w/ a trailing tab, before more content"
+    );
+
+    assert_eq!(
+        to_html("foo\n    ---"),
+        "<p>foo\n---</p>",
+        "should prefer neither for an indented dash line (paragraph continuation)"
+    );
+
+    assert_eq!(
+        to_html("foo\n- - -"),
+        "<p>foo</p>\n<hr />",
+        "should prefer a thematic break for dashes w/ whitespace in between"
+    );
+
+    assert_eq!(
+        to_html("foo\n--- -"),
+        "<p>foo</p>\n<hr />",
+        "should prefer a thematic break for dashes w/ whitespace before the last"
+    );
+
+    assert_eq!(
+        to_html("foo\n***"),
+        "<p>foo</p>\n<hr />",
+        "should prefer a thematic break for asterisks"
+    );
+
+    assert_eq!(
+        to_html("foo\n--- a"),
+        "<p>foo\n--- a</p>",
+        "should prefer neither for dashes followed by text"
+    );
+
+    assert_eq!(
+        to_html("foo  \nbar   \n---"),
+        "<h2>foo<br />\nbar</h2>",
+        "should prefer a setext underline after a hard break (trailing) and trailing spaces"
+    );
+
+    assert_eq!(
+        to_html("foo\\\nbar\\\n---"),
+        "<h2>foo<br />\nbar\\</h2>",
+        "should prefer a setext underline after a hard break (escape) and a final backslash"
+    );
+
+    assert_eq!(
+        to_html("- foo\n---"),
+        "<ul>\n<li>foo</li>\n</ul>\n<hr />",
+        "should prefer a thematic break after a list item"
+    );
+
+    assert_eq!(
+        to_mdast("foo  \nbar   \n---", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 2,
+                setext: true,
+                children: vec![
+                    Node::Text(Text {
+                        value: "foo".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                        data: None,
+                    }),
+                    Node::Break(Break {
+                        position: Some(Position::new(1, 4, 3, 2, 1, 6)),
+                        data: None,
+                        kind: BreakKind::Trailing,
+                    }),
+                    Node::Text(Text {
+                        value: "bar".into(),
+                        position: Some(Position::new(2, 1, 6, 2, 4, 9)),
+                        data: None,
+                    }),
+                ],
+                position: Some(Position::new(1, 1, 0, 3, 4, 16)),
+                data: None,
+                markers: vec![],
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 16)),
+            data: None,
+        }),
+        "should not include a final hard break (trailing) in a setext heading in mdast"
+    );
+
     Ok(())
 }
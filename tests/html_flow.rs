@@ -52,9 +52,11 @@ fn html_flow() -> Result<(), message::Message> {
         Node::Root(Root {
             children: vec![Node::Html(Html {
                 value: "<div>\nstuff\n</div>".into(),
-                position: Some(Position::new(1, 1, 0, 3, 7, 18))
+                position: Some(Position::new(1, 1, 0, 3, 7, 18)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 7, 18))
+            position: Some(Position::new(1, 1, 0, 3, 7, 18)),
+            data: None,
         }),
         "should support HTML (flow) as `Html`s in mdast"
     );
@@ -340,6 +342,45 @@ fn html_flow_2_comment() -> Result<(), message::Message> {
         "should not support lazyness (2)"
     );
 
+    assert_eq!(
+        to_html_with_options("- <!--\n- a", &danger)?,
+        "<ul>\n<li>\n<!--\n</li>\n<li>a</li>\n</ul>",
+        "should not include the line ending before a new container in comments"
+    );
+
+    let strip = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            strip_comments: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\n\n<!-- b -->\n\nc", &strip)?,
+        "<p>a</p>\n<p>c</p>",
+        "should support stripping comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("<!-- Foo\n\nbar\n   baz -->\nokay", &strip)?,
+        "<p>okay</p>",
+        "should support stripping multiline comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("  <!-- a -->\n<div>\n\n<!-- b -->", &strip)?,
+        "<div>\n",
+        "should not strip other HTML when stripping comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("> <!-- a -->\n> b", &strip)?,
+        "<blockquote>\n<p>b</p>\n</blockquote>",
+        "should support stripping comments in containers"
+    );
+
     Ok(())
 }
 
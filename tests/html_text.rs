@@ -444,6 +444,48 @@ to_html_with_options("<x> a", &danger)?,
         "should support turning off html (text)"
     );
 
+    let strip = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            strip_comments: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a <!-- b --> c", &strip)?,
+        "<p>a  c</p>",
+        "should support stripping comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("foo <!-- this is a\ncomment - with hyphen -->", &strip)?,
+        "<p>foo </p>",
+        "should support stripping multiline comments"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <!-- b --> <i>c</i>", &strip)?,
+        "<p>a  <i>c</i></p>",
+        "should not strip other HTML when stripping comments"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <!-- b --> c",
+            &Options {
+                compile: CompileOptions {
+                    strip_comments: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a  c</p>",
+        "should support stripping comments w/o `allow_dangerous_html`"
+    );
+
     assert_eq!(
         to_mdast("alpha <i>bravo</b> charlie.", &Default::default())?,
         Node::Root(Root {
@@ -451,28 +493,35 @@ to_html_with_options("<x> a", &danger)?,
                 children: vec![
                     Node::Text(Text {
                         value: "alpha ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                        position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                        data: None,
                     }),
                     Node::Html(Html {
                         value: "<i>".into(),
-                        position: Some(Position::new(1, 7, 6, 1, 10, 9))
+                        position: Some(Position::new(1, 7, 6, 1, 10, 9)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: "bravo".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 15, 14))
+                        position: Some(Position::new(1, 10, 9, 1, 15, 14)),
+                        data: None,
                     }),
                     Node::Html(Html {
                         value: "</b>".into(),
-                        position: Some(Position::new(1, 15, 14, 1, 19, 18))
+                        position: Some(Position::new(1, 15, 14, 1, 19, 18)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " charlie.".into(),
-                        position: Some(Position::new(1, 19, 18, 1, 28, 27))
+                        position: Some(Position::new(1, 19, 18, 1, 28, 27)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 28, 27))
+                position: Some(Position::new(1, 1, 0, 1, 28, 27)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 28, 27))
+            position: Some(Position::new(1, 1, 0, 1, 28, 27)),
+            data: None,
         }),
         "should support HTML (text) as `Html`s in mdast"
     );
@@ -245,32 +245,39 @@ fn image() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Image(Image {
                         alt: "alpha".into(),
                         url: String::new(),
                         title: None,
-                        position: Some(Position::new(1, 3, 2, 1, 13, 12))
+                        position: Some(Position::new(1, 3, 2, 1, 13, 12)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
-                        position: Some(Position::new(1, 13, 12, 1, 16, 15))
+                        position: Some(Position::new(1, 13, 12, 1, 16, 15)),
+                        data: None,
                     }),
                     Node::Image(Image {
                         alt: "bravo".into(),
                         url: "charlie".into(),
                         title: Some("delta".into()),
-                        position: Some(Position::new(1, 16, 15, 1, 41, 40))
+                        position: Some(Position::new(1, 16, 15, 1, 41, 40)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
-                        position: Some(Position::new(1, 41, 40, 1, 44, 43))
+                        position: Some(Position::new(1, 41, 40, 1, 44, 43)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 44, 43))
+                position: Some(Position::new(1, 1, 0, 1, 44, 43)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 44, 43))
+            position: Some(Position::new(1, 1, 0, 1, 44, 43)),
+            data: None,
         }),
         "should support image (resource) as `Image`s in mdast"
     );
@@ -287,54 +294,112 @@ fn image() -> Result<(), message::Message> {
                     label: Some("x".into()),
                     url: "y".into(),
                     title: None,
-                    position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                    data: None,
                 }),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "a ".into(),
-                            position: Some(Position::new(3, 1, 8, 3, 3, 10))
+                            position: Some(Position::new(3, 1, 8, 3, 3, 10)),
+                            data: None,
                         }),
                         Node::ImageReference(ImageReference {
                             reference_kind: ReferenceKind::Shortcut,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "x".into(),
-                            position: Some(Position::new(3, 3, 10, 3, 7, 14))
+                            position: Some(Position::new(3, 3, 10, 3, 7, 14)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " b ".into(),
-                            position: Some(Position::new(3, 7, 14, 3, 10, 17))
+                            position: Some(Position::new(3, 7, 14, 3, 10, 17)),
+                            data: None,
                         }),
                         Node::ImageReference(ImageReference {
                             reference_kind: ReferenceKind::Collapsed,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "x".into(),
-                            position: Some(Position::new(3, 10, 17, 3, 16, 23))
+                            position: Some(Position::new(3, 10, 17, 3, 16, 23)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " c ".into(),
-                            position: Some(Position::new(3, 16, 23, 3, 19, 26))
+                            position: Some(Position::new(3, 16, 23, 3, 19, 26)),
+                            data: None,
                         }),
                         Node::ImageReference(ImageReference {
                             reference_kind: ReferenceKind::Full,
                             identifier: "x".into(),
                             label: Some("x".into()),
                             alt: "d".into(),
-                            position: Some(Position::new(3, 19, 26, 3, 26, 33))
+                            position: Some(Position::new(3, 19, 26, 3, 26, 33)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " e.".into(),
-                            position: Some(Position::new(3, 26, 33, 3, 29, 36))
+                            position: Some(Position::new(3, 26, 33, 3, 29, 36)),
+                            data: None,
                         }),
                     ],
-                    position: Some(Position::new(3, 1, 8, 3, 29, 36))
+                    position: Some(Position::new(3, 1, 8, 3, 29, 36)),
+                    data: None,
                 }),
             ],
-            position: Some(Position::new(1, 1, 0, 3, 29, 36))
+            position: Some(Position::new(1, 1, 0, 3, 29, 36)),
+            data: None,
         }),
         "should support image (reference) as `ImageReference`s in mdast"
     );
+
+    let alts = |value: &str| -> Result<Vec<String>, message::Message> {
+        Ok(to_mdast(value, &ParseOptions::default())?
+            .iter()
+            .filter_map(|node| match node {
+                Node::Image(Image { alt, .. })
+                | Node::ImageReference(ImageReference { alt, .. }) => Some(alt.clone()),
+                _ => None,
+            })
+            .collect())
+    };
+
+    assert_eq!(
+        alts("![a *b* c](u)")?,
+        vec!["a b c"],
+        "should flatten emphasis in alt in mdast"
+    );
+
+    assert_eq!(
+        alts("![a `b` **c**](u)")?,
+        vec!["a b c"],
+        "should flatten code and strong in alt in mdast"
+    );
+
+    assert_eq!(
+        alts("![a [b](c) d](u)")?,
+        vec!["a b d"],
+        "should flatten links in alt in mdast"
+    );
+
+    assert_eq!(
+        alts("![a ![b *c*](d) e](u)")?,
+        vec!["a b c e"],
+        "should flatten images in alt in mdast"
+    );
+
+    assert_eq!(
+        alts("![a *b* ![c][d]][e]\n\n[d]: u\n[e]: v")?,
+        vec!["a b c"],
+        "should flatten images in alt of image references in mdast"
+    );
+
+    assert_eq!(
+        to_html("![a *b* [c](d) ![e `f`](g)](u)"),
+        "<p><img src=\"u\" alt=\"a b c e f\" /></p>",
+        "should flatten alt in HTML"
+    );
+
     Ok(())
 }
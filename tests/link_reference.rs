@@ -131,6 +131,18 @@ fn link_reference() -> Result<(), message::Message> {
         "should not match references to definitions w/ escapes"
     );
 
+    assert_eq!(
+        to_html("[a b]\n\n[ab]: /url"),
+        "<p>[a b]</p>\n",
+        "should not match references to definitions w/o whitespace"
+    );
+
+    assert_eq!(
+        to_html("[a\tb\n  c]\n\n[A B C]: /url"),
+        "<p><a href=\"/url\">a\tb\nc</a></p>\n",
+        "should collapse whitespace when matching references"
+    );
+
     assert_eq!(
         to_html("[ref[]: /uri\n\n[foo][ref[]"),
         "<p>[ref[]: /uri</p>\n<p>[foo][ref[]</p>",
@@ -434,13 +446,15 @@ fn link_reference() -> Result<(), message::Message> {
                     label: Some("x".into()),
                     url: "y".into(),
                     title: None,
-                    position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6)),
+                    data: None,
                 }),
                 Node::Paragraph(Paragraph {
                     children: vec![
                         Node::Text(Text {
                             value: "a ".into(),
-                            position: Some(Position::new(3, 1, 8, 3, 3, 10))
+                            position: Some(Position::new(3, 1, 8, 3, 3, 10)),
+                            data: None,
                         }),
                         Node::LinkReference(LinkReference {
                             reference_kind: ReferenceKind::Shortcut,
@@ -448,13 +462,16 @@ fn link_reference() -> Result<(), message::Message> {
                             label: Some("x".into()),
                             children: vec![Node::Text(Text {
                                 value: "x".into(),
-                                position: Some(Position::new(3, 4, 11, 3, 5, 12))
+                                position: Some(Position::new(3, 4, 11, 3, 5, 12)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(3, 3, 10, 3, 6, 13))
+                            position: Some(Position::new(3, 3, 10, 3, 6, 13)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " b ".into(),
-                            position: Some(Position::new(3, 6, 13, 3, 9, 16))
+                            position: Some(Position::new(3, 6, 13, 3, 9, 16)),
+                            data: None,
                         }),
                         Node::LinkReference(LinkReference {
                             reference_kind: ReferenceKind::Collapsed,
@@ -462,13 +479,16 @@ fn link_reference() -> Result<(), message::Message> {
                             label: Some("x".into()),
                             children: vec![Node::Text(Text {
                                 value: "x".into(),
-                                position: Some(Position::new(3, 10, 17, 3, 11, 18))
+                                position: Some(Position::new(3, 10, 17, 3, 11, 18)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(3, 9, 16, 3, 14, 21))
+                            position: Some(Position::new(3, 9, 16, 3, 14, 21)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " c ".into(),
-                            position: Some(Position::new(3, 14, 21, 3, 17, 24))
+                            position: Some(Position::new(3, 14, 21, 3, 17, 24)),
+                            data: None,
                         }),
                         Node::LinkReference(LinkReference {
                             reference_kind: ReferenceKind::Full,
@@ -476,19 +496,24 @@ fn link_reference() -> Result<(), message::Message> {
                             label: Some("x".into()),
                             children: vec![Node::Text(Text {
                                 value: "d".into(),
-                                position: Some(Position::new(3, 18, 25, 3, 19, 26))
+                                position: Some(Position::new(3, 18, 25, 3, 19, 26)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(3, 17, 24, 3, 23, 30))
+                            position: Some(Position::new(3, 17, 24, 3, 23, 30)),
+                            data: None,
                         }),
                         Node::Text(Text {
                             value: " e.".into(),
-                            position: Some(Position::new(3, 23, 30, 3, 26, 33))
+                            position: Some(Position::new(3, 23, 30, 3, 26, 33)),
+                            data: None,
                         }),
                     ],
-                    position: Some(Position::new(3, 1, 8, 3, 26, 33))
+                    position: Some(Position::new(3, 1, 8, 3, 26, 33)),
+                    data: None,
                 }),
             ],
-            position: Some(Position::new(1, 1, 0, 3, 26, 33))
+            position: Some(Position::new(1, 1, 0, 3, 26, 33)),
+            data: None,
         }),
         "should support link (reference) as `LinkReference`s in mdast"
     );
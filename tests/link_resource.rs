@@ -475,38 +475,49 @@ fn link_resource() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: String::new(),
                         title: None,
                         children: vec![Node::Text(Text {
                             value: "alpha".into(),
-                            position: Some(Position::new(1, 4, 3, 1, 9, 8))
+                            position: Some(Position::new(1, 4, 3, 1, 9, 8)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 12, 11))
+                        position: Some(Position::new(1, 3, 2, 1, 12, 11)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " b ".into(),
-                        position: Some(Position::new(1, 12, 11, 1, 15, 14))
+                        position: Some(Position::new(1, 12, 11, 1, 15, 14)),
+                        data: None,
                     }),
                     Node::Link(Link {
                         url: "charlie".into(),
                         title: Some("delta".into()),
                         children: vec![Node::Text(Text {
                             value: "bravo".into(),
-                            position: Some(Position::new(1, 16, 15, 1, 21, 20))
+                            position: Some(Position::new(1, 16, 15, 1, 21, 20)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 15, 14, 1, 39, 38))
+                        position: Some(Position::new(1, 15, 14, 1, 39, 38)),
+                        data: None,
+                        markers: vec![],
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
-                        position: Some(Position::new(1, 39, 38, 1, 42, 41))
+                        position: Some(Position::new(1, 39, 38, 1, 42, 41)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 42, 41))
+                position: Some(Position::new(1, 1, 0, 1, 42, 41)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 42, 41))
+            position: Some(Position::new(1, 1, 0, 1, 42, 41)),
+            data: None,
         }),
         "should support link (resource) as `Link`s in mdast"
     );
@@ -521,17 +532,78 @@ fn link_resource() -> Result<(), message::Message> {
                         url: "image".into(),
                         title: None,
                         position: Some(Position::new(1, 2, 1, 1, 16, 15)),
+                        data: None,
                     }),],
                     url: "url".into(),
                     title: None,
                     position: Some(Position::new(1, 1, 0, 1, 22, 21)),
+                    data: None,
+                    markers: vec![],
                 }),],
                 position: Some(Position::new(1, 1, 0, 1, 22, 21)),
+                data: None,
             }),],
-            position: Some(Position::new(1, 1, 0, 1, 22, 21))
+            position: Some(Position::new(1, 1, 0, 1, 22, 21)),
+            data: None,
         }),
         "should support nested links in mdast"
     );
 
+    let value = "a [b](c\\)d 'e&amp;f') ![g](<h i> \"j\\\"k\") l";
+    let tree = to_mdast(value, &Default::default())?;
+    let children = tree.children().unwrap()[0].children().unwrap();
+
+    assert_eq!(
+        children[1],
+        Node::Link(Link {
+            children: vec![Node::Text(Text {
+                value: "b".into(),
+                position: Some(Position::new(1, 4, 3, 1, 5, 4)),
+                data: None,
+            })],
+            url: "c)d".into(),
+            title: Some("e&f".into()),
+            position: Some(Position::new(1, 3, 2, 1, 22, 21)),
+            data: None,
+            markers: vec![],
+        }),
+        "should decode destinations and titles of links in mdast"
+    );
+
+    assert_eq!(
+        children[1].source(value),
+        Some("[b](c\\)d 'e&amp;f')"),
+        "should support getting the source of links"
+    );
+
+    assert_eq!(
+        children[3],
+        Node::Image(Image {
+            alt: "g".into(),
+            url: "h i".into(),
+            title: Some("j\"k".into()),
+            position: Some(Position::new(1, 23, 22, 1, 41, 40)),
+            data: None,
+        }),
+        "should decode destinations and titles of images in mdast"
+    );
+
+    assert_eq!(
+        children[3].source(value),
+        Some("![g](<h i> \"j\\\"k\")"),
+        "should support getting the source of images"
+    );
+
+    assert_eq!(
+        Node::Text(Text {
+            value: "a".into(),
+            position: None,
+            data: None,
+        })
+        .source(value),
+        None,
+        "should not support getting the source of nodes w/o position"
+    );
+
     Ok(())
 }
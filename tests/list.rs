@@ -1,6 +1,6 @@
 use markdown::{
     mdast::{List, ListItem, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
@@ -437,6 +437,12 @@ fn list() -> Result<(), message::Message> {
         "should support tight lists w/ a blank line in fenced code"
     );
 
+    assert_eq!(
+        to_html("- a\n  ```\n\n  ```"),
+        "<ul>\n<li>a\n<pre><code>\n</code></pre>\n</li>\n</ul>",
+        "should support tight lists w/ only a blank line in fenced code after a paragraph"
+    );
+
     assert_eq!(
         to_html("- a\n  - b\n\n    c\n- d"),
         "<ul>\n<li>a\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>\n</li>\n<li>d</li>\n</ul>",
@@ -589,6 +595,44 @@ fn list() -> Result<(), message::Message> {
         "should support turning off lists"
     );
 
+    let renumber = Options {
+        compile: CompileOptions {
+            renumber_ordered_lists: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("5. a\n6. b\n\n7) c"),
+        "<ol start=\"5\">\n<li>a</li>\n<li>b</li>\n</ol>\n<ol start=\"7\">\n<li>c</li>\n</ol>",
+        "should use the number of the first item as `start`"
+    );
+
+    assert_eq!(
+        to_html_with_options("5. a\n6. b\n\n7) c", &renumber)?,
+        "<ol>\n<li>a</li>\n<li>b</li>\n</ol>\n<ol>\n<li>c</li>\n</ol>",
+        "should support `renumber_ordered_lists`"
+    );
+
+    assert_eq!(
+        mdast_to_html(&to_mdast("5. a\n6. b", &renumber.parse)?, &renumber)?,
+        "<ol>\n<li>a</li>\n<li>b</li>\n</ol>",
+        "should support `renumber_ordered_lists` in `mdast_to_html`"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n\nc"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<p>c</p>",
+        "should not make lists loose for blank lines after the last item"
+    );
+
+    assert_eq!(
+        to_html("- a\n  - b\n\n\n- c"),
+        "<ul>\n<li>\n<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should not make nested lists loose for blank lines after them"
+    );
+
     assert_eq!(
         to_mdast("* a", &Default::default())?,
         Node::Root(Root {
@@ -598,19 +642,27 @@ fn list() -> Result<(), message::Message> {
                 start: None,
                 children: vec![Node::ListItem(ListItem {
                     checked: None,
+                    marker: Some('*'),
+                    number: None,
+                    indent: Some(2),
                     spread: false,
                     children: vec![Node::Paragraph(Paragraph {
                         children: vec![Node::Text(Text {
                             value: "a".into(),
-                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                            data: None,
                         }),],
-                        position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                        data: None,
                     })],
-                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                    data: None,
                 })],
-                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+            data: None,
         }),
         "should support lists, list items as `List`, `ListItem`s in mdast"
     );
@@ -625,32 +677,46 @@ fn list() -> Result<(), message::Message> {
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('.'),
+                        number: Some(3),
+                        indent: Some(3),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "a".into(),
-                                position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                                position: Some(Position::new(1, 4, 3, 1, 5, 4)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                            position: Some(Position::new(1, 4, 3, 1, 5, 4)),
+                            data: None,
                         })],
-                        position: Some(Position::new(1, 1, 0, 1, 5, 4))
+                        position: Some(Position::new(1, 1, 0, 1, 5, 4)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('.'),
+                        number: Some(4),
+                        indent: Some(3),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "b".into(),
-                                position: Some(Position::new(2, 4, 8, 2, 5, 9))
+                                position: Some(Position::new(2, 4, 8, 2, 5, 9)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(2, 4, 8, 2, 5, 9))
+                            position: Some(Position::new(2, 4, 8, 2, 5, 9)),
+                            data: None,
                         })],
-                        position: Some(Position::new(2, 1, 5, 2, 5, 9))
+                        position: Some(Position::new(2, 1, 5, 2, 5, 9)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 2, 5, 9))
+                position: Some(Position::new(1, 1, 0, 2, 5, 9)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 5, 9))
+            position: Some(Position::new(1, 1, 0, 2, 5, 9)),
+            data: None,
         }),
         "should support `start` fields on `List` w/ `ordered: true` in mdast"
     );
@@ -665,44 +731,112 @@ fn list() -> Result<(), message::Message> {
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: true,
                         children: vec![
                             Node::Paragraph(Paragraph {
                                 children: vec![Node::Text(Text {
                                     value: "a".into(),
-                                    position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                                    position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                                position: Some(Position::new(1, 3, 2, 1, 4, 3)),
+                                data: None,
                             }),
                             Node::Paragraph(Paragraph {
                                 children: vec![Node::Text(Text {
                                     value: "b".into(),
-                                    position: Some(Position::new(3, 3, 7, 3, 4, 8))
+                                    position: Some(Position::new(3, 3, 7, 3, 4, 8)),
+                                    data: None,
                                 }),],
-                                position: Some(Position::new(3, 3, 7, 3, 4, 8))
+                                position: Some(Position::new(3, 3, 7, 3, 4, 8)),
+                                data: None,
                             })
                         ],
-                        position: Some(Position::new(1, 1, 0, 3, 4, 8))
+                        position: Some(Position::new(1, 1, 0, 3, 4, 8)),
+                        data: None,
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "c".into(),
-                                position: Some(Position::new(4, 3, 11, 4, 4, 12))
+                                position: Some(Position::new(4, 3, 11, 4, 4, 12)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(4, 3, 11, 4, 4, 12))
+                            position: Some(Position::new(4, 3, 11, 4, 4, 12)),
+                            data: None,
                         })],
-                        position: Some(Position::new(4, 1, 9, 4, 4, 12))
+                        position: Some(Position::new(4, 1, 9, 4, 4, 12)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 4, 4, 12))
+                position: Some(Position::new(1, 1, 0, 4, 4, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 4, 4, 12))
+            position: Some(Position::new(1, 1, 0, 4, 4, 12)),
+            data: None,
         }),
         "should support `spread` fields on `List`, `ListItem`s in mdast"
     );
 
+    let spreads = |value: &str| -> Result<(bool, Vec<bool>), message::Message> {
+        let tree = to_mdast(value, &Default::default())?;
+
+        if let Node::List(list) = &tree.children().unwrap()[0] {
+            Ok((
+                list.spread,
+                list.children
+                    .iter()
+                    .map(|child| matches!(child, Node::ListItem(item) if item.spread))
+                    .collect(),
+            ))
+        } else {
+            unreachable!("expected list")
+        }
+    };
+
+    assert_eq!(
+        spreads("- a\n- b\n- c")?,
+        (false, vec![false, false, false]),
+        "should support tight lists in mdast"
+    );
+
+    assert_eq!(
+        spreads("- a\n\n- b\n- c")?,
+        (true, vec![false, false, false]),
+        "should support lists loose between items in mdast"
+    );
+
+    assert_eq!(
+        spreads("- a\n- b\n\n  c\n- d")?,
+        (false, vec![false, true, false]),
+        "should support lists loose in one item in mdast"
+    );
+
+    assert_eq!(
+        spreads("- a\n- b\n\n\n- c")?,
+        (true, vec![false, false, false]),
+        "should not make items spread for blank lines at their end in mdast"
+    );
+
+    assert_eq!(
+        spreads("- ```\n  a\n\n\n  ```\n- b")?,
+        (false, vec![false, false]),
+        "should not make items spread for blank lines in code in mdast"
+    );
+
+    assert_eq!(
+        spreads("- a\n  ```\n\n  ```")?,
+        (false, vec![false]),
+        "should not make items spread for blank lines in code after a paragraph in mdast"
+    );
+
     Ok(())
 }
@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{Math, Node, Root},
+    mdast::{BlockQuote, List, ListItem, Math, Node, Root},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -255,18 +255,76 @@ fn math_flow() -> Result<(), message::Message> {
         "should not support lazyness (3)"
     );
 
+    assert_eq!(
+        to_html_with_options("- $$\n  a\n- b", &math)?,
+        "<ul>\n<li>\n<pre><code class=\"language-math math-display\">a\n</code></pre>\n</li>\n<li>b</li>\n</ul>",
+        "should not include the line ending before a new container in math w/o closing sequence"
+    );
+
     assert_eq!(
         to_mdast("$$extra\nabc\ndef\n$$", &math.parse)?,
         Node::Root(Root {
             children: vec![Node::Math(Math {
                 meta: Some("extra".into()),
                 value: "abc\ndef".into(),
-                position: Some(Position::new(1, 1, 0, 4, 3, 18))
+                position: Some(Position::new(1, 1, 0, 4, 3, 18)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 4, 3, 18))
+            position: Some(Position::new(1, 1, 0, 4, 3, 18)),
+            data: None,
         }),
         "should support math (flow) as `Math`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("> $$ x\n> a\\*b\n>  c\n> $$", &math.parse)?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::Math(Math {
+                    meta: Some("x".into()),
+                    value: "a\\*b\n c".into(),
+                    position: Some(Position::new(1, 3, 2, 4, 5, 23)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 4, 5, 23)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 4, 5, 23)),
+            data: None,
+        }),
+        "should support math (flow) in block quotes in mdast"
+    );
+
+    assert_eq!(
+        to_mdast("- $$\n  a\n\n  b\n  $$", &math.parse)?,
+        Node::Root(Root {
+            children: vec![Node::List(List {
+                children: vec![Node::ListItem(ListItem {
+                    children: vec![Node::Math(Math {
+                        meta: None,
+                        value: "a\n\nb".into(),
+                        position: Some(Position::new(1, 3, 2, 5, 5, 18)),
+                        data: None,
+                    })],
+                    spread: false,
+                    checked: None,
+                    marker: Some('-'),
+                    number: None,
+                    indent: Some(2),
+                    position: Some(Position::new(1, 1, 0, 5, 5, 18)),
+                    data: None,
+                })],
+                ordered: false,
+                start: None,
+                spread: false,
+                position: Some(Position::new(1, 1, 0, 5, 5, 18)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 5, 5, 18)),
+            data: None,
+        }),
+        "should support math (flow) in list items in mdast"
+    );
+
     Ok(())
 }
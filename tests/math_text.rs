@@ -1,5 +1,5 @@
 use markdown::{
-    mdast::{InlineMath, Node, Paragraph, Root, Text},
+    mdast::{BlockQuote, InlineMath, List, ListItem, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
@@ -158,7 +158,8 @@ fn math_text() -> Result<(), message::Message> {
                     allow_dangerous_html: true,
                     allow_dangerous_protocol: true,
                     ..Default::default()
-                }
+                },
+                ..Default::default()
             }
         )?,
         "<p><a href=\"$\">$</p>",
@@ -214,23 +215,78 @@ fn math_text() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::InlineMath(InlineMath {
                         value: "alpha".into(),
-                        position: Some(Position::new(1, 3, 2, 1, 10, 9))
+                        position: Some(Position::new(1, 3, 2, 1, 10, 9)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " b.".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 13, 12))
+                        position: Some(Position::new(1, 10, 9, 1, 13, 12)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
         }),
         "should support math (text) as `InlineMath`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("> - a $\\b$ c", &math.parse)?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::List(List {
+                    children: vec![Node::ListItem(ListItem {
+                        children: vec![Node::Paragraph(Paragraph {
+                            children: vec![
+                                Node::Text(Text {
+                                    value: "a ".into(),
+                                    position: Some(Position::new(1, 5, 4, 1, 7, 6)),
+                                    data: None,
+                                }),
+                                Node::InlineMath(InlineMath {
+                                    value: "\\b".into(),
+                                    position: Some(Position::new(1, 7, 6, 1, 11, 10)),
+                                    data: None,
+                                }),
+                                Node::Text(Text {
+                                    value: " c".into(),
+                                    position: Some(Position::new(1, 11, 10, 1, 13, 12)),
+                                    data: None,
+                                })
+                            ],
+                            position: Some(Position::new(1, 5, 4, 1, 13, 12)),
+                            data: None,
+                        })],
+                        spread: false,
+                        checked: None,
+                        marker: Some('-'),
+                        number: None,
+                        indent: Some(2),
+                        position: Some(Position::new(1, 3, 2, 1, 13, 12)),
+                        data: None,
+                    })],
+                    ordered: false,
+                    start: None,
+                    spread: false,
+                    position: Some(Position::new(1, 3, 2, 1, 13, 12)),
+                    data: None,
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
+        }),
+        "should support math (text) in block quotes and list items in mdast"
+    );
+
     Ok(())
 }
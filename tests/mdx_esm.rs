@@ -259,9 +259,11 @@ fn mdx_esm() -> Result<(), message::Message> {
             children: vec![Node::MdxjsEsm(MdxjsEsm {
                 value: "import a from 'b'\nexport {a}".into(),
                 position: Some(Position::new(1, 1, 0, 2, 11, 28)),
-                stops: vec![(0, 0), (17, 17), (18, 18)]
+                stops: vec![(0, 0), (17, 17), (18, 18)],
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 11, 28))
+            position: Some(Position::new(1, 1, 0, 2, 11, 28)),
+            data: None,
         }),
         "should support mdx esm as `MdxjsEsm`s in mdast"
     );
@@ -137,9 +137,11 @@ fn mdx_expression_flow_agnostic() -> Result<(), message::Message> {
             children: vec![Node::MdxFlowExpression(MdxFlowExpression {
                 value: "alpha +\nbravo".into(),
                 position: Some(Position::new(1, 1, 0, 2, 7, 15)),
-                stops: vec![(0, 1), (7, 8), (8, 9)]
+                stops: vec![(0, 1), (7, 8), (8, 9)],
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 2, 7, 15))
+            position: Some(Position::new(1, 1, 0, 2, 7, 15)),
+            data: None,
         }),
         "should support mdx expressions (flow) as `MdxFlowExpression`s in mdast"
     );
@@ -219,21 +219,26 @@ fn mdx_expression_text_agnostic() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::MdxTextExpression(MdxTextExpression {
                         value: "alpha".into(),
                         position: Some(Position::new(1, 3, 2, 1, 10, 9)),
-                        stops: vec![(0, 3)]
+                        stops: vec![(0, 3)],
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " b.".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 13, 12))
+                        position: Some(Position::new(1, 10, 9, 1, 13, 12)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
         }),
         "should support mdx expressions (text) as `MdxTextExpression`s in mdast"
     );
@@ -1,6 +1,9 @@
 mod test_utils;
 use markdown::{
-    mdast::{List, ListItem, MdxJsxFlowElement, Node, Paragraph, Root, Text},
+    mdast::{
+        AttributeContent, AttributeValue, AttributeValueExpression, Heading, List, ListItem,
+        MdxJsxAttribute, MdxJsxFlowElement, Node, Paragraph, Root, Text,
+    },
     message, to_html_with_options, to_mdast,
     unist::Position,
     Constructs, Options, ParseOptions,
@@ -207,25 +210,77 @@ fn mdx_jsx_flow_essence() -> Result<(), message::Message> {
                     start: None,
                     children: vec![Node::ListItem(ListItem {
                         checked: None,
+                        marker: Some('*'),
+                        number: None,
+                        indent: Some(2),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
                                 value: "a".into(),
-                                position: Some(Position::new(2, 5, 7, 2, 6, 8))
+                                position: Some(Position::new(2, 5, 7, 2, 6, 8)),
+                                data: None,
                             }),],
-                            position: Some(Position::new(2, 5, 7, 2, 6, 8))
+                            position: Some(Position::new(2, 5, 7, 2, 6, 8)),
+                            data: None,
                         })],
-                        position: Some(Position::new(2, 1, 3, 2, 6, 8))
+                        position: Some(Position::new(2, 1, 3, 2, 6, 8)),
+                        data: None,
                     })],
-                    position: Some(Position::new(2, 1, 3, 2, 6, 8))
+                    position: Some(Position::new(2, 1, 3, 2, 6, 8)),
+                    data: None,
                 })],
-                position: Some(Position::new(1, 1, 0, 3, 4, 12))
+                position: Some(Position::new(1, 1, 0, 3, 4, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 3, 4, 12))
+            position: Some(Position::new(1, 1, 0, 3, 4, 12)),
+            data: None,
         }),
         "should support mdx jsx (flow) as `MdxJsxFlowElement`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("<a b=\"c\" d={e + f} {...g}>\n  # h\n</a>", &mdx.parse)?,
+        Node::Root(Root {
+            children: vec![Node::MdxJsxFlowElement(MdxJsxFlowElement {
+                name: Some("a".into()),
+                attributes: vec![
+                    AttributeContent::Property(MdxJsxAttribute {
+                        name: "b".into(),
+                        value: Some(AttributeValue::Literal("c".into())),
+                    }),
+                    AttributeContent::Property(MdxJsxAttribute {
+                        name: "d".into(),
+                        value: Some(AttributeValue::Expression(AttributeValueExpression {
+                            value: "e + f".into(),
+                            stops: vec![(0, 12)]
+                        })),
+                    }),
+                    AttributeContent::Expression {
+                        value: "...g".into(),
+                        stops: vec![(0, 20)]
+                    },
+                ],
+                children: vec![Node::Heading(Heading {
+                    depth: 1,
+                    setext: false,
+                    children: vec![Node::Text(Text {
+                        value: "h".into(),
+                        position: Some(Position::new(2, 5, 31, 2, 6, 32)),
+                        data: None,
+                    })],
+                    position: Some(Position::new(2, 1, 27, 2, 6, 32)),
+                    data: None,
+                    markers: vec![],
+                })],
+                position: Some(Position::new(1, 1, 0, 3, 5, 37)),
+                data: None,
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 5, 37)),
+            data: None,
+        }),
+        "should support attributes and markdown children in `MdxJsxFlowElement`s in mdast"
+    );
+
     Ok(())
 }
 
@@ -55,22 +55,27 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::MdxJsxTextElement(MdxJsxTextElement {
                         name: Some("b".into()),
                         attributes: vec![],
                         children: vec![],
-                        position: Some(Position::new(1, 3, 2, 1, 8, 7))
+                        position: Some(Position::new(1, 3, 2, 1, 8, 7)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " c.".into(),
-                        position: Some(Position::new(1, 8, 7, 1, 11, 10))
+                        position: Some(Position::new(1, 8, 7, 1, 11, 10)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (self-closing)"
     );
@@ -82,7 +87,8 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                 children: vec![
                     Node::Text(Text {
                         value: "a ".into(),
-                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+                        data: None,
                     }),
                     Node::MdxJsxTextElement(MdxJsxTextElement {
                         name: Some("b".into()),
@@ -92,22 +98,29 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                                 children: vec![
                                     Node::Text(Text {
                                         value: "c".into(),
-                                        position: Some(Position::new(1, 7, 6, 1, 8, 7))
+                                        position: Some(Position::new(1, 7, 6, 1, 8, 7)),
+                                        data: None,
                                     }),
                                 ],
-                                position: Some(Position::new(1, 6, 5, 1, 9, 8))
+                                position: Some(Position::new(1, 6, 5, 1, 9, 8)),
+                                marker: Some('*'),
+                                data: None,
                             }),
                         ],
-                        position: Some(Position::new(1, 3, 2, 1, 13, 12))
+                        position: Some(Position::new(1, 3, 2, 1, 13, 12)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: " d.".into(),
-                        position: Some(Position::new(1, 13, 12, 1, 16, 15))
+                        position: Some(Position::new(1, 13, 12, 1, 16, 15)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 16, 15))
+                position: Some(Position::new(1, 1, 0, 1, 16, 15)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 16, 15))
+            position: Some(Position::new(1, 1, 0, 1, 16, 15)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (matched open and close tags)"
     );
@@ -121,16 +134,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         name: Some("a:b".into()),
                         attributes: vec![],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 8, 7))
+                        position: Some(Position::new(1, 1, 0, 1, 8, 7)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 8, 7, 1, 9, 8))
+                        position: Some(Position::new(1, 8, 7, 1, 9, 8)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 9, 8))
+                position: Some(Position::new(1, 1, 0, 1, 9, 8)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 9, 8))
+            position: Some(Position::new(1, 1, 0, 1, 9, 8)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (namespace in tag name)"
     );
@@ -144,16 +161,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                         name: Some("a.b.c".into()),
                         attributes: vec![],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 10, 9))
+                        position: Some(Position::new(1, 1, 0, 1, 10, 9)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 10, 9, 1, 11, 10))
+                        position: Some(Position::new(1, 10, 9, 1, 11, 10)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            position: Some(Position::new(1, 1, 0, 1, 11, 10)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (members in tag name)"
     );
@@ -170,16 +191,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             stops: vec![(0, 4)]
                         }],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                        position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 13, 12, 1, 14, 13))
+                        position: Some(Position::new(1, 13, 12, 1, 14, 13)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 14, 13))
+                position: Some(Position::new(1, 1, 0, 1, 14, 13)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 14, 13))
+            position: Some(Position::new(1, 1, 0, 1, 14, 13)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (attribute expression)"
     );
@@ -202,16 +227,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             })
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 12, 11))
+                        position: Some(Position::new(1, 1, 0, 1, 12, 11)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 12, 11, 1, 13, 12))
+                        position: Some(Position::new(1, 12, 11, 1, 13, 12)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            position: Some(Position::new(1, 1, 0, 1, 13, 12)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (property names)"
     );
@@ -241,16 +270,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             }),
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 24, 23))
+                        position: Some(Position::new(1, 1, 0, 1, 24, 23)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 24, 23, 1, 25, 24))
+                        position: Some(Position::new(1, 24, 23, 1, 25, 24)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 25, 24))
+                position: Some(Position::new(1, 1, 0, 1, 25, 24)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 25, 24))
+            position: Some(Position::new(1, 1, 0, 1, 25, 24)),
+            data: None,
         }),
         "should support mdx jsx (text) as `MdxJsxTextElement`s in mdast (attribute values)"
     );
@@ -269,16 +302,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             }),
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 120, 119))
+                        position: Some(Position::new(1, 1, 0, 1, 120, 119)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 120, 119, 1, 121, 120))
+                        position: Some(Position::new(1, 120, 119, 1, 121, 120)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 121, 120))
+                position: Some(Position::new(1, 1, 0, 1, 121, 120)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 121, 120))
+            position: Some(Position::new(1, 1, 0, 1, 121, 120)),
+            data: None,
         }),
         "should support character references (HTML 4, named) in JSX attribute values"
     );
@@ -304,16 +341,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             }),
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 63, 62))
+                        position: Some(Position::new(1, 1, 0, 1, 63, 62)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 63, 62, 1, 64, 63))
+                        position: Some(Position::new(1, 63, 62, 1, 64, 63)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 64, 63))
+                position: Some(Position::new(1, 1, 0, 1, 64, 63)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 64, 63))
+            position: Some(Position::new(1, 1, 0, 1, 64, 63)),
+            data: None,
         }),
         "should support character references (numeric) in JSX attribute values"
     );
@@ -332,16 +373,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             })
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 78, 77))
+                        position: Some(Position::new(1, 1, 0, 1, 78, 77)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 78, 77, 1, 79, 78))
+                        position: Some(Position::new(1, 78, 77, 1, 79, 78)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 79, 78))
+                position: Some(Position::new(1, 1, 0, 1, 79, 78)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 79, 78))
+            position: Some(Position::new(1, 1, 0, 1, 79, 78)),
+            data: None,
         }),
         "should not support things that look like character references but aren’t"
     );
@@ -368,16 +413,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             })
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 1, 22, 21))
+                        position: Some(Position::new(1, 1, 0, 1, 22, 21)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(1, 22, 21, 1, 23, 22))
+                        position: Some(Position::new(1, 22, 21, 1, 23, 22)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 1, 23, 22))
+                position: Some(Position::new(1, 1, 0, 1, 23, 22)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 23, 22))
+            position: Some(Position::new(1, 1, 0, 1, 23, 22)),
+            data: None,
         }),
         "should support unicode whitespace in a lot of places"
     );
@@ -404,16 +453,20 @@ fn mdx_jsx_text_core() -> Result<(), message::Message> {
                             })
                         ],
                         children: vec![],
-                        position: Some(Position::new(1, 1, 0, 5, 3, 13))
+                        position: Some(Position::new(1, 1, 0, 5, 3, 13)),
+                        data: None,
                     }),
                     Node::Text(Text {
                         value: ".".into(),
-                        position: Some(Position::new(5, 3, 13, 5, 4, 14))
+                        position: Some(Position::new(5, 3, 13, 5, 4, 14)),
+                        data: None,
                     })
                 ],
-                position: Some(Position::new(1, 1, 0, 5, 4, 14))
+                position: Some(Position::new(1, 1, 0, 5, 4, 14)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 5, 4, 14))
+            position: Some(Position::new(1, 1, 0, 5, 4, 14)),
+            data: None,
         }),
         "should support line endings in a lot of places"
     );
@@ -0,0 +1,169 @@
+use markdown::{block_ranges, message, BlockKind, Constructs, ParseOptions};
+use pretty_assertions::assert_eq;
+use std::fs;
+
+/// Get the markdown and kind of each top-level block in `value`.
+fn blocks<'a>(
+    value: &'a str,
+    options: &ParseOptions,
+) -> Result<Vec<(&'a str, BlockKind)>, message::Message> {
+    Ok(block_ranges(value, options)?
+        .into_iter()
+        .map(|(range, kind)| (&value[range], kind))
+        .collect())
+}
+
+#[test]
+fn block_ranges_basic() -> Result<(), message::Message> {
+    let options = ParseOptions::default();
+
+    assert_eq!(
+        blocks("", &options)?,
+        vec![],
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        blocks("\n \n\t", &options)?,
+        vec![],
+        "should support a document with only blank lines"
+    );
+
+    assert_eq!(
+        blocks(
+            "# a #\n\nb\nc\n\n\n```js\nd\n```\n\n<div>\ne\n\n[f]: g\n***\n    h\n\n    \n    i\n\n",
+            &options
+        )?,
+        vec![
+            ("# a #", BlockKind::Heading),
+            ("b\nc", BlockKind::Paragraph),
+            ("```js\nd\n```", BlockKind::Code),
+            ("<div>\ne", BlockKind::Html),
+            ("[f]: g", BlockKind::Definition),
+            ("***", BlockKind::ThematicBreak),
+            ("    h\n\n    \n    i", BlockKind::Code),
+        ],
+        "should get leaf blocks, without the blank lines between them"
+    );
+
+    assert_eq!(
+        blocks("a\nb\n===\n\nc\n-\nd\n\n- e\n---", &options)?,
+        vec![
+            ("a\nb\n===", BlockKind::Heading),
+            ("c\n-", BlockKind::Heading),
+            ("d", BlockKind::Paragraph),
+            ("- e", BlockKind::List),
+            ("---", BlockKind::ThematicBreak),
+        ],
+        "should include the text of setext headings"
+    );
+
+    assert_eq!(
+        blocks(
+            "* a\n* b\n\n  c\n\n\n1. d\n   \n\n> e\nf\n>\n\n> g\n> \n",
+            &options
+        )?,
+        vec![
+            ("* a\n* b\n\n  c", BlockKind::List),
+            ("1. d", BlockKind::List),
+            ("> e\nf\n>", BlockKind::Blockquote),
+            ("> g\n> ", BlockKind::Blockquote),
+        ],
+        "should not include blank lines at the end of containers, but include prefixes"
+    );
+
+    assert_eq!(
+        blocks("> ```\n> a\n>\n\n- ```\n  b\n\n\n", &options)?,
+        vec![
+            ("> ```\n> a\n>", BlockKind::Blockquote),
+            ("- ```\n  b\n\n\n", BlockKind::List),
+        ],
+        "should include blank lines in code that is not closed"
+    );
+
+    assert_eq!(
+        blocks("a\r\n\r\nb\rc\r\r- d\r\n", &options)?,
+        vec![
+            ("a", BlockKind::Paragraph),
+            ("b\rc", BlockKind::Paragraph),
+            ("- d", BlockKind::List),
+        ],
+        "should support other line endings"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn block_ranges_constructs() -> Result<(), message::Message> {
+    let options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            math_flow: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+
+    assert_eq!(
+        blocks(
+            "---\na: b\n---\n\n| c |\n| - |\n| d |\n\ne[^f]\n\n[^f]: g\n\n    h\n\n$$\ni\n$$",
+            &options
+        )?,
+        vec![
+            ("---\na: b\n---", BlockKind::Yaml),
+            ("| c |\n| - |\n| d |", BlockKind::Table),
+            ("e[^f]", BlockKind::Paragraph),
+            ("[^f]: g\n\n    h", BlockKind::FootnoteDefinition),
+            ("$$\ni\n$$", BlockKind::Math),
+        ],
+        "should support GFM, frontmatter, and math"
+    );
+
+    assert_eq!(
+        blocks("+++\na = 1\n+++", &options)?,
+        vec![("+++\na = 1\n+++", BlockKind::Toml)],
+        "should support TOML frontmatter"
+    );
+
+    assert_eq!(
+        blocks("{c}\n\n<D>\n\ne\n\n</D>", &ParseOptions::mdx())?,
+        vec![
+            ("{c}", BlockKind::MdxFlowExpression),
+            ("<D>", BlockKind::MdxJsxFlowElement),
+            ("e", BlockKind::Paragraph),
+            ("</D>", BlockKind::MdxJsxFlowElement),
+        ],
+        "should support MDX expressions, and JSX with a block for each tag"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn block_ranges_outside() -> Result<(), message::Message> {
+    let options = ParseOptions::gfm();
+    let value = fs::read_to_string("readme.md").unwrap();
+    let ranges = block_ranges(&value, &options)?;
+    let mut end = 0;
+
+    assert!(ranges.len() > 10, "should find blocks in the readme");
+
+    for (range, _) in &ranges {
+        assert!(range.start >= end, "should not overlap blocks");
+        assert!(range.start < range.end, "should not have empty blocks");
+        assert!(
+            value[end..range.start].trim().is_empty(),
+            "should only have whitespace between blocks, not {:?}",
+            &value[end..range.start]
+        );
+        end = range.end;
+    }
+
+    assert!(
+        value[end..].trim().is_empty(),
+        "should only have whitespace after the last block"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use markdown::{
+    hast_to_html, mdast_to_html, message, to_hast, to_html, to_html_with_options, to_mdast,
+    CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+/// Get options that collapse whitespace, with GFM.
+fn collapse() -> Options {
+    Options {
+        compile: CompileOptions {
+            collapse_whitespace: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    }
+}
+
+#[test]
+fn collapse_whitespace() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("a  b\nc"),
+        "<p>a  b\nc</p>",
+        "should not collapse whitespace by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  b\t\tc", &collapse())?,
+        "<p>a b c</p>",
+        "should collapse spaces and tabs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\r\nc\rd", &collapse())?,
+        "<p>a b c d</p>",
+        "should collapse line endings in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb", &collapse())?,
+        "<p>a</p>\n<p>b</p>",
+        "should not collapse line endings between blocks"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a  b\n\nc\nd  e\n===", &collapse())?,
+        "<h1>a b</h1>\n<h1>c d e</h1>",
+        "should collapse in headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &collapse())?,
+        "<p>a<br /> b<br /> c</p>",
+        "should collapse the line ending after hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a  b* **c\nd** [e  f](g)", &collapse())?,
+        "<p><em>a b</em> <strong>c d</strong> <a href=\"g\">e f</a></p>",
+        "should collapse in emphasis, strong, and links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a  b\nc](d)", &collapse())?,
+        "<p><img src=\"d\" alt=\"a b c\" /></p>",
+        "should collapse in alt text"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a  b |\n| - |\n| c\t\td |", &collapse())?,
+        "<table>\n<thead>\n<tr>\n<th>a b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c d</td>\n</tr>\n</tbody>\n</table>",
+        "should collapse in table cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `b  c` d", &collapse())?,
+        "<p>a <code>b  c</code> d</p>",
+        "should not collapse in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```a  b\nc  d\n\ne\n```\n\n    f  g", &collapse())?,
+        "<pre><code class=\"language-a\">c  d\n\ne\n</code></pre>\n<pre><code>f  g\n</code></pre>",
+        "should not collapse in code (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](<b  c> \"d  e\")\n\n[f]\n\n[f]: g 'h  i\nj'", &collapse())?,
+        "<p><a href=\"b%20%20c\" title=\"d  e\">a</a></p>\n<p><a href=\"g\" title=\"h  i\nj\">f</a></p>\n",
+        "should not collapse in destinations and titles"
+    );
+
+    let mut danger = collapse();
+    danger.compile.allow_dangerous_html = true;
+
+    assert_eq!(
+        to_html_with_options("<div>\na  b\n</div>\n\nc <i\ntitle=\"d  e\">f</i>", &danger)?,
+        "<div>\na  b\n</div>\n<p>c <i\ntitle=\"d  e\">f</i></p>",
+        "should not collapse in HTML"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn collapse_whitespace_trees() -> Result<(), message::Message> {
+    let options = collapse();
+
+    for value in [
+        "a  b\t\tc\nd\r\ne",
+        "# a  b\n\nc\nd  e\n===",
+        "a  \nb\\\nc",
+        "*a  b* **c\nd** [e  f](g 'h  i') ![j  k\nl](m)",
+        "a `b  c` d\n\n```e  f\ng  h\n```",
+        "[a  b]\n\n[a  b]: c\n\n![d  e][a  b]",
+        "| a  b |\n| - |\n| c\t\td |",
+        "a[^b]\n\n[^b]: c  d\n    e",
+    ] {
+        let html = to_html_with_options(value, &options)?;
+
+        assert_eq!(
+            mdast_to_html(&to_mdast(value, &options.parse)?, &options)?,
+            html,
+            "should match `to_html` in `mdast_to_html` for `{}`",
+            value
+        );
+
+        assert_eq!(
+            hast_to_html(&to_hast(value, &options)?, &options.compile),
+            html,
+            "should match `to_html` in `to_hast` for `{}`",
+            value
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use markdown::{
+    cst::Node, event::Name, message, to_cst, Constructs, Options, ParseOptions, Parser,
+};
+use pretty_assertions::assert_eq;
+use test_utils::corpus::corpus;
+
+mod test_utils {
+    pub mod corpus;
+}
+
+/// Get the names of `nodes` and their descendants, as `name(children)`.
+fn names(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| {
+            if node.children.is_empty() {
+                format!("{:?}", node.name)
+            } else {
+                format!("{:?}({})", node.name, names(&node.children))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Get the source of the leaves in `nodes`.
+fn leaves<'a>(nodes: &[Node], value: &'a str, result: &mut Vec<&'a str>) {
+    for node in nodes {
+        if node.children.is_empty() {
+            result.push(node.source(value));
+        } else {
+            leaves(&node.children, value, result);
+        }
+    }
+}
+
+/// Check that `nodes` are lossless: the leaves make up `value`, and the
+/// children of each node make up the node.
+fn check(nodes: &[Node], value: &str) {
+    let mut result = vec![];
+    leaves(nodes, value, &mut result);
+
+    assert_eq!(
+        result.concat(),
+        value,
+        "should make up the whole value with leaves"
+    );
+
+    for node in nodes {
+        check_node(node, value);
+    }
+}
+
+/// Check that the children of `node` make up `node`.
+fn check_node(node: &Node, value: &str) {
+    if !node.children.is_empty() {
+        assert_eq!(
+            node.children
+                .iter()
+                .map(|child| child.source(value))
+                .collect::<String>(),
+            node.source(value),
+            "should make up `{:?}` with its children in `{:?}`",
+            node.name,
+            value
+        );
+
+        for child in &node.children {
+            check_node(child, value);
+        }
+    }
+}
+
+#[test]
+fn cst() -> Result<(), message::Message> {
+    assert_eq!(
+        names(&to_cst("", &ParseOptions::default())?),
+        "",
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        names(&to_cst("> a\n> *b*", &ParseOptions::default())?),
+        "BlockQuote(BlockQuotePrefix(BlockQuoteMarker SpaceOrTab) Paragraph(Data LineEnding BlockQuotePrefix(BlockQuoteMarker SpaceOrTab) Emphasis(EmphasisSequence EmphasisText(Data) EmphasisSequence)))",
+        "should include markers, whitespace, and line endings"
+    );
+
+    assert_eq!(
+        names(&to_cst("```js\na\n```\n\n\\*", &ParseOptions::default())?),
+        "CodeFenced(CodeFencedFence(CodeFencedFenceSequence CodeFencedFenceInfo(Data)) LineEnding CodeFlowChunk LineEnding CodeFencedFence(CodeFencedFenceSequence)) LineEnding BlankLineEnding Paragraph(CharacterEscape(CharacterEscapeMarker CharacterEscapeValue))",
+        "should include fences, blank lines, and escapes"
+    );
+
+    let value = ">\ta";
+    let nodes = to_cst(value, &ParseOptions::default())?;
+    let mut result = vec![];
+    leaves(&nodes, value, &mut result);
+
+    assert_eq!(
+        result,
+        vec![">", "\t", "", "a"],
+        "should put a split tab in the first node"
+    );
+
+    let value = "> ```\n>\n\na";
+    let nodes = to_cst(value, &ParseOptions::default())?;
+
+    assert_eq!(
+        nodes[0].children[1].source(value),
+        "```\n",
+        "should end unclosed code in a container before the next line ending"
+    );
+
+    check(&nodes, value);
+
+    let value = "- a\r\n\r\n  b";
+    let nodes = Parser::new(Options::default()).to_cst(value)?;
+
+    assert_eq!(
+        (nodes[0].name.clone(), nodes[0].source(value)),
+        (Name::ListUnordered, value),
+        "should support `Parser::to_cst`"
+    );
+
+    check(&nodes, value);
+
+    Ok(())
+}
+
+#[test]
+fn cst_lossless() -> Result<(), message::Message> {
+    let gfm = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    let mdx = ParseOptions::mdx();
+    let values = corpus();
+
+    for value in &values {
+        check(&to_cst(value, &ParseOptions::default())?, value);
+        check(&to_cst(value, &gfm)?, value);
+
+        if let Ok(nodes) = to_cst(value, &mdx) {
+            check(&nodes, value);
+        }
+    }
+
+    Ok(())
+}
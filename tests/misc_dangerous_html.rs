@@ -1,4 +1,6 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -30,5 +32,53 @@ fn dangerous_html() -> Result<(), message::Message> {
         "should be unsafe w/ `allowDangerousHtml`"
     );
 
+    assert_eq!(
+        to_html("a < b"),
+        "<p>a &lt; b</p>",
+        "should escape a `<` that does not start HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("a < b", danger)?,
+        "<p>a &lt; b</p>",
+        "should escape a `<` that does not start HTML w/ `allowDangerousHtml`"
+    );
+
+    assert_eq!(
+        to_html("a <b> c"),
+        "<p>a &lt;b&gt; c</p>",
+        "should escape a `<` that starts HTML by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <b> c", danger)?,
+        "<p>a <b> c</p>",
+        "should not escape a `<` that starts HTML w/ `allowDangerousHtml`"
+    );
+
+    assert_eq!(
+        to_html("a <https://b> c"),
+        "<p>a <a href=\"https://b\">https://b</a> c</p>",
+        "should still support autolinks by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <https://b> <c> <!--d--> < e",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        autolink: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a &lt;https://b&gt; &lt;c&gt; &lt;!--d--&gt; &lt; e</p>",
+        "should escape all `<` w/o autolinks"
+    );
+
     Ok(())
 }
@@ -0,0 +1,66 @@
+use markdown::{
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn encode_non_ascii() -> Result<(), message::Message> {
+    let ascii = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            encode_non_ascii: true,
+            ..Default::default()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("é & 🦀"),
+        "<p>é &amp; 🦀</p>",
+        "should not encode non-ASCII by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("é & ü", &ascii)?,
+        "<p>&#xE9; &amp; &#xFC;</p>",
+        "should encode non-ASCII in text"
+    );
+
+    assert_eq!(
+        to_html_with_options("🦀 𝔸", &ascii)?,
+        "<p>&#x1F980; &#x1D538;</p>",
+        "should encode astral characters as one reference"
+    );
+
+    assert_eq!(
+        to_html_with_options("&eacute; &#x1F980;", &ascii)?,
+        "<p>&#xE9; &#x1F980;</p>",
+        "should encode decoded character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("`é`\n\n```ü\nß\n```", &ascii)?,
+        "<p><code>&#xE9;</code></p>\n<pre><code class=\"language-&#xFC;\">&#xDF;\n</code></pre>",
+        "should encode non-ASCII in code"
+    );
+
+    assert_eq!(
+        to_html_with_options("<b title=\"é\">ü</b>\n\n<div>ß</div>", &ascii)?,
+        "<p><b title=\"&#xE9;\">&#xFC;</b></p>\n<div>&#xDF;</div>",
+        "should encode non-ASCII in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("[é](/ü \"ß\") ![ñ](/ø)", &ascii)?,
+        "<p><a href=\"/%C3%BC\" title=\"&#xDF;\">&#xE9;</a> <img src=\"/%C3%B8\" alt=\"&#xF1;\" /></p>",
+        "should encode non-ASCII in links and images"
+    );
+
+    assert_eq!(
+        mdast_to_html(&to_mdast("é `🦀`", &ascii.parse)?, &ascii)?,
+        "<p>&#xE9; <code>&#x1F980;</code></p>",
+        "should encode non-ASCII in `mdast_to_html`"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use markdown::{
+    hast::{self, Node},
+    hast_to_html, mdast_to_hast, mdast_to_html, message, to_hast, to_mdast, CompileOptions,
+    LineEnding, Options, Quote,
+};
+use pretty_assertions::assert_eq;
+
+/// Compile markdown to HTML through an HTML syntax tree.
+fn through_hast(value: &str, options: &Options) -> Result<String, message::Message> {
+    Ok(hast_to_html(&to_hast(value, options)?, &options.compile))
+}
+
+/// Compile markdown to HTML through a markdown syntax tree.
+fn through_mdast(value: &str, options: &Options) -> Result<String, message::Message> {
+    mdast_to_html(&to_mdast(value, &options.parse)?, options)
+}
+
+/// Find the first element with `tag_name`.
+fn find<'a>(node: &'a Node, tag_name: &str) -> Option<&'a hast::Element> {
+    if let Node::Element(element) = node {
+        if element.tag_name == tag_name {
+            return Some(element);
+        }
+    }
+
+    node.children()?
+        .iter()
+        .find_map(|child| find(child, tag_name))
+}
+
+#[test]
+fn hast_same() -> Result<(), message::Message> {
+    let danger = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            allow_dangerous_protocol: true,
+            ..CompileOptions::default()
+        },
+        ..Options::gfm()
+    };
+    let mut math = Options::default();
+    math.parse.constructs.math_flow = true;
+    math.parse.constructs.math_text = true;
+    let mut ids = Options::gfm();
+    ids.compile.heading_ids = true;
+    ids.compile.source_position = true;
+    ids.compile.wrap_paragraphs = false;
+
+    let cases = [
+        (
+            "# a *b* **c**\n\nd\ne  \nf\\\ng",
+            "headings, emphasis, breaks",
+        ),
+        (
+            "a\n===\n\n***\n\n> b\n> c\n>\n> d",
+            "setext, thematic breaks, block quotes",
+        ),
+        (
+            "```js eval\na < b\n```\n\n    c\n\n```\n```\n\n~~~\n\n\n~~~",
+            "code",
+        ),
+        ("`` a ` b `` `\nc`", "code (text)"),
+        ("* a\n* b\n\n1. c\n2. d\n\n3) e\n\n- f\n\n  g\n- h", "lists"),
+        (
+            "- a\n  > b\n- \n- # c\n  d\n-\n  ```\n  e\n  ```",
+            "blocks in tight items",
+        ),
+        ("- a\n\n  [b]: c\n- d\n  [e]: f", "definitions in items"),
+        (
+            "[a](b&c 'd&e') ![f *g*](h \"i\") <j> <k@l.m> [n](javascript:o)",
+            "links and images",
+        ),
+        (
+            "[a]: e\n[b]: f 'g'\n[A]: h\n\n[a] [b][] [C][a] ![a] ![d][b] [x][y] ![z][]",
+            "references",
+        ),
+        (
+            "&amp; &copy; \\* a&lt;b",
+            "character references and escapes",
+        ),
+        ("<div>\n*a*\n</div>\n\nb <i>c</i> <!--d-->", "HTML"),
+        ("a ~b~ ~~c~~ www.d.com https://e.org f@g.h", "GFM (text)"),
+        (
+            "| a | b | c |\n| - | :-: | --: |\n| d | `e\\|f` |\n| g | h | i | j |",
+            "GFM tables",
+        ),
+        ("| a |\n| - |", "GFM tables w/o body"),
+        ("* [ ] a\n* [x] b\n\n- [x] c\n\n  d", "GFM task list items"),
+        (
+            "a[^b] c[^d] e[^b] [^x]\n\n[^b]: f\n\n[^d]:\n    ```\n    g\n    ```\n\n[^h]: i",
+            "GFM footnotes",
+        ),
+        (
+            "[^a]\n\n[^a]: b[^c]\n\n    d\n\n[^c]: e\n\n> f[^a]",
+            "GFM footnotes in footnotes",
+        ),
+        (
+            "[a](<b> \"c\")\n\n<script>d</script>\n\ne <title>f</title>",
+            "GFM tagfilter",
+        ),
+        ("# a\n\n## a\n\nb\n\n> c", "heading ids, source positions"),
+    ];
+
+    for (value, message) in cases {
+        for (options, kind) in [
+            (&danger, "dangerous"),
+            (&Options::gfm(), "safe"),
+            (&ids, "ids"),
+        ] {
+            assert_eq!(
+                through_hast(value, options)?,
+                through_mdast(value, options)?,
+                "should match `mdast_to_html` for {} ({})",
+                message,
+                kind
+            );
+        }
+    }
+
+    assert_eq!(
+        through_hast("$$\na\n$$\n\n$b$", &math)?,
+        through_mdast("$$\na\n$$\n\n$b$", &math)?,
+        "should match `mdast_to_html` for math"
+    );
+
+    assert_eq!(
+        through_hast("a\r\n\r\nb\r\nc", &Options::default())?,
+        "<p>a</p>\r\n<p>b\r\nc</p>",
+        "should use the line ending found in the tree"
+    );
+
+    let options = Options {
+        compile: CompileOptions {
+            default_line_ending: LineEnding::CarriageReturnLineFeed,
+            encode_non_ascii: true,
+            gfm_footnote_back_label: Some("Arrière".into()),
+            gfm_footnote_clobber_prefix: Some("".into()),
+            gfm_footnote_label: Some("Notes".into()),
+            gfm_footnote_label_attributes: Some("class=\"x\"".into()),
+            gfm_footnote_label_tag_name: Some("h1".into()),
+            gfm_task_list_item_checkable: true,
+            thematic_break_html: Some("<hr class=\"y\">".into()),
+            trailing_newline: Some(false),
+            ..CompileOptions::default()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        through_hast("* [x] a[^b] [c](d 'e')\n\n***\n\n[^b]: f", &options)?,
+        through_mdast("* [x] a[^b] [c](d 'e')\n\n***\n\n[^b]: f", &options)?,
+        "should support compile options"
+    );
+
+    let mut options = Options::default();
+    options.compile.quote = Quote::Single;
+    options.compile.trailing_newline = Some(true);
+
+    assert_eq!(
+        through_hast("[a](b \"c'd\")", &options)?,
+        through_mdast("[a](b \"c'd\")", &options)?,
+        "should support `quote` and `trailing_newline`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hast_tree() -> Result<(), message::Message> {
+    let tree = to_hast("# a\n\n> b *c*", &Options::default())?;
+
+    assert_eq!(
+        format!("{:?}", tree),
+        "Root { children: [Element { children: [Text { value: \"a\", position: Some(1:3-1:4 (2-3)) }], position: Some(1:1-1:4 (0-3)), tag_name: \"h1\", attributes: [] }, Text { value: \"\\n\", position: None }, Element { children: [Text { value: \"\\n\", position: None }, Element { children: [Text { value: \"b \", position: Some(3:3-3:5 (7-9)) }, Element { children: [Text { value: \"c\", position: Some(3:6-3:7 (10-11)) }], position: Some(3:5-3:8 (9-12)), tag_name: \"em\", attributes: [] }], position: Some(3:3-3:8 (7-12)), tag_name: \"p\", attributes: [] }, Text { value: \"\\n\", position: None }], position: Some(3:1-3:8 (5-12)), tag_name: \"blockquote\", attributes: [] }], position: Some(1:1-3:8 (0-12)) }",
+        "should make elements and text, with positions"
+    );
+
+    let tree = to_hast(
+        "[a](b&c \"d\") <e>",
+        &Options {
+            compile: CompileOptions {
+                allow_dangerous_html: true,
+                ..CompileOptions::default()
+            },
+            ..Options::default()
+        },
+    )?;
+
+    assert_eq!(
+        find(&tree, "a").map(|link| link.attributes.clone()),
+        Some(vec![
+            ("href".into(), "b&c".into()),
+            ("title".into(), "d".into())
+        ]),
+        "should not encode attribute values"
+    );
+
+    assert_eq!(
+        find(&tree, "p").map(|paragraph| paragraph.children.last().cloned()),
+        Some(Some(Node::Raw(hast::Raw {
+            value: "<e>".into(),
+            position: Some(markdown::unist::Position::new(1, 14, 13, 1, 17, 16))
+        }))),
+        "should keep HTML as raw with `allow_dangerous_html`"
+    );
+
+    let mut options = Options::gfm();
+    options.compile.heading_ids = true;
+    options.compile.gfm_footnote_label_attributes = Some("class=\"a &amp; b\" hidden".into());
+    let tree = to_hast(
+        "# a b\n\nc[^d]\n\n| e |\n| - |\n| f |\n| g |\n\n[^d]: h",
+        &options,
+    )?;
+
+    assert_eq!(
+        find(&tree, "h1").and_then(|heading| heading.attribute("id")),
+        Some("a-b"),
+        "should add heading ids"
+    );
+
+    assert_eq!(
+        find(&tree, "sup").map(|call| call.position.is_some()),
+        Some(true),
+        "should add the position of footnote calls"
+    );
+
+    assert_eq!(
+        find(&tree, "section")
+            .map(|section| (section.position.is_none(), section.attribute("class"))),
+        Some((true, Some("footnotes"))),
+        "should generate a footnote section without position"
+    );
+
+    assert_eq!(
+        find(&tree, "h2").map(|label| label.attributes.clone()),
+        Some(vec![
+            ("id".into(), "footnote-label".into()),
+            ("class".into(), "a & b".into()),
+            ("hidden".into(), "".into())
+        ]),
+        "should parse `gfm_footnote_label_attributes`"
+    );
+
+    assert_eq!(
+        find(&tree, "tbody").and_then(|body| body
+            .position
+            .as_ref()
+            .map(|position| format!("{:?}", position))),
+        Some("7:1-8:6 (26-37)".into()),
+        "should give the table body the position of its rows"
+    );
+
+    let tree = to_mdast("a", &Default::default())?;
+    assert!(
+        mdast_to_hast(&tree, &Options::default()).is_ok(),
+        "should turn an mdast tree into hast"
+    );
+
+    let mut options = Options::default();
+    options.parse.constructs.mdx_expression_flow = true;
+    let tree = to_mdast("{a}", &options.parse)?;
+
+    assert_eq!(
+        mdast_to_hast(&tree, &options).map_err(|error| error.reason),
+        Err("Cannot turn `MdxFlowExpression` into HTML".into()),
+        "should error on MDX nodes"
+    );
+
+    options.compile.skip_unknown_nodes = true;
+
+    assert_eq!(
+        mdast_to_hast(&tree, &options).map(|tree| tree.children().map(Vec::len)),
+        Ok(Some(0)),
+        "should skip MDX nodes with `skip_unknown_nodes`"
+    );
+
+    Ok(())
+}
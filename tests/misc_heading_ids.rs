@@ -0,0 +1,261 @@
+use markdown::{
+    mdast::{slug_headings, slug_headings_with_slugify, Data, Node},
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast, CompileOptions, Options,
+    ParseOptions, Slugger,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn heading_ids() -> Result<(), message::Message> {
+    let ids = Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("# a"),
+        "<h1>a</h1>",
+        "should not add ids by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Hello, *World*!", &ids)?,
+        "<h1 id=\"hello-world\">Hello, <em>World</em>!</h1>",
+        "should add ids like GitHub (1)"
+    );
+
+    assert_eq!(
+        to_html_with_options("## a_b-c  d. `e`?", &ids)?,
+        "<h2 id=\"a_b-c--d-e\">a_b-c  d. <code>e</code>?</h2>",
+        "should add ids like GitHub (2)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Über 日本語 ✨", &ids)?,
+        "<h1 id=\"über-日本語-\">Über 日本語 ✨</h1>",
+        "should keep letters in other scripts, but drop symbols"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===\n\nb\n---", &ids)?,
+        "<h1 id=\"a\">a</h1>\n<h2 id=\"b\">b</h2>",
+        "should add ids to setext headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\n# a\n\n# a-1\n\n## a", &ids)?,
+        "<h1 id=\"a\">a</h1>\n<h1 id=\"a-1\">a</h1>\n<h1 id=\"a-1-1\">a-1</h1>\n<h2 id=\"a-2\">a</h2>",
+        "should make ids unique"
+    );
+
+    assert_eq!(
+        to_html_with_options("#\n\n# !", &ids)?,
+        "<h1></h1>\n<h1>!</h1>",
+        "should not add empty ids"
+    );
+
+    let custom = Options {
+        slugify: Some(Box::new(|value| {
+            value
+                .chars()
+                .map(|char| match char {
+                    'Ü' | 'ü' => 'u',
+                    ' ' => '-',
+                    _ => char.to_ascii_lowercase(),
+                })
+                .collect()
+        })),
+        ..Options {
+            compile: CompileOptions {
+                heading_ids: true,
+                ..CompileOptions::default()
+            },
+            ..Options::default()
+        }
+    };
+
+    assert_eq!(
+        to_html_with_options("# Über uns\n\n# Uber uns", &custom)?,
+        "<h1 id=\"uber-uns\">Über uns</h1>\n<h1 id=\"uber-uns-1\">Uber uns</h1>",
+        "should support `slugify`, and make its results unique"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a",
+            &Options {
+                slugify: Some(Box::new(|_value| "\"b\" & c".into())),
+                ..Options {
+                    compile: CompileOptions {
+                        heading_ids: true,
+                        ..CompileOptions::default()
+                    },
+                    ..Options::default()
+                }
+            }
+        )?,
+        "<h1 id=\"&quot;b&quot; &amp; c\">a</h1>",
+        "should encode the results of `slugify`"
+    );
+
+    for value in [
+        "# Hello, *World*!\n\n## a_b-c  d. `e`?",
+        "a\n===\n\nb\n---",
+        "# a\n\n# a\n\n# a-1\n\n## a",
+        "#\n\n# !\n\n> # [a](b) <i>c</i> ![d](e)",
+    ] {
+        assert_eq!(
+            mdast_to_html(&to_mdast(value, &ids.parse)?, &ids)?,
+            to_html_with_options(value, &ids)?,
+            "should match `to_html` in `mdast_to_html` for {:?}",
+            value
+        );
+    }
+
+    assert_eq!(
+        mdast_to_html(&to_mdast("# Über\n\n# Uber", &custom.parse)?, &custom)?,
+        "<h1 id=\"uber\">Über</h1>\n<h1 id=\"uber-1\">Uber</h1>",
+        "should support `slugify` in `mdast_to_html`"
+    );
+
+    let mut tree = to_mdast("# a\n\n# b\n\n# c", &ids.parse)?;
+    let children = tree.children_mut().unwrap();
+    for (index, id) in [(0, "x"), (1, "x"), (2, "")] {
+        if let Node::Heading(heading) = &mut children[index] {
+            heading.data = Some(Box::new(Data::from([("id".into(), id.into())])));
+        }
+    }
+
+    assert_eq!(
+        mdast_to_html(&tree, &ids)?,
+        "<h1 id=\"x\">a</h1>\n<h1 id=\"x-1\">b</h1>\n<h1>c</h1>",
+        "should use `data.id` in `mdast_to_html`, and make it unique"
+    );
+
+    assert_eq!(
+        mdast_to_html(&tree, &Options::gfm())?,
+        "<h1>a</h1>\n<h1>b</h1>\n<h1>c</h1>",
+        "should ignore `data.id` if `heading_ids` is off"
+    );
+
+    Ok(())
+}
+
+/// Get the `id`s in the `data` of the headings in `tree`.
+fn ids(tree: &Node) -> Vec<Option<String>> {
+    tree.children()
+        .unwrap()
+        .iter()
+        .filter_map(|node| match node {
+            Node::Heading(heading) => Some(
+                heading
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.get("id"))
+                    .cloned(),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn heading_ids_slug_headings() -> Result<(), message::Message> {
+    let mut slugger = Slugger::default();
+    let mut tree = to_mdast(
+        "# Hello, *World*!\n\n## a\n\n#\n\n> # a\n\n# a-1",
+        &ParseOptions::default(),
+    )?;
+    let slugs = slug_headings(&mut tree, &mut slugger);
+
+    assert_eq!(
+        ids(&tree),
+        vec![
+            Some("hello-world".into()),
+            Some("a".into()),
+            None,
+            Some("a-1-1".into())
+        ],
+        "should add unique slugs to headings as `data.id`, except for headings without text"
+    );
+
+    assert_eq!(
+        slugs.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("a".into(), "a".into()),
+            ("a-1".into(), "a".into()),
+            ("a-1-1".into(), "a-1".into()),
+            ("hello-world".into(), "Hello, World!".into()),
+        ],
+        "should return the text of headings by slug, including nested headings"
+    );
+
+    let ids_options = Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        mdast_to_html(&tree, &ids_options)?,
+        to_html_with_options(
+            "# Hello, *World*!\n\n## a\n\n#\n\n> # a\n\n# a-1",
+            &ids_options
+        )?,
+        "should add the same slugs as `heading_ids`"
+    );
+
+    let mut other = to_mdast("# a", &ParseOptions::default())?;
+    slug_headings(&mut other, &mut slugger);
+
+    assert_eq!(
+        ids(&other),
+        vec![Some("a-2".into())],
+        "should make slugs unique across trees with the same slugger"
+    );
+
+    slugger.reset();
+    slug_headings(&mut other, &mut slugger);
+
+    assert_eq!(
+        ids(&other),
+        vec![Some("a-2".into())],
+        "should keep an existing `data.id`"
+    );
+
+    let mut tree = to_mdast("# a\n\n# b", &ParseOptions::default())?;
+    if let Node::Heading(heading) = &mut tree.children_mut().unwrap()[0] {
+        heading.data = Some(Box::new(Data::from([("x".into(), "y".into())])));
+    }
+    slug_headings(&mut tree, &mut slugger);
+
+    if let Node::Heading(heading) = &tree.children().unwrap()[0] {
+        assert_eq!(
+            heading.data.as_deref(),
+            Some(&Data::from([
+                ("id".into(), "a".into()),
+                ("x".into(), "y".into())
+            ])),
+            "should keep other fields in `data`, and start over after `reset`"
+        );
+    }
+
+    let mut tree = to_mdast("# Über\n\n# Uber", &ParseOptions::default())?;
+
+    assert_eq!(
+        slug_headings_with_slugify(&mut tree, &mut Slugger::default(), &|value| value
+            .replace('Ü', "U")
+            .to_lowercase())
+        .into_keys()
+        .collect::<Vec<_>>(),
+        vec!["uber", "uber-1"],
+        "should support `slug_headings_with_slugify`"
+    );
+
+    Ok(())
+}
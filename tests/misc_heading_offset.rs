@@ -0,0 +1,99 @@
+use markdown::{
+    hast_to_html, mdast::Node, mdast_to_html, message, to_hast, to_html, to_html_with_options,
+    to_mdast_and_html, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+/// Get options that shift headings by `offset`.
+fn offset(offset: i8) -> Options {
+    Options {
+        compile: CompileOptions {
+            heading_offset: offset,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    }
+}
+
+#[test]
+fn heading_offset() -> Result<(), message::Message> {
+    let all = "# a\n\n## b\n\n### c\n\n#### d\n\n##### e\n\n###### f";
+
+    assert_eq!(
+        to_html("# a\n\nb\n---"),
+        "<h1>a</h1>\n<h2>b</h2>",
+        "should not shift headings by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(all, &offset(2))?,
+        "<h3>a</h3>\n<h4>b</h4>\n<h5>c</h5>\n<h6>d</h6>\n<h6>e</h6>\n<h6>f</h6>",
+        "should shift headings down, up to `h6`"
+    );
+
+    assert_eq!(
+        to_html_with_options(all, &offset(-2))?,
+        "<h1>a</h1>\n<h1>b</h1>\n<h1>c</h1>\n<h2>d</h2>\n<h3>e</h3>\n<h4>f</h4>",
+        "should shift headings up, down to `h1`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===\n\nb\n---", &offset(2))?,
+        "<h3>a</h3>\n<h4>b</h4>",
+        "should shift setext headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a", &offset(i8::MAX))?,
+        "<h6>a</h6>",
+        "should support big offsets"
+    );
+
+    assert_eq!(
+        to_html_with_options("###### a", &offset(i8::MIN))?,
+        "<h1>a</h1>",
+        "should support small offsets"
+    );
+
+    let mut options = offset(1);
+    options.compile.heading_ids = true;
+
+    assert_eq!(
+        to_html_with_options("# a", &options)?,
+        "<h2 id=\"a\">a</h2>",
+        "should support `heading_ids`"
+    );
+
+    let (tree, html) = to_mdast_and_html("# a\n\nb\n---", &offset(2))?;
+
+    assert_eq!(
+        html, "<h3>a</h3>\n<h4>b</h4>",
+        "should shift headings in `to_mdast_and_html`"
+    );
+
+    assert_eq!(
+        tree.children().map(|children| children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Heading(heading) => Some(heading.depth),
+                _ => None,
+            })
+            .collect::<Vec<_>>()),
+        Some(vec![1, 2]),
+        "should not change the depth of headings in syntax trees"
+    );
+
+    assert_eq!(
+        mdast_to_html(&tree, &offset(2))?,
+        "<h3>a</h3>\n<h4>b</h4>",
+        "should shift headings in `mdast_to_html`"
+    );
+
+    assert_eq!(
+        hast_to_html(&to_hast(all, &offset(-1))?, &CompileOptions::default()),
+        "<h1>a</h1>\n<h1>b</h1>\n<h2>c</h2>\n<h3>d</h3>\n<h4>e</h4>\n<h5>f</h5>",
+        "should shift headings in `to_hast`"
+    );
+
+    Ok(())
+}
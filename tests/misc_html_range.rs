@@ -0,0 +1,103 @@
+use markdown::{message, to_html_range, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn html_range() -> Result<(), message::Message> {
+    for value in [
+        "# a\n\nb *c*\nd\n\n> e\n\n* f\n\n  g\n* h\n\n***",
+        "[a] ![b]\n\n[a]: c\n\n> [b]: d\n\n```e\nf\n```",
+        "a[^b]\n\n| c |\n| - |\n| d |\n\n[^b]: e\n\n    f",
+        "a\r\n\r\nb\r\n",
+        "",
+    ] {
+        assert_eq!(
+            to_html_range(value, &Options::gfm(), 1..usize::MAX)?,
+            to_html_with_options(value, &Options::gfm())?,
+            "should be the same as `to_html_with_options` for all lines of {:?}",
+            value
+        );
+    }
+
+    assert_eq!(
+        to_html_range("a\n\nb\n\nc", &Options::default(), 3..4)?,
+        "<p>b</p>",
+        "should compile blocks on the lines"
+    );
+
+    assert_eq!(
+        to_html_range("a\nb\n\nc\n\nd\ne", &Options::default(), 2..5)?,
+        "<p>a\nb</p>\n<p>c</p>",
+        "should include blocks that straddle the start of the lines"
+    );
+
+    assert_eq!(
+        to_html_range("a\n\n* b\n\n  c\n* d\n\ne", &Options::default(), 3..4)?,
+        "<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n<li>\n<p>d</p>\n</li>\n</ul>",
+        "should include top-level containers whole"
+    );
+
+    assert_eq!(
+        to_html_range("a\n\n\n\nb", &Options::default(), 2..4)?,
+        "",
+        "should compile nothing if there are no blocks on the lines"
+    );
+
+    assert_eq!(
+        to_html_range("a\r\n\r\nb\r\n\r\nc", &Options::default(), 1..4)?,
+        "<p>a</p>\r\n<p>b</p>",
+        "should use the line endings between blocks"
+    );
+
+    assert_eq!(
+        to_html_range("[a]\n\nb\n\n> [a]: c\n\n[d]: e", &Options::default(), 1..2)?,
+        "<p><a href=\"c\">a</a></p>",
+        "should resolve references with definitions elsewhere"
+    );
+
+    assert_eq!(
+        to_html_range("a[^b]\n\nc\n\n[^b]: d", &Options::gfm(), 1..2)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>",
+        "should resolve footnote calls with definitions elsewhere"
+    );
+
+    assert_eq!(
+        to_html_range("a[^b]\n\nc\n\n[^b]: d", &Options::gfm(), 5..6)?,
+        "<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>d <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should add the footnote section if a called definition is on the lines"
+    );
+
+    let value = "# a\n\nx[^1]\n\n# a\n\ny[^2]\n\n[^1]: p\n[^2]: q";
+    let options = Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_range(value, &options, 5..8)?,
+        "<h1 id=\"a-1\">a</h1>\n<p>y<sup><a href=\"#user-content-fn-2\" id=\"user-content-fnref-2\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">2</a></sup></p>",
+        "should count headings and footnote calls from the start of the document"
+    );
+
+    assert_eq!(
+        to_html_range(value, &options, 9..10)?,
+        "<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-1\">\n<p>p <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n<li id=\"user-content-fn-2\">\n<p>q <a href=\"#user-content-fnref-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should include the footnote section whole"
+    );
+
+    assert_eq!(
+        to_html_range(value, &options, 1..usize::MAX)?,
+        to_html_with_options(value, &options)?,
+        "should be the same as `to_html_with_options` with heading IDs"
+    );
+
+    assert_eq!(
+        to_html_range("a[^b]\n\nc\n\n[^b]: d", &Options::gfm(), 3..4)?,
+        "<p>c</p>",
+        "should not add footnotes that are not called on the lines"
+    );
+
+    Ok(())
+}
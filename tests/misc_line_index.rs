@@ -0,0 +1,48 @@
+use markdown::{message, to_mdast, unist::Point, LineIndex, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn line_index() -> Result<(), message::Message> {
+    for value in [
+        "a\tb *c*",
+        "# a\r\n\r\n> b\r> c **d**\n\n- e\n\n\t- f\n\n\t\tg",
+        "| a |\tb |\n| - | - |\n| 😀\t| *c* |\n\n```\n\td\n```",
+        "\t> a\n>\tb [c](d)\n\n1.\te\n",
+    ] {
+        let tree = to_mdast(value, &ParseOptions::gfm())?;
+        let index = LineIndex::new(value);
+
+        for node in &tree {
+            let position = node.position().unwrap();
+
+            for point in [&position.start, &position.end] {
+                let at = index.offset_to_point(point.offset).unwrap();
+
+                // Containers can take part of a tab, so nodes in them can
+                // start in a tab, which an offset cannot represent.
+                if value[point.offset..].starts_with('\t') {
+                    assert!(
+                        at.line == point.line && at.column <= point.column,
+                        "should get the point of the tab in `{:?}`",
+                        value
+                    );
+                } else {
+                    assert_eq!(
+                        &at, point,
+                        "should get the points of nodes from their offsets in `{:?}`",
+                        value
+                    );
+                }
+
+                assert_eq!(
+                    index.point_to_offset(&Point::new(point.line, point.column, 0)),
+                    Some(point.offset),
+                    "should get the offsets of nodes from their points in `{:?}`",
+                    value
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
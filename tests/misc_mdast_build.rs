@@ -0,0 +1,189 @@
+use markdown::{
+    mdast::{build::*, visit_mut, AlignKind, Node, VisitAction},
+    mdast_to_html, mdast_to_markdown, message, to_mdast, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use test_utils::mdast::strip_positions;
+
+mod test_utils {
+    pub mod mdast;
+}
+
+/// Remove the style of the source from a tree.
+fn unstyle(tree: &mut Node) {
+    visit_mut(tree, |node, _| {
+        match node {
+            Node::Emphasis(node) => node.marker = None,
+            Node::Strong(node) => node.marker = None,
+            Node::InlineCode(node) => node.sequence_size = None,
+            Node::ListItem(node) => {
+                node.marker = None;
+                node.number = None;
+                node.indent = None;
+            }
+            _ => {}
+        }
+        VisitAction::Continue
+    });
+}
+
+/// Parse `value` into a tree without positions and style.
+fn parse(value: &str, options: &ParseOptions) -> Result<Node, message::Message> {
+    let mut tree = to_mdast(value, options)?;
+    strip_positions(&mut tree);
+    unstyle(&mut tree);
+    Ok(tree)
+}
+
+#[test]
+fn mdast_build() -> Result<(), message::Message> {
+    let mut gfm = ParseOptions::gfm();
+    gfm.constructs.math_flow = true;
+    gfm.constructs.math_text = true;
+    gfm.constructs.frontmatter = true;
+
+    assert_eq!(
+        root(vec![
+            heading(2, vec![text("a "), emphasis(vec![text("b")])]),
+            paragraph(vec![
+                strong(vec![text("c")]),
+                break_node(),
+                inline_code("d"),
+                text(" "),
+                link("e", vec![text("f")]),
+                text(" "),
+                image("g", "h"),
+            ]),
+            thematic_break(),
+        ]),
+        parse(
+            "## a *b*\n\n**c**\\\n`d` [f](e) ![h](g)\n\n***",
+            &ParseOptions::default()
+        )?,
+        "should build headings, paragraphs, and phrasing"
+    );
+
+    assert_eq!(
+        root(vec![
+            block_quote(vec![paragraph(vec![text("a")])]),
+            list(false, vec![list_item(vec![paragraph(vec![text("b")])])]),
+            list(true, vec![list_item(vec![paragraph(vec![text("c")])])]),
+            code("d", Some("js")),
+            code("e", None),
+            html("<f>"),
+        ]),
+        parse(
+            "> a\n\n* b\n\n1. c\n\n```js\nd\n```\n\n```\ne\n```\n\n<f>",
+            &ParseOptions::default()
+        )?,
+        "should build flow content"
+    );
+
+    assert_eq!(
+        root(vec![
+            paragraph(vec![
+                link_reference("A", vec![text("b")]),
+                text(" "),
+                image_reference("C", "d"),
+            ]),
+            definition("A", "e"),
+            definition("C", "f"),
+        ]),
+        parse(
+            "[b][A] ![d][C]\n\n[A]: e\n\n[C]: f",
+            &ParseOptions::default()
+        )?,
+        "should build references and definitions"
+    );
+
+    assert_eq!(
+        root(vec![
+            yaml("a: b"),
+            table(
+                vec![AlignKind::Left, AlignKind::None],
+                vec![
+                    table_row(vec![
+                        table_cell(vec![text("c")]),
+                        table_cell(vec![delete(vec![text("d")])]),
+                    ]),
+                    table_row(vec![
+                        table_cell(vec![inline_math("e")]),
+                        table_cell(vec![footnote_reference("F")]),
+                    ]),
+                ],
+            ),
+            math("g"),
+            footnote_definition("F", vec![paragraph(vec![text("h")])]),
+        ]),
+        parse(
+            "---\na: b\n---\n\n| c | ~~d~~ |\n| :- | - |\n| $e$ | [^F] |\n\n$$\ng\n$$\n\n[^F]: h",
+            &gfm
+        )?,
+        "should build GFM and frontmatter"
+    );
+
+    assert_eq!(
+        mdast_to_html(
+            &root(vec![paragraph(vec![text("a "), strong(vec![text("b")])])]),
+            &Options::default()
+        )?,
+        "<p>a <strong>b</strong></p>",
+        "should be usable with `mdast_to_html`"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&root(vec![list(
+            true,
+            vec![list_item(vec![paragraph(vec![text("a")])])]
+        )]))?,
+        "1. a\n",
+        "should be usable with `mdast_to_markdown`"
+    );
+
+    assert_eq!(
+        toml("a = 1"),
+        parse("+++\na = 1\n+++", &gfm)?.children().unwrap()[0],
+        "should build toml"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_build_node() -> Result<(), message::Message> {
+    assert_eq!(
+        Node::root(vec![
+            Node::heading(1, vec![Node::text("a")]),
+            Node::paragraph(vec![Node::emphasis(vec![Node::text("b")])]),
+        ]),
+        root(vec![
+            heading(1, vec![text("a")]),
+            paragraph(vec![emphasis(vec![text("b")])]),
+        ]),
+        "should build the same nodes as the functions"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::root(vec![Node::paragraph(vec![
+            Node::link("a", Some("b"), vec![Node::text("c")]),
+            Node::text(" "),
+            Node::image("d", Some("e"), "f"),
+            Node::text(" "),
+            Node::link("g", None, vec![Node::inline_code("h")]),
+        ])]))?,
+        "[c](a \"b\") ![f](d \"e\") [`h`](g)\n",
+        "should support titles on links and images"
+    );
+
+    assert_eq!(
+        Node::link("a", Some("b"), vec![]),
+        parse("[](a \"b\")", &ParseOptions::default())?
+            .children()
+            .unwrap()[0]
+            .children()
+            .unwrap()[0],
+        "should build links like the parser does"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,273 @@
+use markdown::{mdast::Node, message, to_mdast, ParseOptions, PositionEncoding};
+use pretty_assertions::assert_eq;
+use test_utils::mdast::strip_positions;
+
+mod test_utils {
+    pub mod mdast;
+}
+
+/// Check whether any node in a tree has a position.
+fn has_position(node: &Node) -> bool {
+    node.position().is_some()
+        || node
+            .children()
+            .map_or(false, |children| children.iter().any(has_position))
+}
+
+/// Get the positions of all nodes in a tree, in preorder.
+fn positions(node: &Node, result: &mut Vec<String>) {
+    result.push(format!("{:?}", node.position().unwrap()));
+
+    if let Some(children) = node.children() {
+        for child in children {
+            positions(child, result);
+        }
+    }
+}
+
+/// Parse `value` with a position encoding, and get the positions of all
+/// nodes.
+fn encoded(
+    value: &str,
+    position_encoding: PositionEncoding,
+) -> Result<Vec<String>, message::Message> {
+    let mut result = vec![];
+    positions(
+        &to_mdast(
+            value,
+            &ParseOptions {
+                position_encoding,
+                ..ParseOptions::default()
+            },
+        )?,
+        &mut result,
+    );
+    Ok(result)
+}
+
+/// Get options for GFM or MDX, with or without positions.
+fn options(mdx: bool, mdast_positions: bool) -> ParseOptions {
+    ParseOptions {
+        mdast_positions,
+        ..if mdx {
+            ParseOptions::mdx()
+        } else {
+            ParseOptions::gfm()
+        }
+    }
+}
+
+#[test]
+fn mdast_positions() -> Result<(), message::Message> {
+    assert!(
+        has_position(&to_mdast("# a", &ParseOptions::default())?),
+        "should add positions by default"
+    );
+
+    for (value, mdx) in [
+        ("# a *b*\n\nc  \nd\\\ne", false),
+        ("> - [x]\n>   f\n> * [ ]  g\n\n1. h", false),
+        ("| i | j |\n| - | - |\n| [k][] | ~l~ |\n\n[k]: m", false),
+        ("n[^o]\n\n[^o]: p\n\n```q\nr\n```", false),
+        ("<s>t *u*</s>\n\nv <w x /> {y}", true),
+    ] {
+        let tree = to_mdast(value, &options(mdx, false))?;
+
+        assert!(
+            !has_position(&tree),
+            "should not add positions if `mdast_positions: false` for {:?}",
+            value
+        );
+
+        let mut expected = to_mdast(value, &options(mdx, true))?;
+        strip_positions(&mut expected);
+
+        assert_eq!(
+            tree, expected,
+            "should otherwise build the same tree for {:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn position_encoding() -> Result<(), message::Message> {
+    assert_eq!(
+        encoded("a\nb", PositionEncoding::Utf16)?,
+        encoded("a\nb", PositionEncoding::Utf8)?,
+        "should not change positions for ASCII"
+    );
+
+    assert_eq!(
+        encoded("# 日本 😀\n\ne\u{301} *😀*", PositionEncoding::Utf8)?,
+        vec![
+            "1:1-3:11 (0-25)",
+            "1:1-1:14 (0-13)",
+            "1:3-1:14 (2-13)",
+            "3:1-3:11 (15-25)",
+            "3:1-3:5 (15-19)",
+            "3:5-3:11 (19-25)",
+            "3:6-3:10 (20-24)",
+        ],
+        "should count UTF-8 bytes by default"
+    );
+
+    assert_eq!(
+        encoded("# 日本 😀\n\ne\u{301} *😀*", PositionEncoding::Utf16)?,
+        vec![
+            "1:1-3:8 (0-16)",
+            "1:1-1:8 (0-7)",
+            "1:3-1:8 (2-7)",
+            "3:1-3:8 (9-16)",
+            "3:1-3:4 (9-12)",
+            "3:4-3:8 (12-16)",
+            "3:5-3:7 (13-15)",
+        ],
+        "should count UTF-16 code units (surrogate pairs, combining characters, CJK)"
+    );
+
+    assert_eq!(
+        encoded("# 日本 😀\n\ne\u{301} *😀*", PositionEncoding::CodePoints)?,
+        vec![
+            "1:1-3:7 (0-14)",
+            "1:1-1:7 (0-6)",
+            "1:3-1:7 (2-6)",
+            "3:1-3:7 (8-14)",
+            "3:1-3:4 (8-11)",
+            "3:4-3:7 (11-14)",
+            "3:5-3:6 (12-13)",
+        ],
+        "should count code points (surrogate pairs, combining characters, CJK)"
+    );
+
+    assert_eq!(
+        encoded("> é\r\n> 😀\tb", PositionEncoding::Utf16)?,
+        vec![
+            "1:1-2:7 (0-11)",
+            "1:1-2:7 (0-11)",
+            "1:3-2:7 (2-11)",
+            "1:3-2:7 (2-11)"
+        ],
+        "should count in each line, with CRLF and tabs"
+    );
+
+    assert_eq!(
+        encoded("a\t*😀*", PositionEncoding::Utf16)?,
+        vec![
+            "1:1-1:7 (0-6)",
+            "1:1-1:7 (0-6)",
+            "1:1-1:3 (0-2)",
+            "1:3-1:7 (2-6)",
+            "1:4-1:6 (3-5)"
+        ],
+        "should count a tab as one unit, like the language server protocol"
+    );
+
+    Ok(())
+}
+
+/// Get the marker positions of all headings, inline code, and links in a
+/// tree, in preorder.
+fn markers(node: &Node, result: &mut Vec<String>) {
+    let node_markers = match node {
+        Node::Heading(node) => Some(&node.markers),
+        Node::InlineCode(node) => Some(&node.markers),
+        Node::Link(node) => Some(&node.markers),
+        _ => None,
+    };
+
+    if let Some(node_markers) = node_markers {
+        result.push(format!("{:?}", node_markers));
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            markers(child, result);
+        }
+    }
+}
+
+/// Parse `value` with marker positions, and get them.
+fn marked(
+    value: &str,
+    position_encoding: PositionEncoding,
+) -> Result<Vec<String>, message::Message> {
+    let mut result = vec![];
+    markers(
+        &to_mdast(
+            value,
+            &ParseOptions {
+                mdast_marker_positions: true,
+                position_encoding,
+                ..ParseOptions::gfm()
+            },
+        )?,
+        &mut result,
+    );
+    Ok(result)
+}
+
+#[test]
+fn mdast_marker_positions() -> Result<(), message::Message> {
+    let mut result = vec![];
+    markers(
+        &to_mdast("# a\n\n`b` [c](d)", &ParseOptions::default())?,
+        &mut result,
+    );
+
+    assert_eq!(
+        result,
+        vec!["[]", "[]", "[]"],
+        "should not add marker positions by default"
+    );
+
+    assert_eq!(
+        marked("## a ##\n\n# b\n\nc\n===", PositionEncoding::Utf8)?,
+        vec![
+            "[1:1-1:3 (0-2), 1:6-1:8 (5-7)]",
+            "[3:1-3:2 (9-10)]",
+            "[6:1-6:4 (16-19)]"
+        ],
+        "should add marker positions to headings"
+    );
+
+    assert_eq!(
+        marked("a ``b`` `c`", PositionEncoding::Utf8)?,
+        vec![
+            "[1:3-1:5 (2-4), 1:6-1:8 (5-7)]",
+            "[1:9-1:10 (8-9), 1:11-1:12 (10-11)]"
+        ],
+        "should add marker positions to inline code"
+    );
+
+    assert_eq!(
+        marked(
+            "[a](b) <https://c> www.d.e [f `g`](h \"i\")",
+            PositionEncoding::Utf8
+        )?,
+        vec![
+            "[1:1-1:2 (0-1), 1:3-1:4 (2-3), 1:4-1:5 (3-4), 1:6-1:7 (5-6)]",
+            "[1:8-1:9 (7-8), 1:18-1:19 (17-18)]",
+            "[]",
+            "[1:28-1:29 (27-28), 1:34-1:35 (33-34), 1:35-1:36 (34-35), 1:41-1:42 (40-41)]",
+            "[1:31-1:32 (30-31), 1:33-1:34 (32-33)]"
+        ],
+        "should add marker positions to links (not GFM autolink literals)"
+    );
+
+    assert_eq!(
+        marked("[![a](b) c](d)", PositionEncoding::Utf8)?,
+        vec!["[1:1-1:2 (0-1), 1:11-1:12 (10-11), 1:12-1:13 (11-12), 1:14-1:15 (13-14)]"],
+        "should not add marker positions of nested media to links"
+    );
+
+    assert_eq!(
+        marked("# 😀 #", PositionEncoding::Utf16)?,
+        vec!["[1:1-1:2 (0-1), 1:6-1:7 (5-6)]"],
+        "should encode marker positions"
+    );
+
+    Ok(())
+}
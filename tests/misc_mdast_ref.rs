@@ -0,0 +1,194 @@
+use markdown::{
+    mdast,
+    mdast_ref::{self, Node},
+    message, to_mdast, to_mdast_ref, Constructs, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::{borrow::Cow, fs};
+use test_utils::allocations::allocations;
+
+mod test_utils {
+    pub mod allocations;
+}
+
+/// Get the values of text in `tree`, with whether they borrow.
+fn texts<'a>(node: &Node<'a>, result: &mut Vec<(String, bool)>) {
+    if let Node::Text(text) = node {
+        result.push((
+            text.value.to_string(),
+            matches!(text.value, Cow::Borrowed(_)),
+        ));
+    }
+
+    if let Some(children) = node.children() {
+        for child in children {
+            texts(child, result);
+        }
+    }
+}
+
+/// Get the first child of the first child of `tree`.
+fn first<'a, 'b>(tree: &'b Node<'a>) -> &'b Node<'a> {
+    &tree.children().unwrap()[0]
+}
+
+#[test]
+fn mdast_ref_borrow() -> Result<(), message::Message> {
+    let mut result = vec![];
+    texts(
+        &to_mdast_ref("a\nb *c* d\\*e &amp; f\n  g", &ParseOptions::default())?,
+        &mut result,
+    );
+
+    assert_eq!(
+        result,
+        vec![
+            ("a\nb ".into(), true),
+            ("c".into(), true),
+            (" d*e & f\ng".into(), false),
+        ],
+        "should borrow text, unless it has escapes, references, or indents"
+    );
+
+    let tree = to_mdast_ref(
+        "```js eval\na\n\nb\n```\n\n    c\n      d\n\n<div>\ne\n</div>",
+        &ParseOptions::default(),
+    )?;
+    let children = tree.children().unwrap();
+
+    assert!(
+        matches!(
+            &children[0],
+            mdast_ref::Node::Code(mdast_ref::Code {
+                value: Cow::Borrowed("a\n\nb"),
+                lang: Some(Cow::Borrowed("js")),
+                meta: Some(Cow::Borrowed("eval")),
+                ..
+            })
+        ),
+        "should borrow fenced code and its info"
+    );
+    assert!(
+        matches!(
+            &children[1],
+            mdast_ref::Node::Code(mdast_ref::Code { value: Cow::Owned(value), .. }) if value == "c\n  d"
+        ),
+        "should own indented code that spans lines"
+    );
+    assert!(
+        matches!(
+            &children[2],
+            mdast_ref::Node::Html(mdast_ref::Html {
+                value: Cow::Borrowed("<div>\ne\n</div>"),
+                ..
+            })
+        ),
+        "should borrow HTML"
+    );
+
+    let tree = to_mdast_ref(
+        "[a](b 'c') [d](e\\*f) <g@h.i> `` j ``\n\n[k]: l\n\n[K]",
+        &ParseOptions::default(),
+    )?;
+    let paragraph = first(&tree).children().unwrap();
+
+    assert!(
+        matches!(
+            &paragraph[0],
+            mdast_ref::Node::Link(mdast_ref::Link {
+                url: Cow::Borrowed("b"),
+                title: Some(Cow::Borrowed("c")),
+                ..
+            })
+        ),
+        "should borrow URLs and titles"
+    );
+    assert!(
+        matches!(
+            &paragraph[2],
+            mdast_ref::Node::Link(mdast_ref::Link { url: Cow::Owned(url), .. }) if url == "e*f"
+        ),
+        "should own URLs with escapes"
+    );
+    assert!(
+        matches!(
+            &paragraph[4],
+            mdast_ref::Node::Link(mdast_ref::Link { url: Cow::Owned(url), .. }) if url == "mailto:g@h.i"
+        ),
+        "should own URLs of email autolinks"
+    );
+    assert!(
+        matches!(
+            &paragraph[6],
+            mdast_ref::Node::InlineCode(mdast_ref::InlineCode {
+                value: Cow::Borrowed("j"),
+                padding: true,
+                ..
+            })
+        ),
+        "should borrow inline code, without padding"
+    );
+    assert!(
+        matches!(
+            &tree.children().unwrap()[2],
+            mdast_ref::Node::Paragraph(mdast_ref::Paragraph { children, .. })
+                if matches!(&children[0], mdast_ref::Node::LinkReference(mdast_ref::LinkReference {
+                    identifier: Cow::Owned(identifier),
+                    label: Some(Cow::Borrowed("K")),
+                    ..
+                }) if identifier == "k")
+        ),
+        "should own normalized identifiers, but borrow labels"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_ref_same() -> Result<(), message::Message> {
+    let options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::default()
+    };
+    let value = fs::read_to_string("readme.md").unwrap()
+        + "\n---\na: b\n---\n\n$$\nc\n$$\n\n* [x] d $e$\n\n| f | `g\\|h` |\n| - | - |\n\n![i *j* ![k](l)](m) [^n]\n\n[^n]: o";
+
+    assert_eq!(
+        to_mdast_ref(&value, &options)?.into_owned(),
+        to_mdast(&value, &options)?,
+        "should be the same as `to_mdast` when owned"
+    );
+
+    assert_eq!(
+        mdast::Node::from(to_mdast_ref("a", &options)?),
+        to_mdast("a", &options)?,
+        "should support `From`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_ref_allocations() -> Result<(), message::Message> {
+    let value = fs::read_to_string("readme.md").unwrap();
+    let options = ParseOptions::gfm();
+
+    let (owned, owned_allocations) = allocations(|| to_mdast(&value, &options));
+    let (borrowed, borrowed_allocations) = allocations(|| to_mdast_ref(&value, &options));
+    let (owned, borrowed) = (owned?, borrowed?);
+
+    assert!(
+        borrowed_allocations < owned_allocations,
+        "should allocate less ({} < {})",
+        borrowed_allocations,
+        owned_allocations
+    );
+    assert_eq!(borrowed.into_owned(), owned, "should make the same tree");
+
+    Ok(())
+}
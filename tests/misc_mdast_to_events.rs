@@ -0,0 +1,295 @@
+use markdown::{
+    event::{Event, Kind, Name},
+    mdast::build,
+    mdast_to_events, message, to_events, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use test_utils::corpus::corpus;
+
+mod test_utils {
+    pub mod corpus;
+}
+
+/// Get the kinds and names of `events`, without whitespace.
+fn names(events: &[Event]) -> Vec<(Kind, Name)> {
+    events
+        .iter()
+        .filter(|event| {
+            !matches!(
+                event.name,
+                Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+            )
+        })
+        .map(|event| (event.kind.clone(), event.name.clone()))
+        .collect()
+}
+
+/// Constructs that are not compared, with why.
+const IGNORED: [(Name, &str); 11] = [
+    (Name::ByteOrderMark, "mdast does not keep it"),
+    (
+        Name::BlockQuotePrefix,
+        "mdast does not keep lazy lines, so a prefix is added to them",
+    ),
+    (
+        Name::ThematicBreakSequence,
+        "thematic breaks are serialized as one sequence (`***`)",
+    ),
+    (
+        Name::GfmTableCellDivider,
+        "rows are serialized with a divider at the start and end",
+    ),
+    (
+        Name::CodeFencedFence,
+        "fences are serialized as needed for the code, and always closed",
+    ),
+    (
+        Name::HeadingAtxSequence,
+        "headings are serialized without a closing sequence",
+    ),
+    (
+        Name::Reference,
+        "references are serialized as full or collapsed when the text differs from the label",
+    ),
+    (
+        Name::ResourceDestination,
+        "destinations are serialized in `<` and `>` when needed, and empty ones too",
+    ),
+    (Name::ResourceTitle, "empty titles are not kept"),
+    (
+        Name::DefinitionDestination,
+        "destinations are serialized in `<` and `>` when needed",
+    ),
+    (Name::DefinitionTitle, "empty titles are not kept"),
+];
+
+/// Constructs that are compared as data, with why.
+const TEXT: [(Name, &str); 2] = [
+    (
+        Name::CharacterEscape,
+        "mdast does not keep escapes, and text that could be syntax is escaped",
+    ),
+    (
+        Name::CharacterReference,
+        "mdast does not keep character references, and some characters are serialized as them",
+    ),
+];
+
+/// Constructs that make an input not compared, with why.
+const SKIPPED: [(Name, &str); 7] = [
+    (
+        Name::CodeIndented,
+        "mdast does not keep whether code is indented, it is serialized fenced",
+    ),
+    (
+        Name::HeadingSetext,
+        "mdast does not keep whether headings are setext, they are serialized as ATX",
+    ),
+    (
+        Name::GfmAutolinkLiteralEmail,
+        "autolink literals are serialized as autolinks or links",
+    ),
+    (
+        Name::GfmAutolinkLiteralMailto,
+        "autolink literals are serialized as autolinks or links",
+    ),
+    (
+        Name::GfmAutolinkLiteralProtocol,
+        "autolink literals are serialized as autolinks or links, and text that looks like one is not escaped",
+    ),
+    (
+        Name::GfmAutolinkLiteralWww,
+        "autolink literals are serialized as autolinks or links",
+    ),
+    (
+        Name::GfmAutolinkLiteralXmpp,
+        "autolink literals are serialized as autolinks or links",
+    ),
+];
+
+/// Inputs that are not compared, with why.
+const SKIPPED_VALUES: [(&str, &str); 3] = [
+    (
+        "- ```\n\t\n  ```",
+        "the list item takes the tab, mdast does not keep the empty line of code",
+    ),
+    (
+        "> `a\r\n> b`\r\n> `c\rd`",
+        "mdast does not keep line endings in text as they were: a CRLF is serialized as `\\r&#xA;`",
+    ),
+    (
+        "* [x]\r\n  a\n* [ ]   b\n* [x]\t \r*c*",
+        "mdast does not keep that the text of a task list item starts on the next line",
+    ),
+];
+
+/// Get the index of the exit of the event at `index`.
+fn exit_index(events: &[Event], mut index: usize) -> usize {
+    let name = &events[index].name;
+    let mut depth = 0;
+
+    loop {
+        if events[index].name == *name {
+            if events[index].kind == Kind::Enter {
+                depth += 1;
+            } else {
+                depth -= 1;
+            }
+        }
+
+        if depth == 0 {
+            return index;
+        }
+
+        index += 1;
+    }
+}
+
+/// Get the kinds and names of `events`, without whitespace and the
+/// constructs that are not compared, with adjacent data joined, and
+/// without empty table cells, which are added to rows that are too short.
+fn structure(events: &[Event]) -> Vec<(Kind, Name)> {
+    let mut result = vec![];
+    let mut stack = vec![];
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        // The label of an image is alt text in mdast.
+        let alt = event.name == Name::LabelText
+            && stack.len() > 1
+            && stack[stack.len() - 2] == &Name::Image;
+
+        if alt
+            || IGNORED.iter().any(|(name, _)| *name == event.name)
+            || matches!(
+                event.name,
+                Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+            )
+        {
+            index = exit_index(events, index);
+        } else if TEXT.iter().any(|(name, _)| *name == event.name) || event.name == Name::Data {
+            index = exit_index(events, index);
+
+            if result.last() != Some(&(Kind::Exit, Name::Data)) {
+                result.push((Kind::Enter, Name::Data));
+                result.push((Kind::Exit, Name::Data));
+            }
+        } else if event.kind == Kind::Exit
+            && event.name == Name::GfmTableCell
+            && result.last() == Some(&(Kind::Enter, Name::GfmTableCell))
+        {
+            stack.pop();
+            result.pop();
+        } else {
+            if event.kind == Kind::Enter {
+                stack.push(&event.name);
+            } else {
+                stack.pop();
+            }
+
+            result.push((event.kind.clone(), event.name.clone()));
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+#[test]
+fn mdast_to_events_escape() -> Result<(), message::Message> {
+    let tree = build::root(vec![build::paragraph(vec![
+        build::text("*a* "),
+        build::emphasis(vec![build::text("b")]),
+    ])]);
+    let (value, events) = mdast_to_events(&tree, &ParseOptions::default())?;
+
+    assert_eq!(value, "\\*a\\* _b_\n", "should serialize the tree");
+
+    assert_eq!(
+        names(&events),
+        vec![
+            (Kind::Enter, Name::Paragraph),
+            (Kind::Enter, Name::CharacterEscape),
+            (Kind::Enter, Name::CharacterEscapeMarker),
+            (Kind::Exit, Name::CharacterEscapeMarker),
+            (Kind::Enter, Name::CharacterEscapeValue),
+            (Kind::Exit, Name::CharacterEscapeValue),
+            (Kind::Exit, Name::CharacterEscape),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Enter, Name::CharacterEscape),
+            (Kind::Enter, Name::CharacterEscapeMarker),
+            (Kind::Exit, Name::CharacterEscapeMarker),
+            (Kind::Enter, Name::CharacterEscapeValue),
+            (Kind::Exit, Name::CharacterEscapeValue),
+            (Kind::Exit, Name::CharacterEscape),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Enter, Name::Emphasis),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Enter, Name::EmphasisText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::EmphasisText),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Exit, Name::Emphasis),
+            (Kind::Exit, Name::Paragraph),
+        ],
+        "should use escapes for text that would otherwise be syntax"
+    );
+
+    let event = events
+        .iter()
+        .find(|event| event.name == Name::Emphasis)
+        .unwrap();
+
+    assert_eq!(
+        (event.point.line, event.point.column, event.point.index),
+        (1, 7, 6),
+        "should point into the serialized markdown"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_to_events_round_trip() -> Result<(), message::Message> {
+    let options = ParseOptions::gfm();
+    let skipped = |events: &[Event]| {
+        events
+            .iter()
+            .any(|event| SKIPPED.iter().any(|(name, _)| *name == event.name))
+    };
+    let mut compared = 0;
+
+    for value in corpus() {
+        if SKIPPED_VALUES.iter().any(|(skip, _)| *skip == value) {
+            continue;
+        }
+
+        let expected = to_events(&value, &options)?;
+        let (_, events) = mdast_to_events(&to_mdast(&value, &options)?, &options)?;
+
+        if skipped(&expected) || skipped(&events) {
+            continue;
+        }
+
+        assert_eq!(
+            structure(&events),
+            structure(&expected),
+            "should match `to_events` for `{:?}`",
+            value
+        );
+
+        compared += 1;
+    }
+
+    assert!(compared > 8000, "should compare most of the corpus");
+
+    Ok(())
+}
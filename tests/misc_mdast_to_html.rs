@@ -0,0 +1,418 @@
+use markdown::{
+    mdast::{
+        Code, Definition, FootnoteReference, Heading, Html, Image, InlineCode, LinkReference,
+        MdxFlowExpression, MdxJsxFlowElement, Node, Paragraph, ReferenceKind, Root, TableCell,
+        Text,
+    },
+    mdast_to_html, mdast_to_html_with_renderer, message, to_html_with_options, to_mdast,
+    CompileOptions, Options, Quote, RenderContext, Renderer,
+};
+use pretty_assertions::assert_eq;
+
+/// Compile markdown to HTML through a syntax tree.
+fn through_mdast(value: &str, options: &Options) -> Result<String, message::Message> {
+    mdast_to_html(&to_mdast(value, &options.parse)?, options)
+}
+
+/// Compile GFM to HTML through a syntax tree, with a renderer.
+fn through_renderer(value: &str, renderer: &dyn Renderer) -> Result<String, message::Message> {
+    let options = Options::gfm();
+    mdast_to_html_with_renderer(&to_mdast(value, &options.parse)?, &options, renderer)
+}
+
+#[test]
+fn mdast_to_html_same() -> Result<(), message::Message> {
+    let danger = Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            allow_dangerous_protocol: true,
+            ..CompileOptions::default()
+        },
+        ..Options::gfm()
+    };
+    let mut math = Options::default();
+    math.parse.constructs.math_flow = true;
+    math.parse.constructs.math_text = true;
+
+    let cases = [
+        (
+            "# a *b* **c**\n\nd\ne  \nf\\\ng",
+            "headings, emphasis, breaks",
+        ),
+        (
+            "a\n===\n\n***\n\n> b\n> c\n>\n> d",
+            "setext, thematic breaks, block quotes",
+        ),
+        (
+            "```js eval\na < b\n```\n\n    c\n\n```\n```\n\n~~~\n\n\n~~~",
+            "code",
+        ),
+        ("`` a ` b `` `\nc`", "code (text)"),
+        ("* a\n* b\n\n1. c\n2. d\n\n3) e\n\n- f\n\n  g\n- h", "lists"),
+        (
+            "- a\n  > b\n- \n- # c\n  d\n-\n  ```\n  e\n  ```",
+            "blocks in tight items",
+        ),
+        ("- a\n\n  [b]: c\n- d\n  [e]: f", "definitions in items"),
+        (
+            "[a](b 'c') ![d *e*](f \"g\") <h> <i@j.k> [l](javascript:m)",
+            "links and images",
+        ),
+        (
+            "[a]: e\n[b]: f 'g'\n[A]: h\n\n[a] [b][] [C][a] ![a] ![d][b]",
+            "references",
+        ),
+        (
+            "&amp; &copy; \\* a&lt;b",
+            "character references and escapes",
+        ),
+        ("<div>\n*a*\n</div>\n\nb <i>c</i> <!--d-->", "HTML"),
+        ("a ~b~ ~~c~~ www.d.com https://e.org f@g.h", "GFM (text)"),
+        (
+            "| a | b | c |\n| - | :-: | --: |\n| d | `e\\|f` |\n| g | h | i | j |",
+            "GFM tables",
+        ),
+        ("| a |\n| - |", "GFM tables w/o body"),
+        ("* [ ] a\n* [x] b\n\n- [x] c\n\n  d", "GFM task list items"),
+        (
+            "a[^b] c[^d] e[^b]\n\n[^b]: f\n\n[^d]:\n    ```\n    g\n    ```\n\n[^h]: i",
+            "GFM footnotes",
+        ),
+        (
+            "[^a]\n\n[^a]: b[^c]\n\n    d\n\n[^c]: e\n\n> f[^a]",
+            "GFM footnotes in footnotes",
+        ),
+        (
+            "[a](<b> \"c\")\n\n<script>d</script>\n\ne <title>f</title>",
+            "GFM tagfilter",
+        ),
+    ];
+
+    for (value, message) in cases {
+        assert_eq!(
+            through_mdast(value, &danger)?,
+            to_html_with_options(value, &danger)?,
+            "should match `to_html` for {} (dangerous)",
+            message
+        );
+        assert_eq!(
+            through_mdast(value, &Options::gfm())?,
+            to_html_with_options(value, &Options::gfm())?,
+            "should match `to_html` for {} (safe)",
+            message
+        );
+    }
+
+    assert_eq!(
+        through_mdast("$$\na\n$$\n\n$b$", &math)?,
+        to_html_with_options("$$\na\n$$\n\n$b$", &math)?,
+        "should match `to_html` for math"
+    );
+
+    assert_eq!(
+        through_mdast("a\r\n\r\nb\r\nc", &Options::default())?,
+        "<p>a</p>\r\n<p>b\r\nc</p>",
+        "should use the line ending found in the tree"
+    );
+
+    assert_eq!(
+        through_mdast(
+            "a\n\n<!-- b -->\n\nc <!--d--> e",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    strip_comments: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a</p>\n<p>c  e</p>",
+        "should support `strip_comments`"
+    );
+
+    let options = Options {
+        compile: CompileOptions {
+            gfm_footnote_back_label: Some("Arrière".into()),
+            gfm_footnote_clobber_prefix: Some("".into()),
+            gfm_footnote_label: Some("Notes".into()),
+            gfm_footnote_label_attributes: Some("class=\"x\"".into()),
+            gfm_footnote_label_tag_name: Some("h1".into()),
+            gfm_task_list_item_checkable: true,
+            quote: Quote::Single,
+            ..CompileOptions::default()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        through_mdast("* [x] a[^b] [c](d 'e')\n\n[^b]: f", &options)?,
+        to_html_with_options("* [x] a[^b] [c](d 'e')\n\n[^b]: f", &options)?,
+        "should support compile options"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_to_html_nodes() -> Result<(), message::Message> {
+    assert_eq!(
+        mdast_to_html(
+            &Node::Root(Root {
+                children: vec![
+                    Node::Paragraph(Paragraph {
+                        children: vec![
+                            Node::Text(Text {
+                                value: "<a> & \"b\" ".into(),
+                                position: None,
+                                data: None,
+                            }),
+                            Node::InlineCode(InlineCode {
+                                value: "<c>\nd".into(),
+                                position: None,
+                                sequence_size: None,
+                                padding: false,
+                                data: None,
+                                markers: vec![],
+                            }),
+                            Node::Html(Html {
+                                value: "<e>".into(),
+                                position: None,
+                                data: None,
+                            }),
+                        ],
+                        position: None,
+                        data: None,
+                    }),
+                    Node::Html(Html {
+                        value: "<f>".into(),
+                        position: None,
+                        data: None,
+                    }),
+                ],
+                position: None,
+                data: None,
+            }),
+            &Options::default()
+        )?,
+        "<p>&lt;a&gt; &amp; &quot;b&quot; <code>&lt;c&gt; d</code>&lt;e&gt;</p>\n&lt;f&gt;",
+        "should encode text, code, and HTML"
+    );
+
+    assert_eq!(
+        mdast_to_html(
+            &Node::Root(Root {
+                children: vec![
+                    Node::Paragraph(Paragraph {
+                        children: vec![Node::LinkReference(LinkReference {
+                            children: vec![Node::Text(Text {
+                                value: "c".into(),
+                                position: None,
+                                data: None,
+                            })],
+                            reference_kind: ReferenceKind::Full,
+                            identifier: "A".into(),
+                            label: None,
+                            position: None,
+                            data: None,
+                        })],
+                        position: None,
+                        data: None,
+                    }),
+                    Node::Definition(Definition {
+                        url: "b".into(),
+                        title: None,
+                        identifier: "a".into(),
+                        label: Some("A".into()),
+                        position: None,
+                        data: None,
+                    }),
+                ],
+                position: None,
+                data: None,
+            }),
+            &Options::default()
+        )?,
+        "<p><a href=\"b\">c</a></p>",
+        "should resolve references with definitions later in the tree"
+    );
+
+    assert_eq!(
+        mdast_to_html(
+            &Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::LinkReference(LinkReference {
+                        children: vec![Node::Text(Text {
+                            value: "c".into(),
+                            position: None,
+                            data: None,
+                        })],
+                        reference_kind: ReferenceKind::Full,
+                        identifier: "d".into(),
+                        label: Some("D".into()),
+                        position: None,
+                        data: None,
+                    }),
+                    Node::FootnoteReference(FootnoteReference {
+                        identifier: "e".into(),
+                        label: Some("E".into()),
+                        position: None,
+                        data: None,
+                    }),
+                ],
+                position: None,
+                data: None,
+            }),
+            &Options::default()
+        )?,
+        "<p>[c][D][^E]</p>",
+        "should turn unresolved references into text"
+    );
+
+    let tree = Node::Root(Root {
+        children: vec![
+            Node::MdxFlowExpression(MdxFlowExpression {
+                value: "a".into(),
+                position: None,
+                stops: vec![],
+                data: None,
+            }),
+            Node::MdxJsxFlowElement(MdxJsxFlowElement {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: None,
+                        data: None,
+                    })],
+                    position: None,
+                    data: None,
+                })],
+                name: Some("c".into()),
+                attributes: vec![],
+                position: None,
+                data: None,
+            }),
+        ],
+        position: None,
+        data: None,
+    });
+
+    assert_eq!(
+        mdast_to_html(&tree, &Options::default())
+            .err()
+            .unwrap()
+            .to_string(),
+        "Cannot turn `MdxFlowExpression` into HTML (markdown-rs:unknown-node)",
+        "should error on MDX nodes"
+    );
+
+    assert_eq!(
+        mdast_to_html(
+            &tree,
+            &Options {
+                compile: CompileOptions {
+                    skip_unknown_nodes: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>b</p>",
+        "should skip MDX nodes with `skip_unknown_nodes`"
+    );
+
+    Ok(())
+}
+
+/// Renderer that changes nothing.
+struct Default;
+
+impl Renderer for Default {}
+
+/// Renderer that changes images, code, headings, and table cells.
+struct Custom;
+
+impl Renderer for Custom {
+    fn image(&self, node: &Image, context: &mut RenderContext) -> Result<(), message::Message> {
+        let src = context.sanitize_url(&node.url, true);
+        context.push("<figure><img src=\"");
+        context.push(&src);
+        context.push("\" /><figcaption>");
+        context.push_text(&node.alt);
+        context.push("</figcaption></figure>");
+        Ok(())
+    }
+
+    fn code(&self, node: &Code, context: &mut RenderContext) -> Result<(), message::Message> {
+        if node.lang.as_deref() == Some("raw") {
+            context.line_ending_if_needed();
+            context.push(&node.value);
+            Ok(())
+        } else {
+            context.render_default()
+        }
+    }
+
+    fn heading(&self, node: &Heading, context: &mut RenderContext) -> Result<(), message::Message> {
+        context.line_ending_if_needed();
+        context.push(&format!("<p class=\"h{}\">", node.depth));
+        context.render_children()?;
+        context.push("</p>");
+        Ok(())
+    }
+
+    fn table_cell(
+        &self,
+        _node: &TableCell,
+        context: &mut RenderContext,
+    ) -> Result<(), message::Message> {
+        context.line_ending_if_needed();
+        context.push("<td>[");
+        context.render_children()?;
+        context.push("]</td>");
+        Ok(())
+    }
+}
+
+#[test]
+fn mdast_to_html_renderer() -> Result<(), message::Message> {
+    let value = "# a *b*\n\n![c & d](e:f \"g\") ![h](i)\n\n```raw\n<j>\n```\n\n```k\n<l>\n```\n\n| m | n |\n| - | :-: |\n| o |\n\n- [x] p[^q]\n\n[^q]: r";
+
+    assert_eq!(
+        through_renderer(value, &Default)?,
+        to_html_with_options(value, &Options::gfm())?,
+        "should match `to_html` with a renderer that changes nothing"
+    );
+
+    assert_eq!(
+        through_renderer("# a *b*\n\n## c", &Custom)?,
+        "<p class=\"h1\">a <em>b</em></p>\n<p class=\"h2\">c</p>",
+        "should support overriding a method, and compiling children"
+    );
+
+    assert_eq!(
+        through_renderer("![c & d](e:f \"g\") ![h](i)", &Custom)?,
+        "<p><figure><img src=\"\" /><figcaption>c &amp; d</figcaption></figure> <figure><img src=\"i\" /><figcaption>h</figcaption></figure></p>",
+        "should support overriding a method, and sanitizing URLs"
+    );
+
+    assert_eq!(
+        through_renderer("```raw\n<j>\n```\n\n```k\n<l>\n```", &Custom)?,
+        "<j>\n<pre><code class=\"language-k\">&lt;l&gt;\n</code></pre>",
+        "should support falling back to the default"
+    );
+
+    assert_eq!(
+        through_renderer("| m | n |\n| - | :-: |\n| o |", &Custom)?,
+        "<table>\n<thead>\n<tr>\n<td>[m]</td>\n<td>[n]</td>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>[o]</td>\n<td>[]</td>\n</tr>\n</tbody>\n</table>",
+        "should use the renderer for table cells"
+    );
+
+    assert_eq!(
+        through_renderer("> # a\n\n[^b]\n\n[^b]: # c", &Custom)?,
+        "<blockquote>\n<p class=\"h1\">a</p>\n</blockquote>\n<p><sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p class=\"h1\">c <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should use the renderer in nested nodes and footnote definitions"
+    );
+
+    Ok(())
+}
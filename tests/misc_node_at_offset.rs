@@ -0,0 +1,115 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_mdast,
+    unist::Position,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the names of the nodes at `offset`.
+fn names(tree: &Node, offset: usize) -> Option<Vec<String>> {
+    tree.node_at_offset(offset).map(|nodes| {
+        nodes
+            .iter()
+            .map(|node| {
+                format!("{:?}", node)
+                    .split([' ', '('])
+                    .next()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn node_at_offset() -> Result<(), message::Message> {
+    let tree = to_mdast("# a\n\n\n> b **c**", &Default::default())?;
+
+    assert_eq!(
+        names(&tree, 2),
+        Some(vec!["Root".into(), "Heading".into(), "Text".into()]),
+        "should find the nodes at an offset, outermost first"
+    );
+
+    assert_eq!(
+        names(&tree, 0),
+        Some(vec!["Root".into(), "Heading".into()]),
+        "should find a construct at its markers (1)"
+    );
+
+    assert_eq!(
+        names(&tree, 10),
+        Some(vec![
+            "Root".into(),
+            "BlockQuote".into(),
+            "Paragraph".into(),
+            "Strong".into()
+        ]),
+        "should find a construct at its markers (2)"
+    );
+
+    assert_eq!(
+        names(&tree, 12),
+        Some(vec![
+            "Root".into(),
+            "BlockQuote".into(),
+            "Paragraph".into(),
+            "Strong".into(),
+            "Text".into()
+        ]),
+        "should find the innermost node"
+    );
+
+    assert_eq!(
+        names(&tree, 4),
+        Some(vec!["Root".into()]),
+        "should find the root in blank lines between blocks"
+    );
+
+    assert_eq!(
+        names(&tree, 15),
+        None,
+        "should not find nodes at the end of the document"
+    );
+
+    let tree = Node::Root(Root {
+        children: vec![Node::Paragraph(Paragraph {
+            children: vec![
+                Node::Text(Text {
+                    value: "a".into(),
+                    position: None,
+                    data: None,
+                }),
+                Node::Text(Text {
+                    value: "b".into(),
+                    position: Some(Position::new(1, 2, 1, 1, 3, 2)),
+                    data: None,
+                }),
+            ],
+            position: Some(Position::new(1, 1, 0, 1, 3, 2)),
+            data: None,
+        })],
+        position: None,
+        data: None,
+    });
+
+    assert_eq!(
+        names(&tree, 0),
+        Some(vec!["Paragraph".into()]),
+        "should skip nodes without positions"
+    );
+
+    assert_eq!(
+        names(&tree, 1),
+        Some(vec!["Paragraph".into(), "Text".into()]),
+        "should find nodes in nodes without positions"
+    );
+
+    assert_eq!(
+        names(&tree, 2),
+        None,
+        "should not find nodes outside positions"
+    );
+
+    Ok(())
+}
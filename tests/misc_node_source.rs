@@ -0,0 +1,102 @@
+use markdown::{mdast::Node, mdast_ref, message, to_mdast, to_mdast_ref, ParseOptions};
+use pretty_assertions::assert_eq;
+
+/// Get the source of all nodes in a tree, in preorder.
+fn sources<'a>(value: &'a str, options: &ParseOptions) -> Result<Vec<&'a str>, message::Message> {
+    let tree = to_mdast(value, options)?;
+    Ok(tree
+        .iter()
+        .map(|node| node.source(value).unwrap())
+        .collect())
+}
+
+#[test]
+fn node_source() -> Result<(), message::Message> {
+    assert_eq!(
+        sources("> * a *b* `c`\n>   d", &ParseOptions::default())?,
+        vec![
+            "> * a *b* `c`\n>   d",
+            "> * a *b* `c`\n>   d",
+            "* a *b* `c`\n>   d",
+            "* a *b* `c`\n>   d",
+            "a *b* `c`\n>   d",
+            "a ",
+            "*b*",
+            "b",
+            " ",
+            "`c`",
+            "\n>   d"
+        ],
+        "should get the source of nested constructs"
+    );
+
+    assert_eq!(
+        sources("\tcode\n-\ta\tb", &ParseOptions::default())?,
+        vec![
+            "\tcode\n-\ta\tb",
+            "\tcode",
+            "-\ta\tb",
+            "-\ta\tb",
+            "a\tb",
+            "a\tb"
+        ],
+        "should get the source with tabs"
+    );
+
+    assert_eq!(
+        sources("# a\r\n\r\nb  \r\nc\r\n", &ParseOptions::default())?,
+        vec![
+            "# a\r\n\r\nb  \r\nc\r\n",
+            "# a",
+            "a",
+            "b  \r\nc",
+            "b",
+            "  \r\n",
+            "c"
+        ],
+        "should get the source with CRLF"
+    );
+
+    assert_eq!(
+        sources("Ü *日本* 😀", &ParseOptions::default())?,
+        vec!["Ü *日本* 😀", "Ü *日本* 😀", "Ü ", "*日本*", "日本", " 😀"],
+        "should get the source with multibyte characters"
+    );
+
+    assert_eq!(
+        Node::text("a").source("a"),
+        None,
+        "should not get the source of nodes without position"
+    );
+
+    let value = "> * a *b* `c`\n>   d\n\n```js\n\te\n```";
+    let tree = to_mdast_ref(value, &ParseOptions::default())?;
+    let mut stack = vec![&tree];
+    let mut result = vec![];
+
+    while let Some(node) = stack.pop() {
+        result.push(node.source(value).unwrap());
+        if let Some(children) = node.children() {
+            stack.extend(children.iter().rev());
+        }
+    }
+
+    assert_eq!(
+        result,
+        sources(value, &ParseOptions::default())?,
+        "should get the source of borrowed nodes"
+    );
+
+    assert_eq!(
+        mdast_ref::Node::Root(mdast_ref::Root {
+            children: vec![],
+            position: None,
+            data: None
+        })
+        .source("a"),
+        None,
+        "should not get the source of borrowed nodes without position"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use markdown::{
+    message, to_html_with_options, to_mdast, to_mdast_and_html, CompileOptions, Constructs,
+    Options, ParseOptions, Parser,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn parser() -> Result<(), message::Message> {
+    let parser = Parser::default();
+
+    assert_eq!(
+        parser.to_html("# a")?,
+        "<h1>a</h1>",
+        "should support a default parser"
+    );
+
+    let parser = Parser::new(Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    for value in ["~a~ <b>", "| a |\n| - |", "www.example.com"] {
+        assert_eq!(
+            parser.to_html(value)?,
+            to_html_with_options(
+                value,
+                &Options {
+                    parse: ParseOptions::gfm(),
+                    compile: CompileOptions {
+                        allow_dangerous_html: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            )?,
+            "should match `to_html_with_options` when reused ({:?})",
+            value
+        );
+
+        assert_eq!(
+            parser.to_mdast(value)?,
+            to_mdast(value, &ParseOptions::gfm())?,
+            "should match `to_mdast` when reused ({:?})",
+            value
+        );
+
+        assert_eq!(
+            parser.to_mdast_and_html(value)?,
+            (parser.to_mdast(value)?, parser.to_html(value)?),
+            "should match `to_mdast` and `to_html` in `to_mdast_and_html` ({:?})",
+            value
+        );
+    }
+
+    assert!(
+        parser.options().compile.allow_dangerous_html,
+        "should expose the options"
+    );
+
+    let definitions = || {
+        let mut options = Options::default();
+        options
+            .parse
+            .definitions
+            .insert("A  b".into(), ("/c".into(), Some("d\"e".into())));
+        options
+    };
+    let parser = Parser::new(definitions());
+
+    for value in ["[a B]", "[a b][]\n\n[a b]: /f", "[x]"] {
+        assert_eq!(
+            parser.to_html(value)?,
+            to_html_with_options(value, &definitions())?,
+            "should match `to_html_with_options` with definitions when reused ({:?})",
+            value
+        );
+    }
+
+    assert_eq!(
+        parser.to_html("[a B]")?,
+        "<p><a href=\"/c\" title=\"d&quot;e\">a B</a></p>",
+        "should support definitions in options when reused"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mdast_and_html() -> Result<(), message::Message> {
+    let frontmatter = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                frontmatter: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            heading_ids: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    for (value, options) in [
+        ("", &Options::default()),
+        ("# a *b*\r\n\n> c [d]\n\n[d]: e", &Options::default()),
+        (
+            "---\ntitle: a\n---\n\n# b\n\n- [x] c[^d]\n\n[^d]: e\n\n| f |\n| - |",
+            &frontmatter,
+        ),
+    ] {
+        assert_eq!(
+            to_mdast_and_html(value, options)?,
+            (
+                to_mdast(value, &options.parse)?,
+                to_html_with_options(value, options)?
+            ),
+            "should match `to_mdast` and `to_html_with_options` ({:?})",
+            value
+        );
+    }
+
+    let mdx = Options {
+        parse: ParseOptions::mdx(),
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_mdast_and_html("{", &mdx).unwrap_err().to_string(),
+        to_html_with_options("{", &mdx).unwrap_err().to_string(),
+        "should error like `to_html_with_options` for MDX"
+    );
+
+    Ok(())
+}
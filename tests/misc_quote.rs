@@ -0,0 +1,93 @@
+use markdown::{
+    message, to_html, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions,
+    Quote,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn quote() -> Result<(), message::Message> {
+    let single = Options {
+        compile: CompileOptions {
+            quote: Quote::Single,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let single_gfm = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_flow: true,
+                math_text: true,
+                ..Constructs::gfm()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            quote: Quote::Single,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("[a](b \"c'd\\\"e\")"),
+        "<p><a href=\"b\" title=\"c'd&quot;e\">a</a></p>",
+        "should use double quotes by default, and encode double quotes in titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b \"c'd\\\"e\")", &single)?,
+        "<p><a href='b' title='c&#39;d&quot;e'>a</a></p>",
+        "should support single quotes, and encode single quotes in titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]\n\n[a]: b (c'd\"e)", &single)?,
+        "<p><a href='b' title='c&#39;d&quot;e'>a</a></p>\n",
+        "should support single quotes in references"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a'b](c'd \"e\")", &single)?,
+        "<p><img src='c&#39;d' alt='a&#39;b' title='e' /></p>",
+        "should support single quotes in images"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b/c'd>", &single)?,
+        "<p><a href='https://a.b/c&#39;d'>https://a.b/c'd</a></p>",
+        "should support single quotes in autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n3. b", &single)?,
+        "<pre><code class='language-js'>a\n</code></pre>\n<ol start='3'>\n<li>b</li>\n</ol>",
+        "should support single quotes in code and lists"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "| a | b |\n| :- | -: |\n\n* [x] c\n\n$d$",
+            &single_gfm
+        )?,
+        "<table>\n<thead>\n<tr>\n<th align='left'>a</th>\n<th align='right'>b</th>\n</tr>\n</thead>\n</table>\n<ul>\n<li><input type='checkbox' disabled='' checked='' /> c</li>\n</ul>\n<p><code class='language-math math-inline'>d</code></p>",
+        "should support single quotes in GFM and math"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c", &single_gfm)?,
+        "<p>a<sup><a href='#user-content-fn-b' id='user-content-fnref-b' data-footnote-ref='' aria-describedby='footnote-label'>1</a></sup></p>
+<section data-footnotes='' class='footnotes'><h2 id='footnote-label' class='sr-only'>Footnotes</h2>
+<ol>
+<li id='user-content-fn-b'>
+<p>c <a href='#user-content-fnref-b' data-footnote-backref='' aria-label='Back to content' class='data-footnote-backref'>↩</a></p>
+</li>
+</ol>
+</section>
+",
+        "should support single quotes in footnotes"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "std")]
+
+use markdown::{
+    message::ReadError, to_html_from_reader, to_html_with_options, to_mdast, to_mdast_from_reader,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::{error, io};
+
+/// Reader that gives one byte at a time, optionally failing at the end.
+struct Trickle<'a> {
+    bytes: &'a [u8],
+    fail: bool,
+}
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some((first, rest)) = self.bytes.split_first() {
+            buf[0] = *first;
+            self.bytes = rest;
+            Ok(1)
+        } else if self.fail {
+            Err(io::Error::new(io::ErrorKind::Other, "oops"))
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+#[test]
+fn reader() -> Result<(), ReadError> {
+    let value = "# a *b*\n\n[c][]\n\n[c]: d\n\n| e |\n| - |\n| 日本 😀 |\n";
+
+    assert_eq!(
+        to_html_from_reader(value.as_bytes(), &Options::gfm())?,
+        to_html_with_options(value, &Options::gfm())?,
+        "should compile like `to_html_with_options`"
+    );
+
+    assert_eq!(
+        to_html_from_reader(
+            Trickle {
+                bytes: value.as_bytes(),
+                fail: false
+            },
+            &Options::gfm()
+        )?,
+        to_html_with_options(value, &Options::gfm())?,
+        "should support readers that give a few bytes at a time, such as in characters"
+    );
+
+    assert_eq!(
+        to_html_from_reader(&b""[..], &Options::default())?,
+        "",
+        "should support empty readers"
+    );
+
+    assert!(
+        matches!(
+            to_html_from_reader(&b"a \xff b"[..], &Options::default()),
+            Err(ReadError::Io(error)) if error.kind() == io::ErrorKind::InvalidData
+        ),
+        "should crash on invalid UTF-8"
+    );
+
+    let error = to_html_from_reader(
+        Trickle {
+            bytes: b"a",
+            fail: true,
+        },
+        &Options::default(),
+    )
+    .err()
+    .unwrap();
+
+    assert_eq!(
+        error.to_string(),
+        "Cannot read markdown: oops",
+        "should crash on errors from the reader"
+    );
+
+    assert_eq!(
+        error::Error::source(&error).map(ToString::to_string),
+        Some("oops".into()),
+        "should keep the error from the reader"
+    );
+
+    assert!(
+        matches!(
+            to_html_from_reader(
+                "<!a>".as_bytes(),
+                &Options {
+                    parse: ParseOptions::mdx(),
+                    ..Options::default()
+                }
+            ),
+            Err(ReadError::Message(_))
+        ),
+        "should crash on MDX syntax errors"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reader_mdast() -> Result<(), ReadError> {
+    let value = "# a *b*\n\n[c][]\n\n[c]: d\n\n| e |\n| - |\n| 日本 😀 |\n";
+
+    assert_eq!(
+        to_mdast_from_reader(
+            Trickle {
+                bytes: value.as_bytes(),
+                fail: false
+            },
+            &ParseOptions::gfm()
+        )?,
+        to_mdast(value, &ParseOptions::gfm())?,
+        "should parse like `to_mdast`, also when characters are split between reads"
+    );
+
+    assert!(
+        matches!(
+            to_mdast_from_reader(&b"a \xff b"[..], &ParseOptions::default()),
+            Err(ReadError::Io(error)) if error.kind() == io::ErrorKind::InvalidData
+        ),
+        "should crash on invalid UTF-8"
+    );
+
+    assert!(
+        matches!(
+            to_mdast_from_reader(
+                Trickle {
+                    bytes: b"a",
+                    fail: true
+                },
+                &ParseOptions::default()
+            ),
+            Err(ReadError::Io(error)) if error.to_string() == "oops"
+        ),
+        "should crash on errors from the reader"
+    );
+
+    Ok(())
+}
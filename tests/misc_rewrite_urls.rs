@@ -0,0 +1,102 @@
+use markdown::{
+    mdast::{rewrite_urls, UrlContext, UrlKind},
+    mdast_to_markdown, message, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Rewrite all URLs in `value` with `rewrite`, and serialize the result.
+fn rewrite<F>(value: &str, options: &ParseOptions, rewrite: F) -> Result<String, message::Message>
+where
+    F: FnMut(UrlContext, &str) -> Option<String>,
+{
+    let mut tree = to_mdast(value, options)?;
+    rewrite_urls(&mut tree, rewrite);
+    mdast_to_markdown(&tree)
+}
+
+#[test]
+fn rewrite_urls_visit() -> Result<(), message::Message> {
+    let mut tree = to_mdast(
+        "[a](b \"c\") ![d](e) <ff:g> <h@i.j> www.k.l\n\n[m][n] ![o][p] [q][r] ![s][R]\n\n[n]: t\n[p]: u \"v\"\n[r]: w\n[x]: y",
+        &ParseOptions::gfm(),
+    )?;
+    let mut seen = vec![];
+
+    rewrite_urls(&mut tree, |context, url| {
+        seen.push((
+            context.kind,
+            context.reference,
+            context.autolink,
+            context.title.map(String::from),
+            String::from(url),
+        ));
+        None
+    });
+
+    assert_eq!(
+        seen,
+        vec![
+            (UrlKind::Link, false, false, Some("c".into()), "b".into()),
+            (UrlKind::Image, false, false, None, "e".into()),
+            (UrlKind::Link, false, true, None, "ff:g".into()),
+            (UrlKind::Link, false, true, None, "mailto:h@i.j".into()),
+            (UrlKind::Link, false, true, None, "http://www.k.l".into()),
+            (UrlKind::Link, true, false, None, "t".into()),
+            (UrlKind::Image, true, false, Some("v".into()), "u".into()),
+            (UrlKind::Link, true, false, None, "w".into()),
+            (UrlKind::Link, true, false, None, "y".into()),
+        ],
+        "should visit links, images, and definitions, in order, with info"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rewrite_urls_replace() -> Result<(), message::Message> {
+    assert_eq!(
+        rewrite(
+            "[a](b) ![c](d \"e\")\n\n[f]\n\n[f]: g",
+            &ParseOptions::default(),
+            |_, url| Some(format!("/x/{}", url))
+        )?,
+        "[a](/x/b) ![c](/x/d \"e\")\n\n[f]\n\n[f]: /x/g\n",
+        "should replace URLs when `Some` is returned"
+    );
+
+    assert_eq!(
+        rewrite("[a](b) [c](d)", &ParseOptions::default(), |_, url| {
+            if url == "b" {
+                Some("e".into())
+            } else {
+                None
+            }
+        })?,
+        "[a](e) [c](d)\n",
+        "should keep URLs when `None` is returned"
+    );
+
+    assert_eq!(
+        rewrite(
+            "<https://a.b/c> <d@e.f> www.g.h/i [https://j.k](https://j.k \"l\")",
+            &ParseOptions::gfm(),
+            |_, url| Some(
+                url.replace(".b/", ".z/")
+                    .replace("e.f", "e.z")
+                    .replace(".h/", ".z/")
+            )
+        )?,
+        "<https://a.z/c> <d@e.z> [www.g.z/i](http://www.g.z/i) [https://j.k](https://j.k \"l\")\n",
+        "should replace the text of autolinks, but not of links with a title"
+    );
+
+    assert_eq!(
+        rewrite("<a@b.c>", &ParseOptions::default(), |_, _| Some(
+            "https://d.e".into()
+        ))?,
+        "<https://d.e>\n",
+        "should replace the whole text of autolinks if the prefix changes"
+    );
+
+    Ok(())
+}
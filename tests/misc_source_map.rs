@@ -0,0 +1,274 @@
+use markdown::{
+    event::Name, message, to_html_and_source_map, to_html_with_options, CompileOptions, Mapping,
+    Options, Parser,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the name, markdown, and HTML of each mapping.
+fn pairs<'a>(
+    value: &'a str,
+    html: &'a str,
+    mappings: &[Mapping],
+) -> Vec<(Option<Name>, &'a str, &'a str)> {
+    mappings
+        .iter()
+        .map(|mapping| {
+            (
+                mapping.name.clone(),
+                &value[mapping.input.clone()],
+                &html[mapping.output.clone()],
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn source_map() -> Result<(), message::Message> {
+    let value = "# a\n\nb\nc\n\n***\n\n> d\n> e";
+    let (html, mappings) = to_html_and_source_map(value, &Options::default())?;
+
+    assert_eq!(
+        html,
+        to_html_with_options(value, &Options::default())?,
+        "should compile the same HTML as `to_html_with_options`"
+    );
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![
+            (Some(Name::HeadingAtx), "# a", "<h1>a</h1>"),
+            (Some(Name::Paragraph), "b\nc", "<p>b\nc</p>"),
+            (Some(Name::ThematicBreak), "***", "<hr />"),
+            (
+                Some(Name::BlockQuote),
+                "> d\n> e",
+                "<blockquote>\n<p>d\ne</p>\n</blockquote>"
+            ),
+            (Some(Name::Paragraph), "d\n> e", "<p>d\ne</p>"),
+        ],
+        "should map blocks"
+    );
+
+    let value = "* a\n* b\n\n  c\n\n1. d";
+    let (html, mappings) = to_html_and_source_map(value, &Options::default())?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![
+            (
+                Some(Name::ListUnordered),
+                "* a\n* b\n\n  c\n",
+                "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>"
+            ),
+            (Some(Name::ListItem), "* a", "<li>\n<p>a</p>\n</li>"),
+            (Some(Name::Paragraph), "a", "<p>a</p>"),
+            (
+                Some(Name::ListItem),
+                "* b\n\n  c\n",
+                "<li>\n<p>b</p>\n<p>c</p>\n</li>"
+            ),
+            (Some(Name::Paragraph), "b", "<p>b</p>"),
+            (Some(Name::Paragraph), "c", "<p>c</p>"),
+            (Some(Name::ListOrdered), "1. d", "<ol>\n<li>d</li>\n</ol>"),
+            (Some(Name::ListItem), "1. d", "<li>d</li>"),
+            (Some(Name::Paragraph), "d", "d"),
+        ],
+        "should map lists, list items, and paragraphs in tight lists (without `<p>`)"
+    );
+
+    let value = "```js\na\n```\n\n    b\n\nc\n-\n\n> ```\n> d";
+    let (html, mappings) = to_html_and_source_map(value, &Options::default())?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![
+            (
+                Some(Name::CodeFenced),
+                "```js\na\n```",
+                "<pre><code class=\"language-js\">a\n</code></pre>"
+            ),
+            (
+                Some(Name::CodeIndented),
+                "    b",
+                "<pre><code>b\n</code></pre>"
+            ),
+            (Some(Name::HeadingSetext), "c\n-", "<h2>c</h2>"),
+            (
+                Some(Name::BlockQuote),
+                "> ```\n> d",
+                "<blockquote>\n<pre><code>d\n</code></pre>\n</blockquote>"
+            ),
+            (
+                Some(Name::CodeFenced),
+                "```\n> d",
+                "<pre><code>d\n</code></pre>"
+            ),
+        ],
+        "should map code and headings (setext)"
+    );
+
+    let value = "| a |\n| - |\n| b |";
+    let (html, mappings) = to_html_and_source_map(value, &Options::gfm())?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![(
+            Some(Name::GfmTable),
+            value,
+            "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>"
+        )],
+        "should map tables"
+    );
+
+    let value = "a\n\n[b]: c\n\n<div>";
+    let (html, mappings) = to_html_and_source_map(value, &Options::default())?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![(Some(Name::Paragraph), "a", "<p>a</p>")],
+        "should not map definitions or HTML"
+    );
+
+    let (html, mappings) = to_html_and_source_map("", &Options::default())?;
+
+    assert_eq!(
+        (html.as_str(), mappings),
+        ("", vec![]),
+        "should support an empty document"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn source_map_footnotes() -> Result<(), message::Message> {
+    let value = "a[^b] c[^d]\n\n[^b]: e\n\n    f\n\n[^d]: g\n[^x]: y";
+    let (html, mappings) = to_html_and_source_map(value, &Options::gfm())?;
+    let backreference = |id: &str| {
+        format!(
+            "<a href=\"#user-content-fnref-{}\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a>",
+            id
+        )
+    };
+    let item_b = format!(
+        "<li id=\"user-content-fn-b\">\n<p>e</p>\n<p>f {}</p>\n</li>",
+        backreference("b")
+    );
+    let item_d = format!(
+        "<li id=\"user-content-fn-d\">\n<p>g {}</p>\n</li>",
+        backreference("d")
+    );
+    let section = format!(
+        "<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n{}\n{}\n</ol>\n</section>",
+        item_b, item_d
+    );
+    let paragraph_f = format!("<p>f {}</p>", backreference("b"));
+    let paragraph_g = format!("<p>g {}</p>", backreference("d"));
+
+    assert_eq!(
+        pairs(value, &html, &mappings[1..]),
+        vec![
+            (None, "", section.as_str()),
+            (
+                Some(Name::GfmFootnoteDefinition),
+                "[^b]: e\n\n    f\n",
+                item_b.as_str()
+            ),
+            (Some(Name::Paragraph), "e", "<p>e</p>"),
+            (Some(Name::Paragraph), "f", paragraph_f.as_str()),
+            (
+                Some(Name::GfmFootnoteDefinition),
+                "[^d]: g",
+                item_d.as_str()
+            ),
+            (Some(Name::Paragraph), "g", paragraph_g.as_str()),
+        ],
+        "should map footnote definitions to their items, and the generated section to nothing"
+    );
+
+    assert_eq!(
+        mappings[1].input,
+        value.len()..value.len(),
+        "should use an empty range at the end of the markdown for generated content"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn source_map_options() -> Result<(), message::Message> {
+    let value = "é\n\nü ü";
+    let options = Options {
+        compile: CompileOptions {
+            encode_non_ascii: true,
+            trailing_newline: Some(true),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+    let (html, mappings) = to_html_and_source_map(value, &options)?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![
+            (Some(Name::Paragraph), "é", "<p>&#xE9;</p>"),
+            (Some(Name::Paragraph), "ü ü", "<p>&#xFC; &#xFC;</p>"),
+        ],
+        "should map to encoded non-ASCII characters"
+    );
+
+    let value = "```\na";
+    let options = Options {
+        compile: CompileOptions {
+            trailing_newline: Some(false),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+    let (html, mappings) = to_html_and_source_map(value, &options)?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![(
+            Some(Name::CodeFenced),
+            "```\na",
+            "<pre><code>a\n</code></pre>"
+        )],
+        "should support `trailing_newline: Some(false)`"
+    );
+
+    let value = "# a\n\n- b";
+    let (html, mappings) = Parser::new(Options {
+        compile: CompileOptions {
+            source_position: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    })
+    .to_html_and_source_map(value)?;
+
+    assert_eq!(
+        pairs(value, &html, &mappings),
+        vec![
+            (
+                Some(Name::HeadingAtx),
+                "# a",
+                "<h1 data-line=\"1\" data-end-line=\"1\">a</h1>"
+            ),
+            (
+                Some(Name::ListUnordered),
+                "- b",
+                "<ul data-line=\"3\" data-end-line=\"3\">\n<li data-line=\"3\" data-end-line=\"3\">b</li>\n</ul>"
+            ),
+            (
+                Some(Name::ListItem),
+                "- b",
+                "<li data-line=\"3\" data-end-line=\"3\">b</li>"
+            ),
+            (Some(Name::Paragraph), "b", "b"),
+        ],
+        "should support `Parser::to_html_and_source_map`, and `source_position`"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn source_position() -> Result<(), message::Message> {
+    let mut lines = Options {
+        compile: CompileOptions {
+            source_position: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+    lines.parse.constructs.math_flow = true;
+
+    assert_eq!(
+        to_html("# a\n\nb"),
+        "<h1>a</h1>\n<p>b</p>",
+        "should not add lines by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n===\n\nc *d*\ne", &lines)?,
+        "<h1 data-line=\"1\" data-end-line=\"1\">a</h1>\n<h1 data-line=\"3\" data-end-line=\"4\">b</h1>\n<p data-line=\"6\" data-end-line=\"7\">c <em>d</em>\ne</p>",
+        "should add lines to headings and paragraphs, but not to phrasing"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> b\n\n***", &lines)?,
+        "<blockquote data-line=\"1\" data-end-line=\"2\">\n<p data-line=\"1\" data-end-line=\"2\">a\nb</p>\n</blockquote>\n<hr data-line=\"4\" data-end-line=\"4\" />",
+        "should add lines to block quotes and thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n    b\n\n$$\nc\n$$", &lines)?,
+        "<pre data-line=\"1\" data-end-line=\"3\"><code class=\"language-js\">a\n</code></pre>\n<pre data-line=\"5\" data-end-line=\"5\"><code>b\n</code></pre>\n<pre data-line=\"7\" data-end-line=\"9\"><code class=\"language-math math-display\">c\n</code></pre>",
+        "should add lines to code and math"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b\n\n3. c\n   d", &lines)?,
+        "<ul data-line=\"1\" data-end-line=\"3\">\n<li data-line=\"1\" data-end-line=\"1\">a</li>\n<li data-line=\"2\" data-end-line=\"3\">b</li>\n</ul>\n<ol data-line=\"4\" data-end-line=\"5\" start=\"3\">\n<li data-line=\"4\" data-end-line=\"5\">c\nd</li>\n</ol>",
+        "should add lines to lists and list items, but not to tight paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |", &lines)?,
+        "<table data-line=\"1\" data-end-line=\"3\">\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n</tbody>\n</table>",
+        "should add lines to tables"
+    );
+
+    for value in [
+        "# a\n\nb\n===\n\nc *d*\ne",
+        "> a\n> b\n\n***",
+        "```js\na\n```\n\n    b\n\n$$\nc\n$$",
+        "- a\n- b\n\n3. c\n   d",
+        "| a |\n| - |\n| b |",
+        "> - a\n>\n>   b\n\nc[^d]\n\n[^d]: e",
+    ] {
+        assert_eq!(
+            mdast_to_html(&to_mdast(value, &lines.parse)?, &lines)?,
+            to_html_with_options(value, &lines)?,
+            "should match `to_html` in `mdast_to_html` for {:?}",
+            value
+        );
+    }
+
+    assert_eq!(
+        mdast_to_html(
+            &Node::Root(Root {
+                children: vec![Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: None,
+                        data: None,
+                    })],
+                    position: None,
+                    data: None,
+                })],
+                position: None,
+                data: None,
+            }),
+            &lines
+        )?,
+        "<p>a</p>",
+        "should not add lines to nodes without positions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use markdown::{
+    mdast::{
+        stats::{stats, Stats},
+        NodeKind,
+    },
+    message, to_mdast, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::time::Duration;
+
+/// Parse `value` with GFM and get its statistics.
+fn parse(value: &str) -> Result<Stats, message::Message> {
+    Ok(stats(&to_mdast(value, &ParseOptions::gfm())?))
+}
+
+/// Count the words in `value`.
+fn words(value: &str) -> Result<usize, message::Message> {
+    Ok(parse(value)?.words)
+}
+
+#[test]
+fn stats_words() -> Result<(), message::Message> {
+    assert_eq!(words("")?, 0, "should support empty documents");
+
+    assert_eq!(words("a b  c\td")?, 4, "should split on whitespace");
+
+    assert_eq!(
+        words("a, b. (c) d!? — e")?,
+        5,
+        "should not count punctuation as words"
+    );
+
+    assert_eq!(
+        words("can't can’t e.g. 3.14 1,000 a_b")?,
+        6,
+        "should join letters and numbers around apostrophes, periods, commas, and connectors"
+    );
+
+    assert_eq!(
+        words("a. b 1. c: 2,")?,
+        5,
+        "should not join trailing punctuation"
+    );
+
+    assert_eq!(
+        words("a*b*c **d** `e f`")?,
+        4,
+        "should join words across inline nodes"
+    );
+
+    assert_eq!(
+        words("a\n\nb\n\n# c\n\n- d\n\n| e | f |\n| - | - |")?,
+        6,
+        "should count words in each block on its own"
+    );
+
+    assert_eq!(words("a  \nb\\\nc")?, 3, "should split on hard breaks");
+
+    assert_eq!(
+        words("a ![b c](d) <e>\n\n[f]: g\n\n[^h]\n\n[^h]: i")?,
+        4,
+        "should count alt text and footnote definitions, but not HTML or definitions"
+    );
+
+    assert_eq!(
+        words("```js\nconst a = 1\n```")?,
+        3,
+        "should count words in code"
+    );
+
+    assert_eq!(
+        words("Grüße ça naïve Привет мир")?,
+        5,
+        "should support non-ASCII letters"
+    );
+
+    assert_eq!(
+        words("नमस्ते दुनिया")?,
+        2,
+        "should keep combining marks in words"
+    );
+
+    assert_eq!(
+        words("日本語 ひらがな カタカナ")?,
+        8,
+        "should count each ideograph and hiragana as a word, and katakana together"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stats_counts() -> Result<(), message::Message> {
+    let result = parse(
+        "# a\n\n## b *c*\n\n[d](e) <f@g.h> www.i.j [k][] ![l](m) ![n][k]\n\n[k]: o\n\n```rs\np\n```\n\n```rs\nq\n```\n\n    r\n\n~~~\n~~~",
+    )?;
+
+    assert_eq!(
+        result.nodes,
+        vec![
+            (NodeKind::Root, 1),
+            (NodeKind::Heading, 2),
+            (NodeKind::Text, 12),
+            (NodeKind::Emphasis, 1),
+            (NodeKind::Paragraph, 1),
+            (NodeKind::Link, 3),
+            (NodeKind::LinkReference, 1),
+            (NodeKind::Image, 1),
+            (NodeKind::ImageReference, 1),
+            (NodeKind::Definition, 1),
+            (NodeKind::Code, 4),
+        ],
+        "should count nodes of each kind, in order"
+    );
+
+    assert_eq!(result.count(NodeKind::Heading), 2, "should get a count");
+    assert_eq!(
+        result.count(NodeKind::Table),
+        0,
+        "should get `0` for kinds that are not there"
+    );
+
+    assert_eq!(
+        result
+            .code_languages
+            .iter()
+            .map(|(lang, count)| (lang.as_deref(), *count))
+            .collect::<Vec<_>>(),
+        vec![(None, 2), (Some("rs"), 2)],
+        "should count code by language"
+    );
+
+    assert_eq!(
+        (result.links, result.images),
+        (4, 2),
+        "should count links and images, including references and autolinks"
+    );
+
+    assert_eq!(
+        result.characters,
+        "a\n\nb c\n\nd f@g.h www.i.j k l n\n\np\n\nq\n\nr"
+            .chars()
+            .count(),
+        "should count characters of visible text, with blank lines between blocks"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stats_outline_depth() -> Result<(), message::Message> {
+    assert_eq!(parse("a")?.outline_depth, 0, "should be `0` w/o headings");
+    assert_eq!(
+        parse("## a\n\n## b")?.outline_depth,
+        1,
+        "should be `1` for siblings"
+    );
+    assert_eq!(
+        parse("# a\n\n### b\n\n## c\n\n### d")?.outline_depth,
+        3,
+        "should nest deeper headings, without filling in skipped levels"
+    );
+    assert_eq!(
+        parse("### a\n\n# b\n\n## c")?.outline_depth,
+        2,
+        "should close sections at shallower headings"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn stats_reading_time() -> Result<(), message::Message> {
+    let result = parse(&"a ".repeat(250))?;
+
+    assert_eq!(
+        result.reading_time(200),
+        Duration::from_secs(75),
+        "should estimate reading time"
+    );
+    assert_eq!(
+        result.reading_time(250),
+        Duration::from_secs(60),
+        "should support other speeds"
+    );
+    assert_eq!(
+        parse("")?.reading_time(200),
+        Duration::ZERO,
+        "should be zero for empty documents"
+    );
+    assert_eq!(
+        result.reading_time(0),
+        Duration::ZERO,
+        "should be zero for `0` words per minute"
+    );
+    assert_eq!(
+        Stats {
+            words: 100_000,
+            ..Stats::default()
+        }
+        .reading_time(1),
+        Duration::from_secs(6_000_000),
+        "should not overflow for long reading times"
+    );
+
+    Ok(())
+}
@@ -284,9 +284,19 @@ fn tabs_virtual_spaces() {
 
     assert_eq!(
         to_html("-\ta\n\n\tb"),
-        "<ul>\n<li>\n<p>a</p>\n<p>\tb</p>\n</li>\n</ul>",
-        // To do: CM.js does not output the tab before `b`. See if that makes sense?
-        // "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
         "should support a part of a tab as a container, and the rest of a tab as flow"
     );
+
+    assert_eq!(
+        to_html(">\ta\n>\tb"),
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>",
+        "should strip the rest of a tab after a block quote marker from a paragraph"
+    );
+
+    assert_eq!(
+        to_html("1. a\n\n\tb\n\n- c\n\n \td"),
+        "<ol>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>\n<ul>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ul>",
+        "should strip the rest of a tab in a list item from a paragraph"
+    );
 }
@@ -0,0 +1,366 @@
+use markdown::{
+    event::{source, Event, Kind, Name, VOID_EVENTS},
+    events_to_mdast, message, to_events, to_mdast, Constructs, Options, ParseOptions, Parser,
+};
+use pretty_assertions::assert_eq;
+use std::fs;
+
+/// Get the kinds and names of `events`, without whitespace.
+fn names(events: &[Event]) -> Vec<(Kind, Name)> {
+    events
+        .iter()
+        .filter(|event| {
+            !matches!(
+                event.name,
+                Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+            )
+        })
+        .map(|event| (event.kind.clone(), event.name.clone()))
+        .collect()
+}
+
+/// Check that `events` of `value` are well formed, and panic if not.
+fn check(value: &str, events: &[Event]) {
+    let mut stack: Vec<&Event> = vec![];
+    let mut previous = &events[0].point;
+
+    for (position, event) in events.iter().enumerate() {
+        let point = &event.point;
+        assert!(
+            (point.index, point.vs) >= (previous.index, previous.vs)
+                && (point.line, point.column) >= (previous.line, previous.column),
+            "expected points to not go back, at {} in {:?}",
+            position,
+            value
+        );
+        assert!(
+            value.is_char_boundary(point.index),
+            "expected points on character boundaries, at {} in {:?}",
+            position,
+            value
+        );
+        assert_ne!(
+            event.name,
+            Name::AttentionSequence,
+            "expected no attention sequences, in {:?}",
+            value
+        );
+        previous = point;
+
+        if event.kind == Kind::Enter {
+            if let Some(parent) = stack.last() {
+                assert!(
+                    !VOID_EVENTS.contains(&parent.name),
+                    "expected nothing in void `{:?}`, at {} in {:?}",
+                    parent.name,
+                    position,
+                    value
+                );
+            }
+
+            stack.push(event);
+        } else {
+            let enter = stack.pop().unwrap_or_else(|| {
+                panic!(
+                    "expected an enter before exit, at {} in {:?}",
+                    position, value
+                )
+            });
+            assert_eq!(
+                enter.name, event.name,
+                "expected exits to match enters, at {} in {:?}",
+                position, value
+            );
+        }
+    }
+
+    assert!(
+        stack.is_empty(),
+        "expected everything closed in {:?}",
+        value
+    );
+
+    assert_eq!(
+        events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.kind == Kind::Enter && VOID_EVENTS.contains(&event.name))
+            .map(|(index, _)| source(value, events, index))
+            .collect::<String>(),
+        value,
+        "expected void spans to make up the whole value"
+    );
+}
+
+#[test]
+fn to_events_basic() -> Result<(), message::Message> {
+    assert_eq!(
+        to_events("", &ParseOptions::default())?.len(),
+        0,
+        "should support empty documents"
+    );
+
+    assert_eq!(
+        names(&to_events("# a *b*\n\n> c", &ParseOptions::default())?),
+        vec![
+            (Kind::Enter, Name::HeadingAtx),
+            (Kind::Enter, Name::HeadingAtxSequence),
+            (Kind::Exit, Name::HeadingAtxSequence),
+            (Kind::Enter, Name::HeadingAtxText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Enter, Name::Emphasis),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Enter, Name::EmphasisText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::EmphasisText),
+            (Kind::Enter, Name::EmphasisSequence),
+            (Kind::Exit, Name::EmphasisSequence),
+            (Kind::Exit, Name::Emphasis),
+            (Kind::Exit, Name::HeadingAtxText),
+            (Kind::Exit, Name::HeadingAtx),
+            (Kind::Enter, Name::BlockQuote),
+            (Kind::Enter, Name::BlockQuotePrefix),
+            (Kind::Enter, Name::BlockQuoteMarker),
+            (Kind::Exit, Name::BlockQuoteMarker),
+            (Kind::Exit, Name::BlockQuotePrefix),
+            (Kind::Enter, Name::Paragraph),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::Paragraph),
+            (Kind::Exit, Name::BlockQuote),
+        ],
+        "should turn markdown into events"
+    );
+
+    let value = "a\tü *b*\r\nc";
+    let events = to_events(value, &ParseOptions::default())?;
+
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| event.kind == Kind::Enter && event.name == Name::Data)
+            .map(|event| (event.point.line, event.point.column, event.point.index))
+            .collect::<Vec<_>>(),
+        vec![(1, 1, 0), (1, 9, 6), (2, 1, 10)],
+        "should point to lines, columns (with tab stops, in bytes), and byte offsets"
+    );
+
+    assert_eq!(
+        events
+            .iter()
+            .find(|event| event.name == Name::LineEnding)
+            .map(|event| &value[event.point.index..]),
+        Some("\r\nc"),
+        "should point into the value"
+    );
+
+    assert_eq!(
+        names(&to_events("~a~", &ParseOptions::gfm())?),
+        vec![
+            (Kind::Enter, Name::Paragraph),
+            (Kind::Enter, Name::GfmStrikethrough),
+            (Kind::Enter, Name::GfmStrikethroughSequence),
+            (Kind::Exit, Name::GfmStrikethroughSequence),
+            (Kind::Enter, Name::GfmStrikethroughText),
+            (Kind::Enter, Name::Data),
+            (Kind::Exit, Name::Data),
+            (Kind::Exit, Name::GfmStrikethroughText),
+            (Kind::Enter, Name::GfmStrikethroughSequence),
+            (Kind::Exit, Name::GfmStrikethroughSequence),
+            (Kind::Exit, Name::GfmStrikethrough),
+            (Kind::Exit, Name::Paragraph),
+        ],
+        "should support options"
+    );
+
+    assert_eq!(
+        names(&Parser::new(Options::gfm()).to_events("~a~")?),
+        names(&to_events("~a~", &ParseOptions::gfm())?),
+        "should support `Parser::to_events`"
+    );
+
+    assert_eq!(
+        to_events("<!a>", &ParseOptions::mdx())
+            .err()
+            .unwrap()
+            .to_string(),
+        "1:2: Unexpected character `!` (U+0021) before name, expected a character that can start a name, such as a letter, `$`, or `_` (note: to create a comment in MDX, use `{/* text */}`) (markdown-rs:unexpected-character)",
+        "should error for MDX syntax errors"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_events_invariants() -> Result<(), message::Message> {
+    let mut options = ParseOptions {
+        constructs: Constructs {
+            frontmatter: true,
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    options.constructs.html_flow = true;
+
+    let values = [
+        fs::read_to_string("readme.md").unwrap(),
+        "---\na: b\n---\n\n$$\nc\n$$\n\n$d$ e[^f]\n\n[^f]: g\n\n| h | i |\n| - | - |\n| j |"
+            .into(),
+        "* [x] a\n\n  b\n* c\n\n1. d\n   ```js\n   e\n   ```\n\n\tf\n\n> g\n> h\n>\n> - i\n\n***"
+            .into(),
+        "a\\*b &amp; &#35; <c> <d@e.f> www.g.h ***i** j* _k_l_ [m](n \"o\") ![p][q]\n\n[q]: r\n\ns  \nt\\\nu"
+            .into(),
+        "<div>\na\n</div>\n\n<!--b-->\n\nc\n===\n\nd\n---\n\n# e #\n\n\u{feff}\u{1f600} \u{fc}\t*\u{65e5}*"
+            .into(),
+        "**a*b***c***\n\n*[a*](b) `` ` `` ~~c~~ ~d~~ \n\n\n".into(),
+        ">\ta\n>\t\tb\n\n1. c\n\n\td\n\n- e\n\n \tf\n\n-\t\tg".into(),
+        "[^](a) b[^](c)[^d] [^e][f]\n\n[f]: g".into(),
+    ];
+
+    for value in &values {
+        check(value, &to_events(value, &options)?);
+        check(value, &to_events(value, &ParseOptions::default())?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn to_events_source() -> Result<(), message::Message> {
+    let spans = |value: &str| -> Result<Vec<(Name, usize, usize, String)>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        Ok(events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.kind == Kind::Enter)
+            .map(|(index, event)| {
+                (
+                    event.name.clone(),
+                    event.point.line,
+                    event.point.column,
+                    source(value, &events, index).into(),
+                )
+            })
+            .collect())
+    };
+
+    assert_eq!(
+        spans(">\ta\r\n>\t\tb")?,
+        vec![
+            (Name::BlockQuote, 1, 1, ">\ta\r\n>\t\tb".into()),
+            (Name::BlockQuotePrefix, 1, 1, ">\t".into()),
+            (Name::BlockQuoteMarker, 1, 1, ">".into()),
+            (Name::SpaceOrTab, 1, 2, "\t".into()),
+            (Name::Paragraph, 1, 3, "a\r\n>\t\tb".into()),
+            (Name::SpaceOrTab, 1, 3, "".into()),
+            (Name::Data, 1, 5, "a".into()),
+            (Name::LineEnding, 1, 6, "\r\n".into()),
+            (Name::BlockQuotePrefix, 2, 1, ">\t".into()),
+            (Name::BlockQuoteMarker, 2, 1, ">".into()),
+            (Name::SpaceOrTab, 2, 2, "\t".into()),
+            (Name::SpaceOrTab, 2, 3, "\t".into()),
+            (Name::Data, 2, 9, "b".into()),
+        ],
+        "should get the source of spans with tabs and CRLF"
+    );
+
+    assert_eq!(
+        spans("-\t\tfn 😀() {}")?
+            .into_iter()
+            .filter(|(name, ..)| matches!(
+                name,
+                Name::ListItemPrefix | Name::SpaceOrTab | Name::CodeIndented | Name::CodeFlowChunk
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Name::ListItemPrefix, 1, 1, "-\t".into()),
+            (Name::SpaceOrTab, 1, 2, "\t".into()),
+            (Name::CodeIndented, 1, 3, "\tfn 😀() {}".into()),
+            (Name::SpaceOrTab, 1, 3, "\t".into()),
+            (Name::CodeFlowChunk, 1, 7, "fn 😀() {}".into()),
+        ],
+        "should get the source of code in a tab that is split"
+    );
+
+    assert_eq!(
+        spans("Ü *日本*")?,
+        vec![
+            (Name::Paragraph, 1, 1, "Ü *日本*".into()),
+            (Name::Data, 1, 1, "Ü ".into()),
+            (Name::Emphasis, 1, 4, "*日本*".into()),
+            (Name::EmphasisSequence, 1, 4, "*".into()),
+            (Name::EmphasisText, 1, 5, "日本".into()),
+            (Name::Data, 1, 5, "日本".into()),
+            (Name::EmphasisSequence, 1, 11, "*".into()),
+        ],
+        "should get the source of spans with multibyte characters"
+    );
+
+    let events = to_events("- a\n  - b", &ParseOptions::default())?;
+
+    assert_eq!(
+        (
+            source("- a\n  - b", &events, 1),
+            source("- a\n  - b", &events, events.len() - 2)
+        ),
+        ("- a\n  - b", "- a\n  - b"),
+        "should pair enters and exits of nested spans with the same name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn events_to_mdast_tree() -> Result<(), message::Message> {
+    let gfm = ParseOptions {
+        mdast_marker_positions: true,
+        ..ParseOptions::gfm()
+    };
+
+    for value in [
+        "# a\n\n*b* [c]\n\n[c]: d",
+        "> a\n> - b\n>\n>   ```js\n>   c\n>   ```",
+        "a[^b] ~c~ www.d.e\n\n[^b]: f\n\n| g |\n| - |\n| h |",
+    ] {
+        for options in [&ParseOptions::default(), &gfm] {
+            let events = to_events(value, options)?;
+
+            assert_eq!(
+                events_to_mdast(&events, value, options)?,
+                to_mdast(value, options)?,
+                "should make the same tree as `to_mdast` for `{:?}`",
+                value
+            );
+        }
+    }
+
+    let value = "*a* b";
+    let parser = Parser::new(Options::gfm());
+    let events = parser.to_events(value)?;
+
+    assert_eq!(
+        parser.events_to_mdast(&events, value)?,
+        parser.to_mdast(value)?,
+        "should support `Parser::events_to_mdast`"
+    );
+
+    let value = "<a>*b</a>*";
+    let events = to_events(value, &ParseOptions::mdx())?;
+
+    assert_eq!(
+        events_to_mdast(&events, value, &ParseOptions::mdx())
+            .unwrap_err()
+            .to_string(),
+        to_mdast(value, &ParseOptions::mdx())
+            .unwrap_err()
+            .to_string(),
+        "should error for JSX tags that do not match"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,826 @@
+use markdown::{
+    mdast::{
+        build::{list, list_item, paragraph, root, text},
+        Emphasis, Heading, InlineCode, ListItem, Node, Paragraph, Root, Text,
+    },
+    mdast_to_markdown, mdast_to_markdown_with_options, message, to_mdast, Constructs, ParseOptions,
+    SerializeOptions,
+};
+use pretty_assertions::assert_eq;
+use test_utils::mdast::strip_positions;
+
+mod test_utils {
+    pub mod mdast;
+}
+
+/// Remove the markers of attention and list items in the source from a tree,
+/// so that the markers from the options are used.
+fn unstyle(node: &mut Node) {
+    match node {
+        Node::Emphasis(node) => node.marker = None,
+        Node::Strong(node) => node.marker = None,
+        Node::ListItem(node) => unstyle_list_item(node),
+        _ => {}
+    }
+
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            unstyle(child);
+        }
+    }
+}
+
+/// Parse `value` into a tree without the style of the source.
+fn unstyled(value: &str) -> Result<Node, message::Message> {
+    let mut tree = to_mdast(value, &ParseOptions::default())?;
+    unstyle(&mut tree);
+    Ok(tree)
+}
+
+/// Remove the marker, number, and indent in the source from a list item.
+fn unstyle_list_item(node: &mut ListItem) {
+    node.marker = None;
+    node.number = None;
+    node.indent = None;
+}
+
+/// Remove the style of list items in the source from a tree, as lists can
+/// need other markers when serialized (such as next to another list).
+fn unstyle_lists(node: &mut Node) {
+    if let Node::ListItem(node) = node {
+        unstyle_list_item(node);
+    }
+
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            unstyle_lists(child);
+        }
+    }
+}
+
+/// Turn line endings in text into spaces, as wrapping adds them.
+fn unwrap(node: &mut Node) {
+    if let Node::Text(text) = node {
+        text.value = text.value.replace('\n', " ");
+    }
+
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            unwrap(child);
+        }
+    }
+}
+
+/// Check that `value` survives a round trip: parse, serialize, parse.
+fn round_trip(value: &str, options: &ParseOptions) -> Result<(), message::Message> {
+    round_trip_with_options(value, options, &SerializeOptions::default())
+}
+
+/// Check that `value` survives a round trip with serialize options.
+fn round_trip_with_options(
+    value: &str,
+    options: &ParseOptions,
+    serialize_options: &SerializeOptions,
+) -> Result<(), message::Message> {
+    let mut tree = to_mdast(value, options)?;
+    let result = mdast_to_markdown_with_options(&tree, serialize_options)?;
+    let mut again = to_mdast(&result, options)?;
+    strip_positions(&mut tree);
+    strip_positions(&mut again);
+    unstyle_lists(&mut tree);
+    unstyle_lists(&mut again);
+
+    if serialize_options.line_width.is_some() {
+        unwrap(&mut tree);
+        unwrap(&mut again);
+    }
+
+    assert_eq!(
+        again, tree,
+        "should round trip `{:?}` (as `{:?}`, with {:?})",
+        value, result, serialize_options
+    );
+    Ok(())
+}
+
+#[test]
+fn to_markdown() -> Result<(), message::Message> {
+    let gfm = ParseOptions::gfm();
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast("", &ParseOptions::default())?)?,
+        "",
+        "should support an empty document"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a *b* [c] `d` \\e <f> &amp; #g".into(),
+                    position: None,
+                    data: None,
+                })],
+                position: None,
+                data: None,
+            })],
+            position: None,
+            data: None,
+        }))?,
+        "a \\*b\\* \\[c\\] \\`d\\` \\\\e \\<f> \\&amp; #g\n",
+        "should escape text"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Paragraph(Paragraph {
+            children: vec![Node::Text(Text {
+                value: "# a\n- b\n1. c\n  d  \ne".into(),
+                position: None,
+                data: None,
+            })],
+            position: None,
+            data: None,
+        }))?,
+        "\\# a\n\\- b\n1\\. c\n&#x20; d&#x20;&#x20;\ne\n",
+        "should escape at the start of lines, and encode whitespace around them"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "Title\n=====\n\nSub\ntitle\n---\n\n### C#\n\n#### \\# ####",
+            &ParseOptions::default()
+        )?)?,
+        "Title\n=====\n\nSub\ntitle\n-----\n\n### C#\n\n#### \\#\n",
+        "should serialize headings"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast("# a\n\nb\n-\n\n## c", &ParseOptions::default())?)?,
+        "# a\n\nb\n-\n\n## c\n",
+        "should keep the style of headings"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Root(Root {
+            children: vec![
+                Node::Heading(Heading {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: None,
+                        data: None,
+                    })],
+                    depth: 3,
+                    setext: true,
+                    position: None,
+                    data: None,
+                    markers: vec![],
+                }),
+                Node::Heading(Heading {
+                    children: vec![],
+                    depth: 1,
+                    setext: true,
+                    position: None,
+                    data: None,
+                    markers: vec![],
+                }),
+            ],
+            position: None,
+            data: None,
+        }))?,
+        "### a\n\n#\n",
+        "should serialize setext headings as ATX if they cannot be setext"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "a\\\nb   \nc\n\n> d  \n> e",
+            &ParseOptions::default()
+        )?)?,
+        "a\\\nb  \nc\n\n> d  \n> e\n",
+        "should keep the style of hard breaks"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "_a_ *b* __c__ **d** *_e_*",
+            &ParseOptions::default()
+        )?)?,
+        "_a_ *b* __c__ **d** *_e_*\n",
+        "should keep the markers of attention"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a".into(),
+                        position: None,
+                        data: None,
+                    }),
+                    Node::Emphasis(Emphasis {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: None,
+                            data: None,
+                        })],
+                        position: None,
+                        marker: Some('_'),
+                        data: None,
+                    }),
+                    Node::Text(Text {
+                        value: "c".into(),
+                        position: None,
+                        data: None,
+                    }),
+                ],
+                position: None,
+                data: None,
+            })],
+            position: None,
+            data: None,
+        }))?,
+        "a*b*c\n",
+        "should not keep underscores inside words"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "``a`` ` b ` ```c```  `` `d` ``",
+            &ParseOptions::default()
+        )?)?,
+        "``a`` ` b ` ```c```  `` `d` ``\n",
+        "should keep the size of sequences and padding in code (text)"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: "a`b".into(),
+                    position: None,
+                    sequence_size: Some(1),
+                    padding: false,
+                    data: None,
+                    markers: vec![],
+                })],
+                position: None,
+                data: None,
+            })],
+            position: None,
+            data: None,
+        }))?,
+        "``a`b``\n",
+        "should not use sequences of a size that occurs in code (text)"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "    a\n\n```js eval\n```b\n````\n\n```a`b```",
+            &ParseOptions::default()
+        )?)?,
+        "```\na\n```\n\n````js eval\n```b\n````\n\n```a`b```\n",
+        "should fence code with a fence longer than sequences in it"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "> a\n> > b\n>\n> c\n\n* d\n* e\n  1. f\n\n     g\n  2. h\n\n+ i\n\n***",
+            &ParseOptions::default()
+        )?)?,
+        "> a\n>\n> > b\n>\n> c\n\n* d\n* e\n  1. f\n\n     g\n  2. h\n\n+ i\n\n---\n",
+        "should serialize block quotes and nested lists"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "[a][] ![b](<c d> 'e') <https://f.g> <h@i.j>\n\n[a]: k \"l\"",
+            &ParseOptions::default()
+        )?)?,
+        "[a][] ![b](<c d> \"e\") <https://f.g> <h@i.j>\n\n[a]: k \"l\"\n",
+        "should serialize links, images, and definitions"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "* [x] a~~b~~[^c]\n\n| d | e \\| f |\n| :- | -: |\n| `\\|` | |\n\n[^c]: g\n    h",
+            &gfm
+        )?)?,
+        "* [x] a~~b~~[^c]\n\n| d | e \\| f |\n| :-- | --: |\n| `\\|` | |\n\n[^c]: g\n    h\n",
+        "should serialize GFM"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "---\na: b\n---\n\n$$\nc\n$$\n\n$d$",
+            &ParseOptions {
+                constructs: Constructs {
+                    frontmatter: true,
+                    math_flow: true,
+                    math_text: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?)?,
+        "---\na: b\n---\n\n$$\nc\n$$\n\n$d$\n",
+        "should serialize frontmatter and math"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "<A b=\"c\" d {...e}>\n  f {g}\n</A>\n\n<H />",
+            &ParseOptions::mdx()
+        )?)?,
+        "<A b=\"c\" d {...e}>\n  f {g}\n</A>\n\n<H />\n",
+        "should serialize MDX"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Text(Text {
+            value: "a".into(),
+            position: None,
+            data: None,
+        }))?,
+        "a\n",
+        "should support phrasing as the root"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&Node::Paragraph(Paragraph {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![],
+                position: None,
+                data: None,
+            })],
+            position: None,
+            data: None,
+        }))
+        .err()
+        .unwrap()
+        .to_string(),
+        "Unexpected node `Paragraph` in phrasing (markdown-rs:unexpected-node)",
+        "should crash on flow in phrasing"
+    );
+
+    round_trip("a\\*b\\* \\_c\\_ 1\\. d", &ParseOptions::default())?;
+    round_trip("* a\n* b\n\n- c", &ParseOptions::default())?;
+    round_trip("1. a\n2. b\n\n3) c", &ParseOptions::default())?;
+    round_trip("a!\\\n[b](c)", &ParseOptions::default())?;
+    round_trip("a  \nb\\\nc", &ParseOptions::default())?;
+    round_trip("> a  \n> b\\\n> c", &ParseOptions::default())?;
+    round_trip("* a  \n  b\n\n1. > c\\\n   > d", &ParseOptions::default())?;
+    round_trip("_a_ *b* __c__ **d** ``e`` ` f `", &ParseOptions::default())?;
+    round_trip("| a |\n| - |\n| b |\n\nc ~d~", &gfm)?;
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_task_list_items() -> Result<(), message::Message> {
+    let gfm = ParseOptions::gfm();
+    let mut tree = to_mdast("- [ ] a\n- [x] b\n- c", &gfm)?;
+
+    if let Node::List(list) = &mut tree.children_mut().unwrap()[0] {
+        for child in &mut list.children {
+            if let Node::ListItem(item) = child {
+                item.checked = match item.checked {
+                    Some(checked) => Some(!checked),
+                    None => Some(true),
+                };
+            }
+        }
+    }
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "- [x] a\n- [ ] b\n- [x] c\n",
+        "should serialize changed `checked` fields"
+    );
+
+    round_trip("- [ ] a\n- [x] b", &gfm)?;
+    round_trip("* [X] a\n* [ ] b\n\n  c", &gfm)?;
+    round_trip("1. [x] a\n2. b", &gfm)?;
+    round_trip("- [ ] a\n  - [x] b\n\n    > c", &gfm)?;
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_list_items() -> Result<(), message::Message> {
+    let tree = to_mdast("3) a\n7)   b\n\n     c", &ParseOptions::default())?;
+
+    if let Node::List(list) = &tree.children().unwrap()[0] {
+        if let Node::ListItem(item) = &list.children[1] {
+            assert_eq!(
+                (item.marker, item.number, item.indent),
+                (Some(')'), Some(7), Some(5)),
+                "should add the marker, number, and indent of list items"
+            );
+        }
+    }
+
+    for (value, expected) in [
+        ("* a\n* b", "* a\n* b\n"),
+        ("+ a\n\n  + b", "+ a\n\n  + b\n"),
+        ("3) a\n7) b", "3) a\n7) b\n"),
+        ("1. a\n1. b", "1. a\n1. b\n"),
+        ("-   a\n\n    b", "-   a\n\n    b\n"),
+        ("10.  a\n11. b", "10.  a\n11. b\n"),
+    ] {
+        assert_eq!(
+            mdast_to_markdown(&to_mdast(value, &ParseOptions::default())?)?,
+            expected,
+            "should keep the marker, number, and indent of list items in {:?}",
+            value
+        );
+    }
+
+    let mut item = ListItem {
+        children: vec![paragraph(vec![text("a")])],
+        position: None,
+        data: None,
+        spread: false,
+        checked: None,
+        marker: Some('+'),
+        number: None,
+        indent: Some(9),
+    };
+
+    assert_eq!(
+        mdast_to_markdown(&root(vec![list(false, vec![Node::ListItem(item.clone())])]))?,
+        "+ a\n",
+        "should not use an indent of more than 4 spaces after the marker"
+    );
+
+    item.marker = Some(')');
+
+    assert_eq!(
+        mdast_to_markdown(&root(vec![list(false, vec![Node::ListItem(item)])]))?,
+        "- a\n",
+        "should not use a marker that is invalid for the kind of list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_commonmark() -> Result<(), message::Message> {
+    // Round trip every input of the `CommonMark` test suite.
+    let source = include_str!("commonmark.rs");
+    let start = "to_html_with_options(\n            r###\"";
+    let skip = [
+        // The tree only has the text of image labels, so markup in them cannot
+        // be restored.
+        "![foo *bar*]\n\n[foo *bar*]: train.jpg \"train & tracks\"\n",
+        "![foo *bar*][]\n\n[foo *bar*]: train.jpg \"train & tracks\"\n",
+        "![*foo* bar][]\n\n[*foo* bar]: /url \"title\"\n",
+        "![*foo* bar]\n\n[*foo* bar]: /url \"title\"\n",
+    ];
+    let mut rest = source;
+
+    while let Some(index) = rest.find(start) {
+        rest = &rest[index + start.len()..];
+        let end = rest.find("\"###").unwrap();
+        let value = &rest[..end];
+
+        if !skip.contains(&value) {
+            round_trip(value, &ParseOptions::default())?;
+        }
+
+        rest = &rest[end..];
+    }
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_options() -> Result<(), message::Message> {
+    let mut tree = to_mdast(
+        "* a *b* **c** d*e*f\n\n3. g\n4. h\n\n***\n\n~~~js\ni\n~~~\n\nj k l m n o p",
+        &ParseOptions::default(),
+    )?;
+    unstyle(&mut tree);
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "- a _b_ **c** d*e*f\n\n3. g\n4. h\n\n---\n\n```js\ni\n```\n\nj k l m n o p\n",
+        "should use Prettier’s style by default"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &tree,
+            &SerializeOptions {
+                bullet: '*',
+                bullet_other: None,
+                close_atx: false,
+                emphasis: '*',
+                fence: '~',
+                fence_length: 4,
+                increment_list_marker: false,
+                line_width: Some(6),
+                rule: "* * *".into(),
+                strong: '_',
+            }
+        )?,
+        "* a\n  *b*\n  __c__\n  d*e*f\n\n3. g\n3. h\n\n* * *\n\n~~~~js\ni\n~~~~\n\nj k l\nm n o\np\n",
+        "should support options"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &unstyled("- a\n\n* b\n\n- c")?,
+            &SerializeOptions {
+                bullet: '+',
+                ..Default::default()
+            }
+        )?,
+        "+ a\n\n- b\n\n+ c\n",
+        "should use another bullet for adjacent lists"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &unstyled("- ***\n\n* a")?,
+            &SerializeOptions {
+                bullet: '*',
+                rule: "***".into(),
+                ..Default::default()
+            }
+        )?,
+        "- ***\n\n* a\n",
+        "should use another bullet for items starting with a thematic break"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &unstyled("- a\n\n* b\n\n- c")?,
+            &SerializeOptions {
+                bullet_other: Some('+'),
+                ..Default::default()
+            }
+        )?,
+        "- a\n\n+ b\n\n- c\n",
+        "should support `bullet_other`"
+    );
+
+    let nested = |depth: usize, item: Vec<Node>| {
+        let mut node = list(false, vec![list_item(item)]);
+        for _ in 1..depth {
+            node = list(false, vec![list_item(vec![node])]);
+        }
+        root(vec![node])
+    };
+
+    assert_eq!(
+        mdast_to_markdown(&nested(3, vec![]))?,
+        "- - *\n",
+        "should use another bullet for an empty item after two of the same bullets"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&nested(4, vec![]))?,
+        "- - - *\n",
+        "should use another bullet for an empty item after more of the same bullets"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&nested(2, vec![]))?,
+        "- -\n",
+        "should not use another bullet for an empty item after one bullet"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&nested(3, vec![paragraph(vec![text("a")])]))?,
+        "- - - a\n",
+        "should not use another bullet for a non-empty item"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &nested(3, vec![]),
+            &SerializeOptions {
+                bullet: '+',
+                ..Default::default()
+            }
+        )?,
+        "+ + +\n",
+        "should not use another bullet for pluses, which cannot form thematic breaks"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&root(vec![list(
+            false,
+            vec![list_item(vec![
+                paragraph(vec![text("a")]),
+                list(
+                    false,
+                    vec![list_item(vec![list(false, vec![list_item(vec![])])])]
+                ),
+            ])]
+        )]))?,
+        "- a\n  - -\n",
+        "should not count bullets on other lines"
+    );
+
+    for depth in 2..5 {
+        let tree = nested(depth, vec![]);
+        let mut again = to_mdast(&mdast_to_markdown(&tree)?, &ParseOptions::default())?;
+        strip_positions(&mut again);
+        unstyle_lists(&mut again);
+        assert_eq!(
+            again, tree,
+            "should round trip empty items in {} nested lists",
+            depth
+        );
+    }
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast("- a\n  ***", &ParseOptions::default())?)?,
+        "- a\n  ***\n",
+        "should not use a dash thematic break after a paragraph in a tight item"
+    );
+
+    let close_atx = SerializeOptions {
+        close_atx: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        mdast_to_markdown(&to_mdast(
+            "# a #\n\n### b ###\n\nc\n=",
+            &ParseOptions::default()
+        )?)?,
+        "# a\n\n### b\n\nc\n=\n",
+        "should not add closing sequences to ATX headings by default"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &to_mdast(
+                "# a\n\n### b ###\n\nc\n=\n\n> ## d\n\n##",
+                &ParseOptions::default()
+            )?,
+            &close_atx
+        )?,
+        "# a #\n\n### b ###\n\nc\n=\n\n> ## d ##\n\n## ##\n",
+        "should support `close_atx: true`, but not for setext headings"
+    );
+
+    for value in [
+        "# a #",
+        "## a \\#",
+        "### a # b",
+        "#### #",
+        "##### \\##",
+        "######",
+        "# *a* #",
+    ] {
+        round_trip_with_options(value, &ParseOptions::default(), &close_atx)?;
+    }
+
+    let mut tree = to_mdast("*a* *_b_* **_c_**", &ParseOptions::default())?;
+    unstyle(&mut tree);
+
+    assert_eq!(
+        mdast_to_markdown(&tree)?,
+        "_a_ _*b*_ **_c_**\n",
+        "should use the other marker for emphasis in attention"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &to_mdast("```\n``` `` ~~~~\n```", &ParseOptions::default())?,
+            &SerializeOptions {
+                fence: '~',
+                ..Default::default()
+            }
+        )?,
+        "~~~~~\n``` `` ~~~~\n~~~~~\n",
+        "should use fences longer than sequences in code"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &to_mdast(
+                "> alpha bravo *charlie* delta [echo foxtrot][] 1. golf\n\n[echo foxtrot]: h",
+                &ParseOptions::default()
+            )?,
+            &SerializeOptions {
+                line_width: Some(14),
+                ..Default::default()
+            }
+        )?,
+        "> alpha bravo\n> *charlie*\n> delta\n> [echo foxtrot][]\n> 1\\. golf\n\n[echo foxtrot]: h\n",
+        "should wrap text, but not in references, and escape at the start of lines"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &tree,
+            &SerializeOptions {
+                bullet: 'x',
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Cannot serialize with `x` as `bullet`, expected `*`, `+`, or `-` (markdown-rs:invalid-option)",
+        "should crash on an invalid bullet"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &tree,
+            &SerializeOptions {
+                bullet_other: Some('-'),
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Cannot serialize with `-` as `bullet_other`, expected `*`, `+`, or `-`, different from `bullet` (markdown-rs:invalid-option)",
+        "should crash on a `bullet_other` that is the same as `bullet`"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &tree,
+            &SerializeOptions {
+                fence_length: 2,
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Cannot serialize with `2` as `fence_length`, expected `3` or more (markdown-rs:invalid-option)",
+        "should crash on a short fence"
+    );
+
+    assert_eq!(
+        mdast_to_markdown_with_options(
+            &tree,
+            &SerializeOptions {
+                rule: "-*-".into(),
+                ..Default::default()
+            }
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Cannot serialize with `-*-` as `rule`, expected three or more of `*`, `-`, or `_` (markdown-rs:invalid-option)",
+        "should crash on an invalid rule"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_options_round_trip() -> Result<(), message::Message> {
+    let gfm = ParseOptions::gfm();
+    let value = "# a *b* **c**\n\nd *e* f**g**h _i_j *__k__* __*l*__\n\n* m\n* ***\n  n\n\n- o\n\n  ---\n\n1. p\n2. q\n   ***\n\n5) r\n\n***\n\n```js\ns ```\n```\n\n~~~\nt ~~~~\n~~~\n\n> u v w x y z alpha bravo charlie delta echo foxtrot golf hotel india\n> juliett kilo *lima* mike **november** oscar [papa quebec][] romeo\n\n| sierra | tango uniform |\n| - | - |\n| victor whiskey | x-ray |\n\n[papa quebec]: yankee";
+    let rules = ["---", "***", "___", "* * *", "- - - -"];
+    let mut count = 0;
+
+    for bullet in ['-', '*', '+'] {
+        for emphasis in ['*', '_'] {
+            for strong in ['*', '_'] {
+                for fence in ['`', '~'] {
+                    for fence_length in [3, 4] {
+                        for increment_list_marker in [true, false] {
+                            for line_width in [None, Some(20)] {
+                                for rule in rules {
+                                    round_trip_with_options(
+                                        value,
+                                        &gfm,
+                                        &SerializeOptions {
+                                            bullet,
+                                            bullet_other: None,
+                                            close_atx: false,
+                                            emphasis,
+                                            fence,
+                                            fence_length,
+                                            increment_list_marker,
+                                            line_width,
+                                            rule: rule.into(),
+                                            strong,
+                                        },
+                                    )?;
+                                    count += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(count, 960, "should round trip all combinations");
+
+    Ok(())
+}
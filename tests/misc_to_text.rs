@@ -0,0 +1,185 @@
+use markdown::{message, to_mdast, to_text, Constructs, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_text_document() -> Result<(), message::Message> {
+    assert_eq!(
+        to_text(
+            "# Release *notes*\n\nWe fixed [a bug](https://example.com \"title\") in `parse()`.\n\n## Changes\n\n* one\n* two &amp; three\n    1. nested\n    2. list\n\n```rust\nfn main() {\n\n    println!(\"hi\");\n}\n```\n\nSee also\n===",
+            &ParseOptions::default()
+        )?,
+        "Release notes\n\nWe fixed a bug in parse().\n\nChanges\n\n- one\n- two & three\n  1. nested\n  2. list\n\nfn main() {\n\n    println!(\"hi\");\n}\n\nSee also",
+        "should support a document w/ headings, a list, and a code block"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_text_constructs() -> Result<(), message::Message> {
+    assert_eq!(
+        to_text("a\nb  \nc\\\nd", &ParseOptions::default())?,
+        "a\nb\nc\nd",
+        "should keep line endings in paragraphs"
+    );
+
+    assert_eq!(
+        to_text(
+            "![a *b*](c.png) [d][] <e@f.g>\n\n[d]: h",
+            &ParseOptions::default()
+        )?,
+        "a b d e@f.g",
+        "should use labels of media, and drop definitions"
+    );
+
+    assert_eq!(
+        to_text(
+            "a <b>c</b>\n\n<div>\nd\n</div>\n\n***\n\ne",
+            &ParseOptions::default()
+        )?,
+        "a c\n\ne",
+        "should drop HTML and thematic breaks"
+    );
+
+    assert_eq!(
+        to_text("``  a ` b  ``", &ParseOptions::default())?,
+        " a ` b ",
+        "should strip padding in code (text)"
+    );
+
+    assert_eq!(
+        to_text("    a\n\n    b\n\nc", &ParseOptions::default())?,
+        "a\n\nb\n\nc",
+        "should support code (indented)"
+    );
+
+    assert_eq!(
+        to_text("> a\n>\n> b\n\nc", &ParseOptions::default())?,
+        "a\n\nb\n\nc",
+        "should support block quotes"
+    );
+
+    assert_eq!(
+        to_text("3. a\n\n4. b\n\n   c", &ParseOptions::default())?,
+        "3. a\n\n4. b\n\n   c",
+        "should support loose lists"
+    );
+
+    assert_eq!(
+        to_text(
+            "* [x] a[^b]\n* [ ] c ~d~\n\n| e | f |\n| - | - |\n| g | h |\n\n[^b]: i",
+            &ParseOptions::gfm()
+        )?,
+        "- [x] a\n- [ ] c d\n\ne\tf\ng\th",
+        "should support GFM"
+    );
+
+    assert_eq!(
+        to_text(
+            "---\na: b\n---\n\n$$\nc\n$$\n\n$d$",
+            &ParseOptions {
+                constructs: Constructs {
+                    frontmatter: true,
+                    math_flow: true,
+                    math_text: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "c\n\nd",
+        "should support frontmatter and math"
+    );
+
+    assert_eq!(
+        to_text("<A>\n  b{c}\n</A>\n\n<D>e</D>", &ParseOptions::mdx())?,
+        "b\n\ne",
+        "should support MDX"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn node_to_text() -> Result<(), message::Message> {
+    assert_eq!(
+        to_mdast("# a *b **c*** d", &ParseOptions::default())?.to_text(),
+        "a b c d",
+        "should support nested emphasis"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "[a *b*](c) [d][] <e@f.g>\n\n[d]: h",
+            &ParseOptions::default()
+        )?
+        .to_text(),
+        "a b d e@f.g",
+        "should support links, but not definitions"
+    );
+
+    assert_eq!(
+        to_mdast("![a *b*](c) ![d][]\n\n[d]: h", &ParseOptions::default())?.to_text(),
+        "a b d",
+        "should support images as their alt text"
+    );
+
+    assert_eq!(
+        to_mdast("a `b *c*` d", &ParseOptions::default())?.to_text(),
+        "a b *c* d",
+        "should support code spans"
+    );
+
+    assert_eq!(
+        to_mdast("a <b>c</b> <!--d--> e", &ParseOptions::default())?.to_text(),
+        "a c  e",
+        "should not support HTML"
+    );
+
+    assert_eq!(
+        to_mdast("a\\\nb  \nc", &ParseOptions::default())?.to_text(),
+        "a\nb\nc",
+        "should support breaks as line endings"
+    );
+
+    assert_eq!(
+        to_mdast("a[^b]\n\n[^b]: c", &ParseOptions::gfm())?
+            .children()
+            .unwrap()[0]
+            .to_text(),
+        "a",
+        "should not support footnote references"
+    );
+
+    assert_eq!(
+        to_mdast(
+            "# a\n\nb\n\n> c\n>\n> d\n\n<e>\n\nf",
+            &ParseOptions::default()
+        )?
+        .to_text(),
+        "a\n\nb\n\nc\n\nd\n\nf",
+        "should separate blocks with blank lines, skipping blocks without text"
+    );
+
+    assert_eq!(
+        to_mdast("* a\n* b\n\n1. c\n\n2. d", &ParseOptions::default())?.to_text(),
+        "a\nb\n\nc\n\nd",
+        "should separate items in tight lists with line endings"
+    );
+
+    assert_eq!(
+        to_mdast("| a | b |\n| - | - |\n| c | d |", &ParseOptions::gfm())?.to_text(),
+        "a\tb\nc\td",
+        "should separate table rows with line endings, and cells with tabs"
+    );
+
+    let value = "# a\n\nb\n\n> c\n>\n> d";
+
+    assert_eq!(
+        to_mdast(value, &ParseOptions::default())?.to_text(),
+        to_text(value, &ParseOptions::default())?,
+        "should match `to_text` for blocks"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use markdown::{
+    mdast::{
+        toc::{toc, toc_to_list, toc_with_slugify, TocEntry},
+        Data, Node,
+    },
+    mdast_to_html, mdast_to_markdown, message, to_mdast, CompileOptions, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the depth, text, and slug of entries, with children indented.
+fn outline(entries: &[TocEntry], indent: usize, result: &mut Vec<String>) {
+    for entry in entries {
+        result.push(format!(
+            "{}{} {} #{}",
+            "  ".repeat(indent),
+            entry.depth,
+            entry.text,
+            entry.slug
+        ));
+        outline(&entry.children, indent + 1, result);
+    }
+}
+
+/// Parse `value` and get the outline of its table of contents.
+fn parse(value: &str, max_depth: u8) -> Result<Vec<String>, message::Message> {
+    let mut result = vec![];
+    outline(
+        &toc(&to_mdast(value, &ParseOptions::gfm())?, max_depth),
+        0,
+        &mut result,
+    );
+    Ok(result)
+}
+
+#[test]
+fn toc_entries() -> Result<(), message::Message> {
+    assert_eq!(
+        parse("a", 6)?,
+        Vec::<String>::new(),
+        "should support documents without headings"
+    );
+
+    assert_eq!(
+        parse(
+            "# Alpha\n\n## Bravo *charlie*\n\n### Delta\n\n## Echo\n\n# Foxtrot",
+            6
+        )?,
+        vec![
+            "1 Alpha #alpha",
+            "  2 Bravo charlie #bravo-charlie",
+            "    3 Delta #delta",
+            "  2 Echo #echo",
+            "1 Foxtrot #foxtrot",
+        ],
+        "should nest headings"
+    );
+
+    assert_eq!(
+        parse("## a\n\n#### b\n\n# c\n\n### d\n\n## e", 6)?,
+        vec!["2 a #a", "  4 b #b", "1 c #c", "  3 d #d", "  2 e #e"],
+        "should support skipped levels, and documents not starting at depth 1"
+    );
+
+    assert_eq!(
+        parse("# a\n\n## b\n\n### c\n\n## d", 2)?,
+        vec!["1 a #a", "  2 b #b", "  2 d #d"],
+        "should support `max_depth`"
+    );
+
+    assert_eq!(
+        parse("# a\n\n### a\n\n## a\n\n# a-1", 2)?,
+        vec!["1 a #a", "  2 a #a-2", "1 a-1 #a-1-1"],
+        "should make slugs unique in document order, including headings past `max_depth`"
+    );
+
+    assert_eq!(
+        parse("# a\n\n#\n\n## !\n\n> ## b\n\n- c\n  ===", 6)?,
+        vec!["1 a #a", "  2 b #b", "1 c #c"],
+        "should skip headings without slugs, and find headings in containers"
+    );
+
+    let tree = to_mdast("# a\n\n## b", &ParseOptions::default())?;
+    let entries = toc(&tree, 6);
+    assert_eq!(
+        entries[0].position,
+        tree.children().unwrap()[0].position().cloned(),
+        "should add positions"
+    );
+    assert_eq!(
+        entries[0].children[0].position,
+        tree.children().unwrap()[1].position().cloned(),
+        "should add positions to children"
+    );
+
+    let mut tree = to_mdast("# a\n\n# b", &ParseOptions::default())?;
+    if let Some(Node::Heading(heading)) = tree.children_mut().unwrap().get_mut(1) {
+        heading.data = Some(Box::new(Data::from([("id".into(), "a".into())])));
+    }
+    assert_eq!(
+        toc(&tree, 6)
+            .iter()
+            .map(|entry| entry.slug.clone())
+            .collect::<Vec<_>>(),
+        vec!["a", "a-1"],
+        "should support `data.id`, like `heading_ids`"
+    );
+
+    assert_eq!(
+        toc_with_slugify(
+            &to_mdast("# Über\n\n# Uber", &ParseOptions::default())?,
+            6,
+            &|value| value.replace('Ü', "U").to_lowercase()
+        )
+        .iter()
+        .map(|entry| entry.slug.clone())
+        .collect::<Vec<_>>(),
+        vec!["uber", "uber-1"],
+        "should support `slugify`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn toc_list() -> Result<(), message::Message> {
+    let value = "# Alpha\n\n## Bravo\n\n### Charlie\n\n## Bravo\n\n# *Delta* `echo`";
+    let mut tree = to_mdast(value, &ParseOptions::default())?;
+    let list = toc_to_list(&toc(&tree, 6));
+
+    assert_eq!(
+        mdast_to_markdown(&list)?,
+        "- [Alpha](#alpha)\n  - [Bravo](#bravo)\n    - [Charlie](#charlie)\n  - [Bravo](#bravo-1)\n- [Delta echo](#delta-echo)\n",
+        "should turn a table of contents into a list"
+    );
+
+    assert_eq!(
+        mdast_to_markdown(&toc_to_list(&[]))?,
+        "",
+        "should support an empty table of contents"
+    );
+
+    tree.children_mut().unwrap().insert(1, list);
+    let html = mdast_to_html(
+        &tree,
+        &Options {
+            compile: CompileOptions {
+                heading_ids: true,
+                ..CompileOptions::default()
+            },
+            ..Options::default()
+        },
+    )?;
+
+    for slug in ["alpha", "bravo", "charlie", "bravo-1", "delta-echo"] {
+        assert!(
+            html.contains(&format!("<a href=\"#{}\">", slug))
+                && html.contains(&format!(" id=\"{}\">", slug)),
+            "should link to the `id`s from `heading_ids` ({}) in {}",
+            slug,
+            html
+        );
+    }
+
+    Ok(())
+}
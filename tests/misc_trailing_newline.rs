@@ -0,0 +1,104 @@
+use markdown::{
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+/// Get GFM options with `trailing_newline`.
+fn options(trailing_newline: bool) -> Options {
+    Options {
+        compile: CompileOptions {
+            trailing_newline: Some(trailing_newline),
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    }
+}
+
+#[test]
+fn trailing_newline() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("\n\nfoo"),
+        "<p>foo</p>",
+        "should not add a trailing line ending by default if the document does not end in one"
+    );
+
+    assert_eq!(
+        to_html("\n\nfoo\n\n"),
+        "<p>foo</p>\n",
+        "should add a trailing line ending by default if the document ends in one"
+    );
+
+    assert_eq!(
+        to_html_with_options("\n\nfoo\n\n", &options(true))?,
+        "<p>foo</p>\n",
+        "should add a trailing line ending w/ `trailing_newline: Some(true)` (1)"
+    );
+
+    assert_eq!(
+        to_html_with_options("\n\nfoo", &options(true))?,
+        "<p>foo</p>\n",
+        "should add a trailing line ending w/ `trailing_newline: Some(true)` (2)"
+    );
+
+    assert_eq!(
+        to_html_with_options("\n\nfoo\n\n", &options(false))?,
+        "<p>foo</p>",
+        "should not add a trailing line ending w/ `trailing_newline: Some(false)`"
+    );
+
+    assert_eq!(
+        to_html(" \t\n  \n\nfoo\n \n"),
+        "<p>foo</p>\n",
+        "should not make empty paragraphs for whitespace-only lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("\r\n\r\nfoo\r\nbar", &options(true))?,
+        "<p>foo\r\nbar</p>\r\n",
+        "should use the line ending of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("\r\nfoo\r\n\r\n", &options(false))?,
+        "<p>foo</p>",
+        "should remove CRLF"
+    );
+
+    assert_eq!(
+        to_html_with_options("\n\n", &options(true))?,
+        "",
+        "should not add a line ending to empty output"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\n```\n\n<div>\n", &options(true))?,
+        "<pre><code>a\n</code></pre>\n&lt;div&gt;\n",
+        "should add one line ending after other constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c\n", &options(false))?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>c <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>",
+        "should not add a line ending after footnotes w/ `trailing_newline: Some(false)`"
+    );
+
+    for trailing_newline in [true, false] {
+        for value in [
+            "\n\nfoo\n\n",
+            "\r\n\r\nfoo\r\nbar",
+            "\n\n",
+            "a[^b]\n\n[^b]: c\n",
+        ] {
+            let options = options(trailing_newline);
+            assert_eq!(
+                mdast_to_html(&to_mdast(value, &options.parse)?, &options)?,
+                to_html_with_options(value, &options)?,
+                "should match `to_html` in `mdast_to_html` for {:?} ({:?})",
+                value,
+                trailing_newline
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use markdown::{
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast, CompileOptions, Options,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn wrap_paragraphs() -> Result<(), message::Message> {
+    let unwrapped = Options {
+        compile: CompileOptions {
+            wrap_paragraphs: false,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a *b*"),
+        "<p>a <em>b</em></p>",
+        "should wrap paragraphs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a *b*", &unwrapped)?,
+        "a <em>b</em>",
+        "should support not wrapping paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n\nc", &unwrapped)?,
+        "a\nb\nc",
+        "should put unwrapped paragraphs on their own lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n# b\n\nc", &unwrapped)?,
+        "a\n<h1>b</h1>\nc",
+        "should support other constructs around unwrapped paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n- b\n\n  c\n- d", &unwrapped)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n<li>\n<p>d</p>\n</li>\n</ul>",
+        "should wrap paragraphs in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b", &unwrapped)?,
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should not affect tight lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c", &unwrapped)?,
+        "a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>c <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should wrap paragraphs in footnote definitions"
+    );
+
+    for value in [
+        "a *b*",
+        "a\nb\n\nc",
+        "a\n\n# b\n\nc",
+        "> a\n\n- b\n\n  c\n- d",
+        "a[^b]\n\n[^b]: c",
+    ] {
+        assert_eq!(
+            mdast_to_html(&to_mdast(value, &unwrapped.parse)?, &unwrapped)?,
+            to_html_with_options(value, &unwrapped)?,
+            "should match `to_html` in `mdast_to_html` for {:?}",
+            value
+        );
+    }
+
+    Ok(())
+}
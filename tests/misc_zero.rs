@@ -33,7 +33,8 @@ fn zero() -> Result<(), message::Message> {
         to_mdast("", &Default::default())?,
         Node::Root(Root {
             children: vec![],
-            position: Some(Position::new(1, 1, 0, 1, 1, 0))
+            position: Some(Position::new(1, 1, 0, 1, 1, 0)),
+            data: None,
         }),
         "should support no markdown (ast)"
     );
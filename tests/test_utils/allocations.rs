@@ -0,0 +1,37 @@
+//! Count allocations.
+//!
+//! This installs a global allocator, so it is only included by the tests and
+//! benches that need it.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Allocator that counts allocations on the current thread.
+struct Counter;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+/// Count the allocations made by `callback`.
+pub fn allocations<T>(callback: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = callback();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
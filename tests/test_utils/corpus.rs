@@ -0,0 +1,120 @@
+//! Markdown to test with, found in the tests.
+
+use std::fs;
+
+/// Get the readme and the string literals in the tests, as markdown to test
+/// invariants with.
+pub fn corpus() -> Vec<String> {
+    let mut values = vec![fs::read_to_string("readme.md").unwrap()];
+
+    for entry in fs::read_dir("tests").unwrap() {
+        let path = entry.unwrap().path();
+
+        if path
+            .extension()
+            .map_or(false, |extension| extension == "rs")
+        {
+            values.extend(literals(&fs::read_to_string(path).unwrap()));
+        }
+    }
+
+    assert!(
+        values.len() > 1000,
+        "should find the string literals in tests"
+    );
+
+    values
+}
+
+/// Get the string literals in Rust `code`.
+fn literals(code: &str) -> Vec<String> {
+    let chars = code.chars().collect::<Vec<_>>();
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '/' if chars.get(index + 1) == Some(&'/') => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+            }
+            // Character literals (such as `'"'`), not lifetimes.
+            '\'' => {
+                if chars.get(index + 1) == Some(&'\\') {
+                    index += 3;
+                    while chars[index] != '\'' {
+                        index += 1;
+                    }
+                } else if chars.get(index + 2) == Some(&'\'') {
+                    index += 2;
+                }
+
+                index += 1;
+            }
+            'r' if (index == 0 || !chars[index - 1].is_alphanumeric())
+                && matches!(chars.get(index + 1), Some('"' | '#')) =>
+            {
+                let mut hashes = 0;
+                index += 1;
+                while chars[index] == '#' {
+                    hashes += 1;
+                    index += 1;
+                }
+                index += 1;
+                let start = index;
+
+                while !(chars[index] == '"'
+                    && chars[index + 1..].iter().take(hashes).all(|c| *c == '#'))
+                {
+                    index += 1;
+                }
+
+                result.push(chars[start..index].iter().collect());
+                index += 1 + hashes;
+            }
+            '"' => {
+                let mut value = String::new();
+                index += 1;
+
+                while chars[index] != '"' {
+                    if chars[index] == '\\' {
+                        index += 1;
+                        match chars[index] {
+                            'n' => value.push('\n'),
+                            'r' => value.push('\r'),
+                            't' => value.push('\t'),
+                            '0' => value.push('\0'),
+                            'u' => {
+                                let end =
+                                    index + chars[index..].iter().position(|c| *c == '}').unwrap();
+                                let hex = chars[index + 2..end].iter().collect::<String>();
+                                value.push(
+                                    char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).unwrap(),
+                                );
+                                index = end;
+                            }
+                            // Line continuation.
+                            '\n' => {
+                                while chars[index + 1].is_whitespace() {
+                                    index += 1;
+                                }
+                            }
+                            char => value.push(char),
+                        }
+                    } else {
+                        value.push(chars[index]);
+                    }
+
+                    index += 1;
+                }
+
+                result.push(value);
+                index += 1;
+            }
+            _ => index += 1,
+        }
+    }
+
+    result
+}
@@ -0,0 +1,14 @@
+//! Utilities for syntax trees in tests.
+
+use markdown::mdast::{visit_mut, Node, VisitAction};
+
+/// Remove positions from a tree.
+///
+/// Unlike [`Node::eq_ignoring_positions`][], this keeps the trees around,
+/// so that `assert_eq` can show how they differ.
+pub fn strip_positions(tree: &mut Node) {
+    visit_mut(tree, |node, _| {
+        node.position_set(None);
+        VisitAction::Continue
+    });
+}
@@ -1,8 +1,8 @@
 use markdown::{
     mdast::{Node, Root, ThematicBreak},
-    message, to_html, to_html_with_options, to_mdast,
+    mdast_to_html, message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -188,16 +188,47 @@ fn thematic_break() -> Result<(), message::Message> {
         "should support turning off thematic breaks"
     );
 
+    let divider = Options {
+        compile: CompileOptions {
+            thematic_break_html: Some("<hr class=\"divider\">".into()),
+            source_position: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\n\n***\n\n> ---", &divider)?,
+        "<p data-line=\"1\" data-end-line=\"1\">a</p>\n<hr class=\"divider\">\n<blockquote data-line=\"5\" data-end-line=\"5\">\n<hr class=\"divider\">\n</blockquote>",
+        "should support `thematic_break_html`"
+    );
+
+    assert_eq!(
+        mdast_to_html(&to_mdast("a\n\n***\n\n> ---", &divider.parse)?, &divider)?,
+        to_html_with_options("a\n\n***\n\n> ---", &divider)?,
+        "should support `thematic_break_html` in `mdast_to_html`"
+    );
+
     assert_eq!(
         to_mdast("***", &Default::default())?,
         Node::Root(Root {
             children: vec![Node::ThematicBreak(ThematicBreak {
-                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+                data: None,
             })],
-            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            position: Some(Position::new(1, 1, 0, 1, 4, 3)),
+            data: None,
         }),
         "should support thematic breaks as `ThematicBreak`s in mdast"
     );
 
+    assert!(
+        to_mdast("a\n\n***", &Default::default())?.eq_ignoring_positions(&Node::root(vec![
+            Node::paragraph(vec![Node::text("a")]),
+            Node::thematic_break()
+        ])),
+        "should support comparing thematic breaks without positions"
+    );
+
     Ok(())
 }